@@ -0,0 +1,11 @@
+-- Drop the closed action/resource_type lists on audit_log.
+--
+-- Handlers have long written domain-specific values (NDA_SENT, nda, ...), and
+-- the SoD, access-request and grant-expiry work that follows writes more
+-- (SOD_VIOLATION_BLOCKED, access_request, ACCESS_EXPIRED). Those inserts were
+-- rejected by the original CHECKs and, since audit writes are best-effort,
+-- silently lost. Runs ahead of the SoD rules so every later audit write lands.
+-- Idempotent.
+
+ALTER TABLE audit_log DROP CONSTRAINT IF EXISTS audit_log_action_check;
+ALTER TABLE audit_log DROP CONSTRAINT IF EXISTS audit_log_resource_type_check;
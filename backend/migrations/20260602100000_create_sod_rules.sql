@@ -0,0 +1,61 @@
+-- Separation-of-duties rules (SoD).
+--
+-- Three declarative rule types, enforced centrally by crate::sod::enforce and
+-- reported over existing role assignments by GET /api/sod/violations:
+--   * CONFLICTING_PERMISSIONS  no role may hold both action_key and conflicting_key
+--   * NO_SELF_ACTION           the actor may not perform action_key on themselves
+--   * REQUESTER_NOT_APPROVER   whoever initiated action_key's subject (requester,
+--                              issuer) may not also complete it (approve, sign)
+--
+-- Mirrors the SoD crux of the demo ROLES table (frontend/src/demo/lib/model.ts):
+-- MANAGER may request_attribute but never approve_attribute. Idempotent.
+
+CREATE TABLE IF NOT EXISTS sod_rules (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(100) UNIQUE NOT NULL,
+    rule_type VARCHAR(30) NOT NULL CHECK (rule_type IN (
+        'CONFLICTING_PERMISSIONS', 'NO_SELF_ACTION', 'REQUESTER_NOT_APPROVER'
+    )),
+    action_key VARCHAR(100) NOT NULL,
+    conflicting_key VARCHAR(100),
+    description TEXT,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    -- conflicting_key is required for, and only meaningful on, permission conflicts
+    CONSTRAINT chk_sod_conflicting_key CHECK (
+        (rule_type = 'CONFLICTING_PERMISSIONS') = (conflicting_key IS NOT NULL)
+    )
+);
+
+CREATE INDEX IF NOT EXISTS idx_sod_rules_action ON sod_rules(action_key) WHERE enabled;
+
+-- The request/approve split the access-request workflow builds on. Admin holds
+-- approve (the original admin CROSS JOIN does not re-run for new rows); manager
+-- holds request only — the SoD crux.
+INSERT INTO permissions (key, description) VALUES
+    ('access.request', 'Request computer/data/physical access for a person'),
+    ('access.approve', 'Approve or deny access requests')
+ON CONFLICT (key) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r JOIN permissions p ON p.key = 'access.approve'
+WHERE r.name = 'admin'
+ON CONFLICT DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r JOIN permissions p ON p.key = 'access.request'
+WHERE r.name = 'manager'
+ON CONFLICT DO NOTHING;
+
+INSERT INTO sod_rules (name, rule_type, action_key, conflicting_key, description) VALUES
+    ('request-vs-approve-access', 'CONFLICTING_PERMISSIONS', 'access.request', 'access.approve',
+     'A role that can request access must not be able to approve it'),
+    ('no-self-access-grant', 'NO_SELF_ACTION', 'access.write', NULL,
+     'Nobody may grant access to themselves'),
+    ('no-self-nda-issue', 'NO_SELF_ACTION', 'nda.write', NULL,
+     'Nobody may issue an NDA to themselves'),
+    ('nda-issuer-not-signer', 'REQUESTER_NOT_APPROVER', 'nda.sign', NULL,
+     'The person who issued an NDA may not sign it'),
+    ('access-requester-not-approver', 'REQUESTER_NOT_APPROVER', 'access.approve', NULL,
+     'The person who requested access may not approve the request')
+ON CONFLICT (name) DO NOTHING;
@@ -0,0 +1,49 @@
+-- Access request and approval workflow.
+--
+-- An end user (for themselves) or a holder of access.request (for anyone)
+-- files a request with a justification. It is routed to the resource owner
+-- from access_resource_owners when one is registered for the target, otherwise
+-- to any holder of access.approve (approver_person_id NULL). Approval creates
+-- the computer_access/data_access/physical_access row in the same transaction
+-- and records its id in granted_access_id. Idempotent.
+
+CREATE TABLE IF NOT EXISTS access_resource_owners (
+    id SERIAL PRIMARY KEY,
+    access_type VARCHAR(20) NOT NULL CHECK (access_type IN ('computer', 'data', 'physical')),
+    target VARCHAR(100) NOT NULL,  -- system_name / data_classification / zone_name
+    owner_person_id INTEGER NOT NULL REFERENCES person(id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT uq_access_resource_owner UNIQUE (access_type, target)
+);
+
+CREATE TABLE IF NOT EXISTS access_requests (
+    id SERIAL PRIMARY KEY,
+    person_id INTEGER NOT NULL REFERENCES person(id) ON DELETE CASCADE,
+    requested_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    access_type VARCHAR(20) NOT NULL CHECK (access_type IN ('computer', 'data', 'physical')),
+    target VARCHAR(100) NOT NULL,
+    access_level VARCHAR(20) NOT NULL,
+    expires_at TIMESTAMP,
+    justification TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'PENDING' CHECK (status IN ('PENDING', 'APPROVED', 'DENIED')),
+    approver_person_id INTEGER REFERENCES person(id) ON DELETE SET NULL,
+    decided_by_person_id INTEGER REFERENCES person(id),
+    decided_at TIMESTAMP,
+    decision_reason TEXT,
+    granted_access_id INTEGER,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT chk_access_request_decided CHECK (
+        (status = 'PENDING') = (decided_at IS NULL)
+    )
+);
+
+CREATE INDEX IF NOT EXISTS idx_access_requests_person ON access_requests(person_id);
+CREATE INDEX IF NOT EXISTS idx_access_requests_pending ON access_requests(approver_person_id)
+    WHERE status = 'PENDING';
+
+DROP TRIGGER IF EXISTS update_access_requests_updated_at ON access_requests;
+CREATE TRIGGER update_access_requests_updated_at
+    BEFORE UPDATE ON access_requests
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
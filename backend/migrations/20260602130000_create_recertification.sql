@@ -0,0 +1,76 @@
+-- Periodic access recertification campaigns.
+--
+-- An admin (recert.manage) opens a campaign over a scope; opening snapshots
+-- every currently-active grant in scope into recert_items. Reviewers
+-- (recert.review) mark each item KEEP or REVOKE — REVOKE takes effect
+-- immediately. When the deadline passes the background sweep closes the
+-- campaign and revokes every item still undecided (AUTO_REVOKED).
+--
+-- Scopes (scope_value meaning):
+--   ALL             -                 every active grant
+--   ORGANIZATION    organization id   computer/data/physical grants of persons
+--                                     employed by / consulting for the org
+--                                     (relations), and resource grants on
+--                                     resources linked to the org
+--                                     (resource_org_links.org_id)
+--   CLASSIFICATION  classification    data grants at that level, and resource
+--                                     grants whose tier classification matches
+--   SYSTEM          system / resource computer grants on that system_name, and
+--                                     resource grants on that resource id
+--
+-- grant_id / person_id are TEXT so the digital-resource ids fit alongside the
+-- integer ids of the classic grant tables. Idempotent.
+
+CREATE TABLE IF NOT EXISTS recert_campaigns (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(200) NOT NULL,
+    scope_type VARCHAR(20) NOT NULL CHECK (scope_type IN ('ALL', 'ORGANIZATION', 'CLASSIFICATION', 'SYSTEM')),
+    scope_value VARCHAR(100),
+    deadline TIMESTAMP NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'OPEN' CHECK (status IN ('OPEN', 'CLOSED')),
+    created_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    closed_at TIMESTAMP,
+    CONSTRAINT chk_recert_scope_value CHECK ((scope_type = 'ALL') = (scope_value IS NULL)),
+    CONSTRAINT chk_recert_closed CHECK ((status = 'CLOSED') = (closed_at IS NOT NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_recert_campaigns_open ON recert_campaigns(deadline)
+    WHERE status = 'OPEN';
+
+CREATE TABLE IF NOT EXISTS recert_items (
+    id SERIAL PRIMARY KEY,
+    campaign_id INTEGER NOT NULL REFERENCES recert_campaigns(id) ON DELETE CASCADE,
+    grant_type VARCHAR(20) NOT NULL CHECK (grant_type IN ('computer', 'data', 'physical', 'resource')),
+    grant_id TEXT NOT NULL,
+    person_id TEXT NOT NULL,
+    target TEXT NOT NULL,           -- system_name / data_classification / zone_name / resource_id
+    access_level VARCHAR(20),       -- NULL for resource grants
+    decision VARCHAR(20) CHECK (decision IN ('KEEP', 'REVOKE', 'AUTO_REVOKED')),
+    reviewed_by_person_id INTEGER REFERENCES person(id),
+    reviewed_at TIMESTAMP,
+    note TEXT,
+    CONSTRAINT uq_recert_item UNIQUE (campaign_id, grant_type, grant_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_recert_items_campaign ON recert_items(campaign_id);
+
+INSERT INTO permissions (key, description) VALUES
+    ('recert.manage', 'Open and close access recertification campaigns'),
+    ('recert.review', 'Review grants in recertification campaigns')
+ON CONFLICT (key) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r JOIN permissions p ON p.key IN ('recert.manage', 'recert.review')
+WHERE r.name = 'admin'
+ON CONFLICT DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r JOIN permissions p ON p.key = 'recert.review'
+WHERE r.name = 'manager'
+ON CONFLICT DO NOTHING;
+
+INSERT INTO sod_rules (name, rule_type, action_key, conflicting_key, description) VALUES
+    ('no-self-recertification', 'NO_SELF_ACTION', 'recert.review', NULL,
+     'Nobody may recertify their own access')
+ON CONFLICT (name) DO NOTHING;
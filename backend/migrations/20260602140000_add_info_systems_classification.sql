@@ -0,0 +1,10 @@
+-- Classification for information systems.
+--
+-- Computer access to a classified system is subject to the same clearance
+-- check as data access (crate::access::clearance): the holder's
+-- person.clearance_level must rank at or above the system's classification.
+-- NULL = not classified, no check. Same four levels as person.clearance_level
+-- and data_access.data_classification. Idempotent.
+
+ALTER TABLE info_systems ADD COLUMN IF NOT EXISTS classification VARCHAR(50)
+    CHECK (classification IN ('UNCLASSIFIED', 'CONFIDENTIAL', 'SECRET', 'TOP_SECRET'));
@@ -0,0 +1,72 @@
+-- Access history: who ended a grant, when, and why; clearance change log.
+--
+-- Grants are never deleted — revocation and expiry only change status — so
+-- with these columns a revoked/expired row is its own history record.
+-- revoked_by_person_id is NULL for system transitions (expiry sweep,
+-- recertification deadline). Expiry records its own time and reason.
+--
+-- person_clearance_changes is filled by a trigger so every write path
+-- (person API, imports, manual SQL) is captured. Existing clearances are
+-- backfilled as an initial change at the person's created_at. Idempotent.
+
+ALTER TABLE computer_access
+    ADD COLUMN IF NOT EXISTS revoked_by_person_id INTEGER REFERENCES person(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS revoked_at TIMESTAMP,
+    ADD COLUMN IF NOT EXISTS revocation_reason TEXT;
+
+ALTER TABLE data_access
+    ADD COLUMN IF NOT EXISTS revoked_by_person_id INTEGER REFERENCES person(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS revoked_at TIMESTAMP,
+    ADD COLUMN IF NOT EXISTS revocation_reason TEXT;
+
+ALTER TABLE physical_access
+    ADD COLUMN IF NOT EXISTS revoked_by_person_id INTEGER REFERENCES person(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS revoked_at TIMESTAMP,
+    ADD COLUMN IF NOT EXISTS revocation_reason TEXT;
+
+-- Rows ended before this migration have no recorded time; use updated_at.
+UPDATE computer_access SET revoked_at = updated_at WHERE status <> 'ACTIVE' AND revoked_at IS NULL;
+UPDATE data_access SET revoked_at = updated_at WHERE status <> 'ACTIVE' AND revoked_at IS NULL;
+UPDATE physical_access SET revoked_at = updated_at WHERE status <> 'ACTIVE' AND revoked_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_computer_access_person_all ON computer_access(person_id);
+CREATE INDEX IF NOT EXISTS idx_data_access_person_all ON data_access(person_id);
+CREATE INDEX IF NOT EXISTS idx_physical_access_person_all ON physical_access(person_id);
+
+CREATE TABLE IF NOT EXISTS person_clearance_changes (
+    id SERIAL PRIMARY KEY,
+    person_id INTEGER NOT NULL REFERENCES person(id) ON DELETE CASCADE,
+    old_level VARCHAR(50),
+    new_level VARCHAR(50),
+    changed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_clearance_changes_person ON person_clearance_changes(person_id, changed_at);
+
+CREATE OR REPLACE FUNCTION record_clearance_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        IF NEW.clearance_level IS NOT NULL THEN
+            INSERT INTO person_clearance_changes (person_id, old_level, new_level)
+            VALUES (NEW.id, NULL, NEW.clearance_level);
+        END IF;
+    ELSIF NEW.clearance_level IS DISTINCT FROM OLD.clearance_level THEN
+        INSERT INTO person_clearance_changes (person_id, old_level, new_level)
+        VALUES (NEW.id, OLD.clearance_level, NEW.clearance_level);
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS person_clearance_change ON person;
+CREATE TRIGGER person_clearance_change
+    AFTER INSERT OR UPDATE OF clearance_level ON person
+    FOR EACH ROW
+    EXECUTE FUNCTION record_clearance_change();
+
+INSERT INTO person_clearance_changes (person_id, old_level, new_level, changed_at)
+SELECT p.id, NULL, p.clearance_level, p.created_at
+FROM person p
+WHERE p.clearance_level IS NOT NULL
+  AND NOT EXISTS (SELECT 1 FROM person_clearance_changes c WHERE c.person_id = p.id);
@@ -0,0 +1,56 @@
+-- Access bundles: named provisioning templates.
+--
+-- A bundle groups grant templates (access_bundle_items) across the classic
+-- computer/data/physical tables and digital-resource grants. Assigning a
+-- bundle to a person issues every template in one transaction, after the
+-- usual SoD and clearance checks, and records which grant each template
+-- produced (access_bundle_assignment_grants). Drift is computed by comparing
+-- the bundle's current templates with those grants: a grant that was later
+-- revoked/expired, a template added or removed since assignment. Idempotent.
+
+CREATE TABLE IF NOT EXISTS access_bundles (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(100) UNIQUE NOT NULL,
+    description TEXT,
+    created_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS access_bundle_items (
+    id SERIAL PRIMARY KEY,
+    bundle_id INTEGER NOT NULL REFERENCES access_bundles(id) ON DELETE CASCADE,
+    access_type VARCHAR(20) NOT NULL CHECK (access_type IN ('computer', 'data', 'physical', 'resource')),
+    target VARCHAR(100) NOT NULL,   -- system_name / data_classification / zone_name / resource_id
+    access_level VARCHAR(20),       -- NULL only for resource templates
+    duration_days INTEGER CHECK (duration_days IS NULL OR duration_days > 0),
+    CONSTRAINT uq_bundle_item UNIQUE (bundle_id, access_type, target),
+    CONSTRAINT chk_bundle_item_level CHECK ((access_type = 'resource') = (access_level IS NULL))
+);
+
+CREATE TABLE IF NOT EXISTS access_bundle_assignments (
+    id SERIAL PRIMARY KEY,
+    bundle_id INTEGER NOT NULL REFERENCES access_bundles(id) ON DELETE CASCADE,
+    person_id INTEGER NOT NULL REFERENCES person(id) ON DELETE CASCADE,
+    assigned_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    assigned_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT uq_bundle_assignment UNIQUE (bundle_id, person_id)
+);
+
+CREATE TABLE IF NOT EXISTS access_bundle_assignment_grants (
+    id SERIAL PRIMARY KEY,
+    assignment_id INTEGER NOT NULL REFERENCES access_bundle_assignments(id) ON DELETE CASCADE,
+    item_id INTEGER REFERENCES access_bundle_items(id) ON DELETE SET NULL,  -- NULL once the template is removed
+    access_type VARCHAR(20) NOT NULL,
+    grant_id TEXT NOT NULL,
+    target VARCHAR(100) NOT NULL,
+    access_level VARCHAR(20)
+);
+
+CREATE INDEX IF NOT EXISTS idx_bundle_assignment_grants ON access_bundle_assignment_grants(assignment_id);
+
+DROP TRIGGER IF EXISTS update_access_bundles_updated_at ON access_bundles;
+CREATE TRIGGER update_access_bundles_updated_at
+    BEFORE UPDATE ON access_bundles
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,47 @@
+-- Link computer access to the info_systems registry.
+--
+-- computer_access.info_system_id references the registered system; the
+-- system_name column stays as the display name and is kept in step with
+-- renames by trigger. New grants must name a registered, non-INACTIVE
+-- system (enforced in crate::access::systems). Deleting a system revokes its
+-- active grants first; the FK then clears info_system_id on the ended rows
+-- so their history survives.
+--
+-- Backfill: names already in the registry are linked. Names that are not get
+-- registered as INACTIVE systems so existing grants keep a reference but no
+-- new grant can target them until someone reviews the entry. Idempotent.
+
+ALTER TABLE computer_access
+    ADD COLUMN IF NOT EXISTS info_system_id INTEGER REFERENCES info_systems(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_computer_access_info_system ON computer_access(info_system_id) WHERE status = 'ACTIVE';
+
+INSERT INTO info_systems (system_name, description, environment, status)
+SELECT DISTINCT ca.system_name,
+       'Registered from existing computer access grants; review environment and status',
+       'PROD', 'INACTIVE'
+FROM computer_access ca
+WHERE ca.info_system_id IS NULL
+  AND NOT EXISTS (SELECT 1 FROM info_systems s WHERE s.system_name = ca.system_name)
+ON CONFLICT (system_name) DO NOTHING;
+
+UPDATE computer_access ca
+SET info_system_id = s.id
+FROM info_systems s
+WHERE ca.info_system_id IS NULL
+  AND s.system_name = ca.system_name;
+
+CREATE OR REPLACE FUNCTION sync_computer_access_system_name()
+RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE computer_access SET system_name = NEW.system_name WHERE info_system_id = NEW.id;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS info_system_rename ON info_systems;
+CREATE TRIGGER info_system_rename
+    AFTER UPDATE OF system_name ON info_systems
+    FOR EACH ROW
+    WHEN (NEW.system_name IS DISTINCT FROM OLD.system_name)
+    EXECUTE FUNCTION sync_computer_access_system_name();
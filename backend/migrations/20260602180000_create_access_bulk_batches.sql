@@ -0,0 +1,31 @@
+-- Bulk grant/revoke batches.
+--
+-- Every applied bulk operation gets one batch id (a UUID). The batch row
+-- records what was asked for — operation, grant type, target and the
+-- selector used to pick people — and access_bulk_batch_items records the
+-- per-person outcome, including people that were skipped or blocked. Audit
+-- entries written for the batch carry the same id in their details.
+-- Dry runs are not stored. Idempotent.
+
+CREATE TABLE IF NOT EXISTS access_bulk_batches (
+    id TEXT PRIMARY KEY,
+    operation VARCHAR(10) NOT NULL CHECK (operation IN ('GRANT', 'REVOKE')),
+    access_type VARCHAR(20) NOT NULL CHECK (access_type IN ('computer', 'data', 'physical', 'resource')),
+    target VARCHAR(100) NOT NULL,
+    access_level VARCHAR(20),
+    selector JSONB NOT NULL,
+    reason TEXT,
+    performed_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS access_bulk_batch_items (
+    id SERIAL PRIMARY KEY,
+    batch_id TEXT NOT NULL REFERENCES access_bulk_batches(id) ON DELETE CASCADE,
+    person_id INTEGER NOT NULL,
+    outcome VARCHAR(20) NOT NULL CHECK (outcome IN ('GRANTED', 'REVOKED', 'SKIPPED', 'BLOCKED')),
+    grant_id TEXT,
+    detail TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_access_bulk_batch_items_batch ON access_bulk_batch_items(batch_id);
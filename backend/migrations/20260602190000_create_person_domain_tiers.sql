@@ -0,0 +1,20 @@
+-- Per-domain authorization tiers for ABAC decisions.
+--
+-- Each person holds at most one tier per domain, on that domain's own ladder
+-- (COMPUTER: STANDARD < PRIVILEGED < ROOT, DATA: INTERNAL < RESTRICTED <
+-- CLASSIFIED, PHYSICAL: LOBBY < RESTRICTED_AREA < SECURE_VAULT). Tiers never
+-- compare across domains. Idempotent.
+
+CREATE TABLE IF NOT EXISTS person_domain_tiers (
+    person_id INTEGER NOT NULL REFERENCES person(id) ON DELETE CASCADE,
+    domain VARCHAR(20) NOT NULL,
+    tier VARCHAR(30) NOT NULL,
+    granted_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    granted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (person_id, domain),
+    CONSTRAINT person_domain_tiers_tier_check CHECK (
+        (domain = 'COMPUTER' AND tier IN ('STANDARD', 'PRIVILEGED', 'ROOT'))
+        OR (domain = 'DATA' AND tier IN ('INTERNAL', 'RESTRICTED', 'CLASSIFIED'))
+        OR (domain = 'PHYSICAL' AND tier IN ('LOBBY', 'RESTRICTED_AREA', 'SECURE_VAULT'))
+    )
+);
@@ -0,0 +1,59 @@
+-- Compartments (need-to-know).
+--
+-- compartments defines the codes; person_compartments holds time-windowed
+-- grants with the approving person (revoked grants keep their row);
+-- compartment_requirements lists the compartments a data classification or a
+-- digital resource requires. Grants of data access or of a digital resource
+-- are refused while the person lacks a required compartment. Managed under
+-- compartments.manage (admin). Idempotent.
+
+CREATE TABLE IF NOT EXISTS compartments (
+    code VARCHAR(30) PRIMARY KEY CHECK (code ~ '^[A-Z][A-Z0-9_]*$'),
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS person_compartments (
+    id SERIAL PRIMARY KEY,
+    person_id INTEGER NOT NULL REFERENCES person(id) ON DELETE CASCADE,
+    compartment_code VARCHAR(30) NOT NULL REFERENCES compartments(code),
+    valid_from TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    valid_until TIMESTAMP,
+    approved_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    granted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    revoked_by_person_id INTEGER REFERENCES person(id),
+    revoked_at TIMESTAMP,
+    revocation_reason TEXT,
+    CONSTRAINT person_compartments_window CHECK (valid_until IS NULL OR valid_until > valid_from)
+);
+
+CREATE INDEX IF NOT EXISTS idx_person_compartments_person
+    ON person_compartments(person_id) WHERE revoked_at IS NULL;
+
+CREATE TABLE IF NOT EXISTS compartment_requirements (
+    id SERIAL PRIMARY KEY,
+    target_type VARCHAR(20) NOT NULL CHECK (target_type IN ('data', 'resource')),
+    target VARCHAR(100) NOT NULL, -- data_classification / resource_id
+    compartment_code VARCHAR(30) NOT NULL REFERENCES compartments(code),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (target_type, target, compartment_code)
+);
+
+INSERT INTO compartments (code, name) VALUES
+    ('AURORA', 'Aurora'),
+    ('BLACKWING', 'Blackwing'),
+    ('CITADEL', 'Citadel'),
+    ('SIGINT', 'Signals intelligence'),
+    ('STOCKWATCH', 'Stockwatch'),
+    ('HOMELAND', 'Homeland')
+ON CONFLICT (code) DO NOTHING;
+
+INSERT INTO permissions (key, description) VALUES
+    ('compartments.manage', 'Define compartments and requirements; grant and revoke compartment access')
+ON CONFLICT (key) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r JOIN permissions p ON p.key = 'compartments.manage'
+WHERE r.name = 'admin'
+ON CONFLICT DO NOTHING;
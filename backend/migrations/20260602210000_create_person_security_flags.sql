@@ -0,0 +1,59 @@
+-- Security hold and revocation flags.
+--
+-- A security officer can freeze a person with SECURITY_HOLD or REVOKED. While
+-- either flag is set every decision path denies (ABAC, the digital-resource
+-- resolver, the active access listing); stored grants are left as they are,
+-- so clearing the flag restores them. Each row records who set the flag, why
+-- and when, and later who cleared it; at most one uncleared row per flag.
+-- Managed under security.flags (admin and the new security_officer role).
+-- Idempotent.
+
+CREATE TABLE IF NOT EXISTS person_security_flags (
+    id SERIAL PRIMARY KEY,
+    person_id INTEGER NOT NULL REFERENCES person(id) ON DELETE CASCADE,
+    flag VARCHAR(20) NOT NULL CHECK (flag IN ('SECURITY_HOLD', 'REVOKED')),
+    reason TEXT NOT NULL,
+    set_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    set_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    cleared_by_person_id INTEGER REFERENCES person(id),
+    cleared_at TIMESTAMP,
+    clear_reason TEXT
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_person_security_flags_active
+    ON person_security_flags(person_id, flag) WHERE cleared_at IS NULL;
+
+INSERT INTO roles (name, description) VALUES
+    ('security_officer', 'Set and clear security holds; read persons and audit logs')
+ON CONFLICT (name) DO NOTHING;
+
+-- Allow the role in person.role and seed one officer login (password:
+-- password123, same hash as the other seed users).
+ALTER TABLE person DROP CONSTRAINT IF EXISTS person_role_check;
+ALTER TABLE person ADD CONSTRAINT person_role_check
+    CHECK (role IN ('admin', 'manager', 'operator', 'viewer', 'enduser', 'official',
+                    'security_officer'));
+
+INSERT INTO person (username, password_hash, role, email)
+SELECT 'security', '$2b$12$AGJy4fF9OGK19yHRCxG2Mu/Ju4E5i1RXnm.KYu.Oq3QUdf9vWOVG2',
+       'security_officer', 'security@janus.local'
+WHERE NOT EXISTS (SELECT 1 FROM person WHERE username = 'security');
+
+INSERT INTO permissions (key, description) VALUES
+    ('security.flags', 'Set and clear security hold / revocation flags on persons')
+ON CONFLICT (key) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r JOIN permissions p ON p.key = 'security.flags'
+WHERE r.name IN ('admin', 'security_officer')
+ON CONFLICT DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r JOIN permissions p ON p.key IN ('person.read', 'audit.read')
+WHERE r.name = 'security_officer'
+ON CONFLICT DO NOTHING;
+
+INSERT INTO sod_rules (name, rule_type, action_key, conflicting_key, description) VALUES
+    ('no-self-security-flag', 'NO_SELF_ACTION', 'security.flags', NULL,
+     'Nobody may set or clear a security flag on themselves')
+ON CONFLICT (name) DO NOTHING;
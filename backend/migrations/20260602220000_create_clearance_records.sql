@@ -0,0 +1,87 @@
+-- Clearance lifecycle.
+--
+-- clearance_records is the clearance history of a person: each record names
+-- the level, the granting authority, when it was granted, how long it is
+-- valid and when re-investigation is due. Only the newest record counts: the
+-- effective clearance is its level while it is GRANTED and not past
+-- valid_until, otherwise none (UNCLASSIFIED). SUSPENDED can be reinstated,
+-- REVOKED is final.
+--
+-- person.clearance_level stays as a mirror of the newest record's level for
+-- the person API. Writes to it from any other path (person API, imports,
+-- manual SQL) are recorded by a trigger as an open-ended GRANTED record; the
+-- clearance API writes the record first, so its mirror update is skipped.
+-- Existing clearances are backfilled at the person's created_at. Managed
+-- under clearance.manage (admin, security_officer). Idempotent.
+
+CREATE TABLE IF NOT EXISTS clearance_records (
+    id SERIAL PRIMARY KEY,
+    person_id INTEGER NOT NULL REFERENCES person(id) ON DELETE CASCADE,
+    level VARCHAR(50) NOT NULL
+        CHECK (level IN ('UNCLASSIFIED', 'CONFIDENTIAL', 'SECRET', 'TOP_SECRET')),
+    granting_authority VARCHAR(100) NOT NULL,
+    granted_by_person_id INTEGER REFERENCES person(id) ON DELETE SET NULL,
+    granted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    valid_until TIMESTAMP,
+    reinvestigation_due DATE,
+    status VARCHAR(20) NOT NULL DEFAULT 'GRANTED'
+        CHECK (status IN ('GRANTED', 'SUSPENDED', 'REVOKED')),
+    status_reason TEXT,
+    status_changed_by_person_id INTEGER REFERENCES person(id) ON DELETE SET NULL,
+    status_changed_at TIMESTAMP,
+    CONSTRAINT clearance_records_window CHECK (valid_until IS NULL OR valid_until > granted_at)
+);
+
+CREATE INDEX IF NOT EXISTS idx_clearance_records_person
+    ON clearance_records(person_id, granted_at DESC, id DESC);
+
+CREATE OR REPLACE FUNCTION record_clearance_grant()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.clearance_level IS NULL THEN
+        RETURN NEW;
+    END IF;
+    IF TG_OP = 'UPDATE' AND NEW.clearance_level IS NOT DISTINCT FROM OLD.clearance_level THEN
+        RETURN NEW;
+    END IF;
+    -- Already the newest record: the clearance API wrote it before mirroring.
+    IF EXISTS (
+        SELECT 1 FROM (
+            SELECT level, status FROM clearance_records WHERE person_id = NEW.id
+            ORDER BY granted_at DESC, id DESC LIMIT 1
+        ) newest
+        WHERE newest.level = NEW.clearance_level AND newest.status = 'GRANTED'
+    ) THEN
+        RETURN NEW;
+    END IF;
+    INSERT INTO clearance_records (person_id, level, granting_authority)
+    VALUES (NEW.id, NEW.clearance_level, 'person record');
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS person_clearance_grant ON person;
+CREATE TRIGGER person_clearance_grant
+    AFTER INSERT OR UPDATE OF clearance_level ON person
+    FOR EACH ROW
+    EXECUTE FUNCTION record_clearance_grant();
+
+INSERT INTO clearance_records (person_id, level, granting_authority, granted_at)
+SELECT p.id, p.clearance_level, 'legacy person record', p.created_at
+FROM person p
+WHERE p.clearance_level IS NOT NULL
+  AND NOT EXISTS (SELECT 1 FROM clearance_records c WHERE c.person_id = p.id);
+
+INSERT INTO permissions (key, description) VALUES
+    ('clearance.manage', 'Grant clearances and suspend, reinstate or revoke them')
+ON CONFLICT (key) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r JOIN permissions p ON p.key = 'clearance.manage'
+WHERE r.name IN ('admin', 'security_officer')
+ON CONFLICT DO NOTHING;
+
+INSERT INTO sod_rules (name, rule_type, action_key, conflicting_key, description) VALUES
+    ('no-self-clearance', 'NO_SELF_ACTION', 'clearance.manage', NULL,
+     'Nobody may grant or change their own clearance')
+ON CONFLICT (name) DO NOTHING;
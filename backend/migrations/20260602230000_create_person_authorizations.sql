@@ -0,0 +1,50 @@
+-- Managerial authorization lifecycle.
+--
+-- A line manager authorizes a report after an authorization conversation
+-- (AUTHORIZED, valid until a date, with an optional re-authorization due
+-- date), records one as PENDING while the conversation is outstanding, or
+-- withdraws it (WITHDRAWN). The newest record is the person's authorization;
+-- renewal adds a new record. An AUTHORIZED record past valid_until counts as
+-- EXPIRED. Decisions deny a person whose authorization is anything but
+-- AUTHORIZED; a person with no record is not subject to the rule.
+--
+-- Line managers come from person-to-person relations in force: the manager is
+-- the related person of a 'manager', 'supervisor' or 'reports_to' relation
+-- of the report, or the report is the related person of the manager's
+-- 'subordinate' relation. Managed under authorization.manage (admin,
+-- manager). Idempotent.
+
+CREATE TABLE IF NOT EXISTS person_authorizations (
+    id SERIAL PRIMARY KEY,
+    person_id INTEGER NOT NULL REFERENCES person(id) ON DELETE CASCADE,
+    status VARCHAR(20) NOT NULL CHECK (status IN ('AUTHORIZED', 'WITHDRAWN', 'PENDING')),
+    authorized_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    conversation_date DATE,
+    valid_until TIMESTAMP,
+    reauth_due DATE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    withdrawn_by_person_id INTEGER REFERENCES person(id),
+    withdrawn_at TIMESTAMP,
+    withdrawal_reason TEXT,
+    -- An authorization rests on a conversation and has an end date.
+    CONSTRAINT person_authorizations_authorized_complete CHECK (
+        status <> 'AUTHORIZED' OR (conversation_date IS NOT NULL AND valid_until IS NOT NULL)
+    )
+);
+
+CREATE INDEX IF NOT EXISTS idx_person_authorizations_person
+    ON person_authorizations(person_id, created_at DESC, id DESC);
+
+INSERT INTO permissions (key, description) VALUES
+    ('authorization.manage', 'Authorize reports or withdraw their authorization')
+ON CONFLICT (key) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r JOIN permissions p ON p.key = 'authorization.manage'
+WHERE r.name IN ('admin', 'manager')
+ON CONFLICT DO NOTHING;
+
+INSERT INTO sod_rules (name, rule_type, action_key, conflicting_key, description) VALUES
+    ('no-self-authorization', 'NO_SELF_ACTION', 'authorization.manage', NULL,
+     'Nobody may authorize themselves or withdraw their own authorization')
+ON CONFLICT (name) DO NOTHING;
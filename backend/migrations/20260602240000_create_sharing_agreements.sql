@@ -0,0 +1,49 @@
+-- Inter-organization sharing agreements.
+--
+-- An agreement lets one organization's records be shared with another: a
+-- BILATERAL agreement works both ways, a UNILATERAL one only from
+-- from_organization_id to to_organization_id. Its scope is one authorization
+-- domain (NULL = every domain) up to max_classification, inside the validity
+-- window. The affiliation rule of decisions passes for a person of another
+-- organization only under an agreement in force and in scope (the backend
+-- counterpart of the demo's AGREEMENTS / hasAgreement).
+--
+-- document_reference names the signed agreement (e.g. its archive number).
+-- Managed under agreements.manage (admin, security_officer); readable under
+-- organizations.read. Idempotent.
+
+CREATE TABLE IF NOT EXISTS sharing_agreements (
+    id SERIAL PRIMARY KEY,
+    from_organization_id INTEGER NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    to_organization_id INTEGER NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    direction VARCHAR(20) NOT NULL CHECK (direction IN ('BILATERAL', 'UNILATERAL')),
+    domain VARCHAR(20) CHECK (domain IN ('COMPUTER', 'DATA', 'PHYSICAL')),
+    max_classification VARCHAR(50) NOT NULL
+        CHECK (max_classification IN ('UNCLASSIFIED', 'CONFIDENTIAL', 'SECRET', 'TOP_SECRET')),
+    valid_from TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    valid_until TIMESTAMP,
+    document_reference VARCHAR(200),
+    created_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT sharing_agreements_distinct CHECK (from_organization_id <> to_organization_id),
+    CONSTRAINT sharing_agreements_window CHECK (valid_until IS NULL OR valid_until > valid_from)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sharing_agreements_from ON sharing_agreements(from_organization_id);
+CREATE INDEX IF NOT EXISTS idx_sharing_agreements_to ON sharing_agreements(to_organization_id);
+
+INSERT INTO permissions (key, description) VALUES
+    ('agreements.manage', 'Create, change and delete inter-organization sharing agreements')
+ON CONFLICT (key) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r JOIN permissions p ON p.key = 'agreements.manage'
+WHERE r.name IN ('admin', 'security_officer')
+ON CONFLICT DO NOTHING;
+
+-- Security officers manage agreements, so they also read organizations.
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r JOIN permissions p ON p.key = 'organizations.read'
+WHERE r.name = 'security_officer'
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,41 @@
+-- Per-organization release policies.
+--
+-- When another organization asks for an organization's record on a person,
+-- the holder's release policy decides which rules apply: clearance, domain
+-- tier, need-to-know and affiliation can each be switched off, and
+-- min_clearance_floor adds a clearance the requester must hold whatever the
+-- record requires (the backend counterpart of the demo's EntityPolicy /
+-- evaluateWithPolicy).
+--
+-- Policies are versioned: a change inserts the next version and the highest
+-- version is in force; earlier versions stay for the audit trail. An
+-- organization without a policy releases under the standard one (every rule,
+-- no floor).
+--
+-- Managed under policies.manage (admin, security_officer); readable under
+-- organizations.read. Idempotent.
+
+CREATE TABLE IF NOT EXISTS release_policies (
+    id SERIAL PRIMARY KEY,
+    organization_id INTEGER NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    version INTEGER NOT NULL CHECK (version > 0),
+    label VARCHAR(100) NOT NULL,
+    clearance_rule BOOLEAN NOT NULL DEFAULT TRUE,
+    domain_tier_rule BOOLEAN NOT NULL DEFAULT TRUE,
+    need_to_know_rule BOOLEAN NOT NULL DEFAULT TRUE,
+    affiliation_rule BOOLEAN NOT NULL DEFAULT TRUE,
+    min_clearance_floor VARCHAR(50)
+        CHECK (min_clearance_floor IN ('UNCLASSIFIED', 'CONFIDENTIAL', 'SECRET', 'TOP_SECRET')),
+    created_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT release_policies_version_unique UNIQUE (organization_id, version)
+);
+
+INSERT INTO permissions (key, description) VALUES
+    ('policies.manage', 'Change organizations'' release policies')
+ON CONFLICT (key) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r JOIN permissions p ON p.key = 'policies.manage'
+WHERE r.name IN ('admin', 'security_officer')
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,43 @@
+-- Directional shielding for digital resources and information systems.
+--
+-- A shielded resource can only be reached from its owning organization or an
+-- organization on its allowlist; anyone else is refused whatever their
+-- clearance and grants (the backend counterpart of the demo's
+-- Resource.shielded / allowlist and evaluateResourceAccess).
+--
+-- Digital resources: the owner is the organization holding an active
+-- ASSET_OWNER org link; org ids are the same opaque strings the resource
+-- tables use, so the allowlist has no FK. Shielding is a gate of the
+-- server-side decision (digital_resources::resolver).
+--
+-- Information systems: the owner is owner_organization_id; new computer
+-- grants to a shielded system need the grantee to belong to the owner or an
+-- allowlisted organization (access::systems). A shielded system without an
+-- owner is reachable from the allowlist only.
+--
+-- Unshielded is the default, so nothing changes until a resource is
+-- shielded. Idempotent.
+
+ALTER TABLE resource_networks ADD COLUMN IF NOT EXISTS shielded BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE resource_platforms ADD COLUMN IF NOT EXISTS shielded BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE resource_applications ADD COLUMN IF NOT EXISTS shielded BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE IF NOT EXISTS resource_shield_allowlist (
+    resource_id TEXT NOT NULL,
+    org_id TEXT NOT NULL,
+    added_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (resource_id, org_id)
+);
+
+ALTER TABLE info_systems ADD COLUMN IF NOT EXISTS shielded BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE info_systems
+    ADD COLUMN IF NOT EXISTS owner_organization_id INTEGER REFERENCES organizations(id) ON DELETE SET NULL;
+
+CREATE TABLE IF NOT EXISTS info_system_shield_allowlist (
+    info_system_id INTEGER NOT NULL REFERENCES info_systems(id) ON DELETE CASCADE,
+    organization_id INTEGER NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    added_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (info_system_id, organization_id)
+);
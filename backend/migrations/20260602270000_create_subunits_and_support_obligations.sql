@@ -0,0 +1,50 @@
+-- Subunits with deployment status, and support obligations between
+-- organizations.
+--
+-- A subunit belongs to an organization and is deployed at HOME or ABROAD. A
+-- support obligation says from_organization_id must support
+-- to_organization_id's deployed subunits. While a subunit is ABROAD, persons
+-- of an obliged organization reach it even without standing access; no grant
+-- is stored, the decision reads the deployment and obligations in force (the
+-- backend counterpart of the demo's SUBUNITS / SUPPORT_OBLIGATIONS and
+-- evaluateSubunitAccess).
+--
+-- Deployment changes silently change who can see what, so each one records
+-- who made it and when here and is audited with its reason.
+-- Managed under deployments.manage (admin, security_officer); readable under
+-- organizations.read. Idempotent.
+
+CREATE TABLE IF NOT EXISTS subunits (
+    id SERIAL PRIMARY KEY,
+    organization_id INTEGER NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    name VARCHAR(200) NOT NULL,
+    deployment VARCHAR(10) NOT NULL DEFAULT 'HOME' CHECK (deployment IN ('HOME', 'ABROAD')),
+    deployment_changed_by_person_id INTEGER REFERENCES person(id),
+    deployment_changed_at TIMESTAMP,
+    created_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_subunits_organization ON subunits(organization_id);
+
+CREATE TABLE IF NOT EXISTS support_obligations (
+    id SERIAL PRIMARY KEY,
+    from_organization_id INTEGER NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    to_organization_id INTEGER NOT NULL REFERENCES organizations(id) ON DELETE CASCADE,
+    created_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT support_obligations_distinct CHECK (from_organization_id <> to_organization_id),
+    CONSTRAINT support_obligations_unique UNIQUE (from_organization_id, to_organization_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_support_obligations_to ON support_obligations(to_organization_id);
+
+INSERT INTO permissions (key, description) VALUES
+    ('deployments.manage', 'Manage subunits, their deployment status and support obligations')
+ON CONFLICT (key) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r JOIN permissions p ON p.key = 'deployments.manage'
+WHERE r.name IN ('admin', 'security_officer')
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,69 @@
+-- Federated discovery between Janus instances.
+--
+-- Instances exchange only typed envelopes (PUBLISH, DISCOVER,
+-- DISCOVER_RESULT, REQUEST_DETAIL, DETAIL_RESPONSE — the backend counterpart
+-- of the demo's contract.ts). A holder publishes pointers to persons it
+-- holds under a federation-wide subject id; the hub instance keeps the
+-- pointers and answers discovery; details are requested from the holder
+-- directly, which releases the record only if its ABAC evaluation of the
+-- remote requester allows it and the record is not held or revoked.
+--
+-- federation_peers: the other instances, by entity id, with the base URL
+--   envelopes are sent to. release_agreement marks the peers this instance
+--   has an agreement with (the Affiliation rule for remote requesters).
+-- federation_publications: holder side, local person -> subject id per
+--   domain.
+-- federation_pointers: hub side, the pointers published to this instance.
+-- federation_transcript: every envelope sent or received, for observability
+--   (Network.transcript).
+--
+-- Managed under federation.manage (admin, security_officer). Idempotent.
+
+CREATE TABLE IF NOT EXISTS federation_peers (
+    entity_id VARCHAR(100) PRIMARY KEY,
+    base_url VARCHAR(500) NOT NULL,
+    release_agreement BOOLEAN NOT NULL DEFAULT FALSE,
+    created_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS federation_publications (
+    id SERIAL PRIMARY KEY,
+    person_id INTEGER NOT NULL REFERENCES person(id) ON DELETE CASCADE,
+    subject_id VARCHAR(100) NOT NULL,
+    domain VARCHAR(20) NOT NULL CHECK (domain IN ('COMPUTER', 'DATA', 'PHYSICAL')),
+    published_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    published_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT federation_publications_unique UNIQUE (subject_id, domain)
+);
+
+CREATE INDEX IF NOT EXISTS idx_federation_publications_person ON federation_publications(person_id);
+
+CREATE TABLE IF NOT EXISTS federation_pointers (
+    subject_id VARCHAR(100) NOT NULL,
+    holder VARCHAR(100) NOT NULL,
+    domain VARCHAR(20) NOT NULL,
+    published_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (subject_id, holder, domain)
+);
+
+CREATE TABLE IF NOT EXISTS federation_transcript (
+    id BIGSERIAL PRIMARY KEY,
+    direction VARCHAR(3) NOT NULL CHECK (direction IN ('IN', 'OUT')),
+    peer VARCHAR(100) NOT NULL,
+    kind VARCHAR(20) NOT NULL,
+    envelope JSONB NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_federation_transcript_created ON federation_transcript(created_at);
+
+INSERT INTO permissions (key, description) VALUES
+    ('federation.manage', 'Manage federation peers and the persons published to the federation hub')
+ON CONFLICT (key) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r JOIN permissions p ON p.key = 'federation.manage'
+WHERE r.name IN ('admin', 'security_officer')
+ON CONFLICT DO NOTHING;
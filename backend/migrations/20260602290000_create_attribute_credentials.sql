@@ -0,0 +1,51 @@
+-- Signed attribute credentials and the issuer key registry.
+--
+-- A credential is a set of attribute claims about a person (subject,
+-- entity, clearance, compartments, issuer, plus credential id, issue time
+-- and expiry) with an HMAC-SHA256 signature over their canonical
+-- serialization (the backend counterpart of the demo's credential.ts), so
+-- another system holding the issuer's key can trust a Janus-attested
+-- clearance offline.
+--
+-- credential_issuers: issuer id -> signing key, and whether the issuer is
+--   trusted when verifying. The key is never returned by the API.
+--   CREDENTIAL_ISSUER_ID names the entry this instance issues with.
+-- attribute_credentials: every credential issued here, with its claims and
+--   signature, so it can be revoked; verification rejects revoked ones.
+--
+-- Issuing and revoking need clearance.manage; the registry is managed under
+-- credentials.manage (admin, security_officer). Idempotent.
+
+CREATE TABLE IF NOT EXISTS credential_issuers (
+    issuer_id VARCHAR(100) PRIMARY KEY,
+    signing_key TEXT NOT NULL,
+    trusted BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS attribute_credentials (
+    id UUID PRIMARY KEY,
+    person_id INTEGER NOT NULL REFERENCES person(id) ON DELETE CASCADE,
+    issuer_id VARCHAR(100) NOT NULL,
+    claims JSONB NOT NULL,
+    signature TEXT NOT NULL,
+    issued_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    issued_at TIMESTAMP NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    revoked_at TIMESTAMP,
+    revoked_by_person_id INTEGER REFERENCES person(id),
+    revocation_reason TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_attribute_credentials_person ON attribute_credentials(person_id);
+
+INSERT INTO permissions (key, description) VALUES
+    ('credentials.manage', 'Manage the credential issuer key registry')
+ON CONFLICT (key) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r JOIN permissions p ON p.key = 'credentials.manage'
+WHERE r.name IN ('admin', 'security_officer')
+ON CONFLICT DO NOTHING;
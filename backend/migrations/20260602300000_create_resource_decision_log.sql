@@ -0,0 +1,70 @@
+-- Append-only log of the digital-resource decisions served over HTTP.
+--
+-- Every resolver decision the API returns is recorded before the response
+-- goes out: GET /access, and each subject GET /resources/<id>/who-can-access
+-- lists (allowed or near miss). A row holds the subject, the node, the
+-- decision time, the outcome with its gate trace and policy version, and the
+-- caller. What-if simulations decide nothing and are not logged. There are
+-- no foreign keys, so a record outlives the person or node it is about.
+--
+-- The log is append-only: a trigger rejects every UPDATE, and every DELETE
+-- unless the transaction set janus.decision_log_purge, which only the
+-- retention sweep does. decision_log_settings holds the one retention
+-- setting: records are kept for retention_days after recorded_at.
+--
+-- Readable under audit.read; retention is managed under decision_log.manage
+-- (admin, security_officer). Idempotent.
+
+CREATE TABLE IF NOT EXISTS resource_decision_log (
+    id BIGSERIAL PRIMARY KEY,
+    source VARCHAR(20) NOT NULL CHECK (source IN ('ACCESS', 'WHO_CAN_ACCESS')),
+    person_id INTEGER NOT NULL,
+    resource_id TEXT NOT NULL,
+    org_id TEXT,
+    decided_at TIMESTAMPTZ NOT NULL,
+    allow BOOLEAN NOT NULL,
+    reason VARCHAR(100),
+    gates JSONB NOT NULL,
+    policy_version JSONB,
+    caller_person_id INTEGER,
+    caller_role VARCHAR(50) NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_decision_log_person ON resource_decision_log(person_id, id DESC);
+CREATE INDEX IF NOT EXISTS idx_decision_log_resource ON resource_decision_log(resource_id, id DESC);
+CREATE INDEX IF NOT EXISTS idx_decision_log_recorded_at ON resource_decision_log(recorded_at);
+
+CREATE OR REPLACE FUNCTION protect_resource_decision_log()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' AND current_setting('janus.decision_log_purge', true) = 'on' THEN
+        RETURN OLD;
+    END IF;
+    RAISE EXCEPTION 'resource_decision_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS resource_decision_log_append_only ON resource_decision_log;
+CREATE TRIGGER resource_decision_log_append_only
+    BEFORE UPDATE OR DELETE ON resource_decision_log
+    FOR EACH ROW
+    EXECUTE FUNCTION protect_resource_decision_log();
+
+CREATE TABLE IF NOT EXISTS decision_log_settings (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    retention_days INTEGER NOT NULL DEFAULT 365 CHECK (retention_days BETWEEN 1 AND 3650),
+    updated_by_person_id INTEGER REFERENCES person(id),
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO decision_log_settings (id) VALUES (TRUE) ON CONFLICT (id) DO NOTHING;
+
+INSERT INTO permissions (key, description) VALUES
+    ('decision_log.manage', 'Manage the resource decision log retention')
+ON CONFLICT (key) DO NOTHING;
+
+INSERT INTO role_permissions (role_id, permission_id)
+SELECT r.id, p.id FROM roles r JOIN permissions p ON p.key = 'decision_log.manage'
+WHERE r.name IN ('admin', 'security_officer')
+ON CONFLICT DO NOTHING;
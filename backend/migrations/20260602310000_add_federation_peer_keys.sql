@@ -0,0 +1,10 @@
+-- Per-peer federation keys.
+--
+-- Envelopes used to be accepted from anyone holding the one key shared by the
+-- whole federation, so the `from` entity was only a claim. Each peer now has
+-- its own key: key_hash is the hex SHA-256 of the key the peer presents as
+-- X-Federation-Key, and an envelope is accepted only when it matches the peer
+-- named in `from`. Peers registered before carry no key and are refused until
+-- they are registered again with one. Idempotent.
+
+ALTER TABLE federation_peers ADD COLUMN IF NOT EXISTS key_hash VARCHAR(64);
@@ -0,0 +1,40 @@
+-- Close the gaps in the decision log's append-only guard.
+--
+-- The row trigger never fires on TRUNCATE, so a statement-level trigger now
+-- rejects it too. Purging moves into purge_resource_decision_log(purge_at), a
+-- SECURITY DEFINER function that deletes only the records past the retention
+-- setting at purge_at. It is the only code that sets janus.decision_log_purge,
+-- and it clears the setting again before returning.
+--
+-- The setting is still a convention, not a control: any session that may
+-- DELETE from the table can set it itself. Append-only is enforced against
+-- the application's code paths, not against the database. A deployment that
+-- wants it as a control runs the application under a role that does not own
+-- resource_decision_log, granted INSERT and SELECT on it and EXECUTE on the
+-- function; the function, running as the owner, is then the only way to
+-- delete. Idempotent.
+
+DROP TRIGGER IF EXISTS resource_decision_log_no_truncate ON resource_decision_log;
+CREATE TRIGGER resource_decision_log_no_truncate
+    BEFORE TRUNCATE ON resource_decision_log
+    FOR EACH STATEMENT
+    EXECUTE FUNCTION protect_resource_decision_log();
+
+CREATE OR REPLACE FUNCTION purge_resource_decision_log(purge_at TIMESTAMPTZ)
+RETURNS BIGINT
+LANGUAGE plpgsql
+SECURITY DEFINER
+SET search_path = public, pg_temp
+AS $$
+DECLARE
+    purged BIGINT;
+BEGIN
+    PERFORM set_config('janus.decision_log_purge', 'on', true);
+    DELETE FROM resource_decision_log
+    WHERE recorded_at < purge_at - make_interval(days =>
+          (SELECT retention_days FROM decision_log_settings));
+    GET DIAGNOSTICS purged = ROW_COUNT;
+    PERFORM set_config('janus.decision_log_purge', 'off', true);
+    RETURN purged;
+END;
+$$;
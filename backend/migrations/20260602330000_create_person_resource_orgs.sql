@@ -0,0 +1,20 @@
+-- The resource organization each person acts for.
+--
+-- The resolver's REQUIRED_ROLE and SHIELDING gates match the subject's org
+-- against resource_org_links and the shielding allowlist. Until now the
+-- server-side decisions took that org from the caller (SEED-012), so a
+-- SHIELDING pass could only be refused. A person now has at most one stored
+-- org, set by an admin; GET /access and who-can-access evaluate them as it,
+-- and only what-if simulations still accept an asserted org.
+--
+-- org_id is free text like resource_org_links.org_id; there is no org table.
+-- Idempotent.
+
+CREATE TABLE IF NOT EXISTS person_resource_orgs (
+    person_id INTEGER PRIMARY KEY REFERENCES person(id) ON DELETE CASCADE,
+    org_id TEXT NOT NULL,
+    assigned_by_person_id INTEGER NOT NULL REFERENCES person(id),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_person_resource_orgs_org ON person_resource_orgs(org_id);
@@ -0,0 +1,10 @@
+-- The organization a federation peer stands for.
+--
+-- A record used to be released to any peer flagged with release_agreement.
+-- The flag now only opens the way: the release also needs a sharing
+-- agreement in force from the holder's organization to the peer's, and
+-- follows the holder's release policy. Peers without an organization are
+-- refused until one is set. Idempotent.
+
+ALTER TABLE federation_peers
+    ADD COLUMN IF NOT EXISTS organization_id INTEGER REFERENCES organizations(id) ON DELETE SET NULL;
@@ -0,0 +1,15 @@
+-- Record the rule-based decisions in the decision log as well.
+--
+-- The log held only the digital-resource resolver's decisions. It now also
+-- takes those of POST /api/abac/decide (ABAC_DECIDE), POST /api/abac/release
+-- (ABAC_RELEASE), GET /api/deployments/subunits/<id>/access (SUBUNIT_ACCESS)
+-- and a holder's answer to POST /api/federation/detail-requests
+-- (FEDERATION_DETAIL). For these resource_id names the target rather than a
+-- resource node ("organization:3/DATA", "organization:3/person:12",
+-- "subunit:4", "federation:JANUS_B/S-1") and gates hold the whole decision.
+-- Idempotent.
+
+ALTER TABLE resource_decision_log DROP CONSTRAINT IF EXISTS resource_decision_log_source_check;
+ALTER TABLE resource_decision_log ADD CONSTRAINT resource_decision_log_source_check
+    CHECK (source IN ('ACCESS', 'WHO_CAN_ACCESS', 'ABAC_DECIDE', 'ABAC_RELEASE',
+                      'SUBUNIT_ACCESS', 'FEDERATION_DETAIL'));
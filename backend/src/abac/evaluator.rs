@@ -0,0 +1,451 @@
+// Pure ABAC evaluator.
+//
+// Ported from the TS source of truth in frontend/src/demo/lib/abac.ts
+// (evaluate, hasAgreement, releaseRequirementFor) with the TIERS ladders from
+// model.ts, plus evaluateWithAuth from auditlog.ts and evaluateWithPolicy
+// from policy.ts. This module is PURE: no Rocket, no PgPool; handlers assemble the
+// Principal and Requirement from the DB and pass the applicable agreements in.
+//
+// Invariants carried over from the TS engine:
+//   - tiers compare within their own domain ladder only, never across domains
+//     (TIERS[domain].indexOf — ENGINE-02); unknown tiers rank -1.
+//   - the domain tier rule is only evaluated when the requirement names both
+//     a domain and a tier.
+//   - deny overrides (revoked, security hold) fire regardless of the base
+//     rules and force DENY; base rules are still reported.
+// Detail strings are byte-equal to the TS output (parity: abac_parity.rs).
+use super::models::{AbacDecision, AbacRule, DECISION_ALLOW, DECISION_DENY};
+use crate::digital_resources::resolver::clearance_rank;
+
+pub const DOMAIN_COMPUTER: &str = "COMPUTER";
+pub const DOMAIN_DATA: &str = "DATA";
+pub const DOMAIN_PHYSICAL: &str = "PHYSICAL";
+
+/// Per-domain tier ladder, ordered low to high (TIERS in model.ts).
+pub fn tiers(domain: &str) -> Option<&'static [&'static str]> {
+    match domain {
+        DOMAIN_COMPUTER => Some(&["STANDARD", "PRIVILEGED", "ROOT"]),
+        DOMAIN_DATA => Some(&["INTERNAL", "RESTRICTED", "CLASSIFIED"]),
+        DOMAIN_PHYSICAL => Some(&["LOBBY", "RESTRICTED_AREA", "SECURE_VAULT"]),
+        _ => None,
+    }
+}
+
+/// Position of `tier` on the domain's ladder; -1 when either is unknown.
+pub fn tier_rank(domain: &str, tier: &str) -> i32 {
+    tiers(domain)
+        .and_then(|ladder| ladder.iter().position(|t| *t == tier))
+        .map_or(-1, |i| i as i32)
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SubjectFlags {
+    pub revoked: bool,
+    pub security_hold: bool,
+}
+
+/// The subject of a decision. `domain_auth` holds (domain, tier) pairs.
+#[derive(Debug, Clone, Default)]
+pub struct Principal {
+    pub entity: String,
+    pub clearance: String,
+    pub domain_auth: Vec<(String, String)>,
+    pub compartments: Vec<String>,
+    pub flags: SubjectFlags,
+}
+
+impl Principal {
+    fn tier_for(&self, domain: &str) -> Option<&str> {
+        self.domain_auth
+            .iter()
+            .find(|(d, _)| d == domain)
+            .map(|(_, t)| t.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Requirement {
+    pub min_clearance: String,
+    pub required_compartments: Vec<String>,
+    pub owner_unit: String,
+    pub domain: Option<String>,
+    pub required_tier: Option<String>,
+}
+
+/// Requirement for releasing a holder's record on `subject` to another
+/// entity: the subject's own clearance and compartments, gated by agreement
+/// with the holder (releaseRequirementFor).
+pub fn release_requirement_for(subject: &Principal, holder: &str) -> Requirement {
+    Requirement {
+        min_clearance: subject.clearance.clone(),
+        required_compartments: subject.compartments.clone(),
+        owner_unit: holder.to_string(),
+        domain: None,
+        required_tier: None,
+    }
+}
+
+/// Whether `a` and `b` may share: the same entity, or a pair in `agreements`
+/// in either order.
+pub fn has_agreement(agreements: &[(String, String)], a: &str, b: &str) -> bool {
+    a == b
+        || agreements
+            .iter()
+            .any(|(x, y)| (x == a && y == b) || (x == b && y == a))
+}
+
+fn rule(name: &str, pass: bool, detail: String) -> AbacRule {
+    AbacRule {
+        name: name.to_string(),
+        pass,
+        detail,
+    }
+}
+
+fn cmp(pass: bool) -> &'static str {
+    if pass {
+        "≥"
+    } else {
+        "<"
+    }
+}
+
+/// Evaluate the conjunctive base rules (clearance, domain tier, need-to-know,
+/// affiliation) and the deny overrides.
+pub fn evaluate(
+    principal: &Principal,
+    req: &Requirement,
+    agreements: &[(String, String)],
+) -> AbacDecision {
+    let mut rules = Vec::new();
+
+    let held_rank = clearance_rank(&principal.clearance);
+    let required_rank = clearance_rank(&req.min_clearance);
+    let ok_clear = held_rank >= required_rank;
+    rules.push(rule(
+        "Clearance",
+        ok_clear,
+        format!(
+            "{} ({}) {} required {} ({})",
+            principal.clearance,
+            held_rank,
+            cmp(ok_clear),
+            req.min_clearance,
+            required_rank
+        ),
+    ));
+
+    let domain = req.domain.as_deref().filter(|d| !d.is_empty());
+    let required_tier = req.required_tier.as_deref().filter(|t| !t.is_empty());
+    if let (Some(domain), Some(required_tier)) = (domain, required_tier) {
+        let held = principal.tier_for(domain);
+        let ok_tier =
+            held.is_some_and(|h| tier_rank(domain, h) >= tier_rank(domain, required_tier));
+        rules.push(rule(
+            "Domain tier",
+            ok_tier,
+            match held {
+                None => format!("no {} authorization (requires {})", domain, required_tier),
+                Some(h) => format!(
+                    "{}:{} {} required {}",
+                    domain,
+                    h,
+                    cmp(ok_tier),
+                    required_tier
+                ),
+            },
+        ));
+    }
+
+    let missing: Vec<&str> = req
+        .required_compartments
+        .iter()
+        .filter(|c| !principal.compartments.contains(c))
+        .map(String::as_str)
+        .collect();
+    rules.push(rule(
+        "Need-to-know",
+        missing.is_empty(),
+        if missing.is_empty() {
+            let required = req.required_compartments.join(", ");
+            format!(
+                "holds all required [{}]",
+                if required.is_empty() {
+                    "none"
+                } else {
+                    &required
+                }
+            )
+        } else {
+            format!("missing [{}]", missing.join(", "))
+        },
+    ));
+
+    let ok_aff = has_agreement(agreements, &principal.entity, &req.owner_unit);
+    rules.push(rule(
+        "Affiliation",
+        ok_aff,
+        if !ok_aff {
+            format!(
+                "no agreement between {} and {}",
+                principal.entity, req.owner_unit
+            )
+        } else if principal.entity == req.owner_unit {
+            format!("same entity ({})", req.owner_unit)
+        } else {
+            format!(
+                "cross-entity agreement {}↔{}",
+                principal.entity, req.owner_unit
+            )
+        },
+    ));
+
+    let mut overrides = Vec::new();
+    if principal.flags.revoked {
+        overrides.push(rule(
+            "Revoked",
+            false,
+            "subject access has been revoked".to_string(),
+        ));
+    }
+    if principal.flags.security_hold {
+        overrides.push(rule(
+            "Security hold",
+            false,
+            "flagged by Security Officer".to_string(),
+        ));
+    }
+
+    let base_pass = rules.iter().all(|r| r.pass);
+    let failed = rules
+        .iter()
+        .filter(|r| !r.pass)
+        .map(|r| r.name.clone())
+        .collect();
+    AbacDecision {
+        decision: if base_pass && overrides.is_empty() {
+            DECISION_ALLOW
+        } else {
+            DECISION_DENY
+        }
+        .to_string(),
+        rules,
+        overrides,
+        failed,
+    }
+}
+
+pub const AUTHORIZATION_AUTHORIZED: &str = "AUTHORIZED";
+pub const RULE_AUTHORIZATION_VALID: &str = "Authorization valid";
+
+/// evaluate() plus the managerial authorization rule (evaluateWithAuth):
+/// a subject with an authorization on record whose status is not AUTHORIZED
+/// gets a failing "Authorization valid" rule and is denied. No authorization
+/// on record adds no rule.
+pub fn evaluate_with_auth(
+    principal: &Principal,
+    req: &Requirement,
+    agreements: &[(String, String)],
+    authorization: Option<&str>,
+) -> AbacDecision {
+    with_authorization(evaluate(principal, req, agreements), authorization)
+}
+
+/// Add the managerial authorization rule to an evaluated decision.
+pub fn with_authorization(mut decision: AbacDecision, authorization: Option<&str>) -> AbacDecision {
+    if let Some(status) = authorization.filter(|s| *s != AUTHORIZATION_AUTHORIZED) {
+        decision.decision = DECISION_DENY.to_string();
+        decision.rules.push(rule(
+            RULE_AUTHORIZATION_VALID,
+            false,
+            format!("authorization.status={} (requires AUTHORIZED)", status),
+        ));
+        decision.failed.push(RULE_AUTHORIZATION_VALID.to_string());
+    }
+    decision
+}
+
+/// Which base rules a releasing entity applies (EntityPolicy.rules).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicyRules {
+    pub clearance: bool,
+    pub domain_tier: bool,
+    pub need_to_know: bool,
+    pub affiliation: bool,
+}
+
+/// An entity's release policy: its rule toggles and an optional clearance
+/// floor applied on top of the requirement's minimum.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityPolicy {
+    pub rules: PolicyRules,
+    pub min_clearance_floor: Option<String>,
+}
+
+impl Default for EntityPolicy {
+    /// The standard policy: every rule, no floor.
+    fn default() -> Self {
+        EntityPolicy {
+            rules: PolicyRules {
+                clearance: true,
+                domain_tier: true,
+                need_to_know: true,
+                affiliation: true,
+            },
+            min_clearance_floor: None,
+        }
+    }
+}
+
+/// Evaluate a request under the holder's release policy (evaluateWithPolicy):
+/// disabled rules are skipped, a floor adds a "Clearance floor" rule. Detail
+/// strings follow policy.ts, which words them differently from evaluate().
+pub fn evaluate_with_policy(
+    principal: &Principal,
+    req: &Requirement,
+    agreements: &[(String, String)],
+    policy: &EntityPolicy,
+) -> AbacDecision {
+    let mut rules = Vec::new();
+    let held_rank = clearance_rank(&principal.clearance);
+
+    if policy.rules.clearance {
+        let ok = held_rank >= clearance_rank(&req.min_clearance);
+        rules.push(rule(
+            "Clearance",
+            ok,
+            format!("{} {} {}", principal.clearance, cmp(ok), req.min_clearance),
+        ));
+    }
+
+    if let Some(floor) = policy
+        .min_clearance_floor
+        .as_deref()
+        .filter(|f| !f.is_empty())
+    {
+        let ok = held_rank >= clearance_rank(floor);
+        rules.push(rule(
+            "Clearance floor",
+            ok,
+            format!("{} {} entity floor {}", principal.clearance, cmp(ok), floor),
+        ));
+    }
+
+    let domain = req.domain.as_deref().filter(|d| !d.is_empty());
+    let required_tier = req.required_tier.as_deref().filter(|t| !t.is_empty());
+    if let (true, Some(domain), Some(required_tier)) =
+        (policy.rules.domain_tier, domain, required_tier)
+    {
+        let held = principal.tier_for(domain);
+        let ok = held.is_some_and(|h| tier_rank(domain, h) >= tier_rank(domain, required_tier));
+        rules.push(rule(
+            "Domain tier",
+            ok,
+            match held {
+                None => format!("no {} auth", domain),
+                Some(h) => format!("{}:{} {} {}", domain, h, cmp(ok), required_tier),
+            },
+        ));
+    }
+
+    if policy.rules.need_to_know {
+        let missing: Vec<&str> = req
+            .required_compartments
+            .iter()
+            .filter(|c| !principal.compartments.contains(c))
+            .map(String::as_str)
+            .collect();
+        rules.push(rule(
+            "Need-to-know",
+            missing.is_empty(),
+            if missing.is_empty() {
+                "all compartments held".to_string()
+            } else {
+                format!("missing [{}]", missing.join(", "))
+            },
+        ));
+    }
+
+    if policy.rules.affiliation {
+        let ok = has_agreement(agreements, &principal.entity, &req.owner_unit);
+        rules.push(rule(
+            "Affiliation",
+            ok,
+            if ok {
+                "agreement present".to_string()
+            } else {
+                format!("no agreement {}↔{}", principal.entity, req.owner_unit)
+            },
+        ));
+    }
+
+    let mut overrides = Vec::new();
+    if principal.flags.revoked {
+        overrides.push(rule("Revoked", false, "access revoked".to_string()));
+    }
+    if principal.flags.security_hold {
+        overrides.push(rule(
+            "Security hold",
+            false,
+            "flagged by Security Officer".to_string(),
+        ));
+    }
+
+    let base_pass = rules.iter().all(|r| r.pass);
+    let failed = rules
+        .iter()
+        .filter(|r| !r.pass)
+        .map(|r| r.name.clone())
+        .collect();
+    AbacDecision {
+        decision: if base_pass && overrides.is_empty() {
+            DECISION_ALLOW
+        } else {
+            DECISION_DENY
+        }
+        .to_string(),
+        rules,
+        overrides,
+        failed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tier_rank_is_per_domain() {
+        assert_eq!(tier_rank(DOMAIN_COMPUTER, "ROOT"), 2);
+        assert_eq!(tier_rank(DOMAIN_DATA, "RESTRICTED"), 1);
+        // Tiers never carry across ladders.
+        assert_eq!(tier_rank(DOMAIN_COMPUTER, "RESTRICTED"), -1);
+        assert_eq!(tier_rank("NETWORK", "STANDARD"), -1);
+    }
+
+    #[test]
+    fn test_has_agreement_is_symmetric() {
+        let agreements = vec![("1".to_string(), "2".to_string())];
+        assert!(has_agreement(&agreements, "2", "1"));
+        assert!(has_agreement(&[], "3", "3"));
+        assert!(!has_agreement(&agreements, "1", "3"));
+    }
+
+    #[test]
+    fn test_no_tier_rule_without_domain() {
+        let principal = Principal {
+            entity: "1".to_string(),
+            clearance: "SECRET".to_string(),
+            ..Default::default()
+        };
+        let req = Requirement {
+            min_clearance: "CONFIDENTIAL".to_string(),
+            owner_unit: "1".to_string(),
+            required_tier: Some("ROOT".to_string()),
+            ..Default::default()
+        };
+        let decision = evaluate(&principal, &req, &[]);
+        assert_eq!(decision.decision, DECISION_ALLOW);
+        assert!(decision.rules.iter().all(|r| r.name != "Domain tier"));
+    }
+}
@@ -0,0 +1,294 @@
+// ABAC HTTP handlers (mounted at /api/abac)
+//
+// POST /decide evaluates a stored person against a COMPUTER/DATA/PHYSICAL
+// requirement with the ported evaluator and returns the explainable rule list,
+// including the managerial "Authorization valid" rule. POST /release decides
+// whether an organization may release its record on a person to someone of
+// another organization, under the holder's release policy. Both record their
+// decision in the decision log before returning it.
+// Domain tiers are managed under access.write; reading them and asking for a
+// decision needs person.read.
+use rocket::serde::json::Json;
+use rocket::{delete, get, http::Status, post, put, State};
+use sqlx::PgPool;
+use validator::Validate;
+
+use super::evaluator::{
+    evaluate_with_auth, evaluate_with_policy, release_requirement_for, tier_rank, tiers,
+    with_authorization, Requirement,
+};
+use super::models::{
+    AbacDecisionRequest, AbacDecisionResponse, AbacReleaseRequest, AbacReleaseResponse,
+    PersonDomainTier, SetDomainTierRequest,
+};
+use super::principal::load_principal;
+use crate::audit::handlers::audit;
+use crate::auth::middleware::AuthGuard;
+use crate::authorizations::lifecycle::load_authorization_status;
+use crate::decision_log::log::{
+    record_decisions, ruled_decision_record, SOURCE_ABAC_DECIDE, SOURCE_ABAC_RELEASE,
+};
+use crate::digital_resources::resolver::clearance_rank;
+use crate::release_policies::policy::load_release_policy;
+use crate::shared::rbac::require_permission;
+use crate::shared::response::ApiResponse;
+use crate::sharing_agreements::affiliation::load_affiliation_agreements;
+use crate::sod::enforce::enforce_no_self_action;
+
+const DOMAIN_TIER_COLUMNS: &str = "person_id, domain, tier, granted_by_person_id, granted_at";
+
+fn db_error(e: sqlx::Error) -> Status {
+    eprintln!("Database error: {:?}", e);
+    Status::InternalServerError
+}
+
+/// Evaluate a person against a requirement and record the decision. 400 for
+/// an unknown domain, tier or clearance; 404 for an unknown or offboarded
+/// person.
+#[post("/decide", data = "<data>")]
+pub async fn decide(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    data: Json<AbacDecisionRequest>,
+) -> Result<Json<ApiResponse<AbacDecisionResponse>>, Status> {
+    require_permission(db.inner(), &auth, "person.read").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    if tiers(&data.domain).is_none()
+        || clearance_rank(&data.min_clearance) < 0
+        || data
+            .required_tier
+            .as_deref()
+            .is_some_and(|t| tier_rank(&data.domain, t) < 0)
+    {
+        return Err(Status::BadRequest);
+    }
+
+    let now = chrono::Utc::now().naive_utc();
+    let agreements = load_affiliation_agreements(
+        db.inner(),
+        data.owner_organization_id,
+        Some(&data.domain),
+        &data.min_clearance,
+        now,
+    )
+    .await
+    .map_err(db_error)?;
+    let (principal, organization_id) = load_principal(
+        db.inner(),
+        data.person_id,
+        data.owner_organization_id,
+        &agreements,
+    )
+    .await
+    .map_err(db_error)?
+    .ok_or(Status::NotFound)?;
+    let authorization = load_authorization_status(db.inner(), data.person_id, now)
+        .await
+        .map_err(db_error)?;
+    let requirement = Requirement {
+        min_clearance: data.min_clearance.clone(),
+        required_compartments: data.required_compartments.clone(),
+        owner_unit: data.owner_organization_id.to_string(),
+        domain: Some(data.domain.clone()),
+        required_tier: data.required_tier.clone(),
+    };
+    let decision = evaluate_with_auth(
+        &principal,
+        &requirement,
+        &agreements,
+        authorization.as_deref(),
+    );
+    let record = ruled_decision_record(
+        SOURCE_ABAC_DECIDE,
+        data.person_id,
+        format!(
+            "organization:{}/{}",
+            data.owner_organization_id, data.domain
+        ),
+        organization_id,
+        &decision,
+        &auth.claims,
+    );
+    record_decisions(db.inner(), &[record])
+        .await
+        .map_err(db_error)?;
+
+    Ok(Json(ApiResponse::success(AbacDecisionResponse {
+        person_id: data.person_id,
+        organization_id,
+        decision,
+    })))
+}
+
+/// Decide whether the holder organization may release its record on the
+/// subject to the requester: the requester must meet the subject's clearance
+/// and compartments (releaseRequirementFor) under the holder's release policy
+/// in force, the standard one when none is set. The decision is recorded for
+/// the requester. 404 for an unknown person or organization.
+#[post("/release", data = "<data>")]
+pub async fn release(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    data: Json<AbacReleaseRequest>,
+) -> Result<Json<ApiResponse<AbacReleaseResponse>>, Status> {
+    require_permission(db.inner(), &auth, "person.read").await?;
+    let holder = data.holder_organization_id;
+    sqlx::query_scalar::<_, i32>(
+        "SELECT id FROM organizations WHERE id = $1 AND deleted_at IS NULL",
+    )
+    .bind(holder)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(db_error)?
+    .ok_or(Status::NotFound)?;
+
+    let now = chrono::Utc::now().naive_utc();
+    let (subject, _) = load_principal(db.inner(), data.subject_person_id, holder, &[])
+        .await
+        .map_err(db_error)?
+        .ok_or(Status::NotFound)?;
+    // A person record belongs to no authorization domain: only agreements
+    // covering every domain permit releasing it.
+    let agreements = load_affiliation_agreements(db.inner(), holder, None, &subject.clearance, now)
+        .await
+        .map_err(db_error)?;
+    let (requester, organization_id) =
+        load_principal(db.inner(), data.requester_person_id, holder, &agreements)
+            .await
+            .map_err(db_error)?
+            .ok_or(Status::NotFound)?;
+    let policy = load_release_policy(db.inner(), holder)
+        .await
+        .map_err(db_error)?;
+    let authorization = load_authorization_status(db.inner(), data.requester_person_id, now)
+        .await
+        .map_err(db_error)?;
+
+    let requirement = release_requirement_for(&subject, &holder.to_string());
+    let decision = with_authorization(
+        evaluate_with_policy(
+            &requester,
+            &requirement,
+            &agreements,
+            &policy
+                .as_ref()
+                .map(|p| p.entity_policy())
+                .unwrap_or_default(),
+        ),
+        authorization.as_deref(),
+    );
+    let mut record = ruled_decision_record(
+        SOURCE_ABAC_RELEASE,
+        data.requester_person_id,
+        format!("organization:{}/person:{}", holder, data.subject_person_id),
+        organization_id,
+        &decision,
+        &auth.claims,
+    );
+    record.policy_version = policy.as_ref().map(|p| serde_json::json!(p.version));
+    record_decisions(db.inner(), &[record])
+        .await
+        .map_err(db_error)?;
+
+    Ok(Json(ApiResponse::success(AbacReleaseResponse {
+        requester_person_id: data.requester_person_id,
+        subject_person_id: data.subject_person_id,
+        holder_organization_id: holder,
+        organization_id,
+        policy_version: policy.map(|p| p.version),
+        decision,
+    })))
+}
+
+#[get("/persons/<id>/domain-tiers")]
+pub async fn list_domain_tiers(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<Vec<PersonDomainTier>>>, Status> {
+    require_permission(db.inner(), &auth, "person.read").await?;
+    let tiers = sqlx::query_as::<_, PersonDomainTier>(&format!(
+        "SELECT {} FROM person_domain_tiers WHERE person_id = $1 ORDER BY domain",
+        DOMAIN_TIER_COLUMNS
+    ))
+    .bind(id)
+    .fetch_all(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    Ok(Json(ApiResponse::success(tiers)))
+}
+
+/// Set (or replace) the person's tier in one domain.
+#[put("/persons/<id>/domain-tiers", data = "<data>")]
+pub async fn set_domain_tier(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    data: Json<SetDomainTierRequest>,
+) -> Result<Json<ApiResponse<PersonDomainTier>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "access.write").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    if tier_rank(&data.domain, &data.tier) < 0 {
+        return Err(Status::BadRequest);
+    }
+    enforce_no_self_action(db.inner(), "access.write", &caller, &id).await?;
+
+    let tier = sqlx::query_as::<_, PersonDomainTier>(&format!(
+        "INSERT INTO person_domain_tiers (person_id, domain, tier, granted_by_person_id) \
+         SELECT id, $2, $3, $4 FROM person WHERE id = $1 AND deleted_at IS NULL \
+         ON CONFLICT (person_id, domain) DO UPDATE \
+         SET tier = EXCLUDED.tier, granted_by_person_id = EXCLUDED.granted_by_person_id, \
+             granted_at = CURRENT_TIMESTAMP \
+         RETURNING {}",
+        DOMAIN_TIER_COLUMNS
+    ))
+    .bind(id)
+    .bind(&data.domain)
+    .bind(&data.tier)
+    .bind(caller)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(db_error)?
+    .ok_or(Status::NotFound)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "DOMAIN_TIER_SET",
+        "person",
+        Some(id),
+        format!("{}:{}", tier.domain, tier.tier),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(tier)))
+}
+
+#[delete("/persons/<id>/domain-tiers/<domain>")]
+pub async fn clear_domain_tier(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    domain: &str,
+) -> Result<Json<ApiResponse<()>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "access.write").await?;
+    let removed =
+        sqlx::query("DELETE FROM person_domain_tiers WHERE person_id = $1 AND domain = $2")
+            .bind(id)
+            .bind(domain)
+            .execute(db.inner())
+            .await
+            .map_err(|_| Status::InternalServerError)?;
+    if removed.rows_affected() == 0 {
+        return Err(Status::NotFound);
+    }
+    audit(
+        db.inner(),
+        caller,
+        "DOMAIN_TIER_CLEARED",
+        "person",
+        Some(id),
+        format!("{} tier removed", domain),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(())))
+}
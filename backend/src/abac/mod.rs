@@ -0,0 +1,17 @@
+// ABAC module
+// Explainable clearance / domain tier / need-to-know / affiliation decisions
+
+pub mod evaluator;
+pub mod handlers;
+pub mod models;
+pub mod principal;
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![
+        handlers::decide,
+        handlers::release,
+        handlers::list_domain_tiers,
+        handlers::set_domain_tier,
+        handlers::clear_domain_tier,
+    ]
+}
@@ -0,0 +1,80 @@
+// ABAC decision data models
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+pub const DECISION_ALLOW: &str = "ALLOW";
+pub const DECISION_DENY: &str = "DENY";
+
+/// One evaluated rule, with a human-readable explanation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AbacRule {
+    pub name: String,
+    pub pass: bool,
+    pub detail: String,
+}
+
+/// Same shape as the TS `Decision`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AbacDecision {
+    pub decision: String,         // ALLOW, DENY
+    pub rules: Vec<AbacRule>,     // base conjunctive rules
+    pub overrides: Vec<AbacRule>, // deny overrides that fired
+    pub failed: Vec<String>,      // names of base rules that failed
+}
+
+/// Evaluate whether a person meets a requirement in one authorization domain
+#[derive(Debug, Deserialize, Validate)]
+pub struct AbacDecisionRequest {
+    pub person_id: i32,
+    pub domain: String, // COMPUTER, DATA, PHYSICAL
+    pub required_tier: Option<String>,
+    pub min_clearance: String,
+    #[serde(default)]
+    pub required_compartments: Vec<String>,
+    pub owner_organization_id: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AbacDecisionResponse {
+    pub person_id: i32,
+    pub organization_id: Option<i32>, // the affiliation evaluated; None when unaffiliated
+    #[serde(flatten)]
+    pub decision: AbacDecision,
+}
+
+/// Ask whether the holder organization may release its record on the subject
+/// to the requester
+#[derive(Debug, Deserialize, Validate)]
+pub struct AbacReleaseRequest {
+    pub requester_person_id: i32,
+    pub subject_person_id: i32,
+    pub holder_organization_id: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AbacReleaseResponse {
+    pub requester_person_id: i32,
+    pub subject_person_id: i32,
+    pub holder_organization_id: i32,
+    pub organization_id: Option<i32>, // the requester's affiliation evaluated
+    pub policy_version: Option<i32>,  // None = the standard policy
+    #[serde(flatten)]
+    pub decision: AbacDecision,
+}
+
+/// A person's authorization tier in one domain
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PersonDomainTier {
+    pub person_id: i32,
+    pub domain: String,
+    pub tier: String,
+    pub granted_by_person_id: i32,
+    pub granted_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetDomainTierRequest {
+    pub domain: String,
+    #[validate(length(min = 1, max = 30))]
+    pub tier: String,
+}
@@ -0,0 +1,85 @@
+// Assemble the evaluator's Principal from stored person attributes.
+//
+// entity is the organization the person belongs to (employee or consultant
+// relation), as a string id. A person in several organizations is evaluated
+// as a member of the resource owner when they belong to it, otherwise of
+// their lowest-id organization the owner has a sharing agreement with, and
+// otherwise of their lowest-id organization; unaffiliated persons get "none",
+// which only matches itself. clearance is the effective clearance now;
+// compartments are the grants in force now; flags are the uncleared security
+// flags, which force DENY.
+use sqlx::PgPool;
+
+use super::evaluator::Principal;
+use crate::access::clearance::load_effective_clearance;
+use crate::compartments::checks::active_compartments;
+use crate::security_flags::flags::load_subject_flags;
+
+pub const UNAFFILIATED: &str = "none";
+
+/// Organizations `person_id` is an employee or consultant of, ordered by id.
+pub async fn person_organization_ids(db: &PgPool, person_id: i32) -> Result<Vec<i32>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT DISTINCT org_id FROM ( \
+           SELECT r.entity_id AS org_id FROM relations r \
+            WHERE r.entity_type IN ('vendor', 'organization') \
+              AND r.related_entity_type = 'person' AND r.related_entity_id = $1 \
+              AND r.relation_type IN ('employee', 'consultant') \
+           UNION \
+           SELECT r.related_entity_id FROM relations r \
+            WHERE r.entity_type = 'person' AND r.entity_id = $1 \
+              AND r.related_entity_type IN ('vendor', 'organization') \
+              AND r.relation_type IN ('employee', 'consultant') \
+         ) orgs ORDER BY org_id",
+    )
+    .bind(person_id)
+    .fetch_all(db)
+    .await
+}
+
+/// The person's principal for a decision about something `owner_org_id`
+/// owns, given the decision's (owner, recipient) agreement pairs, plus the
+/// organization chosen as their entity. None when the person does not exist
+/// or is offboarded.
+pub async fn load_principal(
+    db: &PgPool,
+    person_id: i32,
+    owner_org_id: i32,
+    agreements: &[(String, String)],
+) -> Result<Option<(Principal, Option<i32>)>, sqlx::Error> {
+    let now = chrono::Utc::now().naive_utc();
+    let Some(clearance) = load_effective_clearance(db, person_id, now).await? else {
+        return Ok(None);
+    };
+
+    let orgs = person_organization_ids(db, person_id).await?;
+    let entity = if orgs.contains(&owner_org_id) {
+        Some(owner_org_id)
+    } else {
+        orgs.iter()
+            .find(|org| {
+                let org = org.to_string();
+                agreements.iter().any(|(_, recipient)| *recipient == org)
+            })
+            .or(orgs.first())
+            .copied()
+    };
+
+    let domain_auth = sqlx::query_as::<_, (String, String)>(
+        "SELECT domain, tier FROM person_domain_tiers WHERE person_id = $1 ORDER BY domain",
+    )
+    .bind(person_id)
+    .fetch_all(db)
+    .await?;
+
+    let compartments = active_compartments(db, person_id, now).await?;
+
+    let principal = Principal {
+        entity: entity.map_or_else(|| UNAFFILIATED.to_string(), |id| id.to_string()),
+        clearance: clearance.unwrap_or_else(|| "UNCLASSIFIED".to_string()),
+        domain_auth,
+        compartments,
+        flags: load_subject_flags(db, person_id).await?,
+    };
+    Ok(Some((principal, entity)))
+}
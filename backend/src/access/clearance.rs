@@ -0,0 +1,192 @@
+// Clearance checks for classified access grants.
+//
+// Data grants are classified by data_classification; computer grants by the
+// classification of the info_systems row with the same system_name (NULL =
+// unclassified, no check). A grant is allowed only when the holder's
+// effective clearance ranks at or above it, ranked with the resolver's
+// clearance_rank so both access models agree.
+//
+// The effective clearance comes from the newest clearance_records row: its
+// level while it is GRANTED and before valid_until, otherwise none. A person
+// with no clearance ranks as UNCLASSIFIED. person.clearance_level only
+// mirrors the last granted level and is not read by any check.
+use chrono::NaiveDateTime;
+use rocket::http::Status;
+use sqlx::{PgExecutor, PgPool};
+
+use super::grants::{ACCESS_COMPUTER, ACCESS_DATA};
+use super::models::ClearanceViolation;
+use crate::audit::handlers::create_audit_log;
+use crate::audit::models::CreateAuditLogRequest;
+use crate::digital_resources::resolver::clearance_rank;
+
+/// SQL expression for the effective clearance of `person` (a column or
+/// placeholder) at `at`; NULL when there is none.
+pub fn effective_clearance_sql(person: &str, at: &str) -> String {
+    format!(
+        "(SELECT CASE WHEN cr.status = 'GRANTED' \
+                       AND (cr.valid_until IS NULL OR cr.valid_until > {at}) \
+                      THEN cr.level END \
+          FROM clearance_records cr WHERE cr.person_id = {person} \
+          ORDER BY cr.granted_at DESC, cr.id DESC LIMIT 1)"
+    )
+}
+
+/// The person's effective clearance at `now`; the outer None when the person
+/// does not exist or is offboarded.
+pub async fn load_effective_clearance<'e>(
+    executor: impl PgExecutor<'e>,
+    person_id: i32,
+    now: NaiveDateTime,
+) -> Result<Option<Option<String>>, sqlx::Error> {
+    sqlx::query_scalar::<_, Option<String>>(&format!(
+        "SELECT {} FROM person p WHERE p.id = $1 AND p.deleted_at IS NULL",
+        effective_clearance_sql("p.id", "$2")
+    ))
+    .bind(person_id)
+    .bind(now)
+    .fetch_optional(executor)
+    .await
+}
+
+/// Whether a holder with `clearance` may access `classification`. Unknown
+/// classifications fail closed.
+pub fn clearance_covers(clearance: Option<&str>, classification: &str) -> bool {
+    let required = clearance_rank(classification);
+    required >= 0 && clearance_rank(clearance.unwrap_or("UNCLASSIFIED")) >= required
+}
+
+/// Classification a grant of `access_type` on `target` requires, if any.
+pub async fn required_classification(
+    db: &PgPool,
+    access_type: &str,
+    target: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    match access_type {
+        ACCESS_DATA => Ok(Some(target.to_string())),
+        ACCESS_COMPUTER => sqlx::query_scalar::<_, Option<String>>(
+            "SELECT classification FROM info_systems WHERE system_name = $1",
+        )
+        .bind(target)
+        .fetch_optional(db)
+        .await
+        .map(Option::flatten),
+        _ => Ok(None),
+    }
+}
+
+/// Refuse (403 + CLEARANCE_CHECK_BLOCKED audit entry) a grant the person's
+/// clearance does not cover. 404 when the person does not exist.
+pub async fn enforce_clearance(
+    db: &PgPool,
+    actor: i32,
+    person_id: i32,
+    access_type: &str,
+    target: &str,
+) -> Result<(), Status> {
+    let Some(classification) = required_classification(db, access_type, target)
+        .await
+        .map_err(|_| Status::InternalServerError)?
+    else {
+        return Ok(());
+    };
+    let clearance = load_effective_clearance(db, person_id, chrono::Utc::now().naive_utc())
+        .await
+        .map_err(|_| Status::InternalServerError)?
+        .ok_or(Status::NotFound)?;
+
+    if clearance_covers(clearance.as_deref(), &classification) {
+        return Ok(());
+    }
+    let _ = create_audit_log(
+        &CreateAuditLogRequest {
+            person_id: Some(actor),
+            username: actor.to_string(),
+            action: "CLEARANCE_CHECK_BLOCKED".to_string(),
+            resource_type: format!("{}_access", access_type),
+            resource_id: None,
+            details: Some(format!(
+                "{} access to '{}' ({}) refused for person_id={} with clearance {}",
+                access_type,
+                target,
+                classification,
+                person_id,
+                clearance.as_deref().unwrap_or("none")
+            )),
+            ip_address: None,
+            user_agent: None,
+        },
+        db,
+    )
+    .await;
+    Err(Status::Forbidden)
+}
+
+// Every active classified grant with its holder's effective clearance,
+// optionally for one person ($2). The rank comparison happens in Rust so it
+// shares clearance_rank with the grant-time check.
+fn classified_grants_sql() -> String {
+    let clearance = effective_clearance_sql("g.person_id", "$1");
+    format!(
+        r#"
+    SELECT 'data' AS access_type, g.id AS access_id, g.person_id,
+           {clearance} AS clearance_level,
+           g.data_classification AS target, g.data_classification AS classification,
+           g.access_level
+    FROM data_access g
+    WHERE g.status = 'ACTIVE' AND (g.expires_at IS NULL OR g.expires_at > $1)
+      AND ($2::int IS NULL OR g.person_id = $2)
+    UNION ALL
+    SELECT 'computer', g.id, g.person_id, {clearance},
+           g.system_name, s.classification, g.access_level
+    FROM computer_access g
+    JOIN info_systems s ON s.id = g.info_system_id
+    WHERE s.classification IS NOT NULL
+      AND g.status = 'ACTIVE' AND (g.expires_at IS NULL OR g.expires_at > $1)
+      AND ($2::int IS NULL OR g.person_id = $2)
+    ORDER BY person_id, access_type, access_id
+"#
+    )
+}
+
+/// Active data and classified-computer grants whose classification exceeds
+/// the holder's current clearance; all persons when `person_id` is None.
+pub async fn load_clearance_violations<'e>(
+    executor: impl PgExecutor<'e>,
+    person_id: Option<i32>,
+    now: NaiveDateTime,
+) -> Result<Vec<ClearanceViolation>, sqlx::Error> {
+    let candidates = sqlx::query_as::<_, ClearanceViolation>(&classified_grants_sql())
+        .bind(now)
+        .bind(person_id)
+        .fetch_all(executor)
+        .await?;
+    Ok(candidates
+        .into_iter()
+        .filter(|v| !clearance_covers(v.clearance_level.as_deref(), &v.classification))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clearance_covers_by_rank() {
+        assert!(clearance_covers(Some("TOP_SECRET"), "SECRET"));
+        assert!(clearance_covers(Some("SECRET"), "SECRET"));
+        assert!(!clearance_covers(Some("CONFIDENTIAL"), "SECRET"));
+    }
+
+    #[test]
+    fn test_clearance_covers_missing_clearance_is_unclassified() {
+        assert!(clearance_covers(None, "UNCLASSIFIED"));
+        assert!(!clearance_covers(None, "CONFIDENTIAL"));
+    }
+
+    #[test]
+    fn test_clearance_covers_unknown_fails_closed() {
+        assert!(!clearance_covers(Some("TOP_SECRET"), "COSMIC"));
+        assert!(!clearance_covers(Some("bogus"), "UNCLASSIFIED"));
+    }
+}
@@ -0,0 +1,156 @@
+// Automatic expiry of computer/data/physical access grants.
+//
+// A background task (spawned from create_rocket) periodically flips ACTIVE
+// grants whose expires_at / valid_until has passed to EXPIRED and writes one
+// ACCESS_EXPIRED audit entry per grant. Between runs the read paths filter on
+// the expiry column themselves, so a lapsed grant is never reported as active.
+// The sweep also ends the data and resource grants whose required compartment
+// has lapsed; those stay in force until the next run.
+use std::time::Duration;
+
+use chrono::{NaiveDateTime, Utc};
+use sqlx::PgPool;
+
+use super::revocation::revoke_missing_compartments;
+use crate::audit::handlers::create_audit_log;
+use crate::audit::models::CreateAuditLogRequest;
+
+/// Default seconds between expiry sweeps; override with ACCESS_EXPIRY_INTERVAL_SECS.
+pub const DEFAULT_EXPIRY_INTERVAL_SECS: u64 = 300;
+
+/// Interval from ACCESS_EXPIRY_INTERVAL_SECS, falling back to the default on
+/// missing, unparsable or zero values.
+pub fn expiry_interval(raw: Option<String>) -> Duration {
+    let secs = raw
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|s| *s > 0)
+        .unwrap_or(DEFAULT_EXPIRY_INTERVAL_SECS);
+    Duration::from_secs(secs)
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct ExpiredRow {
+    id: i32,
+    person_id: i32,
+    target: String,
+}
+
+/// Expire every lapsed ACTIVE grant, end the grants whose compartment lapsed,
+/// and audit each transition. Returns the number of grants ended.
+pub async fn expire_grants(db: &PgPool, now: NaiveDateTime) -> Result<usize, sqlx::Error> {
+    let sweeps = [
+        (
+            "computer_access",
+            "UPDATE computer_access \
+             SET status = 'EXPIRED', revoked_at = expires_at, revocation_reason = 'Expired' \
+             WHERE status = 'ACTIVE' AND expires_at IS NOT NULL AND expires_at <= $1 \
+             RETURNING id, person_id, system_name AS target",
+        ),
+        (
+            "data_access",
+            "UPDATE data_access \
+             SET status = 'EXPIRED', revoked_at = expires_at, revocation_reason = 'Expired' \
+             WHERE status = 'ACTIVE' AND expires_at IS NOT NULL AND expires_at <= $1 \
+             RETURNING id, person_id, data_classification AS target",
+        ),
+        (
+            "physical_access",
+            "UPDATE physical_access \
+             SET status = 'EXPIRED', revoked_at = valid_until, revocation_reason = 'Expired' \
+             WHERE status = 'ACTIVE' AND valid_until IS NOT NULL AND valid_until <= $1 \
+             RETURNING id, person_id, zone_name AS target",
+        ),
+    ];
+
+    let mut expired = 0;
+    for (resource_type, sql) in sweeps {
+        let rows = sqlx::query_as::<_, ExpiredRow>(sql)
+            .bind(now)
+            .fetch_all(db)
+            .await?;
+        for row in &rows {
+            let _ = create_audit_log(
+                &CreateAuditLogRequest {
+                    person_id: None,
+                    username: "system".to_string(),
+                    action: "ACCESS_EXPIRED".to_string(),
+                    resource_type: resource_type.to_string(),
+                    resource_id: Some(row.id),
+                    details: Some(format!(
+                        "Grant on {} for person {} expired",
+                        row.target, row.person_id
+                    )),
+                    ip_address: None,
+                    user_agent: None,
+                },
+                db,
+            )
+            .await;
+        }
+        expired += rows.len();
+    }
+
+    let mut tx = db.begin().await?;
+    let revoked = revoke_missing_compartments(&mut tx, None, None, now).await?;
+    tx.commit().await?;
+    for (holder, grant) in &revoked {
+        let _ = create_audit_log(
+            &CreateAuditLogRequest {
+                person_id: None,
+                username: "system".to_string(),
+                action: "ACCESS_REVOKED".to_string(),
+                resource_type: if grant.access_type == "resource" {
+                    "resource_access_grants".to_string()
+                } else {
+                    format!("{}_access", grant.access_type)
+                },
+                resource_id: grant.grant_id.parse().ok(),
+                details: Some(format!(
+                    "Grant on {} for person {} ended: required compartment lapsed",
+                    grant.target, holder
+                )),
+                ip_address: None,
+                user_agent: None,
+            },
+            db,
+        )
+        .await;
+    }
+    Ok(expired + revoked.len())
+}
+
+/// Run expire_grants forever on a fixed interval. Errors are logged and the
+/// next tick retries.
+pub async fn run_expiry_task(db: PgPool, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        match expire_grants(&db, Utc::now().naive_utc()).await {
+            Ok(0) => {}
+            Ok(n) => println!("⏳ Expired {} access grant(s)", n),
+            Err(e) => eprintln!("Access expiry sweep failed: {:?}", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expiry_interval_fallbacks() {
+        assert_eq!(
+            expiry_interval(None).as_secs(),
+            DEFAULT_EXPIRY_INTERVAL_SECS
+        );
+        assert_eq!(
+            expiry_interval(Some("abc".into())).as_secs(),
+            DEFAULT_EXPIRY_INTERVAL_SECS
+        );
+        assert_eq!(
+            expiry_interval(Some("0".into())).as_secs(),
+            DEFAULT_EXPIRY_INTERVAL_SECS
+        );
+        assert_eq!(expiry_interval(Some(" 60 ".into())).as_secs(), 60);
+    }
+}
@@ -0,0 +1,265 @@
+// Shared grant writers for computer/data/physical access.
+//
+// The direct grant endpoints and every workflow that ends in a grant (access
+// request approval, ...) insert through these so the row shape stays in one
+// place; revocation (direct, recertification, ...) likewise goes through the
+// revoke helpers. Generic over the executor so callers can run them inside a
+// transaction.
+use sqlx::PgExecutor;
+
+use super::models::{ComputerAccess, DataAccess, PhysicalAccess};
+
+pub const ACCESS_COMPUTER: &str = "computer";
+pub const ACCESS_DATA: &str = "data";
+pub const ACCESS_PHYSICAL: &str = "physical";
+/// Digital-resource grants (resource_access_grants); revoke-only here.
+pub const ACCESS_RESOURCE: &str = "resource";
+
+pub const COMPUTER_ACCESS_COLUMNS: &str =
+    "id, person_id, system_name, info_system_id, access_level, \
+    granted_by_person_id, granted_at, expires_at, status, revoked_by_person_id, revoked_at, \
+    revocation_reason, created_at, updated_at";
+
+pub const DATA_ACCESS_COLUMNS: &str = "id, person_id, data_classification, access_level, \
+    granted_by_person_id, granted_at, expires_at, status, revoked_by_person_id, revoked_at, \
+    revocation_reason, created_at, updated_at";
+
+pub const PHYSICAL_ACCESS_COLUMNS: &str = "id, person_id, zone_name, access_level, valid_from, \
+    valid_until, granted_by_person_id, status, revoked_by_person_id, revoked_at, \
+    revocation_reason, created_at, updated_at";
+
+/// Allowed access_level values per access type (mirrors the table CHECKs).
+pub fn valid_access_level(access_type: &str, access_level: &str) -> bool {
+    match access_type {
+        ACCESS_COMPUTER => matches!(access_level, "READ" | "WRITE" | "ADMIN"),
+        ACCESS_DATA => matches!(access_level, "READ" | "WRITE" | "DELETE"),
+        ACCESS_PHYSICAL => matches!(access_level, "VISITOR" | "STANDARD" | "RESTRICTED" | "FULL"),
+        _ => false,
+    }
+}
+
+/// Whether `target` is grantable for the access type; data targets must be a
+/// classification the data_access CHECK accepts.
+pub fn valid_target(access_type: &str, target: &str) -> bool {
+    match access_type {
+        ACCESS_DATA => matches!(
+            target,
+            "UNCLASSIFIED" | "CONFIDENTIAL" | "SECRET" | "TOP_SECRET"
+        ),
+        ACCESS_COMPUTER | ACCESS_PHYSICAL => !target.trim().is_empty(),
+        _ => false,
+    }
+}
+
+/// Insert a computer grant linked to the registered system of that name
+/// (check it with systems::enforce_grantable_system first).
+pub async fn insert_computer_access<'e>(
+    executor: impl PgExecutor<'e>,
+    person_id: i32,
+    system_name: &str,
+    access_level: &str,
+    granted_by_person_id: i32,
+    expires_at: Option<chrono::NaiveDateTime>,
+) -> Result<ComputerAccess, sqlx::Error> {
+    sqlx::query_as::<_, ComputerAccess>(&format!(
+        r#"
+        INSERT INTO computer_access
+        (person_id, system_name, access_level, granted_by_person_id, expires_at, status,
+         info_system_id)
+        VALUES ($1, $2, $3, $4, $5, 'ACTIVE',
+                (SELECT id FROM info_systems WHERE system_name = $2))
+        RETURNING {}
+        "#,
+        COMPUTER_ACCESS_COLUMNS
+    ))
+    .bind(person_id)
+    .bind(system_name)
+    .bind(access_level)
+    .bind(granted_by_person_id)
+    .bind(expires_at)
+    .fetch_one(executor)
+    .await
+}
+
+pub async fn insert_data_access<'e>(
+    executor: impl PgExecutor<'e>,
+    person_id: i32,
+    data_classification: &str,
+    access_level: &str,
+    granted_by_person_id: i32,
+    expires_at: Option<chrono::NaiveDateTime>,
+) -> Result<DataAccess, sqlx::Error> {
+    sqlx::query_as::<_, DataAccess>(&format!(
+        r#"
+        INSERT INTO data_access
+        (person_id, data_classification, access_level, granted_by_person_id, expires_at, status)
+        VALUES ($1, $2, $3, $4, $5, 'ACTIVE')
+        RETURNING {}
+        "#,
+        DATA_ACCESS_COLUMNS
+    ))
+    .bind(person_id)
+    .bind(data_classification)
+    .bind(access_level)
+    .bind(granted_by_person_id)
+    .bind(expires_at)
+    .fetch_one(executor)
+    .await
+}
+
+pub async fn insert_physical_access<'e>(
+    executor: impl PgExecutor<'e>,
+    person_id: i32,
+    zone_name: &str,
+    access_level: &str,
+    granted_by_person_id: i32,
+    valid_until: Option<chrono::NaiveDateTime>,
+) -> Result<PhysicalAccess, sqlx::Error> {
+    sqlx::query_as::<_, PhysicalAccess>(&format!(
+        r#"
+        INSERT INTO physical_access
+        (person_id, zone_name, access_level, valid_from, valid_until, granted_by_person_id, status)
+        VALUES ($1, $2, $3, CURRENT_TIMESTAMP, $4, $5, 'ACTIVE')
+        RETURNING {}
+        "#,
+        PHYSICAL_ACCESS_COLUMNS
+    ))
+    .bind(person_id)
+    .bind(zone_name)
+    .bind(access_level)
+    .bind(valid_until)
+    .bind(granted_by_person_id)
+    .fetch_one(executor)
+    .await
+}
+
+/// Insert a digital-resource grant, or return the id of the identical grant
+/// (same person, resource and window) if one already exists — the same
+/// idempotency as POST /api/digital-resources/grants, but usable in a
+/// transaction.
+pub async fn insert_resource_grant<'e>(
+    executor: impl PgExecutor<'e>,
+    person_id: &str,
+    resource_id: &str,
+    valid_from: Option<chrono::DateTime<chrono::Utc>>,
+    valid_until: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<String, sqlx::Error> {
+    sqlx::query_scalar(
+        "INSERT INTO resource_access_grants (id, person_id, resource_id, valid_from, valid_until) \
+         VALUES ($1, $2, $3, $4, $5) \
+         ON CONFLICT (person_id, resource_id, valid_from, valid_until) \
+         DO UPDATE SET id = resource_access_grants.id \
+         RETURNING id",
+    )
+    .bind(uuid::Uuid::new_v4().to_string())
+    .bind(person_id)
+    .bind(resource_id)
+    .bind(valid_from)
+    .bind(valid_until)
+    .fetch_one(executor)
+    .await
+}
+
+/// Table backing a computer/data/physical access type.
+pub fn grant_table(access_type: &str) -> Option<&'static str> {
+    match access_type {
+        ACCESS_COMPUTER => Some("computer_access"),
+        ACCESS_DATA => Some("data_access"),
+        ACCESS_PHYSICAL => Some("physical_access"),
+        _ => None,
+    }
+}
+
+/// Mark an ACTIVE computer/data/physical grant REVOKED, recording who (None =
+/// system) and why. Returns false when the grant does not exist, is no longer
+/// active, or the access type is unknown.
+pub async fn revoke_access_grant<'e>(
+    executor: impl PgExecutor<'e>,
+    access_type: &str,
+    id: i32,
+    revoked_by_person_id: Option<i32>,
+    reason: Option<&str>,
+) -> Result<bool, sqlx::Error> {
+    let Some(table) = grant_table(access_type) else {
+        return Ok(false);
+    };
+    let result = sqlx::query(&format!(
+        "UPDATE {} SET status = 'REVOKED', revoked_by_person_id = $2, \
+         revoked_at = CURRENT_TIMESTAMP, revocation_reason = $3 \
+         WHERE id = $1 AND status = 'ACTIVE'",
+        table
+    ))
+    .bind(id)
+    .bind(revoked_by_person_id)
+    .bind(reason)
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Revoke every ACTIVE computer grant on an info system (system deletion).
+/// Returns the revoked grant ids.
+pub async fn revoke_system_grants<'e>(
+    executor: impl PgExecutor<'e>,
+    info_system_id: i32,
+    revoked_by_person_id: Option<i32>,
+    reason: &str,
+) -> Result<Vec<i32>, sqlx::Error> {
+    sqlx::query_scalar(
+        "UPDATE computer_access SET status = 'REVOKED', revoked_by_person_id = $2, \
+         revoked_at = CURRENT_TIMESTAMP, revocation_reason = $3 \
+         WHERE info_system_id = $1 AND status = 'ACTIVE' \
+         RETURNING id",
+    )
+    .bind(info_system_id)
+    .bind(revoked_by_person_id)
+    .bind(reason)
+    .fetch_all(executor)
+    .await
+}
+
+/// End a digital-resource grant now. resource_access_grants has no status
+/// column, so revocation closes the validity window (the resolver then ignores
+/// it). Returns false when the grant is missing or already ended.
+pub async fn end_resource_grant<'e>(
+    executor: impl PgExecutor<'e>,
+    id: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE resource_access_grants SET valid_until = NOW() \
+         WHERE id = $1 AND (valid_until IS NULL OR valid_until > NOW())",
+    )
+    .bind(id)
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_access_level_per_type() {
+        assert!(valid_access_level(ACCESS_COMPUTER, "ADMIN"));
+        assert!(!valid_access_level(ACCESS_COMPUTER, "DELETE"));
+        assert!(valid_access_level(ACCESS_DATA, "DELETE"));
+        assert!(valid_access_level(ACCESS_PHYSICAL, "VISITOR"));
+        assert!(!valid_access_level(ACCESS_PHYSICAL, "READ"));
+        assert!(!valid_access_level("bogus", "READ"));
+    }
+
+    #[test]
+    fn test_valid_target_data_needs_known_classification() {
+        assert!(valid_target(ACCESS_DATA, "SECRET"));
+        assert!(!valid_target(ACCESS_DATA, "RESTRICTED"));
+        assert!(valid_target(ACCESS_COMPUTER, "File Server"));
+        assert!(!valid_target(ACCESS_PHYSICAL, "  "));
+    }
+
+    #[test]
+    fn test_grant_table() {
+        assert_eq!(grant_table(ACCESS_DATA), Some("data_access"));
+        assert_eq!(grant_table(ACCESS_RESOURCE), None);
+    }
+}
@@ -1,11 +1,23 @@
+use chrono::Utc;
 use rocket::serde::json::Json;
 use rocket::{http::Status, State};
 use sqlx::PgPool;
 
+use crate::access::clearance::{enforce_clearance, load_clearance_violations};
+use crate::access::grants::{
+    grant_table, insert_computer_access, insert_data_access, insert_physical_access,
+    revoke_access_grant, ACCESS_COMPUTER, ACCESS_DATA, COMPUTER_ACCESS_COLUMNS,
+    DATA_ACCESS_COLUMNS, PHYSICAL_ACCESS_COLUMNS,
+};
 use crate::access::models::*;
+use crate::access::systems::{enforce_grantable_system, enforce_system_shielding};
+use crate::access::timeline::load_person_timeline;
 use crate::auth::middleware::AuthGuard;
+use crate::compartments::checks::enforce_compartments;
+use crate::security_flags::flags::active_flags;
 use crate::shared::rbac::role_has_permission;
 use crate::shared::response::ApiResponse;
+use crate::sod::enforce::enforce_no_self_action;
 
 /// Grant computer access to a personnel member
 #[post("/api/access/computer", data = "<data>")]
@@ -25,24 +37,39 @@ pub async fn grant_computer_access(
         .sub
         .parse::<i32>()
         .map_err(|_| Status::InternalServerError)?;
+    enforce_no_self_action(
+        db.inner(),
+        "access.write",
+        &granted_by_person_id,
+        &data.person_id,
+    )
+    .await?;
+    enforce_grantable_system(db.inner(), &data.system_name).await?;
+    enforce_system_shielding(
+        db.inner(),
+        granted_by_person_id,
+        data.person_id,
+        &data.system_name,
+    )
+    .await?;
+    enforce_clearance(
+        db.inner(),
+        granted_by_person_id,
+        data.person_id,
+        ACCESS_COMPUTER,
+        &data.system_name,
+    )
+    .await?;
 
     // Insert computer access
-    let access = sqlx::query_as::<_, ComputerAccess>(
-        r#"
-        INSERT INTO computer_access 
-        (person_id, system_name, access_level, granted_by_person_id, expires_at, status)
-        VALUES ($1, $2, $3, $4, $5, 'ACTIVE')
-        RETURNING 
-            id, person_id, system_name, access_level, granted_by_person_id,
-            granted_at, expires_at, status, created_at, updated_at
-        "#,
+    let access = insert_computer_access(
+        db.inner(),
+        data.person_id,
+        &data.system_name,
+        &data.access_level,
+        granted_by_person_id,
+        data.expires_at,
     )
-    .bind(data.person_id)
-    .bind(&data.system_name)
-    .bind(&data.access_level)
-    .bind(granted_by_person_id)
-    .bind(data.expires_at)
-    .fetch_one(db.inner())
     .await
     .map_err(|_| Status::InternalServerError)?;
 
@@ -67,24 +94,39 @@ pub async fn grant_data_access(
         .sub
         .parse::<i32>()
         .map_err(|_| Status::InternalServerError)?;
+    enforce_no_self_action(
+        db.inner(),
+        "access.write",
+        &granted_by_person_id,
+        &data.person_id,
+    )
+    .await?;
+    enforce_clearance(
+        db.inner(),
+        granted_by_person_id,
+        data.person_id,
+        ACCESS_DATA,
+        &data.data_classification,
+    )
+    .await?;
+    enforce_compartments(
+        db.inner(),
+        granted_by_person_id,
+        Some(data.person_id),
+        ACCESS_DATA,
+        &data.data_classification,
+    )
+    .await?;
 
     // Insert data access
-    let access = sqlx::query_as::<_, DataAccess>(
-        r#"
-        INSERT INTO data_access 
-        (person_id, data_classification, access_level, granted_by_person_id, expires_at, status)
-        VALUES ($1, $2, $3, $4, $5, 'ACTIVE')
-        RETURNING 
-            id, person_id, data_classification, access_level, granted_by_person_id,
-            granted_at, expires_at, status, created_at, updated_at
-        "#,
+    let access = insert_data_access(
+        db.inner(),
+        data.person_id,
+        &data.data_classification,
+        &data.access_level,
+        granted_by_person_id,
+        data.expires_at,
     )
-    .bind(data.person_id)
-    .bind(&data.data_classification)
-    .bind(&data.access_level)
-    .bind(granted_by_person_id)
-    .bind(data.expires_at)
-    .fetch_one(db.inner())
     .await
     .map_err(|_| Status::InternalServerError)?;
 
@@ -109,78 +151,105 @@ pub async fn grant_physical_access(
         .sub
         .parse::<i32>()
         .map_err(|_| Status::InternalServerError)?;
+    enforce_no_self_action(
+        db.inner(),
+        "access.write",
+        &granted_by_person_id,
+        &data.person_id,
+    )
+    .await?;
 
     // Insert physical access
-    let access = sqlx::query_as::<_, PhysicalAccess>(
-        r#"
-        INSERT INTO physical_access 
-        (person_id, zone_name, access_level, valid_from, valid_until, granted_by_person_id, status)
-        VALUES ($1, $2, $3, CURRENT_TIMESTAMP, $4, $5, 'ACTIVE')
-        RETURNING 
-            id, person_id, zone_name, access_level, valid_from, valid_until,
-            granted_by_person_id, status, created_at, updated_at
-        "#,
+    let access = insert_physical_access(
+        db.inner(),
+        data.person_id,
+        &data.zone_name,
+        &data.access_level,
+        granted_by_person_id,
+        data.valid_until,
     )
-    .bind(data.person_id)
-    .bind(&data.zone_name)
-    .bind(&data.access_level)
-    .bind(data.valid_until)
-    .bind(granted_by_person_id)
-    .fetch_one(db.inner())
     .await
     .map_err(|_| Status::InternalServerError)?;
 
     Ok(Json(ApiResponse::success(access)))
 }
 
-/// List all access for a person
-#[get("/api/persons/<id>/access")]
+/// List access for a person: active grants by default, or every grant
+/// including revoked/expired history with `include_inactive=true`. While the
+/// person has a security flag nothing is active, so the default listing is
+/// empty; the history still shows the stored grants.
+#[get("/api/persons/<id>/access?<include_inactive>")]
 pub async fn list_person_access(
     db: &State<PgPool>,
     _auth: AuthGuard,
     id: i32,
+    include_inactive: Option<bool>,
 ) -> Result<Json<ApiResponse<PersonAccess>>, Status> {
+    // Lapsed grants count as inactive even before the expiry sweep flips them
+    let now = Utc::now().naive_utc();
+    let include_inactive = include_inactive.unwrap_or(false);
+    let security_flags = active_flags(db.inner(), id)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    if !security_flags.is_empty() && !include_inactive {
+        return Ok(Json(ApiResponse::success(PersonAccess {
+            computer_access: Vec::new(),
+            data_access: Vec::new(),
+            physical_access: Vec::new(),
+            security_flags,
+        })));
+    }
+
     // Query computer access
-    let computer_access = sqlx::query_as::<_, ComputerAccess>(
+    let computer_access = sqlx::query_as::<_, ComputerAccess>(&format!(
         r#"
-        SELECT id, person_id, system_name, access_level, granted_by_person_id,
-               granted_at, expires_at, status, created_at, updated_at
+        SELECT {}
         FROM computer_access
-        WHERE person_id = $1 AND status = 'ACTIVE'
+        WHERE person_id = $1
+          AND ($3 OR (status = 'ACTIVE' AND (expires_at IS NULL OR expires_at > $2)))
         ORDER BY granted_at DESC
         "#,
-    )
+        COMPUTER_ACCESS_COLUMNS
+    ))
     .bind(id)
+    .bind(now)
+    .bind(include_inactive)
     .fetch_all(db.inner())
     .await
     .map_err(|_| Status::InternalServerError)?;
 
     // Query data access
-    let data_access = sqlx::query_as::<_, DataAccess>(
+    let data_access = sqlx::query_as::<_, DataAccess>(&format!(
         r#"
-        SELECT id, person_id, data_classification, access_level, granted_by_person_id,
-               granted_at, expires_at, status, created_at, updated_at
+        SELECT {}
         FROM data_access
-        WHERE person_id = $1 AND status = 'ACTIVE'
+        WHERE person_id = $1
+          AND ($3 OR (status = 'ACTIVE' AND (expires_at IS NULL OR expires_at > $2)))
         ORDER BY granted_at DESC
         "#,
-    )
+        DATA_ACCESS_COLUMNS
+    ))
     .bind(id)
+    .bind(now)
+    .bind(include_inactive)
     .fetch_all(db.inner())
     .await
     .map_err(|_| Status::InternalServerError)?;
 
     // Query physical access
-    let physical_access = sqlx::query_as::<_, PhysicalAccess>(
+    let physical_access = sqlx::query_as::<_, PhysicalAccess>(&format!(
         r#"
-        SELECT id, person_id, zone_name, access_level, valid_from, valid_until,
-               granted_by_person_id, status, created_at, updated_at
+        SELECT {}
         FROM physical_access
-        WHERE person_id = $1 AND status = 'ACTIVE'
+        WHERE person_id = $1
+          AND ($3 OR (status = 'ACTIVE' AND (valid_until IS NULL OR valid_until > $2)))
         ORDER BY valid_from DESC
         "#,
-    )
+        PHYSICAL_ACCESS_COLUMNS
+    ))
     .bind(id)
+    .bind(now)
+    .bind(include_inactive)
     .fetch_all(db.inner())
     .await
     .map_err(|_| Status::InternalServerError)?;
@@ -189,18 +258,56 @@ pub async fn list_person_access(
         computer_access,
         data_access,
         physical_access,
+        security_flags,
     };
 
     Ok(Json(ApiResponse::success(access)))
 }
 
-/// Revoke access (mark as REVOKED)
-#[delete("/api/access/<access_type>/<id>")]
+/// Chronological access history for a person: grants and their ends,
+/// digital-resource grants, NDAs and clearance changes. Visible to the person
+/// themselves and to holders of person.read.
+#[get("/api/persons/<id>/access/timeline")]
+pub async fn get_person_access_timeline(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<Vec<TimelineEvent>>>, Status> {
+    let is_self = auth.claims.sub.parse::<i32>().ok() == Some(id);
+    if !is_self
+        && !role_has_permission(db.inner(), &auth.claims.role, "person.read")
+            .await
+            .unwrap_or(false)
+    {
+        return Err(Status::Forbidden);
+    }
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM person WHERE id = $1)")
+        .bind(id)
+        .fetch_one(db.inner())
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    if !exists {
+        return Err(Status::NotFound);
+    }
+
+    let events = load_person_timeline(db.inner(), id, Utc::now().naive_utc())
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            Status::InternalServerError
+        })?;
+
+    Ok(Json(ApiResponse::success(events)))
+}
+
+/// Revoke access (mark as REVOKED, recording the revoker and optional reason)
+#[delete("/api/access/<access_type>/<id>?<reason>")]
 pub async fn revoke_access(
     db: &State<PgPool>,
     auth: AuthGuard,
     access_type: &str,
     id: i32,
+    reason: Option<String>,
 ) -> Result<Json<ApiResponse<&'static str>>, Status> {
     if !role_has_permission(db.inner(), &auth.claims.role, "access.write")
         .await
@@ -208,38 +315,46 @@ pub async fn revoke_access(
     {
         return Err(Status::Forbidden);
     }
-    match access_type {
-        "computer" => {
-            sqlx::query!(
-                "UPDATE computer_access SET status = 'REVOKED' WHERE id = $1",
-                id
-            )
-            .execute(db.inner())
-            .await
-            .map_err(|_| Status::InternalServerError)?;
-        }
-        "data" => {
-            sqlx::query!(
-                "UPDATE data_access SET status = 'REVOKED' WHERE id = $1",
-                id
-            )
-            .execute(db.inner())
-            .await
-            .map_err(|_| Status::InternalServerError)?;
-        }
-        "physical" => {
-            sqlx::query!(
-                "UPDATE physical_access SET status = 'REVOKED' WHERE id = $1",
-                id
-            )
-            .execute(db.inner())
-            .await
-            .map_err(|_| Status::InternalServerError)?;
-        }
-        _ => {
-            return Err(Status::BadRequest);
-        }
+    if grant_table(access_type).is_none() {
+        return Err(Status::BadRequest);
     }
+    let revoked_by_person_id = auth
+        .claims
+        .sub
+        .parse::<i32>()
+        .map_err(|_| Status::InternalServerError)?;
+    revoke_access_grant(
+        db.inner(),
+        access_type,
+        id,
+        Some(revoked_by_person_id),
+        reason.as_deref(),
+    )
+    .await
+    .map_err(|_| Status::InternalServerError)?;
 
     Ok(Json(ApiResponse::success("Access revoked successfully")))
 }
+
+/// List active data and classified-computer grants whose classification now
+/// exceeds the holder's clearance (e.g. after a clearance downgrade)
+#[get("/api/access/clearance-violations")]
+pub async fn list_clearance_violations(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+) -> Result<Json<ApiResponse<Vec<ClearanceViolation>>>, Status> {
+    if !role_has_permission(db.inner(), &auth.claims.role, "audit.read")
+        .await
+        .unwrap_or(false)
+    {
+        return Err(Status::Forbidden);
+    }
+    let violations = load_clearance_violations(db.inner(), None, Utc::now().naive_utc())
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            Status::InternalServerError
+        })?;
+
+    Ok(Json(ApiResponse::success(violations)))
+}
@@ -1,2 +1,8 @@
+pub mod clearance;
+pub mod expiry;
+pub mod grants;
 pub mod handlers;
 pub mod models;
+pub mod revocation;
+pub mod systems;
+pub mod timeline;
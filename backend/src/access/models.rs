@@ -8,12 +8,19 @@ pub struct ComputerAccess {
     pub id: i32,
     pub person_id: i32, // Changed from personnel_id
     pub system_name: String,
-    pub access_level: String,      // READ, WRITE, ADMIN
-    pub granted_by_person_id: i32, // Changed from granted_by
+    pub info_system_id: Option<i32>, // None once the system was deleted
+    pub access_level: String,        // READ, WRITE, ADMIN
+    pub granted_by_person_id: i32,   // Changed from granted_by
     pub granted_at: chrono::NaiveDateTime,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_at: Option<chrono::NaiveDateTime>,
     pub status: String, // ACTIVE, REVOKED, EXPIRED
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_by_person_id: Option<i32>, // None for system transitions (expiry, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<chrono::NaiveDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revocation_reason: Option<String>,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
 }
@@ -21,7 +28,7 @@ pub struct ComputerAccess {
 #[derive(Deserialize, Validate, Debug)]
 pub struct CreateComputerAccessRequest {
     #[validate(length(min = 1, max = 100))]
-    pub system_name: String,
+    pub system_name: String, // must be registered in info_systems
 
     pub access_level: String, // READ, WRITE, ADMIN
 
@@ -43,6 +50,12 @@ pub struct DataAccess {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_at: Option<chrono::NaiveDateTime>,
     pub status: String, // ACTIVE, REVOKED, EXPIRED
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_by_person_id: Option<i32>, // None for system transitions (expiry, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<chrono::NaiveDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revocation_reason: Option<String>,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
 }
@@ -71,6 +84,12 @@ pub struct PhysicalAccess {
     pub valid_until: Option<chrono::NaiveDateTime>,
     pub granted_by_person_id: i32, // Changed from granted_by
     pub status: String,            // ACTIVE, REVOKED, EXPIRED
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_by_person_id: Option<i32>, // None for system transitions (expiry, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<chrono::NaiveDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revocation_reason: Option<String>,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
 }
@@ -94,4 +113,46 @@ pub struct PersonAccess {
     pub computer_access: Vec<ComputerAccess>,
     pub data_access: Vec<DataAccess>,
     pub physical_access: Vec<PhysicalAccess>,
+    pub security_flags: Vec<String>, // uncleared SECURITY_HOLD / REVOKED; no grant is in effect
+}
+
+/// An active grant whose classification exceeds the holder's current clearance
+#[derive(Serialize, Debug, sqlx::FromRow)]
+pub struct ClearanceViolation {
+    pub access_type: String, // computer, data
+    pub access_id: i32,
+    pub person_id: i32,
+    pub clearance_level: Option<String>,
+    pub target: String, // system_name / data_classification
+    pub classification: String,
+    pub access_level: String,
+}
+
+/// One entry in a person's access timeline
+#[derive(Serialize, Debug, sqlx::FromRow)]
+pub struct TimelineEvent {
+    pub at: chrono::NaiveDateTime,
+    pub event: String, // ACCESS_GRANTED, ACCESS_REVOKED, ACCESS_EXPIRED, RESOURCE_GRANT_*, NDA_*, CLEARANCE_*, AUTHORIZATION_*
+    pub source: String, // table the event comes from
+    pub source_id: String,
+    pub actor_person_id: Option<i32>, // None for system transitions
+    pub summary: String,
+}
+
+/// A grant ended by a revocation cascade
+#[derive(Serialize, Debug, Clone, sqlx::FromRow)]
+pub struct RevokedGrant {
+    pub access_type: String, // computer, data, physical, resource, compartment, credential
+    pub grant_id: String,
+    // system_name / data_classification / zone_name / resource_id / code / claimed clearance
+    pub target: String,
+}
+
+/// Everything an offboarding ended
+#[derive(Serialize, Debug, Default)]
+pub struct OffboardingSummary {
+    pub person_id: i32,
+    pub revoked_grants: Vec<RevokedGrant>,
+    pub revoked_nda_ids: Vec<i32>,
+    pub denied_request_ids: Vec<i32>,
 }
@@ -0,0 +1,297 @@
+// Revocation cascades.
+//
+// offboard_person ends everything a departing person holds — computer, data
+// and physical grants, digital-resource grants, compartment grants, attribute
+// credentials, NDAs still awaiting signature and pending access requests — and
+// soft-deletes the person, all inside the caller's transaction. Once deleted_at is set the AuthGuard rejects the
+// person's outstanding tokens.
+//
+// revoke_exceeding_clearance re-evaluates a person's classified grants after
+// a clearance change and revokes those the new clearance no longer covers.
+// Digital-resource grants are not touched there: the resolver checks
+// clearance on every decision. Attribute credentials are: other systems trust
+// them offline and only see the revocation, so the open ones claiming more
+// than the new clearance are revoked with the grants (revoke_open_credentials).
+// Offboarding and a security flag revoke every open credential.
+//
+// revoke_missing_compartments ends the data and digital-resource grants whose
+// holder no longer holds a compartment the target requires. The resolver does
+// not check compartments, so resource grants are ended here as well. It runs
+// in the transaction that revokes a compartment or adds a requirement, and in
+// the expiry sweep for compartments that lapsed.
+use std::fmt::Display;
+
+use chrono::NaiveDateTime;
+use sqlx::{PgPool, Postgres, Transaction};
+
+use super::clearance::{load_clearance_violations, load_effective_clearance};
+use super::grants::{end_resource_grant, revoke_access_grant, ACCESS_RESOURCE};
+use super::models::{OffboardingSummary, RevokedGrant};
+use crate::audit::handlers::create_audit_log;
+use crate::audit::models::CreateAuditLogRequest;
+use crate::compartments::checks::load_compartment_violations;
+use crate::digital_resources::resolver::clearance_rank;
+
+// (access_type, table, target column) per classic grant table.
+const CLASSIC_GRANTS: [(&str, &str, &str); 3] = [
+    ("computer", "computer_access", "system_name"),
+    ("data", "data_access", "data_classification"),
+    ("physical", "physical_access", "zone_name"),
+];
+
+/// Revoke everything `person_id` holds and soft-delete them. Returns None when
+/// the person does not exist or is already deleted.
+pub async fn offboard_person(
+    tx: &mut Transaction<'_, Postgres>,
+    person_id: i32,
+    actor: i32,
+    reason: &str,
+) -> Result<Option<OffboardingSummary>, sqlx::Error> {
+    let deleted = sqlx::query(
+        "UPDATE person SET deleted_at = CURRENT_TIMESTAMP WHERE id = $1 AND deleted_at IS NULL",
+    )
+    .bind(person_id)
+    .execute(&mut **tx)
+    .await?;
+    if deleted.rows_affected() == 0 {
+        return Ok(None);
+    }
+
+    let mut summary = OffboardingSummary {
+        person_id,
+        ..Default::default()
+    };
+    for (access_type, table, target) in CLASSIC_GRANTS {
+        let ended = sqlx::query_as::<_, RevokedGrant>(&format!(
+            "UPDATE {table} SET status = 'REVOKED', revoked_by_person_id = $2, \
+             revoked_at = CURRENT_TIMESTAMP, revocation_reason = $3 \
+             WHERE person_id = $1 AND status = 'ACTIVE' \
+             RETURNING '{access_type}' AS access_type, id::text AS grant_id, {target} AS target"
+        ))
+        .bind(person_id)
+        .bind(actor)
+        .bind(reason)
+        .fetch_all(&mut **tx)
+        .await?;
+        summary.revoked_grants.extend(ended);
+    }
+
+    // Resource grants have no status: not-yet-started ones are dropped, the
+    // rest have their window closed now.
+    let dropped = sqlx::query_as::<_, RevokedGrant>(
+        "DELETE FROM resource_access_grants \
+         WHERE person_id = $1::text AND valid_from > NOW() \
+         RETURNING 'resource' AS access_type, id AS grant_id, resource_id AS target",
+    )
+    .bind(person_id)
+    .fetch_all(&mut **tx)
+    .await?;
+    let ended = sqlx::query_as::<_, RevokedGrant>(
+        "UPDATE resource_access_grants SET valid_until = NOW() \
+         WHERE person_id = $1::text AND (valid_until IS NULL OR valid_until > NOW()) \
+         RETURNING 'resource' AS access_type, id AS grant_id, resource_id AS target",
+    )
+    .bind(person_id)
+    .fetch_all(&mut **tx)
+    .await?;
+    summary.revoked_grants.extend(dropped);
+    summary.revoked_grants.extend(ended);
+
+    let compartments = sqlx::query_as::<_, RevokedGrant>(
+        "UPDATE person_compartments SET revoked_at = CURRENT_TIMESTAMP, \
+         revoked_by_person_id = $2, revocation_reason = $3 \
+         WHERE person_id = $1 AND revoked_at IS NULL \
+           AND (valid_until IS NULL OR valid_until > NOW()) \
+         RETURNING 'compartment' AS access_type, id::text AS grant_id, \
+           compartment_code AS target",
+    )
+    .bind(person_id)
+    .bind(actor)
+    .bind(reason)
+    .fetch_all(&mut **tx)
+    .await?;
+    summary.revoked_grants.extend(compartments);
+    let credentials = revoke_open_credentials(tx, person_id, actor, reason, None).await?;
+    summary.revoked_grants.extend(credentials);
+
+    summary.revoked_nda_ids = sqlx::query_scalar(
+        "UPDATE nda SET status = 'REVOKED', rejection_reason = $2, updated_at = CURRENT_TIMESTAMP \
+         WHERE person_id = $1 AND status IN ('PENDING', 'ACTIVE') RETURNING id",
+    )
+    .bind(person_id)
+    .bind(reason)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    summary.denied_request_ids = sqlx::query_scalar(
+        "UPDATE access_requests SET status = 'DENIED', decided_by_person_id = $2, \
+         decided_at = CURRENT_TIMESTAMP, decision_reason = $3, updated_at = CURRENT_TIMESTAMP \
+         WHERE person_id = $1 AND status = 'PENDING' RETURNING id",
+    )
+    .bind(person_id)
+    .bind(actor)
+    .bind(reason)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(Some(summary))
+}
+
+/// Revoke the person's active classified grants and open attribute
+/// credentials that their current clearance (as already written in this
+/// transaction) no longer covers.
+pub async fn revoke_exceeding_clearance(
+    tx: &mut Transaction<'_, Postgres>,
+    person_id: i32,
+    actor: i32,
+    now: NaiveDateTime,
+) -> Result<Vec<RevokedGrant>, sqlx::Error> {
+    let clearance = load_effective_clearance(&mut **tx, person_id, now)
+        .await?
+        .flatten()
+        .unwrap_or_else(|| "none".to_string());
+    let mut revoked = revoke_open_credentials(
+        tx,
+        person_id,
+        actor,
+        &format!("Clearance now {}", clearance),
+        Some(&clearance),
+    )
+    .await?;
+
+    let violations = load_clearance_violations(&mut **tx, Some(person_id), now).await?;
+    for v in violations {
+        let reason = format!(
+            "Clearance {} no longer covers {}",
+            v.clearance_level.as_deref().unwrap_or("none"),
+            v.classification
+        );
+        if revoke_access_grant(
+            &mut **tx,
+            &v.access_type,
+            v.access_id,
+            Some(actor),
+            Some(&reason),
+        )
+        .await?
+        {
+            revoked.push(RevokedGrant {
+                access_type: v.access_type,
+                grant_id: v.access_id.to_string(),
+                target: v.target,
+            });
+        }
+    }
+    Ok(revoked)
+}
+
+/// Revoke the person's unexpired attribute credentials not yet revoked: all
+/// of them when `up_to` is None, else those claiming a clearance above
+/// `up_to` (an unknown level such as "none" covers none).
+pub async fn revoke_open_credentials(
+    tx: &mut Transaction<'_, Postgres>,
+    person_id: i32,
+    actor: i32,
+    reason: &str,
+    up_to: Option<&str>,
+) -> Result<Vec<RevokedGrant>, sqlx::Error> {
+    let open: Vec<(uuid::Uuid, String)> = sqlx::query_as(
+        "SELECT id, COALESCE(claims->>'clearance', '') FROM attribute_credentials \
+         WHERE person_id = $1 AND revoked_at IS NULL AND expires_at > NOW() FOR UPDATE",
+    )
+    .bind(person_id)
+    .fetch_all(&mut **tx)
+    .await?;
+    let held = up_to.map(clearance_rank);
+    let ids: Vec<uuid::Uuid> = open
+        .into_iter()
+        .filter(|(_, claimed)| held.is_none_or(|held| clearance_rank(claimed) > held))
+        .map(|(id, _)| id)
+        .collect();
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    sqlx::query_as::<_, RevokedGrant>(
+        "UPDATE attribute_credentials SET revoked_at = CURRENT_TIMESTAMP, \
+         revoked_by_person_id = $2, revocation_reason = $3 \
+         WHERE id = ANY($1) AND revoked_at IS NULL \
+         RETURNING 'credential' AS access_type, id::text AS grant_id, \
+           COALESCE(claims->>'clearance', '') AS target",
+    )
+    .bind(&ids)
+    .bind(actor)
+    .bind(reason)
+    .fetch_all(&mut **tx)
+    .await
+}
+
+/// End the in-force data and resource grants at `now` whose holder lacks a
+/// compartment the target requires; every holder when `person_id` is None.
+/// Returns each ended grant with its holder.
+pub async fn revoke_missing_compartments(
+    tx: &mut Transaction<'_, Postgres>,
+    person_id: Option<i32>,
+    actor: Option<i32>,
+    now: NaiveDateTime,
+) -> Result<Vec<(String, RevokedGrant)>, sqlx::Error> {
+    let violations = load_compartment_violations(&mut **tx, person_id, now).await?;
+    let mut revoked = Vec::with_capacity(violations.len());
+    for v in violations {
+        let ended = if v.access_type == ACCESS_RESOURCE {
+            end_resource_grant(&mut **tx, &v.grant_id).await?
+        } else {
+            let reason = format!("Missing compartments [{}]", v.missing.join(", "));
+            match v.grant_id.parse() {
+                Ok(id) => {
+                    revoke_access_grant(&mut **tx, &v.access_type, id, actor, Some(&reason)).await?
+                }
+                Err(_) => false,
+            }
+        };
+        if ended {
+            revoked.push((
+                v.holder,
+                RevokedGrant {
+                    access_type: v.access_type,
+                    grant_id: v.grant_id,
+                    target: v.target,
+                },
+            ));
+        }
+    }
+    Ok(revoked)
+}
+
+/// One ACCESS_REVOKED audit entry per grant ended by a cascade.
+pub async fn audit_revoked_grants(
+    db: &PgPool,
+    actor: i32,
+    person_id: impl Display,
+    grants: &[RevokedGrant],
+    reason: &str,
+) {
+    for grant in grants {
+        let _ = create_audit_log(
+            &CreateAuditLogRequest {
+                person_id: Some(actor),
+                username: actor.to_string(),
+                action: "ACCESS_REVOKED".to_string(),
+                resource_type: match grant.access_type.as_str() {
+                    "resource" => "resource_access_grants".to_string(),
+                    "compartment" => "person_compartments".to_string(),
+                    "credential" => "attribute_credentials".to_string(),
+                    other => format!("{}_access", other),
+                },
+                resource_id: grant.grant_id.parse().ok(),
+                details: Some(format!(
+                    "{} grant {} on '{}' for person_id={}: {}",
+                    grant.access_type, grant.grant_id, grant.target, person_id, reason
+                )),
+                ip_address: None,
+                user_agent: None,
+            },
+            db,
+        )
+        .await;
+    }
+}
@@ -0,0 +1,156 @@
+// Info-system registry checks for computer grants.
+//
+// Computer grants reference info_systems by id (insert_computer_access
+// resolves it from the name); every grant path checks the name here first.
+// INACTIVE systems take no new grants; MAINTENANCE ones do. A shielded
+// system only takes grants to employees or consultants of its owning
+// organization or an allowlisted one, and shielding a system revokes the
+// grants it no longer admits (revoke_unadmitted_grants).
+use std::collections::HashSet;
+
+use rocket::http::Status;
+use sqlx::{PgPool, Postgres, Transaction};
+
+use super::models::RevokedGrant;
+use crate::audit::handlers::create_audit_log;
+use crate::audit::models::CreateAuditLogRequest;
+
+// (person_id, org_id) for every employee or consultant relation, recorded in
+// either direction.
+const ORG_MEMBERS_SQL: &str = "SELECT r.related_entity_id AS person_id, r.entity_id AS org_id \
+       FROM relations r \
+      WHERE r.entity_type IN ('vendor', 'organization') \
+        AND r.related_entity_type = 'person' \
+        AND r.relation_type IN ('employee', 'consultant') \
+     UNION ALL \
+     SELECT r.entity_id, r.related_entity_id FROM relations r \
+      WHERE r.entity_type = 'person' \
+        AND r.related_entity_type IN ('vendor', 'organization') \
+        AND r.relation_type IN ('employee', 'consultant')";
+
+/// Refuse a grant on `system_name` unless it is registered (404) and not
+/// INACTIVE (409).
+pub async fn enforce_grantable_system(db: &PgPool, system_name: &str) -> Result<(), Status> {
+    let status: String =
+        sqlx::query_scalar("SELECT status FROM info_systems WHERE system_name = $1")
+            .bind(system_name)
+            .fetch_optional(db)
+            .await
+            .map_err(|_| Status::InternalServerError)?
+            .ok_or(Status::NotFound)?;
+    if status == "INACTIVE" {
+        return Err(Status::Conflict);
+    }
+    Ok(())
+}
+
+/// Which of `person_ids` the shielded system `system_name` admits, or None
+/// when it is not shielded (or not registered).
+pub async fn shield_admitted_persons(
+    db: &PgPool,
+    system_name: &str,
+    person_ids: &[i32],
+) -> Result<Option<HashSet<i32>>, sqlx::Error> {
+    let shielded: Option<bool> =
+        sqlx::query_scalar("SELECT shielded FROM info_systems WHERE system_name = $1")
+            .bind(system_name)
+            .fetch_optional(db)
+            .await?;
+    if shielded != Some(true) {
+        return Ok(None);
+    }
+    let admitted: Vec<i32> = sqlx::query_scalar(&format!(
+        "WITH admitted AS ( \
+           SELECT owner_organization_id AS org_id FROM info_systems WHERE system_name = $1 \
+           UNION SELECT a.organization_id FROM info_system_shield_allowlist a \
+             JOIN info_systems s ON s.id = a.info_system_id WHERE s.system_name = $1 \
+         ) \
+         SELECT DISTINCT person_id FROM ({}) m \
+         WHERE m.person_id = ANY($2) AND m.org_id IN (SELECT org_id FROM admitted)",
+        ORG_MEMBERS_SQL
+    ))
+    .bind(system_name)
+    .bind(person_ids)
+    .fetch_all(db)
+    .await?;
+    Ok(Some(admitted.into_iter().collect()))
+}
+
+/// Refuse (403, audited) a grant on a shielded `system_name` to a person
+/// outside its owner and allowlist.
+pub async fn enforce_system_shielding(
+    db: &PgPool,
+    actor: i32,
+    person_id: i32,
+    system_name: &str,
+) -> Result<(), Status> {
+    let Some(admitted) = shield_admitted_persons(db, system_name, &[person_id])
+        .await
+        .map_err(|_| Status::InternalServerError)?
+    else {
+        return Ok(());
+    };
+    if admitted.contains(&person_id) {
+        return Ok(());
+    }
+    let _ = create_audit_log(
+        &CreateAuditLogRequest {
+            person_id: Some(actor),
+            username: actor.to_string(),
+            action: "SHIELDING_BLOCKED".to_string(),
+            resource_type: "computer_access".to_string(),
+            resource_id: None,
+            details: Some(format!(
+                "computer access to shielded '{}' refused for person_id={}: not in the owner or an allowlisted organization",
+                system_name, person_id
+            )),
+            ip_address: None,
+            user_agent: None,
+        },
+        db,
+    )
+    .await;
+    Err(Status::Forbidden)
+}
+
+/// Revoke the active grants on `info_system_id` held by persons its shielding,
+/// as written in this transaction, does not admit; nothing when the system is
+/// not shielded. Returns each revoked grant with its holder.
+pub async fn revoke_unadmitted_grants(
+    tx: &mut Transaction<'_, Postgres>,
+    info_system_id: i32,
+    actor: i32,
+) -> Result<Vec<(i32, RevokedGrant)>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (i32, String, String, String)>(&format!(
+        "UPDATE computer_access ca SET status = 'REVOKED', revoked_by_person_id = $2, \
+           revoked_at = CURRENT_TIMESTAMP, \
+           revocation_reason = 'Not admitted by the system''s shielding' \
+         FROM info_systems s \
+         WHERE s.id = $1 AND s.shielded AND ca.info_system_id = s.id AND ca.status = 'ACTIVE' \
+           AND NOT EXISTS ( \
+             SELECT 1 FROM ({}) m \
+             WHERE m.person_id = ca.person_id \
+               AND (m.org_id = s.owner_organization_id \
+                    OR m.org_id IN (SELECT organization_id FROM info_system_shield_allowlist \
+                                    WHERE info_system_id = $1))) \
+         RETURNING ca.person_id, 'computer', ca.id::text, ca.system_name",
+        ORG_MEMBERS_SQL
+    ))
+    .bind(info_system_id)
+    .bind(actor)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(person_id, access_type, grant_id, target)| {
+            (
+                person_id,
+                RevokedGrant {
+                    access_type,
+                    grant_id,
+                    target,
+                },
+            )
+        })
+        .collect())
+}
@@ -0,0 +1,99 @@
+// Per-person access timeline.
+//
+// Merges every access-relevant event for one person into one chronological
+// list: classic grants (granted, revoked, expired), digital-resource grants
+// (started, ended), NDAs (issued, signed, rejected/revoked), clearance level
+// changes, clearance records' expiry and latest status change, and managerial
+// authorizations (granted or pending, withdrawn). Digital-resource grants use opaque text subject ids; they are
+// matched on the person's integer id rendered as text.
+use chrono::NaiveDateTime;
+use sqlx::PgPool;
+
+use super::models::TimelineEvent;
+
+// Each branch yields (at, event, source, source_id, actor_person_id, summary).
+// $1 = person id, $2 = now (naive UTC).
+const TIMELINE_SQL: &str = r#"
+    SELECT granted_at AS at, 'ACCESS_GRANTED' AS event, 'computer_access' AS source,
+           id::text AS source_id, granted_by_person_id AS actor_person_id,
+           access_level || ' access to ' || system_name AS summary
+    FROM computer_access WHERE person_id = $1
+    UNION ALL
+    SELECT revoked_at, 'ACCESS_' || status, 'computer_access', id::text, revoked_by_person_id,
+           system_name || COALESCE(': ' || revocation_reason, '')
+    FROM computer_access WHERE person_id = $1 AND status <> 'ACTIVE' AND revoked_at IS NOT NULL
+    UNION ALL
+    SELECT granted_at, 'ACCESS_GRANTED', 'data_access', id::text, granted_by_person_id,
+           access_level || ' access to ' || data_classification || ' data'
+    FROM data_access WHERE person_id = $1
+    UNION ALL
+    SELECT revoked_at, 'ACCESS_' || status, 'data_access', id::text, revoked_by_person_id,
+           data_classification || ' data' || COALESCE(': ' || revocation_reason, '')
+    FROM data_access WHERE person_id = $1 AND status <> 'ACTIVE' AND revoked_at IS NOT NULL
+    UNION ALL
+    SELECT valid_from, 'ACCESS_GRANTED', 'physical_access', id::text, granted_by_person_id,
+           access_level || ' access to zone ' || zone_name
+    FROM physical_access WHERE person_id = $1
+    UNION ALL
+    SELECT revoked_at, 'ACCESS_' || status, 'physical_access', id::text, revoked_by_person_id,
+           'zone ' || zone_name || COALESCE(': ' || revocation_reason, '')
+    FROM physical_access WHERE person_id = $1 AND status <> 'ACTIVE' AND revoked_at IS NOT NULL
+    UNION ALL
+    SELECT COALESCE(valid_from, created_at) AT TIME ZONE 'UTC', 'RESOURCE_GRANT_STARTED',
+           'resource_access_grants', id, NULL, 'Access to resource ' || resource_id
+    FROM resource_access_grants WHERE person_id = $1::text
+    UNION ALL
+    SELECT valid_until AT TIME ZONE 'UTC', 'RESOURCE_GRANT_ENDED', 'resource_access_grants',
+           id, NULL, 'Access to resource ' || resource_id
+    FROM resource_access_grants
+    WHERE person_id = $1::text AND valid_until IS NOT NULL AND valid_until AT TIME ZONE 'UTC' <= $2
+    UNION ALL
+    SELECT issued_at, 'NDA_ISSUED', 'nda', id::text, issued_by_person_id,
+           title || ' v' || version
+    FROM nda WHERE person_id = $1
+    UNION ALL
+    SELECT signed_at, 'NDA_SIGNED', 'nda', id::text, person_id, title || ' v' || version
+    FROM nda WHERE person_id = $1 AND signed_at IS NOT NULL
+    UNION ALL
+    SELECT updated_at, 'NDA_' || status, 'nda', id::text, NULL,
+           title || ' v' || version || COALESCE(': ' || rejection_reason, '')
+    FROM nda WHERE person_id = $1 AND status IN ('REJECTED', 'REVOKED')
+    UNION ALL
+    SELECT changed_at, 'CLEARANCE_CHANGED', 'person_clearance_changes', id::text, NULL,
+           COALESCE(old_level, 'none') || ' -> ' || COALESCE(new_level, 'none')
+    FROM person_clearance_changes WHERE person_id = $1
+    UNION ALL
+    SELECT status_changed_at,
+           CASE status WHEN 'GRANTED' THEN 'CLEARANCE_REINSTATED' ELSE 'CLEARANCE_' || status END,
+           'clearance_records', id::text, status_changed_by_person_id,
+           level || COALESCE(': ' || status_reason, '')
+    FROM clearance_records WHERE person_id = $1 AND status_changed_at IS NOT NULL
+    UNION ALL
+    SELECT valid_until, 'CLEARANCE_EXPIRED', 'clearance_records', id::text, NULL,
+           level || ' granted by ' || granting_authority
+    FROM clearance_records WHERE person_id = $1 AND valid_until IS NOT NULL AND valid_until <= $2
+    UNION ALL
+    SELECT created_at,
+           CASE status WHEN 'PENDING' THEN 'AUTHORIZATION_PENDING' ELSE 'AUTHORIZATION_GRANTED' END,
+           'person_authorizations', id::text, authorized_by_person_id,
+           COALESCE('until ' || valid_until::date::text, 'awaiting conversation')
+    FROM person_authorizations WHERE person_id = $1
+    UNION ALL
+    SELECT withdrawn_at, 'AUTHORIZATION_WITHDRAWN', 'person_authorizations', id::text,
+           withdrawn_by_person_id, COALESCE(withdrawal_reason, '')
+    FROM person_authorizations WHERE person_id = $1 AND withdrawn_at IS NOT NULL
+    ORDER BY at, event, source_id
+"#;
+
+/// Every access-relevant event for `person_id`, oldest first.
+pub async fn load_person_timeline(
+    db: &PgPool,
+    person_id: i32,
+    now: NaiveDateTime,
+) -> Result<Vec<TimelineEvent>, sqlx::Error> {
+    sqlx::query_as::<_, TimelineEvent>(TIMELINE_SQL)
+        .bind(person_id)
+        .bind(now)
+        .fetch_all(db)
+        .await
+}
@@ -0,0 +1,533 @@
+// Bulk grant/revoke HTTP handlers (mounted at /api/access/bulk)
+//
+// Both endpoints take a selector and default to a dry run that returns the
+// per-person plan without writing anything. With "dry_run": false the same
+// plan is applied in one transaction under a new batch id, which is stored
+// with every outcome and quoted in each audit entry the batch writes. Persons
+// who gained the access since the plan was made are skipped, not re-granted.
+// Target-level checks (system status, resource existence) fail the whole call;
+// person-level checks (SoD self-grant, clearance, compartments, shielding)
+// only block that person.
+use std::collections::HashSet;
+
+use rocket::serde::json::Json;
+use rocket::{get, http::Status, post, State};
+use sqlx::{PgPool, Postgres, Transaction};
+use validator::Validate;
+
+use super::models::{
+    BulkBatch, BulkBatchDetail, BulkGrantRequest, BulkItem, BulkResult, BulkRevokeRequest,
+    BulkSelector, OUTCOME_BLOCKED, OUTCOME_GRANTED, OUTCOME_REVOKED,
+};
+use super::planning::{
+    load_active_grants, plan_grant, plan_revoke, resolve_selector, skip_held, Candidate,
+    MAX_BULK_SELECTION,
+};
+use crate::access::clearance::required_classification;
+use crate::access::grants::{
+    end_resource_grant, grant_table, insert_computer_access, insert_data_access,
+    insert_physical_access, insert_resource_grant, revoke_access_grant, valid_access_level,
+    valid_target, ACCESS_COMPUTER, ACCESS_DATA, ACCESS_PHYSICAL, ACCESS_RESOURCE,
+};
+use crate::access::systems::{enforce_grantable_system, shield_admitted_persons};
+use crate::audit::handlers::create_audit_log;
+use crate::audit::models::CreateAuditLogRequest;
+use crate::auth::middleware::AuthGuard;
+use crate::compartments::checks::{active_compartments_by_person, required_compartments};
+use crate::digital_resources::handlers::assert_resource_exists;
+use crate::shared::rbac::role_has_permission;
+use crate::shared::response::ApiResponse;
+use crate::sod::enforce::load_enabled_rules;
+use crate::sod::rules::self_action_rule;
+
+const BATCH_COLUMNS: &str = "id, operation, access_type, target, access_level, selector, reason, \
+    performed_by_person_id, created_at";
+
+/// Require access.write (admin role for digital-resource grants) and return
+/// the caller's person id.
+async fn require_bulk_write(
+    db: &PgPool,
+    auth: &AuthGuard,
+    access_type: &str,
+) -> Result<i32, Status> {
+    if !role_has_permission(db, &auth.claims.role, "access.write")
+        .await
+        .unwrap_or(false)
+    {
+        return Err(Status::Forbidden);
+    }
+    if access_type == ACCESS_RESOURCE && auth.claims.role != "admin" {
+        return Err(Status::Forbidden);
+    }
+    auth.claims
+        .sub
+        .parse::<i32>()
+        .map_err(|_| Status::InternalServerError)
+}
+
+fn known_access_type(access_type: &str) -> bool {
+    access_type == ACCESS_RESOURCE || grant_table(access_type).is_some()
+}
+
+/// 400 unless exactly one selector is set and a holders_of selector names a
+/// known access type; 400 when the selection exceeds MAX_BULK_SELECTION.
+async fn select(
+    db: &PgPool,
+    selector: &BulkSelector,
+    now: chrono::NaiveDateTime,
+) -> Result<Vec<Candidate>, Status> {
+    if !selector.is_single()
+        || selector
+            .holders_of
+            .as_ref()
+            .is_some_and(|h| !known_access_type(&h.access_type))
+    {
+        return Err(Status::BadRequest);
+    }
+    let mut candidates = resolve_selector(db, selector, now).await.map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?;
+    if candidates.len() > MAX_BULK_SELECTION {
+        return Err(Status::BadRequest);
+    }
+    let ids: Vec<i32> = candidates.iter().map(|c| c.person_id).collect();
+    let mut held = active_compartments_by_person(db, &ids, now)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    for candidate in &mut candidates {
+        candidate.compartments = held.remove(&candidate.person_id).unwrap_or_default();
+    }
+    Ok(candidates)
+}
+
+/// What an applied batch records; shared by the batch row and its audit trail.
+struct BatchRecord<'a> {
+    id: String,
+    operation: &'static str,
+    access_type: &'a str,
+    target: &'a str,
+    access_level: Option<&'a str>,
+    selector: &'a BulkSelector,
+    reason: Option<&'a str>,
+    actor: i32,
+}
+
+async fn insert_batch(
+    tx: &mut Transaction<'_, Postgres>,
+    batch: &BatchRecord<'_>,
+    items: &[BulkItem],
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO access_bulk_batches \
+         (id, operation, access_type, target, access_level, selector, reason, performed_by_person_id) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+    )
+    .bind(&batch.id)
+    .bind(batch.operation)
+    .bind(batch.access_type)
+    .bind(batch.target)
+    .bind(batch.access_level)
+    .bind(serde_json::to_value(batch.selector).unwrap_or_default())
+    .bind(batch.reason)
+    .bind(batch.actor)
+    .execute(&mut **tx)
+    .await?;
+    for item in items {
+        sqlx::query(
+            "INSERT INTO access_bulk_batch_items (batch_id, person_id, outcome, grant_id, detail) \
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(&batch.id)
+        .bind(item.person_id)
+        .bind(&item.outcome)
+        .bind(&item.grant_id)
+        .bind(&item.detail)
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Per-grant entries plus one BULK_ACCESS_* summary, all quoting the batch id.
+async fn audit_batch(db: &PgPool, batch: &BatchRecord<'_>, items: &[BulkItem]) {
+    let (actor, access_type) = (batch.actor, batch.access_type);
+    let (action, applied) = if batch.operation == "GRANT" {
+        ("ACCESS_GRANTED", OUTCOME_GRANTED)
+    } else {
+        ("ACCESS_REVOKED", OUTCOME_REVOKED)
+    };
+    let resource_type = if access_type == ACCESS_RESOURCE {
+        "resource_access_grants".to_string()
+    } else {
+        format!("{}_access", access_type)
+    };
+    let mut count = 0;
+    for item in items.iter().filter(|i| i.outcome == applied) {
+        count += 1;
+        let _ = create_audit_log(
+            &CreateAuditLogRequest {
+                person_id: Some(actor),
+                username: actor.to_string(),
+                action: action.to_string(),
+                resource_type: resource_type.clone(),
+                resource_id: item.grant_id.as_deref().and_then(|id| id.parse().ok()),
+                details: Some(format!(
+                    "batch {}: {} grant {} on '{}' for person_id={}",
+                    batch.id,
+                    access_type,
+                    item.grant_id.as_deref().unwrap_or("-"),
+                    batch.target,
+                    item.person_id
+                )),
+                ip_address: None,
+                user_agent: None,
+            },
+            db,
+        )
+        .await;
+    }
+    let _ = create_audit_log(
+        &CreateAuditLogRequest {
+            person_id: Some(actor),
+            username: actor.to_string(),
+            action: format!("BULK_ACCESS_{}", batch.operation),
+            resource_type: "access_bulk_batch".to_string(),
+            resource_id: None,
+            details: Some(format!(
+                "batch {}: {} {} '{}': {} applied of {} item(s)",
+                batch.id,
+                batch.operation,
+                access_type,
+                batch.target,
+                count,
+                items.len()
+            )),
+            ip_address: None,
+            user_agent: None,
+        },
+        db,
+    )
+    .await;
+}
+
+/// Block the planned grants a shielded system does not admit; a no-op for
+/// unshielded systems.
+async fn block_shielded_out(
+    db: &PgPool,
+    system_name: &str,
+    items: &mut [BulkItem],
+) -> Result<(), Status> {
+    let planned: Vec<i32> = items
+        .iter()
+        .filter(|i| i.outcome == OUTCOME_GRANTED)
+        .map(|i| i.person_id)
+        .collect();
+    let Some(admitted) = shield_admitted_persons(db, system_name, &planned)
+        .await
+        .map_err(|_| Status::InternalServerError)?
+    else {
+        return Ok(());
+    };
+    for item in items
+        .iter_mut()
+        .filter(|i| i.outcome == OUTCOME_GRANTED && !admitted.contains(&i.person_id))
+    {
+        item.outcome = OUTCOME_BLOCKED.to_string();
+        item.detail =
+            Some("Shielded system: not in the owner or an allowlisted organization".to_string());
+    }
+    Ok(())
+}
+
+/// Grant one access to every selected person. Dry run unless
+/// "dry_run": false.
+#[post("/grant", data = "<data>")]
+pub async fn bulk_grant(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    data: Json<BulkGrantRequest>,
+) -> Result<Json<ApiResponse<BulkResult>>, Status> {
+    let caller = require_bulk_write(db.inner(), &auth, &data.access_type).await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+
+    // Target checks, same as the single-grant endpoints.
+    if data.access_type == ACCESS_RESOURCE {
+        if data.access_level.is_some() {
+            return Err(Status::BadRequest);
+        }
+        assert_resource_exists(&data.target, db.inner()).await?;
+    } else {
+        let level = data.access_level.as_deref().ok_or(Status::BadRequest)?;
+        if !valid_access_level(&data.access_type, level)
+            || !valid_target(&data.access_type, &data.target)
+        {
+            return Err(Status::BadRequest);
+        }
+        if data.access_type == ACCESS_COMPUTER {
+            enforce_grantable_system(db.inner(), &data.target).await?;
+        }
+    }
+
+    let now = chrono::Utc::now().naive_utc();
+    let candidates = select(db.inner(), &data.selector, now).await?;
+    let rules = load_enabled_rules(db.inner()).await.map_err(|e| {
+        eprintln!("DB error loading sod_rules: {:?}", e);
+        Status::InternalServerError
+    })?;
+    let self_rule =
+        self_action_rule(&rules, "access.write", &caller, &caller).map(|r| r.name.as_str());
+    let required = required_classification(db.inner(), &data.access_type, &data.target)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let needed_compartments = required_compartments(db.inner(), &data.access_type, &data.target)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let holders: HashSet<i32> =
+        load_active_grants(db.inner(), &data.access_type, &data.target, now)
+            .await
+            .map_err(|_| Status::InternalServerError)?
+            .into_iter()
+            .map(|g| g.person_id)
+            .collect();
+    let mut items = plan_grant(
+        &candidates,
+        &holders,
+        caller,
+        self_rule,
+        required.as_deref(),
+        &needed_compartments,
+    );
+    if data.access_type == ACCESS_COMPUTER {
+        block_shielded_out(db.inner(), &data.target, &mut items).await?;
+    }
+
+    if data.dry_run.unwrap_or(true) {
+        return Ok(Json(ApiResponse::success(BulkResult {
+            batch_id: None,
+            dry_run: true,
+            selected: candidates.len(),
+            items,
+        })));
+    }
+
+    let batch = BatchRecord {
+        id: uuid::Uuid::new_v4().to_string(),
+        operation: "GRANT",
+        access_type: &data.access_type,
+        target: &data.target,
+        access_level: data.access_level.as_deref(),
+        selector: &data.selector,
+        reason: None,
+        actor: caller,
+    };
+    let mut tx = db
+        .inner()
+        .begin()
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    // Lock the persons about to be granted and read the holders again: a
+    // concurrent batch that granted them since the plan was made has
+    // committed by now, and its grants turn these into skips.
+    let planned: Vec<i32> = items
+        .iter()
+        .filter(|i| i.outcome == OUTCOME_GRANTED)
+        .map(|i| i.person_id)
+        .collect();
+    sqlx::query("SELECT id FROM person WHERE id = ANY($1) ORDER BY id FOR UPDATE")
+        .bind(&planned)
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let holders: HashSet<i32> = load_active_grants(&mut *tx, &data.access_type, &data.target, now)
+        .await
+        .map_err(|_| Status::InternalServerError)?
+        .into_iter()
+        .map(|g| g.person_id)
+        .collect();
+    skip_held(&mut items, &holders);
+
+    let level = data.access_level.as_deref().unwrap_or_default();
+    for item in items.iter_mut().filter(|i| i.outcome == OUTCOME_GRANTED) {
+        let grant_id = match data.access_type.as_str() {
+            ACCESS_COMPUTER => insert_computer_access(
+                &mut *tx,
+                item.person_id,
+                &data.target,
+                level,
+                caller,
+                data.expires_at,
+            )
+            .await
+            .map(|g| g.id.to_string()),
+            ACCESS_DATA => insert_data_access(
+                &mut *tx,
+                item.person_id,
+                &data.target,
+                level,
+                caller,
+                data.expires_at,
+            )
+            .await
+            .map(|g| g.id.to_string()),
+            ACCESS_PHYSICAL => insert_physical_access(
+                &mut *tx,
+                item.person_id,
+                &data.target,
+                level,
+                caller,
+                data.expires_at,
+            )
+            .await
+            .map(|g| g.id.to_string()),
+            _ => {
+                insert_resource_grant(
+                    &mut *tx,
+                    &item.person_id.to_string(),
+                    &data.target,
+                    None,
+                    data.expires_at.map(|t| t.and_utc()),
+                )
+                .await
+            }
+        }
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            Status::InternalServerError
+        })?;
+        item.grant_id = Some(grant_id);
+    }
+    insert_batch(&mut tx, &batch, &items).await.map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?;
+    tx.commit().await.map_err(|_| Status::InternalServerError)?;
+
+    audit_batch(db.inner(), &batch, &items).await;
+
+    Ok(Json(ApiResponse::success(BulkResult {
+        batch_id: Some(batch.id),
+        dry_run: false,
+        selected: candidates.len(),
+        items,
+    })))
+}
+
+/// Revoke every active grant of one type on one target from the selected
+/// persons. Dry run unless "dry_run": false.
+#[post("/revoke", data = "<data>")]
+pub async fn bulk_revoke(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    data: Json<BulkRevokeRequest>,
+) -> Result<Json<ApiResponse<BulkResult>>, Status> {
+    let caller = require_bulk_write(db.inner(), &auth, &data.access_type).await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    if !known_access_type(&data.access_type) {
+        return Err(Status::BadRequest);
+    }
+
+    let now = chrono::Utc::now().naive_utc();
+    let candidates = select(db.inner(), &data.selector, now).await?;
+    let grants = load_active_grants(db.inner(), &data.access_type, &data.target, now)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let items = plan_revoke(&candidates, &grants);
+
+    if data.dry_run.unwrap_or(true) {
+        return Ok(Json(ApiResponse::success(BulkResult {
+            batch_id: None,
+            dry_run: true,
+            selected: candidates.len(),
+            items,
+        })));
+    }
+
+    let reason = data.reason.as_deref().unwrap_or("Bulk revoke");
+    let batch = BatchRecord {
+        id: uuid::Uuid::new_v4().to_string(),
+        operation: "REVOKE",
+        access_type: &data.access_type,
+        target: &data.target,
+        access_level: None,
+        selector: &data.selector,
+        reason: Some(reason),
+        actor: caller,
+    };
+    let mut tx = db
+        .inner()
+        .begin()
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    for item in items.iter().filter(|i| i.outcome == OUTCOME_REVOKED) {
+        let grant_id = item.grant_id.as_deref().unwrap_or_default();
+        let result = if data.access_type == ACCESS_RESOURCE {
+            end_resource_grant(&mut *tx, grant_id).await
+        } else {
+            revoke_access_grant(
+                &mut *tx,
+                &data.access_type,
+                grant_id.parse().unwrap_or_default(),
+                Some(caller),
+                Some(reason),
+            )
+            .await
+        };
+        match result {
+            Ok(true) => {}
+            // Ended by someone else since the plan was made; retry the call.
+            Ok(false) => return Err(Status::Conflict),
+            Err(e) => {
+                eprintln!("Database error: {:?}", e);
+                return Err(Status::InternalServerError);
+            }
+        }
+    }
+    insert_batch(&mut tx, &batch, &items).await.map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?;
+    tx.commit().await.map_err(|_| Status::InternalServerError)?;
+
+    audit_batch(db.inner(), &batch, &items).await;
+
+    Ok(Json(ApiResponse::success(BulkResult {
+        batch_id: Some(batch.id),
+        dry_run: false,
+        selected: candidates.len(),
+        items,
+    })))
+}
+
+/// A stored batch with every per-person outcome (audit.read).
+#[get("/<batch_id>")]
+pub async fn get_batch(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    batch_id: &str,
+) -> Result<Json<ApiResponse<BulkBatchDetail>>, Status> {
+    if !role_has_permission(db.inner(), &auth.claims.role, "audit.read")
+        .await
+        .unwrap_or(false)
+    {
+        return Err(Status::Forbidden);
+    }
+    let batch = sqlx::query_as::<_, BulkBatch>(&format!(
+        "SELECT {} FROM access_bulk_batches WHERE id = $1",
+        BATCH_COLUMNS
+    ))
+    .bind(batch_id)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?
+    .ok_or(Status::NotFound)?;
+    let items = sqlx::query_as::<_, BulkItem>(
+        "SELECT person_id, outcome, grant_id, detail FROM access_bulk_batch_items \
+         WHERE batch_id = $1 ORDER BY id",
+    )
+    .bind(batch_id)
+    .fetch_all(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    Ok(Json(ApiResponse::success(BulkBatchDetail { batch, items })))
+}
@@ -0,0 +1,15 @@
+// Bulk access module
+// Grant or revoke one access for a selection of persons, with dry-run preview
+// and per-batch audit ids
+
+pub mod handlers;
+pub mod models;
+pub mod planning;
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![
+        handlers::bulk_grant,
+        handlers::bulk_revoke,
+        handlers::get_batch,
+    ]
+}
@@ -0,0 +1,97 @@
+// Bulk access operation data models
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+pub const OUTCOME_GRANTED: &str = "GRANTED";
+pub const OUTCOME_REVOKED: &str = "REVOKED";
+pub const OUTCOME_SKIPPED: &str = "SKIPPED";
+pub const OUTCOME_BLOCKED: &str = "BLOCKED";
+
+/// Current holders of an active grant, as a selector
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoldersOf {
+    pub access_type: String,
+    pub target: String,
+}
+
+/// Who a bulk operation applies to. Exactly one field must be set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkSelector {
+    pub person_ids: Option<Vec<i32>>,
+    pub department: Option<String>,
+    pub organization_id: Option<i32>,
+    pub holders_of: Option<HoldersOf>,
+}
+
+impl BulkSelector {
+    pub fn is_single(&self) -> bool {
+        [
+            self.person_ids.is_some(),
+            self.department.is_some(),
+            self.organization_id.is_some(),
+            self.holders_of.is_some(),
+        ]
+        .iter()
+        .filter(|set| **set)
+        .count()
+            == 1
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct BulkGrantRequest {
+    pub selector: BulkSelector,
+    pub access_type: String, // computer, data, physical, resource
+    #[validate(length(min = 1, max = 100))]
+    pub target: String, // system_name / data_classification / zone_name / resource_id
+    pub access_level: Option<String>, // required except for resource grants
+    pub expires_at: Option<chrono::NaiveDateTime>,
+    pub dry_run: Option<bool>, // defaults to true; send false to apply
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct BulkRevokeRequest {
+    pub selector: BulkSelector,
+    pub access_type: String,
+    #[validate(length(min = 1, max = 100))]
+    pub target: String,
+    #[validate(length(max = 500))]
+    pub reason: Option<String>,
+    pub dry_run: Option<bool>, // defaults to true; send false to apply
+}
+
+/// Per-person outcome; in a dry run, the outcome applying would have
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BulkItem {
+    pub person_id: i32,
+    pub outcome: String, // GRANTED, REVOKED, SKIPPED, BLOCKED
+    pub grant_id: Option<String>,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkResult {
+    pub batch_id: Option<String>, // None for dry runs
+    pub dry_run: bool,
+    pub selected: usize,
+    pub items: Vec<BulkItem>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct BulkBatch {
+    pub id: String,
+    pub operation: String, // GRANT, REVOKE
+    pub access_type: String,
+    pub target: String,
+    pub access_level: Option<String>,
+    pub selector: serde_json::Value,
+    pub reason: Option<String>,
+    pub performed_by_person_id: i32,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkBatchDetail {
+    pub batch: BulkBatch,
+    pub items: Vec<BulkItem>,
+}
@@ -0,0 +1,454 @@
+// Bulk operation planning.
+//
+// A bulk call resolves its selector to candidate persons, loads who already
+// holds the target, and then plan_grant / plan_revoke decide each person's
+// outcome without touching the DB. Dry runs return that plan as-is; applying
+// executes the GRANTED / REVOKED items in one transaction, after locking the
+// persons to grant and re-reading the holders (skip_held).
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::NaiveDateTime;
+use sqlx::PgExecutor;
+
+use super::models::{
+    BulkItem, BulkSelector, OUTCOME_BLOCKED, OUTCOME_GRANTED, OUTCOME_REVOKED, OUTCOME_SKIPPED,
+};
+use crate::access::clearance::{clearance_covers, effective_clearance_sql};
+use crate::access::grants::{ACCESS_COMPUTER, ACCESS_DATA, ACCESS_PHYSICAL, ACCESS_RESOURCE};
+use crate::compartments::checks::missing_compartments;
+
+/// Largest selection a single bulk call may touch.
+pub const MAX_BULK_SELECTION: usize = 1000;
+
+/// A selected person; `exists` is false for explicit ids that are unknown or
+/// offboarded. `compartments` (held now) is filled in after selection.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct Candidate {
+    pub person_id: i32,
+    pub clearance_level: Option<String>,
+    pub exists: bool,
+    #[sqlx(default)]
+    pub compartments: Vec<String>,
+}
+
+/// (person_id, grant_id) of an active grant on a target.
+#[derive(Debug, Clone, PartialEq, sqlx::FromRow)]
+pub struct ActiveGrant {
+    pub person_id: i32,
+    pub grant_id: String,
+}
+
+/// Query for the active grants of one access type on a target. $1 is the
+/// target, $2 the evaluation time.
+fn active_grants_sql(access_type: &str) -> Option<&'static str> {
+    match access_type {
+        ACCESS_COMPUTER => Some(
+            "SELECT person_id, id::text AS grant_id FROM computer_access \
+             WHERE system_name = $1 AND status = 'ACTIVE' \
+               AND (expires_at IS NULL OR expires_at > $2) ORDER BY id",
+        ),
+        ACCESS_DATA => Some(
+            "SELECT person_id, id::text AS grant_id FROM data_access \
+             WHERE data_classification = $1 AND status = 'ACTIVE' \
+               AND (expires_at IS NULL OR expires_at > $2) ORDER BY id",
+        ),
+        ACCESS_PHYSICAL => Some(
+            "SELECT person_id, id::text AS grant_id FROM physical_access \
+             WHERE zone_name = $1 AND status = 'ACTIVE' \
+               AND (valid_until IS NULL OR valid_until > $2) ORDER BY id",
+        ),
+        // Only grants to persons; delegate grants keyed by other ids are left out.
+        ACCESS_RESOURCE => Some(
+            "SELECT person_id::int AS person_id, id AS grant_id FROM resource_access_grants \
+             WHERE resource_id = $1 AND person_id ~ '^[0-9]+$' \
+               AND (valid_from IS NULL OR valid_from <= $2::timestamp AT TIME ZONE 'UTC') \
+               AND (valid_until IS NULL OR valid_until > $2::timestamp AT TIME ZONE 'UTC') \
+             ORDER BY id",
+        ),
+        _ => None,
+    }
+}
+
+/// Active grants of `access_type` on `target` at `now`. Unknown access types
+/// have none.
+pub async fn load_active_grants<'e>(
+    executor: impl PgExecutor<'e>,
+    access_type: &str,
+    target: &str,
+    now: NaiveDateTime,
+) -> Result<Vec<ActiveGrant>, sqlx::Error> {
+    let Some(sql) = active_grants_sql(access_type) else {
+        return Ok(Vec::new());
+    };
+    sqlx::query_as::<_, ActiveGrant>(sql)
+        .bind(target)
+        .bind(now)
+        .fetch_all(executor)
+        .await
+}
+
+// The clearance is the effective one at $2 (see access::clearance).
+fn candidate_columns() -> String {
+    format!(
+        "p.id AS person_id, {} AS clearance_level, TRUE AS \"exists\"",
+        effective_clearance_sql("p.id", "$2")
+    )
+}
+
+/// Persons the selector picks, ordered by id. Explicit ids keep unknown or
+/// offboarded entries (exists = false) so the plan can report them.
+pub async fn resolve_selector(
+    db: &sqlx::PgPool,
+    selector: &BulkSelector,
+    now: NaiveDateTime,
+) -> Result<Vec<Candidate>, sqlx::Error> {
+    if let Some(ids) = &selector.person_ids {
+        let found = sqlx::query_as::<_, Candidate>(&format!(
+            "SELECT {} FROM person p WHERE p.id = ANY($1) AND p.deleted_at IS NULL",
+            candidate_columns()
+        ))
+        .bind(ids)
+        .bind(now)
+        .fetch_all(db)
+        .await?;
+        let mut by_id: BTreeMap<i32, Candidate> = ids
+            .iter()
+            .map(|&id| {
+                (
+                    id,
+                    Candidate {
+                        person_id: id,
+                        clearance_level: None,
+                        exists: false,
+                        compartments: Vec::new(),
+                    },
+                )
+            })
+            .collect();
+        for candidate in found {
+            by_id.insert(candidate.person_id, candidate);
+        }
+        return Ok(by_id.into_values().collect());
+    }
+
+    if let Some(department) = &selector.department {
+        return sqlx::query_as::<_, Candidate>(&format!(
+            "SELECT {} FROM person p WHERE p.department = $1 AND p.deleted_at IS NULL \
+             ORDER BY p.id",
+            candidate_columns()
+        ))
+        .bind(department)
+        .bind(now)
+        .fetch_all(db)
+        .await;
+    }
+
+    if let Some(organization_id) = selector.organization_id {
+        return sqlx::query_as::<_, Candidate>(&format!(
+            "SELECT {} FROM person p WHERE p.deleted_at IS NULL AND EXISTS ( \
+               SELECT 1 FROM relations r WHERE r.relation_type IN ('employee', 'consultant') \
+                 AND ((r.entity_type IN ('vendor', 'organization') AND r.entity_id = $1 \
+                       AND r.related_entity_type = 'person' AND r.related_entity_id = p.id) \
+                   OR (r.entity_type = 'person' AND r.entity_id = p.id \
+                       AND r.related_entity_type IN ('vendor', 'organization') \
+                       AND r.related_entity_id = $1))) \
+             ORDER BY p.id",
+            candidate_columns()
+        ))
+        .bind(organization_id)
+        .bind(now)
+        .fetch_all(db)
+        .await;
+    }
+
+    if let Some(holders) = &selector.holders_of {
+        let ids: Vec<i32> = load_active_grants(db, &holders.access_type, &holders.target, now)
+            .await?
+            .into_iter()
+            .map(|g| g.person_id)
+            .collect();
+        return sqlx::query_as::<_, Candidate>(&format!(
+            "SELECT {} FROM person p WHERE p.id = ANY($1) AND p.deleted_at IS NULL \
+             ORDER BY p.id",
+            candidate_columns()
+        ))
+        .bind(&ids)
+        .bind(now)
+        .fetch_all(db)
+        .await;
+    }
+
+    Ok(Vec::new())
+}
+
+fn item(
+    person_id: i32,
+    outcome: &str,
+    grant_id: Option<String>,
+    detail: Option<String>,
+) -> BulkItem {
+    BulkItem {
+        person_id,
+        outcome: outcome.to_string(),
+        grant_id,
+        detail,
+    }
+}
+
+/// Outcome per candidate for a bulk grant. `self_rule` names the
+/// NO_SELF_ACTION rule that blocks the caller granting to themselves, if
+/// enabled; `required` is the classification the target needs, if any, and
+/// `required_compartments` the compartments it needs.
+pub fn plan_grant(
+    candidates: &[Candidate],
+    holders: &HashSet<i32>,
+    actor: i32,
+    self_rule: Option<&str>,
+    required: Option<&str>,
+    required_compartments: &[String],
+) -> Vec<BulkItem> {
+    candidates
+        .iter()
+        .map(|c| {
+            if !c.exists {
+                return item(
+                    c.person_id,
+                    OUTCOME_BLOCKED,
+                    None,
+                    Some("Person not found".to_string()),
+                );
+            }
+            if let Some(rule) = self_rule.filter(|_| c.person_id == actor) {
+                return item(
+                    c.person_id,
+                    OUTCOME_BLOCKED,
+                    None,
+                    Some(format!("SoD rule '{}': access.write on self", rule)),
+                );
+            }
+            if let Some(classification) = required {
+                if !clearance_covers(c.clearance_level.as_deref(), classification) {
+                    return item(
+                        c.person_id,
+                        OUTCOME_BLOCKED,
+                        None,
+                        Some(format!(
+                            "Clearance {} does not cover {}",
+                            c.clearance_level.as_deref().unwrap_or("none"),
+                            classification
+                        )),
+                    );
+                }
+            }
+            let missing = missing_compartments(required_compartments, &c.compartments);
+            if !missing.is_empty() {
+                return item(
+                    c.person_id,
+                    OUTCOME_BLOCKED,
+                    None,
+                    Some(format!("Missing compartments [{}]", missing.join(", "))),
+                );
+            }
+            if holders.contains(&c.person_id) {
+                return item(
+                    c.person_id,
+                    OUTCOME_SKIPPED,
+                    None,
+                    Some("Already holds active access".to_string()),
+                );
+            }
+            item(c.person_id, OUTCOME_GRANTED, None, None)
+        })
+        .collect()
+}
+
+/// Turn planned grants into skips for persons in `holders`: the holders read
+/// again under lock when the plan is applied.
+pub fn skip_held(items: &mut [BulkItem], holders: &HashSet<i32>) {
+    for planned in items
+        .iter_mut()
+        .filter(|i| i.outcome == OUTCOME_GRANTED && holders.contains(&i.person_id))
+    {
+        *planned = item(
+            planned.person_id,
+            OUTCOME_SKIPPED,
+            None,
+            Some("Already holds active access".to_string()),
+        );
+    }
+}
+
+/// Outcome per candidate for a bulk revoke: one REVOKED item per active grant
+/// the person holds on the target, SKIPPED when they hold none.
+pub fn plan_revoke(candidates: &[Candidate], grants: &[ActiveGrant]) -> Vec<BulkItem> {
+    let mut items = Vec::new();
+    for c in candidates {
+        if !c.exists {
+            items.push(item(
+                c.person_id,
+                OUTCOME_BLOCKED,
+                None,
+                Some("Person not found".to_string()),
+            ));
+            continue;
+        }
+        let held: Vec<&ActiveGrant> = grants
+            .iter()
+            .filter(|g| g.person_id == c.person_id)
+            .collect();
+        if held.is_empty() {
+            items.push(item(
+                c.person_id,
+                OUTCOME_SKIPPED,
+                None,
+                Some("No active access".to_string()),
+            ));
+        }
+        for g in held {
+            items.push(item(
+                c.person_id,
+                OUTCOME_REVOKED,
+                Some(g.grant_id.clone()),
+                None,
+            ));
+        }
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(person_id: i32, clearance: Option<&str>) -> Candidate {
+        Candidate {
+            person_id,
+            clearance_level: clearance.map(str::to_string),
+            exists: true,
+            compartments: Vec::new(),
+        }
+    }
+
+    fn outcomes(items: &[BulkItem]) -> Vec<(i32, &str)> {
+        items
+            .iter()
+            .map(|i| (i.person_id, i.outcome.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_plan_grant_outcomes() {
+        let mut missing = candidate(99, None);
+        missing.exists = false;
+        let candidates = vec![
+            candidate(1, Some("SECRET")),
+            candidate(5, Some("SECRET")),
+            candidate(6, Some("TOP_SECRET")),
+            candidate(7, Some("CONFIDENTIAL")),
+            missing,
+        ];
+        let holders: HashSet<i32> = [6].into_iter().collect();
+        let items = plan_grant(
+            &candidates,
+            &holders,
+            1,
+            Some("No self grant"),
+            Some("SECRET"),
+            &[],
+        );
+        assert_eq!(
+            outcomes(&items),
+            vec![
+                (1, OUTCOME_BLOCKED),
+                (5, OUTCOME_GRANTED),
+                (6, OUTCOME_SKIPPED),
+                (7, OUTCOME_BLOCKED),
+                (99, OUTCOME_BLOCKED),
+            ]
+        );
+        assert!(items[3].detail.as_deref().unwrap().contains("CONFIDENTIAL"));
+    }
+
+    #[test]
+    fn test_plan_grant_self_allowed_without_rule() {
+        let items = plan_grant(&[candidate(1, None)], &HashSet::new(), 1, None, None, &[]);
+        assert_eq!(outcomes(&items), vec![(1, OUTCOME_GRANTED)]);
+    }
+
+    #[test]
+    fn test_plan_grant_blocks_missing_compartments() {
+        let mut cleared = candidate(5, Some("SECRET"));
+        cleared.compartments = vec!["AURORA".to_string(), "SIGINT".to_string()];
+        let mut partial = candidate(6, Some("SECRET"));
+        partial.compartments = vec!["SIGINT".to_string()];
+        let required = vec!["AURORA".to_string(), "SIGINT".to_string()];
+        let items = plan_grant(
+            &[cleared, partial],
+            &HashSet::new(),
+            1,
+            None,
+            None,
+            &required,
+        );
+        assert_eq!(
+            outcomes(&items),
+            vec![(5, OUTCOME_GRANTED), (6, OUTCOME_BLOCKED)]
+        );
+        assert_eq!(
+            items[1].detail.as_deref(),
+            Some("Missing compartments [AURORA]")
+        );
+    }
+
+    #[test]
+    fn test_skip_held_only_touches_planned_grants() {
+        let mut missing = candidate(99, None);
+        missing.exists = false;
+        let mut items = plan_grant(
+            &[candidate(5, None), candidate(6, None), missing],
+            &HashSet::new(),
+            1,
+            None,
+            None,
+            &[],
+        );
+        let holders: HashSet<i32> = [6, 99].into_iter().collect();
+        skip_held(&mut items, &holders);
+        assert_eq!(
+            outcomes(&items),
+            vec![
+                (5, OUTCOME_GRANTED),
+                (6, OUTCOME_SKIPPED),
+                (99, OUTCOME_BLOCKED)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_revoke_one_item_per_grant() {
+        let grants = vec![
+            ActiveGrant {
+                person_id: 5,
+                grant_id: "10".to_string(),
+            },
+            ActiveGrant {
+                person_id: 5,
+                grant_id: "11".to_string(),
+            },
+            ActiveGrant {
+                person_id: 8,
+                grant_id: "12".to_string(),
+            },
+        ];
+        let items = plan_revoke(&[candidate(5, None), candidate(6, None)], &grants);
+        assert_eq!(
+            outcomes(&items),
+            vec![
+                (5, OUTCOME_REVOKED),
+                (5, OUTCOME_REVOKED),
+                (6, OUTCOME_SKIPPED)
+            ]
+        );
+        assert_eq!(items[1].grant_id.as_deref(), Some("11"));
+    }
+}
@@ -0,0 +1,486 @@
+// Access bundle HTTP handlers (mounted at /api/access-bundles)
+//
+// Bundles are managed and assigned under access.write. Assignment runs every
+// per-grant check up front (SoD, system status, clearance, admin role for
+// resource grants) and then issues all templates in one transaction: either
+// the person gets the whole bundle or nothing. Re-assigning tops up templates
+// that are missing or whose grant has ended.
+use rocket::serde::json::Json;
+use rocket::{delete, get, http::Status, post, State};
+use sqlx::PgPool;
+use validator::Validate;
+
+use super::models::{
+    AccessBundle, AccessBundleDetail, AccessBundleItem, AssignBundleRequest, BundleAssignment,
+    BundleAssignmentResult, BundleDriftReport, BundleItemRequest, CreateAccessBundleRequest,
+};
+use super::provisioning::{
+    compute_drift, issue_item, items_to_issue, load_assignment_grants, ASSIGNMENT_COLUMNS,
+    ITEM_COLUMNS,
+};
+use crate::access::clearance::enforce_clearance;
+use crate::access::grants::{valid_access_level, valid_target, ACCESS_COMPUTER, ACCESS_RESOURCE};
+use crate::access::systems::{enforce_grantable_system, enforce_system_shielding};
+use crate::audit::handlers::create_audit_log;
+use crate::audit::models::CreateAuditLogRequest;
+use crate::auth::middleware::AuthGuard;
+use crate::compartments::checks::enforce_compartments;
+use crate::digital_resources::handlers::assert_resource_exists;
+use crate::shared::rbac::role_has_permission;
+use crate::shared::response::ApiResponse;
+use crate::sod::enforce::enforce_no_self_action;
+
+const BUNDLE_COLUMNS: &str = "id, name, description, created_by_person_id, created_at, updated_at";
+
+/// Require access.write and return the caller's person id.
+async fn require_access_write(db: &PgPool, auth: &AuthGuard) -> Result<i32, Status> {
+    if !role_has_permission(db, &auth.claims.role, "access.write")
+        .await
+        .unwrap_or(false)
+    {
+        return Err(Status::Forbidden);
+    }
+    auth.claims
+        .sub
+        .parse::<i32>()
+        .map_err(|_| Status::InternalServerError)
+}
+
+/// 400 for a malformed template, 404 for an unknown digital resource or info
+/// system, 409 for an INACTIVE system.
+async fn validate_item(db: &PgPool, item: &BundleItemRequest) -> Result<(), Status> {
+    if item.access_type == ACCESS_RESOURCE {
+        if item.access_level.is_some() {
+            return Err(Status::BadRequest);
+        }
+        return assert_resource_exists(&item.target, db).await;
+    }
+    let level = item.access_level.as_deref().ok_or(Status::BadRequest)?;
+    if !valid_access_level(&item.access_type, level)
+        || !valid_target(&item.access_type, &item.target)
+    {
+        return Err(Status::BadRequest);
+    }
+    if item.access_type == ACCESS_COMPUTER {
+        enforce_grantable_system(db, &item.target).await?;
+    }
+    Ok(())
+}
+
+async fn fetch_bundle(db: &PgPool, id: i32) -> Result<AccessBundle, Status> {
+    sqlx::query_as::<_, AccessBundle>(&format!(
+        "SELECT {} FROM access_bundles WHERE id = $1",
+        BUNDLE_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(db)
+    .await
+    .map_err(|_| Status::InternalServerError)?
+    .ok_or(Status::NotFound)
+}
+
+async fn fetch_items(db: &PgPool, bundle_id: i32) -> Result<Vec<AccessBundleItem>, Status> {
+    sqlx::query_as::<_, AccessBundleItem>(&format!(
+        "SELECT {} FROM access_bundle_items WHERE bundle_id = $1 ORDER BY id",
+        ITEM_COLUMNS
+    ))
+    .bind(bundle_id)
+    .fetch_all(db)
+    .await
+    .map_err(|_| Status::InternalServerError)
+}
+
+async fn audit(db: &PgPool, actor: i32, action: &str, bundle_id: i32, details: String) {
+    let _ = create_audit_log(
+        &CreateAuditLogRequest {
+            person_id: Some(actor),
+            username: actor.to_string(),
+            action: action.to_string(),
+            resource_type: "access_bundle".to_string(),
+            resource_id: Some(bundle_id),
+            details: Some(details),
+            ip_address: None,
+            user_agent: None,
+        },
+        db,
+    )
+    .await;
+}
+
+#[get("/")]
+pub async fn list_bundles(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+) -> Result<Json<ApiResponse<Vec<AccessBundle>>>, Status> {
+    require_access_write(db.inner(), &auth).await?;
+    let bundles = sqlx::query_as::<_, AccessBundle>(&format!(
+        "SELECT {} FROM access_bundles ORDER BY name",
+        BUNDLE_COLUMNS
+    ))
+    .fetch_all(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    Ok(Json(ApiResponse::success(bundles)))
+}
+
+/// Create a bundle with its templates. 409 when the name is taken.
+#[post("/", data = "<data>")]
+pub async fn create_bundle(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    data: Json<CreateAccessBundleRequest>,
+) -> Result<Json<ApiResponse<AccessBundleDetail>>, Status> {
+    let caller = require_access_write(db.inner(), &auth).await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    for item in &data.items {
+        validate_item(db.inner(), item).await?;
+    }
+
+    let mut tx = db
+        .inner()
+        .begin()
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let bundle = sqlx::query_as::<_, AccessBundle>(&format!(
+        "INSERT INTO access_bundles (name, description, created_by_person_id) \
+         VALUES ($1, $2, $3) ON CONFLICT (name) DO NOTHING RETURNING {}",
+        BUNDLE_COLUMNS
+    ))
+    .bind(&data.name)
+    .bind(&data.description)
+    .bind(caller)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?
+    .ok_or(Status::Conflict)?;
+
+    let mut items = Vec::with_capacity(data.items.len());
+    for item in &data.items {
+        let row = sqlx::query_as::<_, AccessBundleItem>(&format!(
+            "INSERT INTO access_bundle_items (bundle_id, access_type, target, access_level, duration_days) \
+             VALUES ($1, $2, $3, $4, $5) ON CONFLICT DO NOTHING RETURNING {}",
+            ITEM_COLUMNS
+        ))
+        .bind(bundle.id)
+        .bind(&item.access_type)
+        .bind(&item.target)
+        .bind(&item.access_level)
+        .bind(item.duration_days)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            Status::InternalServerError
+        })?
+        // Same type and target twice in one bundle
+        .ok_or(Status::BadRequest)?;
+        items.push(row);
+    }
+    tx.commit().await.map_err(|_| Status::InternalServerError)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "ACCESS_BUNDLE_CREATED",
+        bundle.id,
+        format!("Bundle '{}' with {} template(s)", bundle.name, items.len()),
+    )
+    .await;
+
+    Ok(Json(ApiResponse::success(AccessBundleDetail {
+        bundle,
+        items,
+    })))
+}
+
+#[get("/<id>")]
+pub async fn get_bundle(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<AccessBundleDetail>>, Status> {
+    require_access_write(db.inner(), &auth).await?;
+    let bundle = fetch_bundle(db.inner(), id).await?;
+    let items = fetch_items(db.inner(), id).await?;
+    Ok(Json(ApiResponse::success(AccessBundleDetail {
+        bundle,
+        items,
+    })))
+}
+
+/// Delete a bundle. Grants it issued stay in place.
+#[delete("/<id>")]
+pub async fn delete_bundle(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<()>>, Status> {
+    let caller = require_access_write(db.inner(), &auth).await?;
+    let bundle = fetch_bundle(db.inner(), id).await?;
+    sqlx::query("DELETE FROM access_bundles WHERE id = $1")
+        .bind(id)
+        .execute(db.inner())
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    audit(
+        db.inner(),
+        caller,
+        "ACCESS_BUNDLE_DELETED",
+        id,
+        format!("Bundle '{}' deleted", bundle.name),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// Add a template. Existing assignments report it as TEMPLATE_ADDED drift
+/// until re-assigned.
+#[post("/<id>/items", data = "<data>")]
+pub async fn add_item(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    data: Json<BundleItemRequest>,
+) -> Result<Json<ApiResponse<AccessBundleItem>>, Status> {
+    let caller = require_access_write(db.inner(), &auth).await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    fetch_bundle(db.inner(), id).await?;
+    validate_item(db.inner(), &data).await?;
+    let item = sqlx::query_as::<_, AccessBundleItem>(&format!(
+        "INSERT INTO access_bundle_items (bundle_id, access_type, target, access_level, duration_days) \
+         VALUES ($1, $2, $3, $4, $5) ON CONFLICT DO NOTHING RETURNING {}",
+        ITEM_COLUMNS
+    ))
+    .bind(id)
+    .bind(&data.access_type)
+    .bind(&data.target)
+    .bind(&data.access_level)
+    .bind(data.duration_days)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?
+    .ok_or(Status::Conflict)?;
+    audit(
+        db.inner(),
+        caller,
+        "ACCESS_BUNDLE_UPDATED",
+        id,
+        format!("Template added: {} '{}'", item.access_type, item.target),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(item)))
+}
+
+/// Remove a template. Grants it issued stay active and are reported as
+/// TEMPLATE_REMOVED drift.
+#[delete("/<id>/items/<item_id>")]
+pub async fn delete_item(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    item_id: i32,
+) -> Result<Json<ApiResponse<()>>, Status> {
+    let caller = require_access_write(db.inner(), &auth).await?;
+    let item = sqlx::query_as::<_, AccessBundleItem>(&format!(
+        "DELETE FROM access_bundle_items WHERE id = $1 AND bundle_id = $2 RETURNING {}",
+        ITEM_COLUMNS
+    ))
+    .bind(item_id)
+    .bind(id)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?
+    .ok_or(Status::NotFound)?;
+    audit(
+        db.inner(),
+        caller,
+        "ACCESS_BUNDLE_UPDATED",
+        id,
+        format!("Template removed: {} '{}'", item.access_type, item.target),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// Assign a bundle: issue every template the person does not already hold
+/// through this bundle, all in one transaction.
+#[post("/<id>/assign", data = "<data>")]
+pub async fn assign_bundle(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    data: Json<AssignBundleRequest>,
+) -> Result<Json<ApiResponse<BundleAssignmentResult>>, Status> {
+    let caller = require_access_write(db.inner(), &auth).await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    let person_id = data.person_id;
+    let bundle = fetch_bundle(db.inner(), id).await?;
+    let items = fetch_items(db.inner(), id).await?;
+
+    let person_exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM person WHERE id = $1 AND deleted_at IS NULL)",
+    )
+    .bind(person_id)
+    .fetch_one(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    if !person_exists {
+        return Err(Status::NotFound);
+    }
+
+    // Same checks as the individual grant endpoints, before anything is written.
+    enforce_no_self_action(db.inner(), "access.write", &caller, &person_id).await?;
+    if items.iter().any(|i| i.access_type == ACCESS_RESOURCE) && auth.claims.role != "admin" {
+        return Err(Status::Forbidden);
+    }
+    for item in &items {
+        if item.access_type == ACCESS_COMPUTER {
+            enforce_grantable_system(db.inner(), &item.target).await?;
+            enforce_system_shielding(db.inner(), caller, person_id, &item.target).await?;
+        }
+        enforce_clearance(
+            db.inner(),
+            caller,
+            person_id,
+            &item.access_type,
+            &item.target,
+        )
+        .await?;
+        enforce_compartments(
+            db.inner(),
+            caller,
+            Some(person_id),
+            &item.access_type,
+            &item.target,
+        )
+        .await?;
+    }
+
+    let now = chrono::Utc::now().naive_utc();
+    let mut tx = db
+        .inner()
+        .begin()
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let assignment = sqlx::query_as::<_, BundleAssignment>(&format!(
+        "INSERT INTO access_bundle_assignments (bundle_id, person_id, assigned_by_person_id) \
+         VALUES ($1, $2, $3) \
+         ON CONFLICT (bundle_id, person_id) DO UPDATE \
+         SET assigned_by_person_id = EXCLUDED.assigned_by_person_id, assigned_at = CURRENT_TIMESTAMP \
+         RETURNING {}",
+        ASSIGNMENT_COLUMNS
+    ))
+    .bind(id)
+    .bind(person_id)
+    .bind(caller)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?;
+
+    // The upsert holds the assignment row until commit, so a concurrent assign
+    // of the same bundle to the same person waits above and then reads the
+    // grants this one issued instead of issuing them again.
+    let held = load_assignment_grants(&mut *tx, assignment.id, now)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let pending = items_to_issue(&items, &held);
+    for item in &pending {
+        issue_item(&mut tx, assignment.id, item, person_id, caller, now)
+            .await
+            .map_err(|e| {
+                eprintln!("Database error: {:?}", e);
+                Status::InternalServerError
+            })?;
+    }
+    tx.commit().await.map_err(|_| Status::InternalServerError)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "ACCESS_BUNDLE_ASSIGNED",
+        id,
+        format!(
+            "Bundle '{}' assigned to person_id={}: {} grant(s) issued",
+            bundle.name,
+            person_id,
+            pending.len()
+        ),
+    )
+    .await;
+
+    let grants = load_assignment_grants(db.inner(), assignment.id, now)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    Ok(Json(ApiResponse::success(BundleAssignmentResult {
+        assignment,
+        grants,
+    })))
+}
+
+#[get("/<id>/assignments")]
+pub async fn list_assignments(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<Vec<BundleAssignment>>>, Status> {
+    require_access_write(db.inner(), &auth).await?;
+    fetch_bundle(db.inner(), id).await?;
+    let assignments = sqlx::query_as::<_, BundleAssignment>(&format!(
+        "SELECT {} FROM access_bundle_assignments WHERE bundle_id = $1 ORDER BY person_id",
+        ASSIGNMENT_COLUMNS
+    ))
+    .bind(id)
+    .fetch_all(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    Ok(Json(ApiResponse::success(assignments)))
+}
+
+/// Drift of every assignment against the bundle's current templates;
+/// `drifted_only=true` omits assignments that are in sync.
+#[get("/<id>/drift?<drifted_only>")]
+pub async fn get_drift(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    drifted_only: Option<bool>,
+) -> Result<Json<ApiResponse<Vec<BundleDriftReport>>>, Status> {
+    require_access_write(db.inner(), &auth).await?;
+    fetch_bundle(db.inner(), id).await?;
+    let items = fetch_items(db.inner(), id).await?;
+    let assignments = sqlx::query_as::<_, BundleAssignment>(&format!(
+        "SELECT {} FROM access_bundle_assignments WHERE bundle_id = $1 ORDER BY person_id",
+        ASSIGNMENT_COLUMNS
+    ))
+    .bind(id)
+    .fetch_all(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+
+    let now = chrono::Utc::now().naive_utc();
+    let mut reports = Vec::with_capacity(assignments.len());
+    for assignment in assignments {
+        let grants = load_assignment_grants(db.inner(), assignment.id, now)
+            .await
+            .map_err(|_| Status::InternalServerError)?;
+        let drift = compute_drift(&items, &grants);
+        if drifted_only.unwrap_or(false) && drift.is_empty() {
+            continue;
+        }
+        reports.push(BundleDriftReport {
+            assignment,
+            in_sync: drift.is_empty(),
+            drift,
+        });
+    }
+    Ok(Json(ApiResponse::success(reports)))
+}
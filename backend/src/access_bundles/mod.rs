@@ -0,0 +1,20 @@
+// Access bundles module
+// Named grant templates assigned in one call, with drift reporting
+
+pub mod handlers;
+pub mod models;
+pub mod provisioning;
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![
+        handlers::list_bundles,
+        handlers::create_bundle,
+        handlers::get_bundle,
+        handlers::delete_bundle,
+        handlers::add_item,
+        handlers::delete_item,
+        handlers::assign_bundle,
+        handlers::list_assignments,
+        handlers::get_drift,
+    ]
+}
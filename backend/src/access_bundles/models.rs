@@ -0,0 +1,102 @@
+// Access bundle data models
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AccessBundle {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_by_person_id: i32,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+/// One grant template in a bundle
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AccessBundleItem {
+    pub id: i32,
+    pub bundle_id: i32,
+    pub access_type: String,          // computer, data, physical, resource
+    pub target: String,               // system_name / data_classification / zone_name / resource_id
+    pub access_level: Option<String>, // None for resource templates
+    pub duration_days: Option<i32>,   // grant expires this many days after assignment
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccessBundleDetail {
+    pub bundle: AccessBundle,
+    pub items: Vec<AccessBundleItem>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct BundleItemRequest {
+    pub access_type: String,
+
+    #[validate(length(min = 1, max = 100))]
+    pub target: String,
+
+    pub access_level: Option<String>,
+
+    #[validate(range(min = 1, max = 3650))]
+    pub duration_days: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateAccessBundleRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+
+    pub description: Option<String>,
+
+    #[validate]
+    pub items: Vec<BundleItemRequest>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct AssignBundleRequest {
+    #[validate(range(min = 1))]
+    pub person_id: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct BundleAssignment {
+    pub id: i32,
+    pub bundle_id: i32,
+    pub person_id: i32,
+    pub assigned_by_person_id: i32,
+    pub assigned_at: chrono::NaiveDateTime,
+}
+
+/// A grant issued by an assignment, with its current state
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AssignmentGrant {
+    pub item_id: Option<i32>, // None once the template was removed from the bundle
+    pub access_type: String,
+    pub grant_id: String,
+    pub target: String,
+    pub access_level: Option<String>,
+    pub active: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BundleAssignmentResult {
+    pub assignment: BundleAssignment,
+    pub grants: Vec<AssignmentGrant>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BundleDrift {
+    pub kind: String, // GRANT_INACTIVE, TEMPLATE_ADDED, TEMPLATE_REMOVED
+    pub access_type: String,
+    pub target: String,
+    pub item_id: Option<i32>,
+    pub grant_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BundleDriftReport {
+    pub assignment: BundleAssignment,
+    pub in_sync: bool,
+    pub drift: Vec<BundleDrift>,
+}
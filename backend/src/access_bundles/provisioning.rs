@@ -0,0 +1,269 @@
+// Bundle provisioning and drift.
+//
+// issue_item turns one template into a grant through the shared access
+// writers, so bundle grants are ordinary grants (revocable, expirable,
+// recertifiable). Each issued grant is linked back to its template in
+// access_bundle_assignment_grants; compute_drift compares those links with the
+// bundle's current templates.
+use chrono::{Duration, NaiveDateTime, TimeZone, Utc};
+use sqlx::{PgExecutor, Postgres, Transaction};
+
+use super::models::{AccessBundleItem, AssignmentGrant, BundleDrift};
+use crate::access::grants::{
+    insert_computer_access, insert_data_access, insert_physical_access, insert_resource_grant,
+    ACCESS_COMPUTER, ACCESS_DATA, ACCESS_PHYSICAL, ACCESS_RESOURCE,
+};
+
+pub const ITEM_COLUMNS: &str = "id, bundle_id, access_type, target, access_level, duration_days";
+
+pub const ASSIGNMENT_COLUMNS: &str = "id, bundle_id, person_id, assigned_by_person_id, assigned_at";
+
+pub const DRIFT_GRANT_INACTIVE: &str = "GRANT_INACTIVE";
+pub const DRIFT_TEMPLATE_ADDED: &str = "TEMPLATE_ADDED";
+pub const DRIFT_TEMPLATE_REMOVED: &str = "TEMPLATE_REMOVED";
+
+// Linked grants with their live state. $1 = assignment id, $2 = now (naive UTC).
+const ASSIGNMENT_GRANTS_SQL: &str = r#"
+    SELECT g.item_id, g.access_type, g.grant_id, g.target, g.access_level,
+           CASE g.access_type
+             WHEN 'computer' THEN EXISTS (SELECT 1 FROM computer_access a
+                 WHERE a.id::text = g.grant_id AND a.status = 'ACTIVE'
+                   AND (a.expires_at IS NULL OR a.expires_at > $2))
+             WHEN 'data' THEN EXISTS (SELECT 1 FROM data_access a
+                 WHERE a.id::text = g.grant_id AND a.status = 'ACTIVE'
+                   AND (a.expires_at IS NULL OR a.expires_at > $2))
+             WHEN 'physical' THEN EXISTS (SELECT 1 FROM physical_access a
+                 WHERE a.id::text = g.grant_id AND a.status = 'ACTIVE'
+                   AND (a.valid_until IS NULL OR a.valid_until > $2))
+             ELSE EXISTS (SELECT 1 FROM resource_access_grants a
+                 WHERE a.id = g.grant_id
+                   AND (a.valid_until IS NULL OR a.valid_until AT TIME ZONE 'UTC' > $2))
+           END AS active
+    FROM access_bundle_assignment_grants g
+    WHERE g.assignment_id = $1
+    ORDER BY g.id
+"#;
+
+pub async fn load_assignment_grants<'e>(
+    executor: impl PgExecutor<'e>,
+    assignment_id: i32,
+    now: NaiveDateTime,
+) -> Result<Vec<AssignmentGrant>, sqlx::Error> {
+    sqlx::query_as::<_, AssignmentGrant>(ASSIGNMENT_GRANTS_SQL)
+        .bind(assignment_id)
+        .bind(now)
+        .fetch_all(executor)
+        .await
+}
+
+/// Templates that still need a grant for this assignment: never issued, or
+/// issued but no longer active.
+pub fn items_to_issue<'a>(
+    items: &'a [AccessBundleItem],
+    grants: &[AssignmentGrant],
+) -> Vec<&'a AccessBundleItem> {
+    items
+        .iter()
+        .filter(|item| {
+            !grants
+                .iter()
+                .any(|g| g.item_id == Some(item.id) && g.active)
+        })
+        .collect()
+}
+
+/// Differences between the bundle's current templates and what an assignment
+/// actually holds. Ended grants of removed templates are not drift.
+pub fn compute_drift(items: &[AccessBundleItem], grants: &[AssignmentGrant]) -> Vec<BundleDrift> {
+    let mut drift = Vec::new();
+    for item in items {
+        let linked: Vec<&AssignmentGrant> = grants
+            .iter()
+            .filter(|g| g.item_id == Some(item.id))
+            .collect();
+        if linked.is_empty() {
+            drift.push(BundleDrift {
+                kind: DRIFT_TEMPLATE_ADDED.to_string(),
+                access_type: item.access_type.clone(),
+                target: item.target.clone(),
+                item_id: Some(item.id),
+                grant_id: None,
+            });
+        } else if !linked.iter().any(|g| g.active) {
+            drift.push(BundleDrift {
+                kind: DRIFT_GRANT_INACTIVE.to_string(),
+                access_type: item.access_type.clone(),
+                target: item.target.clone(),
+                item_id: Some(item.id),
+                grant_id: linked.last().map(|g| g.grant_id.clone()),
+            });
+        }
+    }
+    for grant in grants.iter().filter(|g| g.item_id.is_none() && g.active) {
+        drift.push(BundleDrift {
+            kind: DRIFT_TEMPLATE_REMOVED.to_string(),
+            access_type: grant.access_type.clone(),
+            target: grant.target.clone(),
+            item_id: None,
+            grant_id: Some(grant.grant_id.clone()),
+        });
+    }
+    drift
+}
+
+/// Issue the grant for one template and link it to the assignment. Returns
+/// the new grant id.
+pub async fn issue_item(
+    tx: &mut Transaction<'_, Postgres>,
+    assignment_id: i32,
+    item: &AccessBundleItem,
+    person_id: i32,
+    granted_by_person_id: i32,
+    now: NaiveDateTime,
+) -> Result<String, sqlx::Error> {
+    let until = item
+        .duration_days
+        .map(|days| now + Duration::days(i64::from(days)));
+    let level = item.access_level.as_deref().unwrap_or_default();
+    let grant_id = match item.access_type.as_str() {
+        ACCESS_COMPUTER => insert_computer_access(
+            &mut **tx,
+            person_id,
+            &item.target,
+            level,
+            granted_by_person_id,
+            until,
+        )
+        .await?
+        .id
+        .to_string(),
+        ACCESS_DATA => insert_data_access(
+            &mut **tx,
+            person_id,
+            &item.target,
+            level,
+            granted_by_person_id,
+            until,
+        )
+        .await?
+        .id
+        .to_string(),
+        ACCESS_PHYSICAL => insert_physical_access(
+            &mut **tx,
+            person_id,
+            &item.target,
+            level,
+            granted_by_person_id,
+            until,
+        )
+        .await?
+        .id
+        .to_string(),
+        ACCESS_RESOURCE => {
+            insert_resource_grant(
+                &mut **tx,
+                &person_id.to_string(),
+                &item.target,
+                Some(Utc.from_utc_datetime(&now)),
+                until.map(|u| Utc.from_utc_datetime(&u)),
+            )
+            .await?
+        }
+        other => {
+            return Err(sqlx::Error::Protocol(format!(
+                "unknown bundle access type {}",
+                other
+            )))
+        }
+    };
+
+    // A re-issued template replaces its ended link.
+    sqlx::query(
+        "DELETE FROM access_bundle_assignment_grants WHERE assignment_id = $1 AND item_id = $2",
+    )
+    .bind(assignment_id)
+    .bind(item.id)
+    .execute(&mut **tx)
+    .await?;
+    sqlx::query(
+        "INSERT INTO access_bundle_assignment_grants \
+         (assignment_id, item_id, access_type, grant_id, target, access_level) \
+         VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(assignment_id)
+    .bind(item.id)
+    .bind(&item.access_type)
+    .bind(&grant_id)
+    .bind(&item.target)
+    .bind(&item.access_level)
+    .execute(&mut **tx)
+    .await?;
+    Ok(grant_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: i32, access_type: &str, target: &str) -> AccessBundleItem {
+        AccessBundleItem {
+            id,
+            bundle_id: 1,
+            access_type: access_type.to_string(),
+            target: target.to_string(),
+            access_level: Some("READ".to_string()),
+            duration_days: None,
+        }
+    }
+
+    fn grant(item_id: Option<i32>, grant_id: &str, active: bool) -> AssignmentGrant {
+        AssignmentGrant {
+            item_id,
+            access_type: "computer".to_string(),
+            grant_id: grant_id.to_string(),
+            target: "File Server".to_string(),
+            access_level: Some("READ".to_string()),
+            active,
+        }
+    }
+
+    #[test]
+    fn test_in_sync_assignment_has_no_drift() {
+        let items = vec![item(1, "computer", "File Server")];
+        let grants = vec![grant(Some(1), "10", true)];
+        assert!(compute_drift(&items, &grants).is_empty());
+        assert!(items_to_issue(&items, &grants).is_empty());
+    }
+
+    #[test]
+    fn test_drift_kinds() {
+        let items = vec![
+            item(1, "computer", "File Server"),
+            item(2, "data", "SECRET"),
+        ];
+        let grants = vec![grant(Some(1), "10", false), grant(None, "11", true)];
+        let kinds: Vec<String> = compute_drift(&items, &grants)
+            .into_iter()
+            .map(|d| d.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                DRIFT_GRANT_INACTIVE,
+                DRIFT_TEMPLATE_ADDED,
+                DRIFT_TEMPLATE_REMOVED
+            ]
+        );
+        let pending: Vec<i32> = items_to_issue(&items, &grants)
+            .into_iter()
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(pending, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_ended_grant_of_removed_template_is_not_drift() {
+        let grants = vec![grant(None, "10", false)];
+        assert!(compute_drift(&[], &grants).is_empty());
+    }
+}
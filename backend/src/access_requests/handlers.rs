@@ -0,0 +1,512 @@
+// Access request HTTP handlers (mounted at /api/access-requests)
+//
+// Lifecycle: PENDING -> APPROVED (grant row created in the same transaction)
+//                    -> DENIED   (reason required)
+// A request is routed to the registered owner of its target, or to any holder
+// of access.approve when the target has no owner. Every transition notifies
+// the parties over the WebSocket channel and writes an audit entry.
+use rocket::serde::json::Json;
+use rocket::{delete, get, http::Status, post, State};
+use sqlx::PgPool;
+use validator::Validate;
+
+use super::models::{
+    AccessRequest, AccessResourceOwner, CreateAccessRequestRequest,
+    CreateAccessResourceOwnerRequest, DecideAccessRequestRequest,
+};
+use crate::access::clearance::enforce_clearance;
+use crate::access::grants::{
+    insert_computer_access, insert_data_access, insert_physical_access, valid_access_level,
+    valid_target, ACCESS_COMPUTER, ACCESS_DATA, ACCESS_PHYSICAL,
+};
+use crate::access::systems::{enforce_grantable_system, enforce_system_shielding};
+use crate::audit::handlers::create_audit_log;
+use crate::audit::models::CreateAuditLogRequest;
+use crate::auth::middleware::AuthGuard;
+use crate::compartments::checks::enforce_compartments;
+use crate::messaging::models::WebSocketMessage;
+use crate::messaging::websocket::WebSocketManager;
+use crate::shared::rbac::role_has_permission;
+use crate::shared::response::ApiResponse;
+use crate::sod::enforce::{enforce_no_self_action, enforce_requester_not_approver};
+
+const REQUEST_COLUMNS: &str = "id, person_id, requested_by_person_id, access_type, target, \
+    access_level, expires_at, justification, status, approver_person_id, decided_by_person_id, \
+    decided_at, decision_reason, granted_access_id, created_at, updated_at";
+
+fn caller_id(auth: &AuthGuard) -> Result<i32, Status> {
+    auth.claims
+        .sub
+        .parse::<i32>()
+        .map_err(|_| Status::InternalServerError)
+}
+
+async fn has_permission(db: &PgPool, auth: &AuthGuard, key: &str) -> bool {
+    role_has_permission(db, &auth.claims.role, key)
+        .await
+        .unwrap_or(false)
+}
+
+async fn fetch_request(db: &PgPool, id: i32) -> Result<AccessRequest, Status> {
+    sqlx::query_as::<_, AccessRequest>(&format!(
+        "SELECT {} FROM access_requests WHERE id = $1",
+        REQUEST_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(db)
+    .await
+    .map_err(|_| Status::InternalServerError)?
+    .ok_or(Status::NotFound)
+}
+
+/// The caller may decide a request if it was routed to them, or if they hold
+/// access.approve.
+async fn can_decide(db: &PgPool, auth: &AuthGuard, caller: i32, request: &AccessRequest) -> bool {
+    request.approver_person_id == Some(caller) || has_permission(db, auth, "access.approve").await
+}
+
+/// Persons who should hear about a new request: the routed owner, or every
+/// holder of access.approve when there is none.
+async fn approver_ids(db: &PgPool, request: &AccessRequest) -> Vec<i32> {
+    if let Some(owner) = request.approver_person_id {
+        return vec![owner];
+    }
+    sqlx::query_scalar::<_, i32>(
+        "SELECT p.id FROM person p \
+         JOIN roles r ON r.name = p.role \
+         JOIN role_permissions rp ON rp.role_id = r.id \
+         JOIN permissions perm ON perm.id = rp.permission_id \
+         WHERE perm.key = 'access.approve' AND p.deleted_at IS NULL",
+    )
+    .fetch_all(db)
+    .await
+    .unwrap_or_default()
+}
+
+async fn notify(ws: &WebSocketManager, recipients: &[i32], request: &AccessRequest) {
+    let mut recipients = recipients.to_vec();
+    recipients.sort_unstable();
+    recipients.dedup();
+    ws.broadcast_to_users(
+        &recipients,
+        WebSocketMessage::AccessRequestUpdate {
+            request_id: request.id,
+            person_id: request.person_id,
+            access_type: request.access_type.clone(),
+            target: request.target.clone(),
+            status: request.status.clone(),
+        },
+    )
+    .await;
+}
+
+async fn audit(db: &PgPool, actor: i32, action: &str, request: &AccessRequest, details: String) {
+    let _ = create_audit_log(
+        &CreateAuditLogRequest {
+            person_id: Some(actor),
+            username: actor.to_string(),
+            action: action.to_string(),
+            resource_type: "access_request".to_string(),
+            resource_id: Some(request.id),
+            details: Some(details),
+            ip_address: None,
+            user_agent: None,
+        },
+        db,
+    )
+    .await;
+}
+
+/// File an access request. Anyone may request for themselves; requesting for
+/// another person requires access.request.
+#[post("/", data = "<data>")]
+pub async fn create_access_request(
+    db: &State<PgPool>,
+    ws: &State<WebSocketManager>,
+    auth: AuthGuard,
+    data: Json<CreateAccessRequestRequest>,
+) -> Result<Json<ApiResponse<AccessRequest>>, Status> {
+    data.validate().map_err(|_| Status::BadRequest)?;
+    if !valid_access_level(&data.access_type, &data.access_level)
+        || !valid_target(&data.access_type, &data.target)
+    {
+        return Err(Status::BadRequest);
+    }
+    let caller = caller_id(&auth)?;
+    let person_id = data.person_id.unwrap_or(caller);
+    if person_id != caller && !has_permission(db.inner(), &auth, "access.request").await {
+        return Err(Status::Forbidden);
+    }
+    if data.access_type == ACCESS_COMPUTER {
+        enforce_grantable_system(db.inner(), &data.target).await?;
+    }
+
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM person WHERE id = $1 AND deleted_at IS NULL)",
+    )
+    .bind(person_id)
+    .fetch_one(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    if !exists {
+        return Err(Status::NotFound);
+    }
+
+    // Route to the target's registered owner, if any.
+    let approver_person_id: Option<i32> = sqlx::query_scalar(
+        "SELECT owner_person_id FROM access_resource_owners WHERE access_type = $1 AND target = $2",
+    )
+    .bind(&data.access_type)
+    .bind(&data.target)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+
+    let request = sqlx::query_as::<_, AccessRequest>(&format!(
+        "INSERT INTO access_requests \
+         (person_id, requested_by_person_id, access_type, target, access_level, expires_at, \
+          justification, approver_person_id) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8) \
+         RETURNING {}",
+        REQUEST_COLUMNS
+    ))
+    .bind(person_id)
+    .bind(caller)
+    .bind(&data.access_type)
+    .bind(&data.target)
+    .bind(&data.access_level)
+    .bind(data.expires_at)
+    .bind(&data.justification)
+    .bind(approver_person_id)
+    .fetch_one(db.inner())
+    .await
+    .map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?;
+
+    audit(
+        db.inner(),
+        caller,
+        "ACCESS_REQUESTED",
+        &request,
+        format!(
+            "{} {} access to '{}' requested for person_id={}",
+            request.access_level, request.access_type, request.target, request.person_id
+        ),
+    )
+    .await;
+    let mut recipients = approver_ids(db.inner(), &request).await;
+    recipients.push(request.person_id);
+    notify(ws.inner(), &recipients, &request).await;
+
+    Ok(Json(ApiResponse::success(request)))
+}
+
+/// List requests. Approvers see every request; everyone else sees requests
+/// they filed, are the subject of, or were routed.
+#[get("/?<status>")]
+pub async fn list_access_requests(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    status: Option<String>,
+) -> Result<Json<ApiResponse<Vec<AccessRequest>>>, Status> {
+    let caller = caller_id(&auth)?;
+    let see_all = has_permission(db.inner(), &auth, "access.approve").await;
+
+    let requests = sqlx::query_as::<_, AccessRequest>(&format!(
+        "SELECT {} FROM access_requests \
+         WHERE ($1::text IS NULL OR status = $1) \
+           AND ($2 OR person_id = $3 OR requested_by_person_id = $3 OR approver_person_id = $3) \
+         ORDER BY created_at DESC",
+        REQUEST_COLUMNS
+    ))
+    .bind(status)
+    .bind(see_all)
+    .bind(caller)
+    .fetch_all(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+
+    Ok(Json(ApiResponse::success(requests)))
+}
+
+#[get("/<id>")]
+pub async fn get_access_request(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<AccessRequest>>, Status> {
+    let caller = caller_id(&auth)?;
+    let request = fetch_request(db.inner(), id).await?;
+    let involved = request.person_id == caller
+        || request.requested_by_person_id == caller
+        || request.approver_person_id == Some(caller);
+    if !involved && !has_permission(db.inner(), &auth, "access.approve").await {
+        return Err(Status::Forbidden);
+    }
+    Ok(Json(ApiResponse::success(request)))
+}
+
+/// Approve a pending request: creates the access row and marks the request
+/// APPROVED in one transaction.
+#[post("/<id>/approve", data = "<data>")]
+pub async fn approve_access_request(
+    db: &State<PgPool>,
+    ws: &State<WebSocketManager>,
+    auth: AuthGuard,
+    id: i32,
+    data: Json<DecideAccessRequestRequest>,
+) -> Result<Json<ApiResponse<AccessRequest>>, Status> {
+    data.validate().map_err(|_| Status::BadRequest)?;
+    let caller = caller_id(&auth)?;
+    let request = fetch_request(db.inner(), id).await?;
+    if !can_decide(db.inner(), &auth, caller, &request).await {
+        return Err(Status::Forbidden);
+    }
+    if request.status != "PENDING" {
+        return Err(Status::Conflict);
+    }
+    enforce_requester_not_approver(
+        db.inner(),
+        "access.approve",
+        &request.requested_by_person_id,
+        &caller,
+    )
+    .await?;
+    enforce_no_self_action(db.inner(), "access.write", &caller, &request.person_id).await?;
+    if request.access_type == ACCESS_COMPUTER {
+        // The system may have been deactivated or shielded since the request
+        // was filed.
+        enforce_grantable_system(db.inner(), &request.target).await?;
+        enforce_system_shielding(db.inner(), caller, request.person_id, &request.target).await?;
+    }
+    enforce_clearance(
+        db.inner(),
+        caller,
+        request.person_id,
+        &request.access_type,
+        &request.target,
+    )
+    .await?;
+    enforce_compartments(
+        db.inner(),
+        caller,
+        Some(request.person_id),
+        &request.access_type,
+        &request.target,
+    )
+    .await?;
+
+    let mut tx = db
+        .inner()
+        .begin()
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+
+    // Re-check PENDING under a row lock so two approvers cannot both grant.
+    let still_pending: Option<bool> = sqlx::query_scalar(
+        "SELECT status = 'PENDING' FROM access_requests WHERE id = $1 FOR UPDATE",
+    )
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    if still_pending != Some(true) {
+        return Err(Status::Conflict);
+    }
+
+    let granted_access_id = match request.access_type.as_str() {
+        ACCESS_COMPUTER => insert_computer_access(
+            &mut *tx,
+            request.person_id,
+            &request.target,
+            &request.access_level,
+            caller,
+            request.expires_at,
+        )
+        .await
+        .map(|a| a.id),
+        ACCESS_DATA => insert_data_access(
+            &mut *tx,
+            request.person_id,
+            &request.target,
+            &request.access_level,
+            caller,
+            request.expires_at,
+        )
+        .await
+        .map(|a| a.id),
+        ACCESS_PHYSICAL => insert_physical_access(
+            &mut *tx,
+            request.person_id,
+            &request.target,
+            &request.access_level,
+            caller,
+            request.expires_at,
+        )
+        .await
+        .map(|a| a.id),
+        _ => return Err(Status::BadRequest),
+    }
+    .map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?;
+
+    let request = sqlx::query_as::<_, AccessRequest>(&format!(
+        "UPDATE access_requests \
+         SET status = 'APPROVED', decided_by_person_id = $1, decided_at = CURRENT_TIMESTAMP, \
+             decision_reason = $2, granted_access_id = $3 \
+         WHERE id = $4 \
+         RETURNING {}",
+        REQUEST_COLUMNS
+    ))
+    .bind(caller)
+    .bind(&data.reason)
+    .bind(granted_access_id)
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+
+    tx.commit().await.map_err(|_| Status::InternalServerError)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "ACCESS_REQUEST_APPROVED",
+        &request,
+        format!(
+            "{} access id={} granted to person_id={}",
+            request.access_type, granted_access_id, request.person_id
+        ),
+    )
+    .await;
+    notify(
+        ws.inner(),
+        &[request.requested_by_person_id, request.person_id],
+        &request,
+    )
+    .await;
+
+    Ok(Json(ApiResponse::success(request)))
+}
+
+/// Deny a pending request. A reason is mandatory.
+#[post("/<id>/deny", data = "<data>")]
+pub async fn deny_access_request(
+    db: &State<PgPool>,
+    ws: &State<WebSocketManager>,
+    auth: AuthGuard,
+    id: i32,
+    data: Json<DecideAccessRequestRequest>,
+) -> Result<Json<ApiResponse<AccessRequest>>, Status> {
+    data.validate().map_err(|_| Status::BadRequest)?;
+    let reason = data.reason.as_deref().ok_or(Status::BadRequest)?;
+    let caller = caller_id(&auth)?;
+    let request = fetch_request(db.inner(), id).await?;
+    if !can_decide(db.inner(), &auth, caller, &request).await {
+        return Err(Status::Forbidden);
+    }
+
+    let request = sqlx::query_as::<_, AccessRequest>(&format!(
+        "UPDATE access_requests \
+         SET status = 'DENIED', decided_by_person_id = $1, decided_at = CURRENT_TIMESTAMP, \
+             decision_reason = $2 \
+         WHERE id = $3 AND status = 'PENDING' \
+         RETURNING {}",
+        REQUEST_COLUMNS
+    ))
+    .bind(caller)
+    .bind(reason)
+    .bind(id)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?
+    .ok_or(Status::Conflict)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "ACCESS_REQUEST_DENIED",
+        &request,
+        format!("Reason: {}", reason),
+    )
+    .await;
+    notify(
+        ws.inner(),
+        &[request.requested_by_person_id, request.person_id],
+        &request,
+    )
+    .await;
+
+    Ok(Json(ApiResponse::success(request)))
+}
+
+#[get("/owners")]
+pub async fn list_resource_owners(
+    db: &State<PgPool>,
+    _auth: AuthGuard,
+) -> Result<Json<ApiResponse<Vec<AccessResourceOwner>>>, Status> {
+    let owners = sqlx::query_as::<_, AccessResourceOwner>(
+        "SELECT id, access_type, target, owner_person_id, created_at \
+         FROM access_resource_owners ORDER BY access_type, target",
+    )
+    .fetch_all(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    Ok(Json(ApiResponse::success(owners)))
+}
+
+/// Register (or replace) the owner who approves requests for a target.
+#[post("/owners", data = "<data>")]
+pub async fn set_resource_owner(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    data: Json<CreateAccessResourceOwnerRequest>,
+) -> Result<Json<ApiResponse<AccessResourceOwner>>, Status> {
+    if !has_permission(db.inner(), &auth, "access.write").await {
+        return Err(Status::Forbidden);
+    }
+    data.validate().map_err(|_| Status::BadRequest)?;
+    if !valid_target(&data.access_type, &data.target) {
+        return Err(Status::BadRequest);
+    }
+    let owner = sqlx::query_as::<_, AccessResourceOwner>(
+        "INSERT INTO access_resource_owners (access_type, target, owner_person_id) \
+         VALUES ($1, $2, $3) \
+         ON CONFLICT (access_type, target) DO UPDATE SET owner_person_id = EXCLUDED.owner_person_id \
+         RETURNING id, access_type, target, owner_person_id, created_at",
+    )
+    .bind(&data.access_type)
+    .bind(&data.target)
+    .bind(data.owner_person_id)
+    .fetch_one(db.inner())
+    .await
+    .map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?;
+    Ok(Json(ApiResponse::success(owner)))
+}
+
+#[delete("/owners/<id>")]
+pub async fn delete_resource_owner(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<&'static str>>, Status> {
+    if !has_permission(db.inner(), &auth, "access.write").await {
+        return Err(Status::Forbidden);
+    }
+    let result = sqlx::query("DELETE FROM access_resource_owners WHERE id = $1")
+        .bind(id)
+        .execute(db.inner())
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    if result.rows_affected() == 0 {
+        return Err(Status::NotFound);
+    }
+    Ok(Json(ApiResponse::success("Owner removed")))
+}
@@ -0,0 +1,18 @@
+// Access request module
+// Request / approve / deny workflow that ends in a computer, data or physical grant
+
+pub mod handlers;
+pub mod models;
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![
+        handlers::create_access_request,
+        handlers::list_access_requests,
+        handlers::get_access_request,
+        handlers::approve_access_request,
+        handlers::deny_access_request,
+        handlers::list_resource_owners,
+        handlers::set_resource_owner,
+        handlers::delete_resource_owner,
+    ]
+}
@@ -0,0 +1,70 @@
+// Access request data models
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// A request for computer/data/physical access awaiting (or past) a decision
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AccessRequest {
+    pub id: i32,
+    pub person_id: i32,
+    pub requested_by_person_id: i32,
+    pub access_type: String, // computer, data, physical
+    pub target: String,      // system_name / data_classification / zone_name
+    pub access_level: String,
+    pub expires_at: Option<chrono::NaiveDateTime>,
+    pub justification: String,
+    pub status: String,                  // PENDING, APPROVED, DENIED
+    pub approver_person_id: Option<i32>, // routed owner; None = any access.approve holder
+    pub decided_by_person_id: Option<i32>,
+    pub decided_at: Option<chrono::NaiveDateTime>,
+    pub decision_reason: Option<String>,
+    pub granted_access_id: Option<i32>,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateAccessRequestRequest {
+    /// Subject of the request; defaults to the caller.
+    #[validate(range(min = 1))]
+    pub person_id: Option<i32>,
+
+    pub access_type: String,
+
+    #[validate(length(min = 1, max = 100))]
+    pub target: String,
+
+    pub access_level: String,
+
+    pub expires_at: Option<chrono::NaiveDateTime>,
+
+    #[validate(length(min = 10))]
+    pub justification: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct DecideAccessRequestRequest {
+    #[validate(length(min = 1))]
+    pub reason: Option<String>,
+}
+
+/// Registered owner who approves requests for one access target
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct AccessResourceOwner {
+    pub id: i32,
+    pub access_type: String,
+    pub target: String,
+    pub owner_person_id: i32,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateAccessResourceOwnerRequest {
+    pub access_type: String,
+
+    #[validate(length(min = 1, max = 100))]
+    pub target: String,
+
+    #[validate(range(min = 1))]
+    pub owner_person_id: i32,
+}
@@ -1,7 +1,7 @@
 // Audit log HTTP handlers
 use rocket::serde::json::Json;
 use rocket::{get, http::Status, State};
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 
 use super::models::{AuditLog, CreateAuditLogRequest};
 use crate::auth::middleware::AuthGuard;
@@ -127,6 +127,15 @@ pub async fn list_audit_logs(
 pub async fn create_audit_log(
     log_request: &CreateAuditLogRequest,
     db: &PgPool,
+) -> Result<AuditLog, sqlx::Error> {
+    insert_audit_log(log_request, db).await
+}
+
+/// Create an audit log entry on any executor, so a change that must not go
+/// unaudited can write its entry inside its own transaction.
+pub async fn insert_audit_log<'e>(
+    log_request: &CreateAuditLogRequest,
+    executor: impl PgExecutor<'e>,
 ) -> Result<AuditLog, sqlx::Error> {
     sqlx::query_as::<sqlx::Postgres, AuditLog>(
         r#"
@@ -144,10 +153,36 @@ pub async fn create_audit_log(
     .bind(log_request.details.as_deref())
     .bind(log_request.ip_address.as_deref())
     .bind(log_request.user_agent.as_deref())
-    .fetch_one(db)
+    .fetch_one(executor)
     .await
 }
 
+/// Record an action `actor` took on one resource. Best effort: a failed write
+/// is dropped and never fails the request.
+pub async fn audit(
+    db: &PgPool,
+    actor: i32,
+    action: &str,
+    resource_type: &str,
+    resource_id: Option<i32>,
+    details: String,
+) {
+    let _ = create_audit_log(
+        &CreateAuditLogRequest {
+            person_id: Some(actor),
+            username: actor.to_string(),
+            action: action.to_string(),
+            resource_type: resource_type.to_string(),
+            resource_id,
+            details: Some(details),
+            ip_address: None,
+            user_agent: None,
+        },
+        db,
+    )
+    .await;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
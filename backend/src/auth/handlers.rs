@@ -25,7 +25,7 @@ pub async fn login(
     let person_auth = sqlx::query_as::<_, PersonAuth>(
         "SELECT id, username, password_hash, role, created_at, updated_at 
          FROM person 
-         WHERE username = $1 AND password_hash IS NOT NULL",
+         WHERE username = $1 AND password_hash IS NOT NULL AND deleted_at IS NULL",
     )
     .bind(&login_request.username)
     .fetch_optional(db.inner())
@@ -2,6 +2,7 @@
 use super::jwt::{validate_jwt, Claims};
 use rocket::http::Status;
 use rocket::request::{FromRequest, Outcome, Request};
+use sqlx::PgPool;
 
 #[allow(dead_code)]
 pub struct AuthGuard {
@@ -23,10 +24,29 @@ impl<'r> FromRequest<'r> for AuthGuard {
         match token {
             Some(token) => {
                 let token = token.trim_start_matches("Bearer ");
-                match validate_jwt(token, jwt_secret) {
-                    Ok(claims) => Outcome::Success(AuthGuard { claims }),
-                    Err(_) => Outcome::Error((Status::Unauthorized, ())),
+                let claims = match validate_jwt(token, jwt_secret) {
+                    Ok(claims) => claims,
+                    Err(_) => return Outcome::Error((Status::Unauthorized, ())),
+                };
+                // Tokens of offboarded (soft-deleted) persons stop working at once
+                // rather than at expiry.
+                if let (Some(db), Ok(person_id)) = (
+                    request.rocket().state::<PgPool>(),
+                    claims.sub.parse::<i32>(),
+                ) {
+                    let deleted: Result<Option<bool>, _> = sqlx::query_scalar(
+                        "SELECT deleted_at IS NOT NULL FROM person WHERE id = $1",
+                    )
+                    .bind(person_id)
+                    .fetch_optional(db)
+                    .await;
+                    match deleted {
+                        Ok(Some(true)) => return Outcome::Error((Status::Unauthorized, ())),
+                        Err(_) => return Outcome::Error((Status::InternalServerError, ())),
+                        _ => {}
+                    }
                 }
+                Outcome::Success(AuthGuard { claims })
             }
             None => Outcome::Error((Status::Unauthorized, ())),
         }
@@ -0,0 +1,247 @@
+// Managerial authorization HTTP handlers (mounted at /api/authorizations)
+//
+// Authorizing and withdrawing need authorization.manage and are limited to the
+// caller's own reports (403 otherwise); nobody may act on themselves (SoD).
+// A person's authorization history is readable under person.read. Changes
+// take effect on the next decision.
+use chrono::NaiveDateTime;
+use rocket::serde::json::Json;
+use rocket::{get, http::Status, post, State};
+use sqlx::PgPool;
+use validator::Validate;
+
+use super::lifecycle::{
+    effective_status, is_line_manager, reporting_lines_sql, STATUS_PENDING, STATUS_WITHDRAWN,
+};
+use super::models::{
+    AuthorizeRequest, PersonAuthorization, PersonAuthorizationHistory, ReportAuthorization,
+    WithdrawAuthorizationRequest,
+};
+use crate::abac::evaluator::AUTHORIZATION_AUTHORIZED;
+use crate::audit::handlers::audit;
+use crate::auth::middleware::AuthGuard;
+use crate::shared::rbac::require_permission;
+use crate::shared::response::ApiResponse;
+use crate::sod::enforce::enforce_no_self_action;
+
+const AUTHORIZATION_COLUMNS: &str = "id, person_id, status, authorized_by_person_id, \
+                                     conversation_date, valid_until, reauth_due, created_at, \
+                                     withdrawn_by_person_id, withdrawn_at, withdrawal_reason";
+
+fn db_error(e: sqlx::Error) -> Status {
+    eprintln!("Database error: {:?}", e);
+    Status::InternalServerError
+}
+
+fn with_effective_status(
+    mut record: PersonAuthorization,
+    now: NaiveDateTime,
+) -> PersonAuthorization {
+    record.effective_status = effective_status(&record.status, record.valid_until, now);
+    record
+}
+
+/// 403 unless the caller has authorization.manage, is not the person and is
+/// their line manager now. Returns the caller's id.
+async fn require_line_manager(
+    db: &PgPool,
+    auth: &AuthGuard,
+    person_id: i32,
+    now: NaiveDateTime,
+) -> Result<i32, Status> {
+    let caller = require_permission(db, auth, "authorization.manage").await?;
+    enforce_no_self_action(db, "authorization.manage", &caller, &person_id).await?;
+    if !is_line_manager(db, caller, person_id, now)
+        .await
+        .map_err(db_error)?
+    {
+        return Err(Status::Forbidden);
+    }
+    Ok(caller)
+}
+
+async fn current_record(
+    db: &PgPool,
+    person_id: i32,
+) -> Result<Option<PersonAuthorization>, sqlx::Error> {
+    sqlx::query_as::<_, PersonAuthorization>(&format!(
+        "SELECT {} FROM person_authorizations WHERE person_id = $1 \
+         ORDER BY created_at DESC, id DESC LIMIT 1",
+        AUTHORIZATION_COLUMNS
+    ))
+    .bind(person_id)
+    .fetch_optional(db)
+    .await
+}
+
+/// The caller's reports in force now, with each one's current authorization.
+#[get("/reports")]
+pub async fn list_reports(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+) -> Result<Json<ApiResponse<Vec<ReportAuthorization>>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "authorization.manage").await?;
+    let now = chrono::Utc::now().naive_utc();
+    let reports = sqlx::query_as::<_, (i32, String, String)>(&format!(
+        "SELECT p.id, p.first_name, p.last_name FROM person p \
+         WHERE p.deleted_at IS NULL \
+           AND p.id IN (SELECT report_id FROM {} lines WHERE manager_id = $1) \
+         ORDER BY p.last_name, p.first_name, p.id",
+        reporting_lines_sql("$2")
+    ))
+    .bind(caller)
+    .bind(now)
+    .fetch_all(db.inner())
+    .await
+    .map_err(db_error)?;
+
+    let mut listed = Vec::with_capacity(reports.len());
+    for (person_id, first_name, last_name) in reports {
+        let authorization = current_record(db.inner(), person_id)
+            .await
+            .map_err(db_error)?
+            .map(|record| with_effective_status(record, now));
+        listed.push(ReportAuthorization {
+            person_id,
+            first_name,
+            last_name,
+            authorization,
+        });
+    }
+    Ok(Json(ApiResponse::success(listed)))
+}
+
+/// A person's authorization records, newest first, with the status decisions
+/// currently see.
+#[get("/persons/<id>")]
+pub async fn get_person_authorization(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<PersonAuthorizationHistory>>, Status> {
+    require_permission(db.inner(), &auth, "person.read").await?;
+    let now = chrono::Utc::now().naive_utc();
+    let records: Vec<PersonAuthorization> = sqlx::query_as::<_, PersonAuthorization>(&format!(
+        "SELECT {} FROM person_authorizations WHERE person_id = $1 \
+         ORDER BY created_at DESC, id DESC",
+        AUTHORIZATION_COLUMNS
+    ))
+    .bind(id)
+    .fetch_all(db.inner())
+    .await
+    .map_err(db_error)?
+    .into_iter()
+    .map(|record| with_effective_status(record, now))
+    .collect();
+    Ok(Json(ApiResponse::success(PersonAuthorizationHistory {
+        person_id: id,
+        effective_status: records.first().map(|r| r.effective_status.clone()),
+        records,
+    })))
+}
+
+/// Authorize a report (or record a pending authorization); a new record
+/// supersedes the current one, which is how authorizations are renewed. 400
+/// for a conversation date in the future, a `valid_until` that is missing or
+/// already past for an authorization, or given for a pending one.
+#[post("/persons/<id>", data = "<data>")]
+pub async fn authorize(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    data: Json<AuthorizeRequest>,
+) -> Result<Json<ApiResponse<PersonAuthorization>>, Status> {
+    data.validate().map_err(|_| Status::BadRequest)?;
+    let now = chrono::Utc::now().naive_utc();
+    let status = match (data.conversation_date, data.valid_until) {
+        (Some(date), Some(until)) if date <= now.date() && until > now => AUTHORIZATION_AUTHORIZED,
+        (None, None) => STATUS_PENDING,
+        _ => return Err(Status::BadRequest),
+    };
+    let caller = require_line_manager(db.inner(), &auth, id, now).await?;
+
+    let record = sqlx::query_as::<_, PersonAuthorization>(&format!(
+        "INSERT INTO person_authorizations \
+           (person_id, status, authorized_by_person_id, conversation_date, valid_until, \
+            reauth_due, created_at) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING {}",
+        AUTHORIZATION_COLUMNS
+    ))
+    .bind(id)
+    .bind(status)
+    .bind(caller)
+    .bind(data.conversation_date)
+    .bind(data.valid_until)
+    .bind(data.reauth_due)
+    .bind(now)
+    .fetch_one(db.inner())
+    .await
+    .map_err(db_error)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "AUTHORIZE_SUBJECT",
+        "person_authorizations",
+        Some(id),
+        match record.valid_until {
+            Some(until) => format!("{} until {}", record.status, until),
+            None => record.status.clone(),
+        },
+    )
+    .await;
+    Ok(Json(ApiResponse::success(with_effective_status(
+        record, now,
+    ))))
+}
+
+/// Withdraw the report's current authorization. 404 when nothing is on
+/// record, 409 when it is already withdrawn.
+#[post("/persons/<id>/withdraw", data = "<data>")]
+pub async fn withdraw(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    data: Json<WithdrawAuthorizationRequest>,
+) -> Result<Json<ApiResponse<PersonAuthorization>>, Status> {
+    data.validate().map_err(|_| Status::BadRequest)?;
+    let now = chrono::Utc::now().naive_utc();
+    let caller = require_line_manager(db.inner(), &auth, id, now).await?;
+
+    let current = current_record(db.inner(), id)
+        .await
+        .map_err(db_error)?
+        .ok_or(Status::NotFound)?;
+    if current.status == STATUS_WITHDRAWN {
+        return Err(Status::Conflict);
+    }
+    // The status guard keeps a concurrent withdrawal from being recorded twice.
+    let record = sqlx::query_as::<_, PersonAuthorization>(&format!(
+        "UPDATE person_authorizations SET status = $2, withdrawn_by_person_id = $3, \
+           withdrawn_at = $4, withdrawal_reason = $5 \
+         WHERE id = $1 AND status <> $2 RETURNING {}",
+        AUTHORIZATION_COLUMNS
+    ))
+    .bind(current.id)
+    .bind(STATUS_WITHDRAWN)
+    .bind(caller)
+    .bind(now)
+    .bind(&data.reason)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(db_error)?
+    .ok_or(Status::Conflict)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "WITHDRAW_AUTHORIZATION",
+        "person_authorizations",
+        Some(id),
+        format!("{} withdrawn: {}", current.status, data.reason),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(with_effective_status(
+        record, now,
+    ))))
+}
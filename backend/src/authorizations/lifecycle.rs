@@ -0,0 +1,104 @@
+// Managerial authorization rules.
+//
+// Reporting lines are read from person-to-person relations in force (see the
+// person_authorizations migration for which relation types count). The
+// effective status is what decisions see: an AUTHORIZED record past its
+// valid_until is EXPIRED.
+use chrono::NaiveDateTime;
+use sqlx::PgExecutor;
+
+use crate::abac::evaluator::AUTHORIZATION_AUTHORIZED;
+
+pub const STATUS_WITHDRAWN: &str = "WITHDRAWN";
+pub const STATUS_PENDING: &str = "PENDING";
+pub const STATUS_EXPIRED: &str = "EXPIRED";
+
+/// Derived table of (report_id, manager_id) pairs in force at `at`.
+pub fn reporting_lines_sql(at: &str) -> String {
+    format!(
+        "(SELECT r.entity_id AS report_id, r.related_entity_id AS manager_id FROM relations r \
+          WHERE r.entity_type = 'person' AND r.related_entity_type = 'person' \
+            AND r.relation_type IN ('manager', 'supervisor', 'reports_to') \
+            AND r.valid_from <= {at} AND (r.valid_until IS NULL OR r.valid_until > {at}) \
+          UNION \
+          SELECT r.related_entity_id, r.entity_id FROM relations r \
+          WHERE r.entity_type = 'person' AND r.related_entity_type = 'person' \
+            AND r.relation_type = 'subordinate' \
+            AND r.valid_from <= {at} AND (r.valid_until IS NULL OR r.valid_until > {at}))"
+    )
+}
+
+/// The status decisions see for a record.
+pub fn effective_status(
+    status: &str,
+    valid_until: Option<NaiveDateTime>,
+    now: NaiveDateTime,
+) -> String {
+    if status == AUTHORIZATION_AUTHORIZED && valid_until.is_none_or(|until| until <= now) {
+        return STATUS_EXPIRED.to_string();
+    }
+    status.to_string()
+}
+
+/// Whether `manager_id` is a line manager of `person_id` at `now`.
+pub async fn is_line_manager<'e>(
+    executor: impl PgExecutor<'e>,
+    manager_id: i32,
+    person_id: i32,
+    now: NaiveDateTime,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar(&format!(
+        "SELECT EXISTS (SELECT 1 FROM {} lines WHERE manager_id = $1 AND report_id = $2)",
+        reporting_lines_sql("$3")
+    ))
+    .bind(manager_id)
+    .bind(person_id)
+    .bind(now)
+    .fetch_one(executor)
+    .await
+}
+
+/// The effective status of the person's newest authorization record; None
+/// when they have none.
+pub async fn load_authorization_status<'e>(
+    executor: impl PgExecutor<'e>,
+    person_id: i32,
+    now: NaiveDateTime,
+) -> Result<Option<String>, sqlx::Error> {
+    Ok(sqlx::query_as::<_, (String, Option<NaiveDateTime>)>(
+        "SELECT status, valid_until FROM person_authorizations WHERE person_id = $1 \
+         ORDER BY created_at DESC, id DESC LIMIT 1",
+    )
+    .bind(person_id)
+    .fetch_optional(executor)
+    .await?
+    .map(|(status, valid_until)| effective_status(&status, valid_until, now)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effective_status_expires_authorized_only() {
+        let now = chrono::NaiveDate::from_ymd_opt(2026, 6, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let later = now + chrono::Duration::days(1);
+        assert_eq!(
+            effective_status("AUTHORIZED", Some(later), now),
+            "AUTHORIZED"
+        );
+        assert_eq!(
+            effective_status("AUTHORIZED", Some(now), now),
+            STATUS_EXPIRED
+        );
+        assert_eq!(effective_status("AUTHORIZED", None, now), STATUS_EXPIRED);
+        assert_eq!(effective_status(STATUS_PENDING, None, now), STATUS_PENDING);
+        assert_eq!(
+            effective_status(STATUS_WITHDRAWN, Some(later), now),
+            STATUS_WITHDRAWN
+        );
+    }
+}
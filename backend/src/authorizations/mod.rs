@@ -0,0 +1,16 @@
+// Authorizations module
+// Managerial authorization lifecycle: line managers authorize reports or
+// withdraw their authorization, which expires and needs renewal
+
+pub mod handlers;
+pub mod lifecycle;
+pub mod models;
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![
+        handlers::list_reports,
+        handlers::get_person_authorization,
+        handlers::authorize,
+        handlers::withdraw,
+    ]
+}
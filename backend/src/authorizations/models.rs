@@ -0,0 +1,58 @@
+// Managerial authorization data models
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// One authorization record; only a person's newest record counts.
+/// `effective_status` is `status`, or EXPIRED for an AUTHORIZED record past
+/// `valid_until`, computed at read time.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PersonAuthorization {
+    pub id: i32,
+    pub person_id: i32,
+    pub status: String, // AUTHORIZED, WITHDRAWN, PENDING
+    pub authorized_by_person_id: i32,
+    pub conversation_date: Option<NaiveDate>,
+    pub valid_until: Option<NaiveDateTime>,
+    pub reauth_due: Option<NaiveDate>,
+    pub created_at: NaiveDateTime,
+    pub withdrawn_by_person_id: Option<i32>,
+    pub withdrawn_at: Option<NaiveDateTime>,
+    pub withdrawal_reason: Option<String>,
+    #[sqlx(default)]
+    pub effective_status: String,
+}
+
+/// A person's current authorization status (None when nothing is on record)
+/// and their records, newest first
+#[derive(Debug, Serialize)]
+pub struct PersonAuthorizationHistory {
+    pub person_id: i32,
+    pub effective_status: Option<String>,
+    pub records: Vec<PersonAuthorization>,
+}
+
+/// One of the caller's reports with their current authorization, if any
+#[derive(Debug, Serialize)]
+pub struct ReportAuthorization {
+    pub person_id: i32,
+    pub first_name: String,
+    pub last_name: String,
+    pub authorization: Option<PersonAuthorization>,
+}
+
+/// Authorize a report. With `conversation_date` the record is AUTHORIZED and
+/// `valid_until` is required; without it the record is PENDING until the
+/// conversation has taken place.
+#[derive(Debug, Deserialize, Validate)]
+pub struct AuthorizeRequest {
+    pub conversation_date: Option<NaiveDate>,
+    pub valid_until: Option<NaiveDateTime>,
+    pub reauth_due: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct WithdrawAuthorizationRequest {
+    #[validate(length(min = 1))]
+    pub reason: String,
+}
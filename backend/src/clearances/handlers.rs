@@ -0,0 +1,290 @@
+// Clearance HTTP handlers (mounted at /api/clearances)
+//
+// A person's clearance history is readable under person.read; granting and
+// status changes need clearance.manage, and nobody may change their own
+// clearance (SoD). Every write re-evaluates the person's classified grants in
+// the same transaction (see access::revocation), and person.clearance_level
+// is kept as a mirror of the newest granted level.
+use chrono::{Duration, NaiveDateTime};
+use rocket::serde::json::Json;
+use rocket::{get, http::Status, post, State};
+use sqlx::PgPool;
+use validator::Validate;
+
+use super::lifecycle::{
+    transition_action, transition_allowed, valid_level, STATUS_GRANTED, STATUS_REVOKED,
+    STATUS_SUSPENDED,
+};
+use super::models::{
+    ChangeClearanceStatusRequest, ClearanceRecord, GrantClearanceRequest, PersonClearance,
+    ReinvestigationDue,
+};
+use crate::access::clearance::load_effective_clearance;
+use crate::access::revocation::{audit_revoked_grants, revoke_exceeding_clearance};
+use crate::audit::handlers::audit;
+use crate::auth::middleware::AuthGuard;
+use crate::shared::rbac::require_permission;
+use crate::shared::response::ApiResponse;
+use crate::sod::enforce::enforce_no_self_action;
+
+const RECORD_COLUMNS: &str = "id, person_id, level, granting_authority, granted_by_person_id, \
+                              granted_at, valid_until, reinvestigation_due, status, \
+                              status_reason, status_changed_by_person_id, status_changed_at";
+
+const MAX_WITHIN_DAYS: i64 = 3650;
+
+fn db_error(e: sqlx::Error) -> Status {
+    eprintln!("Database error: {:?}", e);
+    Status::InternalServerError
+}
+
+fn now() -> NaiveDateTime {
+    chrono::Utc::now().naive_utc()
+}
+
+/// A person's effective clearance and every clearance record, newest first.
+/// 404 for an unknown or offboarded person.
+#[get("/persons/<id>")]
+pub async fn get_person_clearance(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<PersonClearance>>, Status> {
+    require_permission(db.inner(), &auth, "person.read").await?;
+    let effective_clearance = load_effective_clearance(db.inner(), id, now())
+        .await
+        .map_err(db_error)?
+        .ok_or(Status::NotFound)?;
+    let records = sqlx::query_as::<_, ClearanceRecord>(&format!(
+        "SELECT {} FROM clearance_records WHERE person_id = $1 \
+         ORDER BY granted_at DESC, id DESC",
+        RECORD_COLUMNS
+    ))
+    .bind(id)
+    .fetch_all(db.inner())
+    .await
+    .map_err(db_error)?;
+    Ok(Json(ApiResponse::success(PersonClearance {
+        person_id: id,
+        effective_clearance,
+        records,
+    })))
+}
+
+/// Grant a clearance; it supersedes the person's current record whatever its
+/// status. 400 for an unknown level or a `valid_until` already past, 404 for
+/// an unknown or offboarded person. Grants the new level no longer covers are
+/// revoked.
+#[post("/persons/<id>", data = "<data>")]
+pub async fn grant_clearance(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    data: Json<GrantClearanceRequest>,
+) -> Result<Json<ApiResponse<ClearanceRecord>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "clearance.manage").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    let now = now();
+    if !valid_level(&data.level) || data.valid_until.is_some_and(|until| until <= now) {
+        return Err(Status::BadRequest);
+    }
+    enforce_no_self_action(db.inner(), "clearance.manage", &caller, &id).await?;
+
+    let mut tx = db.inner().begin().await.map_err(db_error)?;
+    let person_exists = sqlx::query_scalar::<_, i32>(
+        "SELECT id FROM person WHERE id = $1 AND deleted_at IS NULL FOR UPDATE",
+    )
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(db_error)?
+    .is_some();
+    if !person_exists {
+        return Err(Status::NotFound);
+    }
+    let record = sqlx::query_as::<_, ClearanceRecord>(&format!(
+        "INSERT INTO clearance_records \
+           (person_id, level, granting_authority, granted_by_person_id, granted_at, \
+            valid_until, reinvestigation_due) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING {}",
+        RECORD_COLUMNS
+    ))
+    .bind(id)
+    .bind(&data.level)
+    .bind(&data.granting_authority)
+    .bind(caller)
+    .bind(now)
+    .bind(data.valid_until)
+    .bind(data.reinvestigation_due)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(db_error)?;
+    sqlx::query(
+        "UPDATE person SET clearance_level = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+    )
+    .bind(id)
+    .bind(&record.level)
+    .execute(&mut *tx)
+    .await
+    .map_err(db_error)?;
+    let revoked = revoke_exceeding_clearance(&mut tx, id, caller, now)
+        .await
+        .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
+
+    let summary = format!(
+        "{} granted by {}{}",
+        record.level,
+        record.granting_authority,
+        record
+            .valid_until
+            .map(|until| format!(", valid until {}", until))
+            .unwrap_or_default()
+    );
+    audit_revoked_grants(
+        db.inner(),
+        caller,
+        id,
+        &revoked,
+        &format!("Clearance {}", summary),
+    )
+    .await;
+    audit(
+        db.inner(),
+        caller,
+        "CLEARANCE_GRANTED",
+        "clearance_records",
+        Some(id),
+        summary,
+    )
+    .await;
+    Ok(Json(ApiResponse::success(record)))
+}
+
+/// Suspend, reinstate or revoke a clearance record. Only a person's newest
+/// record can change (409 otherwise, and for a transition the lifecycle does
+/// not allow); 400 for an unknown status or missing reason, 404 for an
+/// unknown record. Grants the resulting clearance no longer covers are
+/// revoked, and stay revoked when a suspended record is reinstated.
+#[post("/records/<id>/status", data = "<data>")]
+pub async fn change_status(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    data: Json<ChangeClearanceStatusRequest>,
+) -> Result<Json<ApiResponse<ClearanceRecord>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "clearance.manage").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    if ![STATUS_GRANTED, STATUS_SUSPENDED, STATUS_REVOKED].contains(&data.status.as_str()) {
+        return Err(Status::BadRequest);
+    }
+
+    let mut tx = db.inner().begin().await.map_err(db_error)?;
+    let current = sqlx::query_as::<_, ClearanceRecord>(&format!(
+        "SELECT {} FROM clearance_records WHERE id = $1 FOR UPDATE",
+        RECORD_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(db_error)?
+    .ok_or(Status::NotFound)?;
+    enforce_no_self_action(db.inner(), "clearance.manage", &caller, &current.person_id).await?;
+
+    let newest: i32 = sqlx::query_scalar(
+        "SELECT id FROM clearance_records WHERE person_id = $1 \
+         ORDER BY granted_at DESC, id DESC LIMIT 1",
+    )
+    .bind(current.person_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(db_error)?;
+    if newest != id || !transition_allowed(&current.status, &data.status) {
+        return Err(Status::Conflict);
+    }
+
+    let now = now();
+    let record = sqlx::query_as::<_, ClearanceRecord>(&format!(
+        "UPDATE clearance_records SET status = $2, status_reason = $3, \
+           status_changed_by_person_id = $4, status_changed_at = $5 \
+         WHERE id = $1 RETURNING {}",
+        RECORD_COLUMNS
+    ))
+    .bind(id)
+    .bind(&data.status)
+    .bind(&data.reason)
+    .bind(caller)
+    .bind(now)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(db_error)?;
+    let revoked = if record.status == STATUS_GRANTED {
+        Vec::new()
+    } else {
+        revoke_exceeding_clearance(&mut tx, record.person_id, caller, now)
+            .await
+            .map_err(db_error)?
+    };
+    tx.commit().await.map_err(db_error)?;
+
+    let action = transition_action(&record.status);
+    let summary = format!(
+        "{} clearance {}: {}",
+        record.level, record.status, data.reason
+    );
+    audit_revoked_grants(
+        db.inner(),
+        caller,
+        record.person_id,
+        &revoked,
+        &format!("Clearance {}", summary),
+    )
+    .await;
+    audit(
+        db.inner(),
+        caller,
+        action,
+        "clearance_records",
+        Some(record.person_id),
+        summary,
+    )
+    .await;
+    Ok(Json(ApiResponse::success(record)))
+}
+
+/// Current clearances whose re-investigation date or expiry falls within
+/// `within_days` (default 90, at most 3650) of today, overdue ones included,
+/// soonest first. Suspended and revoked clearances are not listed.
+#[get("/reinvestigations-due?<within_days>")]
+pub async fn list_reinvestigations_due(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    within_days: Option<i64>,
+) -> Result<Json<ApiResponse<Vec<ReinvestigationDue>>>, Status> {
+    require_permission(db.inner(), &auth, "clearance.manage").await?;
+    let within_days = within_days.unwrap_or(90);
+    if !(0..=MAX_WITHIN_DAYS).contains(&within_days) {
+        return Err(Status::BadRequest);
+    }
+    let now = now();
+    let due = sqlx::query_as::<_, ReinvestigationDue>(
+        "SELECT p.id AS person_id, p.first_name, p.last_name, c.id AS record_id, c.level, \
+           c.granting_authority, c.reinvestigation_due, c.valid_until, \
+           LEAST(c.reinvestigation_due, c.valid_until::date) AS due, \
+           LEAST(c.reinvestigation_due, c.valid_until::date) < $1::date AS overdue \
+         FROM ( \
+           SELECT DISTINCT ON (person_id) * FROM clearance_records \
+           ORDER BY person_id, granted_at DESC, id DESC \
+         ) c \
+         JOIN person p ON p.id = c.person_id AND p.deleted_at IS NULL \
+         WHERE c.status = 'GRANTED' \
+           AND LEAST(c.reinvestigation_due, c.valid_until::date) <= $2::date \
+         ORDER BY due, p.id",
+    )
+    .bind(now)
+    .bind(now + Duration::days(within_days))
+    .fetch_all(db.inner())
+    .await
+    .map_err(db_error)?;
+    Ok(Json(ApiResponse::success(due)))
+}
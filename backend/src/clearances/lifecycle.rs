@@ -0,0 +1,60 @@
+// Clearance record status rules.
+//
+// GRANTED and SUSPENDED move between each other (suspend / reinstate); either
+// may be REVOKED, which is final. A revoked person needs a new grant.
+// Suspending revokes the classified grants the person is left without, and
+// reinstating restores only the clearance: those grants must be issued again.
+pub const STATUS_GRANTED: &str = "GRANTED";
+pub const STATUS_SUSPENDED: &str = "SUSPENDED";
+pub const STATUS_REVOKED: &str = "REVOKED";
+
+/// Levels a person can hold (the resolver also ranks RESTRICTED, which is a
+/// resource classification only).
+pub const LEVELS: [&str; 4] = ["UNCLASSIFIED", "CONFIDENTIAL", "SECRET", "TOP_SECRET"];
+
+pub fn valid_level(level: &str) -> bool {
+    LEVELS.contains(&level)
+}
+
+/// Whether a record in `from` may move to `to`.
+pub fn transition_allowed(from: &str, to: &str) -> bool {
+    matches!(
+        (from, to),
+        (STATUS_GRANTED, STATUS_SUSPENDED)
+            | (STATUS_SUSPENDED, STATUS_GRANTED)
+            | (STATUS_GRANTED, STATUS_REVOKED)
+            | (STATUS_SUSPENDED, STATUS_REVOKED)
+    )
+}
+
+/// Audit action for a move to `to`.
+pub fn transition_action(to: &str) -> &'static str {
+    match to {
+        STATUS_SUSPENDED => "CLEARANCE_SUSPENDED",
+        STATUS_REVOKED => "CLEARANCE_REVOKED",
+        _ => "CLEARANCE_REINSTATED",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transitions() {
+        assert!(transition_allowed(STATUS_GRANTED, STATUS_SUSPENDED));
+        assert!(transition_allowed(STATUS_SUSPENDED, STATUS_GRANTED));
+        assert!(transition_allowed(STATUS_SUSPENDED, STATUS_REVOKED));
+        assert!(!transition_allowed(STATUS_GRANTED, STATUS_GRANTED));
+        assert!(!transition_allowed(STATUS_REVOKED, STATUS_GRANTED));
+        assert!(!transition_allowed(STATUS_GRANTED, "EXPIRED"));
+    }
+
+    #[test]
+    fn test_valid_level() {
+        assert!(valid_level("UNCLASSIFIED"));
+        assert!(valid_level("TOP_SECRET"));
+        assert!(!valid_level("RESTRICTED"));
+        assert!(!valid_level("COSMIC"));
+    }
+}
@@ -0,0 +1,16 @@
+// Clearances module
+// Clearance record history (level, granting authority, validity, status) and
+// the re-investigation due list
+
+pub mod handlers;
+pub mod lifecycle;
+pub mod models;
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![
+        handlers::get_person_clearance,
+        handlers::grant_clearance,
+        handlers::change_status,
+        handlers::list_reinvestigations_due,
+    ]
+}
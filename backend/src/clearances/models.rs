@@ -0,0 +1,64 @@
+// Clearance data models
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// One clearance grant; only a person's newest record is in force
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ClearanceRecord {
+    pub id: i32,
+    pub person_id: i32,
+    pub level: String,
+    pub granting_authority: String,
+    pub granted_by_person_id: Option<i32>, // None for backfilled / person-record grants
+    pub granted_at: NaiveDateTime,
+    pub valid_until: Option<NaiveDateTime>,
+    pub reinvestigation_due: Option<NaiveDate>,
+    pub status: String, // GRANTED, SUSPENDED, REVOKED
+    pub status_reason: Option<String>,
+    pub status_changed_by_person_id: Option<i32>,
+    pub status_changed_at: Option<NaiveDateTime>,
+}
+
+/// A person's effective clearance now and their records, newest first
+#[derive(Debug, Serialize)]
+pub struct PersonClearance {
+    pub person_id: i32,
+    pub effective_clearance: Option<String>,
+    pub records: Vec<ClearanceRecord>,
+}
+
+/// Grant a clearance; the caller is recorded as granter. No `valid_until`
+/// means open-ended.
+#[derive(Debug, Deserialize, Validate)]
+pub struct GrantClearanceRequest {
+    pub level: String,
+    #[validate(length(min = 1, max = 100))]
+    pub granting_authority: String,
+    pub valid_until: Option<NaiveDateTime>,
+    pub reinvestigation_due: Option<NaiveDate>,
+}
+
+/// Suspend, reinstate (GRANTED) or revoke the current record
+#[derive(Debug, Deserialize, Validate)]
+pub struct ChangeClearanceStatusRequest {
+    pub status: String,
+    #[validate(length(min = 1))]
+    pub reason: String,
+}
+
+/// A current clearance whose re-investigation or expiry falls inside the
+/// requested window; `due` is the earlier of the two
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ReinvestigationDue {
+    pub person_id: i32,
+    pub first_name: String,
+    pub last_name: String,
+    pub record_id: i32,
+    pub level: String,
+    pub granting_authority: String,
+    pub reinvestigation_due: Option<NaiveDate>,
+    pub valid_until: Option<NaiveDateTime>,
+    pub due: NaiveDate,
+    pub overdue: bool,
+}
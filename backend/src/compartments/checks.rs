@@ -0,0 +1,226 @@
+// Compartment (need-to-know) checks for access grants.
+//
+// A data grant needs every compartment listed for its data_classification, a
+// digital-resource grant every compartment listed for its resource_id;
+// computer and physical grants carry no compartment requirement. A person
+// holds a compartment while a grant of it is unrevoked and inside its
+// validity window. load_compartment_violations finds the grants already issued
+// that a revoked or lapsed compartment, or a requirement added later, leaves
+// without their basis.
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use rocket::http::Status;
+use sqlx::{PgExecutor, PgPool};
+
+use super::models::CompartmentViolation;
+use crate::access::grants::{ACCESS_DATA, ACCESS_RESOURCE};
+use crate::audit::handlers::create_audit_log;
+use crate::audit::models::CreateAuditLogRequest;
+
+/// Condition on `person_compartments pc` for a grant in force at $2.
+pub const ACTIVE_GRANT_CONDITION: &str = "pc.revoked_at IS NULL AND pc.valid_from <= $2 \
+     AND (pc.valid_until IS NULL OR pc.valid_until > $2)";
+
+/// Whether grants of `access_type` can carry compartment requirements.
+pub fn has_requirements(access_type: &str) -> bool {
+    access_type == ACCESS_DATA || access_type == ACCESS_RESOURCE
+}
+
+/// Compartments `access_type` on `target` requires, ordered by code.
+pub async fn required_compartments<'e>(
+    executor: impl PgExecutor<'e>,
+    access_type: &str,
+    target: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    if !has_requirements(access_type) {
+        return Ok(Vec::new());
+    }
+    sqlx::query_scalar(
+        "SELECT compartment_code FROM compartment_requirements \
+         WHERE target_type = $1 AND target = $2 ORDER BY compartment_code",
+    )
+    .bind(access_type)
+    .bind(target)
+    .fetch_all(executor)
+    .await
+}
+
+/// Compartments the person holds at `now`, ordered by code.
+pub async fn active_compartments<'e>(
+    executor: impl PgExecutor<'e>,
+    person_id: i32,
+    now: NaiveDateTime,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar(&format!(
+        "SELECT DISTINCT pc.compartment_code FROM person_compartments pc \
+         WHERE pc.person_id = $1 AND {} ORDER BY pc.compartment_code",
+        ACTIVE_GRANT_CONDITION
+    ))
+    .bind(person_id)
+    .bind(now)
+    .fetch_all(executor)
+    .await
+}
+
+/// Compartments held at `now` by each of `person_ids`; persons holding none
+/// are absent.
+pub async fn active_compartments_by_person<'e>(
+    executor: impl PgExecutor<'e>,
+    person_ids: &[i32],
+    now: NaiveDateTime,
+) -> Result<HashMap<i32, Vec<String>>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (i32, String)>(&format!(
+        "SELECT DISTINCT pc.person_id, pc.compartment_code FROM person_compartments pc \
+         WHERE pc.person_id = ANY($1) AND {} ORDER BY pc.person_id, pc.compartment_code",
+        ACTIVE_GRANT_CONDITION
+    ))
+    .bind(person_ids)
+    .bind(now)
+    .fetch_all(executor)
+    .await?;
+    let mut held: HashMap<i32, Vec<String>> = HashMap::new();
+    for (person_id, code) in rows {
+        held.entry(person_id).or_default().push(code);
+    }
+    Ok(held)
+}
+
+/// The entries of `required` that `held` lacks, in `required` order.
+pub fn missing_compartments(required: &[String], held: &[String]) -> Vec<String> {
+    required
+        .iter()
+        .filter(|c| !held.contains(c))
+        .cloned()
+        .collect()
+}
+
+// Compartments required by a grant's target that its holder does not hold at
+// $2; `holder` and `target` are column expressions on alias `g`.
+fn missing_sql(target_type: &str, holder: &str, target: &str) -> String {
+    format!(
+        "ARRAY(SELECT r.compartment_code FROM compartment_requirements r \
+               WHERE r.target_type = '{target_type}' AND r.target = {target} \
+                 AND NOT EXISTS (SELECT 1 FROM person_compartments pc \
+                                 WHERE pc.person_id::text = {holder} \
+                                   AND pc.compartment_code = r.compartment_code \
+                                   AND {cond}) \
+               ORDER BY r.compartment_code)",
+        cond = ACTIVE_GRANT_CONDITION
+    )
+}
+
+/// In-force data and resource grants at `now` whose holder lacks a required
+/// compartment; all holders when `person_id` is None.
+pub async fn load_compartment_violations<'e>(
+    executor: impl PgExecutor<'e>,
+    person_id: Option<i32>,
+    now: NaiveDateTime,
+) -> Result<Vec<CompartmentViolation>, sqlx::Error> {
+    sqlx::query_as::<_, CompartmentViolation>(&format!(
+        r#"
+    SELECT * FROM (
+        SELECT 'data' AS access_type, g.id::text AS grant_id, g.person_id::text AS holder,
+               g.data_classification AS target, {data_missing} AS missing
+        FROM data_access g
+        WHERE g.status = 'ACTIVE' AND (g.expires_at IS NULL OR g.expires_at > $2)
+          AND ($1::int IS NULL OR g.person_id = $1)
+        UNION ALL
+        SELECT 'resource', g.id, g.person_id, g.resource_id, {resource_missing}
+        FROM resource_access_grants g
+        WHERE (g.valid_until IS NULL OR g.valid_until > $2)
+          AND ($1::int IS NULL OR g.person_id = $1::text)
+    ) v
+    WHERE cardinality(v.missing) > 0
+    ORDER BY holder, access_type, grant_id
+"#,
+        data_missing = missing_sql(ACCESS_DATA, "g.person_id::text", "g.data_classification"),
+        resource_missing = missing_sql(ACCESS_RESOURCE, "g.person_id", "g.resource_id"),
+    ))
+    .bind(person_id)
+    .bind(now)
+    .fetch_all(executor)
+    .await
+}
+
+/// Refuse (403 + COMPARTMENT_CHECK_BLOCKED audit entry) a grant of a target
+/// whose required compartments the person does not all hold. `person_id` is
+/// None for holders that are not persons (non-numeric resource grantees),
+/// which fail whenever a compartment is required.
+pub async fn enforce_compartments(
+    db: &PgPool,
+    actor: i32,
+    person_id: Option<i32>,
+    access_type: &str,
+    target: &str,
+) -> Result<(), Status> {
+    let required = required_compartments(db, access_type, target)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    if required.is_empty() {
+        return Ok(());
+    }
+    let held = match person_id {
+        Some(id) => active_compartments(db, id, chrono::Utc::now().naive_utc())
+            .await
+            .map_err(|_| Status::InternalServerError)?,
+        None => Vec::new(),
+    };
+    let missing = missing_compartments(&required, &held);
+    if missing.is_empty() {
+        return Ok(());
+    }
+    let _ = create_audit_log(
+        &CreateAuditLogRequest {
+            person_id: Some(actor),
+            username: actor.to_string(),
+            action: "COMPARTMENT_CHECK_BLOCKED".to_string(),
+            resource_type: if access_type == ACCESS_RESOURCE {
+                "resource_access_grants".to_string()
+            } else {
+                format!("{}_access", access_type)
+            },
+            resource_id: None,
+            details: Some(format!(
+                "{} access to '{}' refused for person_id={}: missing compartments [{}]",
+                access_type,
+                target,
+                person_id.map_or_else(|| "none".to_string(), |id| id.to_string()),
+                missing.join(", ")
+            )),
+            ip_address: None,
+            user_agent: None,
+        },
+        db,
+    )
+    .await;
+    Err(Status::Forbidden)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_missing_compartments_keeps_required_order() {
+        let required = codes(&["AURORA", "BLACKWING", "SIGINT"]);
+        assert_eq!(
+            missing_compartments(&required, &codes(&["SIGINT"])),
+            codes(&["AURORA", "BLACKWING"])
+        );
+        assert!(missing_compartments(&required, &required).is_empty());
+        assert!(missing_compartments(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_only_data_and_resource_grants_have_requirements() {
+        assert!(has_requirements("data"));
+        assert!(has_requirements("resource"));
+        assert!(!has_requirements("computer"));
+        assert!(!has_requirements("physical"));
+    }
+}
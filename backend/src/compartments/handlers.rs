@@ -0,0 +1,441 @@
+// Compartment HTTP handlers (mounted at /api/compartments)
+//
+// Definitions, requirements and person grants are read under person.read and
+// changed under compartments.manage. The caller granting a compartment is
+// recorded as its approver and may not grant to themselves (SoD). Revoked
+// grants keep their row for the history. Revoking a compartment or adding a
+// requirement ends, in the same transaction, the data and resource grants
+// left without a compartment they need.
+use rocket::serde::json::Json;
+use rocket::{delete, get, http::Status, post, State};
+use sqlx::PgPool;
+use validator::Validate;
+
+use super::checks::{has_requirements, ACTIVE_GRANT_CONDITION};
+use super::models::{
+    Compartment, CompartmentRequirement, CreateCompartmentRequest,
+    CreateCompartmentRequirementRequest, GrantCompartmentRequest, PersonCompartment,
+};
+use crate::access::grants::{valid_target, ACCESS_DATA};
+use crate::access::models::RevokedGrant;
+use crate::access::revocation::{audit_revoked_grants, revoke_missing_compartments};
+use crate::audit::handlers::audit;
+use crate::auth::middleware::AuthGuard;
+use crate::digital_resources::handlers::assert_resource_exists;
+use crate::shared::rbac::require_permission;
+use crate::shared::response::ApiResponse;
+use crate::sod::enforce::enforce_no_self_action;
+
+const COMPARTMENT_COLUMNS: &str = "code, name, description, created_at";
+const REQUIREMENT_COLUMNS: &str = "id, target_type, target, compartment_code, created_at";
+
+/// Grant columns on alias `pc`; `active` is evaluated at $2.
+fn grant_columns() -> String {
+    format!(
+        "pc.id, pc.person_id, pc.compartment_code, pc.valid_from, pc.valid_until, \
+         pc.approved_by_person_id, pc.granted_at, pc.revoked_by_person_id, pc.revoked_at, \
+         pc.revocation_reason, ({}) AS active",
+        ACTIVE_GRANT_CONDITION
+    )
+}
+
+async fn compartment_exists(db: &PgPool, code: &str) -> Result<bool, Status> {
+    sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM compartments WHERE code = $1)")
+        .bind(code)
+        .fetch_one(db)
+        .await
+        .map_err(|_| Status::InternalServerError)
+}
+
+fn valid_code(code: &str) -> bool {
+    code.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+        && code
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[get("/")]
+pub async fn list_compartments(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+) -> Result<Json<ApiResponse<Vec<Compartment>>>, Status> {
+    require_permission(db.inner(), &auth, "person.read").await?;
+    let compartments = sqlx::query_as::<_, Compartment>(&format!(
+        "SELECT {} FROM compartments ORDER BY code",
+        COMPARTMENT_COLUMNS
+    ))
+    .fetch_all(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    Ok(Json(ApiResponse::success(compartments)))
+}
+
+/// Define a compartment. 409 when the code is taken.
+#[post("/", data = "<data>")]
+pub async fn create_compartment(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    data: Json<CreateCompartmentRequest>,
+) -> Result<Json<ApiResponse<Compartment>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "compartments.manage").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    if !valid_code(&data.code) {
+        return Err(Status::BadRequest);
+    }
+    let compartment = sqlx::query_as::<_, Compartment>(&format!(
+        "INSERT INTO compartments (code, name, description) VALUES ($1, $2, $3) \
+         ON CONFLICT (code) DO NOTHING RETURNING {}",
+        COMPARTMENT_COLUMNS
+    ))
+    .bind(&data.code)
+    .bind(&data.name)
+    .bind(&data.description)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?
+    .ok_or(Status::Conflict)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "COMPARTMENT_CREATED",
+        "compartments",
+        None,
+        format!("compartment {} ({})", compartment.code, compartment.name),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(compartment)))
+}
+
+/// Requirements, optionally narrowed to one target type and target.
+#[get("/requirements?<target_type>&<target>")]
+pub async fn list_requirements(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    target_type: Option<String>,
+    target: Option<String>,
+) -> Result<Json<ApiResponse<Vec<CompartmentRequirement>>>, Status> {
+    require_permission(db.inner(), &auth, "person.read").await?;
+    let requirements = sqlx::query_as::<_, CompartmentRequirement>(&format!(
+        "SELECT {} FROM compartment_requirements \
+         WHERE ($1::text IS NULL OR target_type = $1) AND ($2::text IS NULL OR target = $2) \
+         ORDER BY target_type, target, compartment_code",
+        REQUIREMENT_COLUMNS
+    ))
+    .bind(target_type)
+    .bind(target)
+    .fetch_all(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    Ok(Json(ApiResponse::success(requirements)))
+}
+
+/// Require a compartment for a data classification or digital resource. 400
+/// for another target type or an invalid classification, 404 for an unknown
+/// compartment or resource, 409 when already required.
+#[post("/requirements", data = "<data>")]
+pub async fn add_requirement(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    data: Json<CreateCompartmentRequirementRequest>,
+) -> Result<Json<ApiResponse<CompartmentRequirement>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "compartments.manage").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    if !has_requirements(&data.target_type) {
+        return Err(Status::BadRequest);
+    }
+    if data.target_type == ACCESS_DATA {
+        if !valid_target(ACCESS_DATA, &data.target) {
+            return Err(Status::BadRequest);
+        }
+    } else {
+        assert_resource_exists(&data.target, db.inner()).await?;
+    }
+    if !compartment_exists(db.inner(), &data.compartment_code).await? {
+        return Err(Status::NotFound);
+    }
+
+    let mut tx = db
+        .inner()
+        .begin()
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let requirement = sqlx::query_as::<_, CompartmentRequirement>(&format!(
+        "INSERT INTO compartment_requirements (target_type, target, compartment_code) \
+         VALUES ($1, $2, $3) ON CONFLICT DO NOTHING RETURNING {}",
+        REQUIREMENT_COLUMNS
+    ))
+    .bind(&data.target_type)
+    .bind(&data.target)
+    .bind(&data.compartment_code)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?
+    .ok_or(Status::Conflict)?;
+    let revoked =
+        revoke_missing_compartments(&mut tx, None, Some(caller), chrono::Utc::now().naive_utc())
+            .await
+            .map_err(|e| {
+                eprintln!("Database error: {:?}", e);
+                Status::InternalServerError
+            })?;
+    tx.commit().await.map_err(|_| Status::InternalServerError)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "COMPARTMENT_REQUIREMENT_ADDED",
+        "compartment_requirements",
+        Some(requirement.id),
+        format!(
+            "{} '{}' requires {}",
+            requirement.target_type, requirement.target, requirement.compartment_code
+        ),
+    )
+    .await;
+    let reason = format!(
+        "{} now requires {}",
+        requirement.target, requirement.compartment_code
+    );
+    for (holder, grant) in &revoked {
+        audit_revoked_grants(
+            db.inner(),
+            caller,
+            holder,
+            std::slice::from_ref(grant),
+            &reason,
+        )
+        .await;
+    }
+    Ok(Json(ApiResponse::success(requirement)))
+}
+
+#[delete("/requirements/<id>")]
+pub async fn delete_requirement(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<()>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "compartments.manage").await?;
+    let removed = sqlx::query_as::<_, CompartmentRequirement>(&format!(
+        "DELETE FROM compartment_requirements WHERE id = $1 RETURNING {}",
+        REQUIREMENT_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?
+    .ok_or(Status::NotFound)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "COMPARTMENT_REQUIREMENT_REMOVED",
+        "compartment_requirements",
+        Some(id),
+        format!(
+            "{} '{}' no longer requires {}",
+            removed.target_type, removed.target, removed.compartment_code
+        ),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(())))
+}
+
+/// A person's compartment grants in force, or their full history with
+/// `include_inactive=true`.
+#[get("/persons/<id>?<include_inactive>")]
+pub async fn list_person_compartments(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    include_inactive: Option<bool>,
+) -> Result<Json<ApiResponse<Vec<PersonCompartment>>>, Status> {
+    require_permission(db.inner(), &auth, "person.read").await?;
+    let grants = sqlx::query_as::<_, PersonCompartment>(&format!(
+        "SELECT {} FROM person_compartments pc \
+         WHERE pc.person_id = $1 AND ($3 OR ({})) \
+         ORDER BY pc.compartment_code, pc.valid_from DESC",
+        grant_columns(),
+        ACTIVE_GRANT_CONDITION
+    ))
+    .bind(id)
+    .bind(chrono::Utc::now().naive_utc())
+    .bind(include_inactive.unwrap_or(false))
+    .fetch_all(db.inner())
+    .await
+    .map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?;
+    Ok(Json(ApiResponse::success(grants)))
+}
+
+/// Grant a compartment to a person for a validity window. 400 for an empty
+/// window, 404 for an unknown person or compartment, 409 when an unrevoked
+/// grant of the compartment has not yet ended.
+#[post("/persons/<id>/grants", data = "<data>")]
+pub async fn grant_compartment(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    data: Json<GrantCompartmentRequest>,
+) -> Result<Json<ApiResponse<PersonCompartment>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "compartments.manage").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    let now = chrono::Utc::now().naive_utc();
+    let valid_from = data.valid_from.unwrap_or(now);
+    if data.valid_until.is_some_and(|until| until <= valid_from) {
+        return Err(Status::BadRequest);
+    }
+    enforce_no_self_action(db.inner(), "access.write", &caller, &id).await?;
+
+    let person_exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM person WHERE id = $1 AND deleted_at IS NULL)",
+    )
+    .bind(id)
+    .fetch_one(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    if !person_exists || !compartment_exists(db.inner(), &data.compartment_code).await? {
+        return Err(Status::NotFound);
+    }
+    let open: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM person_compartments \
+         WHERE person_id = $1 AND compartment_code = $2 AND revoked_at IS NULL \
+           AND (valid_until IS NULL OR valid_until > $3))",
+    )
+    .bind(id)
+    .bind(&data.compartment_code)
+    .bind(now)
+    .fetch_one(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    if open {
+        return Err(Status::Conflict);
+    }
+
+    let grant = sqlx::query_as::<_, PersonCompartment>(&format!(
+        "INSERT INTO person_compartments AS pc \
+           (person_id, compartment_code, valid_from, valid_until, approved_by_person_id) \
+         VALUES ($1, $3, $4, $5, $6) RETURNING {}",
+        grant_columns()
+    ))
+    .bind(id)
+    .bind(now)
+    .bind(&data.compartment_code)
+    .bind(valid_from)
+    .bind(data.valid_until)
+    .bind(caller)
+    .fetch_one(db.inner())
+    .await
+    .map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?;
+
+    audit(
+        db.inner(),
+        caller,
+        "COMPARTMENT_GRANTED",
+        "person_compartments",
+        Some(grant.id),
+        format!(
+            "{} granted to person_id={} from {}{}",
+            grant.compartment_code,
+            id,
+            grant.valid_from,
+            grant
+                .valid_until
+                .map_or_else(String::new, |until| format!(" until {}", until))
+        ),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(grant)))
+}
+
+/// Revoke a compartment grant, recording the revoker and optional reason.
+/// 409 when it is already revoked.
+#[delete("/grants/<id>?<reason>")]
+pub async fn revoke_compartment(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    reason: Option<String>,
+) -> Result<Json<ApiResponse<PersonCompartment>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "compartments.manage").await?;
+    let now = chrono::Utc::now().naive_utc();
+    let mut tx = db
+        .inner()
+        .begin()
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let revoked_at: Option<Option<chrono::NaiveDateTime>> =
+        sqlx::query_scalar("SELECT revoked_at FROM person_compartments WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|_| Status::InternalServerError)?;
+    match revoked_at {
+        None => return Err(Status::NotFound),
+        Some(Some(_)) => return Err(Status::Conflict),
+        Some(None) => {}
+    }
+
+    let grant = sqlx::query_as::<_, PersonCompartment>(&format!(
+        "UPDATE person_compartments pc SET revoked_at = CURRENT_TIMESTAMP, \
+           revoked_by_person_id = $3, revocation_reason = $4 \
+         WHERE pc.id = $1 AND pc.revoked_at IS NULL RETURNING {}",
+        grant_columns()
+    ))
+    .bind(id)
+    .bind(now)
+    .bind(caller)
+    .bind(reason.as_deref())
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?
+    .ok_or(Status::Conflict)?;
+    // Grants that needed the compartment end with it.
+    let revoked = revoke_missing_compartments(&mut tx, Some(grant.person_id), Some(caller), now)
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            Status::InternalServerError
+        })?;
+    tx.commit().await.map_err(|_| Status::InternalServerError)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "COMPARTMENT_REVOKED",
+        "person_compartments",
+        Some(id),
+        format!(
+            "{} revoked for person_id={}: {}",
+            grant.compartment_code,
+            grant.person_id,
+            reason.as_deref().unwrap_or("no reason given")
+        ),
+    )
+    .await;
+    let grants: Vec<RevokedGrant> = revoked.into_iter().map(|(_, g)| g).collect();
+    audit_revoked_grants(
+        db.inner(),
+        caller,
+        grant.person_id,
+        &grants,
+        &format!("Compartment {} revoked", grant.compartment_code),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(grant)))
+}
@@ -0,0 +1,20 @@
+// Compartments module
+// Need-to-know compartments, time-windowed person grants and the compartments
+// data classifications and digital resources require
+
+pub mod checks;
+pub mod handlers;
+pub mod models;
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![
+        handlers::list_compartments,
+        handlers::create_compartment,
+        handlers::list_requirements,
+        handlers::add_requirement,
+        handlers::delete_requirement,
+        handlers::list_person_compartments,
+        handlers::grant_compartment,
+        handlers::revoke_compartment,
+    ]
+}
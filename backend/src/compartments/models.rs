@@ -0,0 +1,75 @@
+// Compartment data models
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Compartment {
+    pub code: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateCompartmentRequest {
+    #[validate(length(min = 1, max = 30))]
+    pub code: String, // upper-case letters, digits and underscores
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// A compartment a grant on `target` requires
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CompartmentRequirement {
+    pub id: i32,
+    pub target_type: String, // data, resource
+    pub target: String,      // data_classification / resource_id
+    pub compartment_code: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateCompartmentRequirementRequest {
+    pub target_type: String,
+    #[validate(length(min = 1, max = 100))]
+    pub target: String,
+    pub compartment_code: String,
+}
+
+/// An in-force data or resource grant whose holder lacks a compartment its
+/// target requires
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct CompartmentViolation {
+    pub access_type: String, // data, resource
+    pub grant_id: String,
+    pub holder: String, // person id; resource grantees need not be persons
+    pub target: String, // data_classification / resource_id
+    pub missing: Vec<String>,
+}
+
+/// A person's grant of one compartment; `active` is computed at read time
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PersonCompartment {
+    pub id: i32,
+    pub person_id: i32,
+    pub compartment_code: String,
+    pub valid_from: NaiveDateTime,
+    pub valid_until: Option<NaiveDateTime>,
+    pub approved_by_person_id: i32,
+    pub granted_at: NaiveDateTime,
+    pub revoked_by_person_id: Option<i32>,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub revocation_reason: Option<String>,
+    pub active: bool,
+}
+
+/// Grant a compartment; the caller is recorded as approver. `valid_from`
+/// defaults to now, no `valid_until` means open-ended.
+#[derive(Debug, Deserialize, Validate)]
+pub struct GrantCompartmentRequest {
+    pub compartment_code: String,
+    pub valid_from: Option<NaiveDateTime>,
+    pub valid_until: Option<NaiveDateTime>,
+}
@@ -0,0 +1,406 @@
+// Credential HTTP handlers (mounted at /api/credentials)
+//
+// POST /issue signs the person's clearance attributes now (effective
+// clearance, compartments in force, organization) with this instance's
+// issuer key, named by CREDENTIAL_ISSUER_ID; the credential expires after
+// valid_days and never after the clearance itself. POST /verify checks a
+// presented credential against the issuer registry and the revocations made
+// here. A clearance downgrade, suspension or revocation, a security flag and
+// offboarding revoke a person's open credentials too (access::revocation).
+// Issuing and revoking need clearance.manage, verifying and listing
+// person.read; the issuer registry is managed under credentials.manage.
+use chrono::{Duration, Timelike, Utc};
+use rocket::serde::json::Json;
+use rocket::{delete, get, http::Status, post, put, State};
+use sqlx::PgPool;
+use validator::Validate;
+
+use super::models::{
+    CredentialIssuer, IssueCredentialRequest, IssueCredentialResponse, IssuedCredential,
+    RevokeCredentialRequest, SetIssuerRequest,
+};
+use super::signing::{
+    claim_timestamp, issue_credential, verify_credential, AttrClaims, Credential, IssuerKey,
+    VerifyResult,
+};
+use crate::abac::principal::{person_organization_ids, UNAFFILIATED};
+use crate::access::clearance::load_effective_clearance;
+use crate::audit::handlers::audit;
+use crate::auth::middleware::AuthGuard;
+use crate::compartments::checks::active_compartments;
+use crate::security_flags::flags::load_subject_flags;
+use crate::shared::rbac::require_permission;
+use crate::shared::response::ApiResponse;
+use crate::sod::enforce::enforce_no_self_action;
+
+const ISSUER_COLUMNS: &str = "issuer_id, trusted, created_by_person_id, created_at, updated_at";
+const CREDENTIAL_COLUMNS: &str = "id, person_id, issuer_id, issued_by_person_id, issued_at, \
+                                  expires_at, revoked_at, revoked_by_person_id, revocation_reason";
+
+/// Issuer id this instance signs with when CREDENTIAL_ISSUER_ID is unset.
+pub const DEFAULT_ISSUER_ID: &str = "JANUS";
+pub const DEFAULT_VALID_DAYS: i64 = 90;
+pub const MAX_VALID_DAYS: i64 = 365;
+
+fn db_error(e: sqlx::Error) -> Status {
+    eprintln!("Database error: {:?}", e);
+    Status::InternalServerError
+}
+
+fn local_issuer_id() -> String {
+    std::env::var("CREDENTIAL_ISSUER_ID")
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| DEFAULT_ISSUER_ID.to_string())
+}
+
+#[get("/issuers")]
+pub async fn list_issuers(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+) -> Result<Json<ApiResponse<Vec<CredentialIssuer>>>, Status> {
+    require_permission(db.inner(), &auth, "credentials.manage").await?;
+    let issuers = sqlx::query_as::<_, CredentialIssuer>(&format!(
+        "SELECT {} FROM credential_issuers ORDER BY issuer_id",
+        ISSUER_COLUMNS
+    ))
+    .fetch_all(db.inner())
+    .await
+    .map_err(db_error)?;
+    Ok(Json(ApiResponse::success(issuers)))
+}
+
+/// Register an issuer, rotate its key or change whether it is trusted. 400
+/// for a blank or overlong issuer id or a key shorter than 16 characters, 404
+/// for an unknown issuer given without a key.
+#[put("/issuers/<issuer_id>", data = "<data>")]
+pub async fn set_issuer(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    issuer_id: &str,
+    data: Json<SetIssuerRequest>,
+) -> Result<Json<ApiResponse<CredentialIssuer>>, Status> {
+    let actor = require_permission(db.inner(), &auth, "credentials.manage").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    let issuer_id = issuer_id.trim();
+    if issuer_id.is_empty() || issuer_id.len() > 100 {
+        return Err(Status::BadRequest);
+    }
+
+    let issuer = match &data.signing_key {
+        Some(key) => sqlx::query_as::<_, CredentialIssuer>(&format!(
+            "INSERT INTO credential_issuers (issuer_id, signing_key, trusted, created_by_person_id) \
+             VALUES ($1, $2, $3, $4) \
+             ON CONFLICT (issuer_id) DO UPDATE SET signing_key = EXCLUDED.signing_key, \
+                 trusted = EXCLUDED.trusted, updated_at = CURRENT_TIMESTAMP \
+             RETURNING {}",
+            ISSUER_COLUMNS
+        ))
+        .bind(issuer_id)
+        .bind(key)
+        .bind(data.trusted)
+        .bind(actor)
+        .fetch_one(db.inner())
+        .await
+        .map_err(db_error)?,
+        None => sqlx::query_as::<_, CredentialIssuer>(&format!(
+            "UPDATE credential_issuers SET trusted = $2, updated_at = CURRENT_TIMESTAMP \
+             WHERE issuer_id = $1 RETURNING {}",
+            ISSUER_COLUMNS
+        ))
+        .bind(issuer_id)
+        .bind(data.trusted)
+        .fetch_optional(db.inner())
+        .await
+        .map_err(db_error)?
+        .ok_or(Status::NotFound)?,
+    };
+
+    audit(
+        db.inner(),
+        actor,
+        "CREDENTIAL_ISSUER_UPDATED",
+        "credential_issuer",
+        None,
+        format!(
+            "Issuer {} {} (trusted: {})",
+            issuer.issuer_id,
+            if data.signing_key.is_some() {
+                "key set"
+            } else {
+                "key unchanged"
+            },
+            issuer.trusted
+        ),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(issuer)))
+}
+
+#[delete("/issuers/<issuer_id>")]
+pub async fn delete_issuer(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    issuer_id: &str,
+) -> Result<Json<ApiResponse<String>>, Status> {
+    let actor = require_permission(db.inner(), &auth, "credentials.manage").await?;
+    let deleted = sqlx::query("DELETE FROM credential_issuers WHERE issuer_id = $1")
+        .bind(issuer_id)
+        .execute(db.inner())
+        .await
+        .map_err(db_error)?;
+    if deleted.rows_affected() == 0 {
+        return Err(Status::NotFound);
+    }
+    audit(
+        db.inner(),
+        actor,
+        "CREDENTIAL_ISSUER_DELETED",
+        "credential_issuer",
+        None,
+        format!("Issuer {} removed", issuer_id),
+    )
+    .await;
+    Ok(Json(ApiResponse::success("Issuer removed".to_string())))
+}
+
+/// Issue a signed clearance credential for a person. Other systems trust it
+/// offline, so a revoked or held person gets none and nobody issues their
+/// own (the no-self-clearance rule). 400 for valid_days outside 1..=365, 403
+/// on self, 404 for an unknown or offboarded person, 409 when the person has
+/// no clearance in force or is revoked or on security hold, 503 when this
+/// instance's issuer has no key in the registry.
+#[post("/issue", data = "<data>")]
+pub async fn issue(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    data: Json<IssueCredentialRequest>,
+) -> Result<Json<ApiResponse<IssueCredentialResponse>>, Status> {
+    let actor = require_permission(db.inner(), &auth, "clearance.manage").await?;
+    let valid_days = data.valid_days.unwrap_or(DEFAULT_VALID_DAYS);
+    if !(1..=MAX_VALID_DAYS).contains(&valid_days) {
+        return Err(Status::BadRequest);
+    }
+    enforce_no_self_action(db.inner(), "clearance.manage", &actor, &data.person_id).await?;
+
+    let now = Utc::now()
+        .naive_utc()
+        .with_nanosecond(0)
+        .unwrap_or_default();
+    let clearance = load_effective_clearance(db.inner(), data.person_id, now)
+        .await
+        .map_err(db_error)?
+        .ok_or(Status::NotFound)?
+        .ok_or(Status::Conflict)?;
+    let flags = load_subject_flags(db.inner(), data.person_id)
+        .await
+        .map_err(db_error)?;
+    if flags.revoked || flags.security_hold {
+        return Err(Status::Conflict);
+    }
+    let issuer_id = local_issuer_id();
+    let signing_key: String =
+        sqlx::query_scalar("SELECT signing_key FROM credential_issuers WHERE issuer_id = $1")
+            .bind(&issuer_id)
+            .fetch_optional(db.inner())
+            .await
+            .map_err(db_error)?
+            .ok_or(Status::ServiceUnavailable)?;
+
+    let clearance_until: Option<chrono::NaiveDateTime> = sqlx::query_scalar(
+        "SELECT valid_until FROM clearance_records WHERE person_id = $1 \
+         ORDER BY granted_at DESC, id DESC LIMIT 1",
+    )
+    .bind(data.person_id)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(db_error)?
+    .flatten();
+    let requested_until = now + Duration::days(valid_days);
+    let expires_at = clearance_until
+        .map(|until| until.with_nanosecond(0).unwrap_or(until))
+        .filter(|until| *until < requested_until)
+        .unwrap_or(requested_until);
+
+    let entity = person_organization_ids(db.inner(), data.person_id)
+        .await
+        .map_err(db_error)?
+        .first()
+        .map_or_else(|| UNAFFILIATED.to_string(), |id| id.to_string());
+    let compartments = active_compartments(db.inner(), data.person_id, now)
+        .await
+        .map_err(db_error)?;
+
+    let id = uuid::Uuid::new_v4();
+    let credential = issue_credential(
+        AttrClaims {
+            subject: data.person_id.to_string(),
+            entity,
+            clearance,
+            compartments,
+            issuer: issuer_id.clone(),
+            credential_id: Some(id.to_string()),
+            issued_at: Some(claim_timestamp(now)),
+            expires_at: Some(claim_timestamp(expires_at)),
+        },
+        &signing_key,
+    );
+
+    let issued = sqlx::query_as::<_, IssuedCredential>(&format!(
+        "INSERT INTO attribute_credentials \
+             (id, person_id, issuer_id, claims, signature, issued_by_person_id, issued_at, expires_at) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8) RETURNING {}",
+        CREDENTIAL_COLUMNS
+    ))
+    .bind(id)
+    .bind(data.person_id)
+    .bind(&issuer_id)
+    .bind(serde_json::to_value(&credential.payload).unwrap_or_default())
+    .bind(&credential.sig)
+    .bind(actor)
+    .bind(now)
+    .bind(expires_at)
+    .fetch_one(db.inner())
+    .await
+    .map_err(db_error)?;
+
+    audit(
+        db.inner(),
+        actor,
+        "CREDENTIAL_ISSUED",
+        "person",
+        Some(data.person_id),
+        format!(
+            "Credential {} ({}) issued by {}, expires {}",
+            id,
+            credential.payload.clearance,
+            issuer_id,
+            credential.payload.expires_at.as_deref().unwrap_or_default()
+        ),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(IssueCredentialResponse {
+        issued,
+        credential,
+    })))
+}
+
+/// Verify a presented credential: trusted issuer with a key on file, valid
+/// signature, not expired, and not revoked here. An invalid credential is
+/// a 200 with valid = false and the reason.
+#[post("/verify", data = "<data>")]
+pub async fn verify(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    data: Json<Credential>,
+) -> Result<Json<ApiResponse<VerifyResult>>, Status> {
+    require_permission(db.inner(), &auth, "person.read").await?;
+    let issuer = sqlx::query_as::<_, (bool, String)>(
+        "SELECT trusted, signing_key FROM credential_issuers WHERE issuer_id = $1",
+    )
+    .bind(&data.payload.issuer)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(db_error)?
+    .map(|(trusted, key)| IssuerKey {
+        trusted,
+        key: Some(key),
+    });
+
+    let revoked = match data
+        .payload
+        .credential_id
+        .as_deref()
+        .and_then(|id| uuid::Uuid::parse_str(id).ok())
+    {
+        Some(id) => sqlx::query_scalar::<_, Option<String>>(
+            "SELECT revocation_reason FROM attribute_credentials \
+             WHERE id = $1 AND revoked_at IS NOT NULL",
+        )
+        .bind(id)
+        .fetch_optional(db.inner())
+        .await
+        .map_err(db_error)?
+        .map(|reason| reason.unwrap_or_else(|| "revoked".to_string())),
+        None => None,
+    };
+
+    let now = Utc::now().naive_utc();
+    Ok(Json(ApiResponse::success(verify_credential(
+        &data,
+        issuer.as_ref(),
+        now,
+        revoked.as_deref(),
+    ))))
+}
+
+/// Credentials issued here for a person, newest first.
+#[get("/persons/<id>")]
+pub async fn list_person_credentials(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<Vec<IssuedCredential>>>, Status> {
+    require_permission(db.inner(), &auth, "person.read").await?;
+    let credentials = sqlx::query_as::<_, IssuedCredential>(&format!(
+        "SELECT {} FROM attribute_credentials WHERE person_id = $1 ORDER BY issued_at DESC, id",
+        CREDENTIAL_COLUMNS
+    ))
+    .bind(id)
+    .fetch_all(db.inner())
+    .await
+    .map_err(db_error)?;
+    Ok(Json(ApiResponse::success(credentials)))
+}
+
+/// Revoke a credential issued here; verification rejects it from now on.
+/// 404 for an unknown credential, 409 when it is already revoked.
+#[post("/<id>/revoke", data = "<data>")]
+pub async fn revoke(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: &str,
+    data: Json<RevokeCredentialRequest>,
+) -> Result<Json<ApiResponse<IssuedCredential>>, Status> {
+    let actor = require_permission(db.inner(), &auth, "clearance.manage").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    let id = uuid::Uuid::parse_str(id).map_err(|_| Status::NotFound)?;
+
+    let revoked = sqlx::query_as::<_, IssuedCredential>(&format!(
+        "UPDATE attribute_credentials \
+         SET revoked_at = CURRENT_TIMESTAMP, revoked_by_person_id = $2, revocation_reason = $3 \
+         WHERE id = $1 AND revoked_at IS NULL RETURNING {}",
+        CREDENTIAL_COLUMNS
+    ))
+    .bind(id)
+    .bind(actor)
+    .bind(&data.reason)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(db_error)?;
+    let Some(revoked) = revoked else {
+        let exists: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM attribute_credentials WHERE id = $1)")
+                .bind(id)
+                .fetch_one(db.inner())
+                .await
+                .map_err(db_error)?;
+        return Err(if exists {
+            Status::Conflict
+        } else {
+            Status::NotFound
+        });
+    };
+
+    audit(
+        db.inner(),
+        actor,
+        "CREDENTIAL_REVOKED",
+        "person",
+        Some(revoked.person_id),
+        format!("Credential {} revoked: {}", revoked.id, data.reason),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(revoked)))
+}
@@ -0,0 +1,19 @@
+// Credentials module
+// Signed attribute credentials attesting a person's clearance, their
+// verification and revocation, and the issuer key registry
+
+pub mod handlers;
+pub mod models;
+pub mod signing;
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![
+        handlers::list_issuers,
+        handlers::set_issuer,
+        handlers::delete_issuer,
+        handlers::issue,
+        handlers::verify,
+        handlers::list_person_credentials,
+        handlers::revoke,
+    ]
+}
@@ -0,0 +1,63 @@
+// Credential data models
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::signing::Credential;
+
+/// An issuer in the key registry; the signing key is never serialized
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CredentialIssuer {
+    pub issuer_id: String,
+    pub trusted: bool,
+    pub created_by_person_id: i32,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Register an issuer or update it; a new issuer needs a key, an existing
+/// one keeps its key when none is given
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetIssuerRequest {
+    #[validate(length(min = 16, max = 512))]
+    pub signing_key: Option<String>,
+    #[serde(default = "default_trusted")]
+    pub trusted: bool,
+}
+
+fn default_trusted() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct IssueCredentialRequest {
+    pub person_id: i32,
+    pub valid_days: Option<i64>, // defaults to 90, at most 365
+}
+
+/// A credential issued by this instance
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct IssuedCredential {
+    pub id: uuid::Uuid,
+    pub person_id: i32,
+    pub issuer_id: String,
+    pub issued_by_person_id: i32,
+    pub issued_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub revoked_by_person_id: Option<i32>,
+    pub revocation_reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IssueCredentialResponse {
+    #[serde(flatten)]
+    pub issued: IssuedCredential,
+    pub credential: Credential,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RevokeCredentialRequest {
+    #[validate(length(min = 1, max = 500))]
+    pub reason: String,
+}
@@ -0,0 +1,269 @@
+// Pure credential signing and verification.
+//
+// Ported from frontend/src/demo/lib/credential.ts (canonical, sign,
+// issueCredential, verifyCredential). The signature is base64 HMAC-SHA256
+// over the canonical serialization: the claims as JSON with keys sorted and
+// compartments sorted, so any verifier that rebuilds the same bytes gets the
+// same signature. Claims carrying only the TS fields serialize byte-equal to
+// the TS canonical(); the backend's credentialId, issuedAt and expiresAt
+// claims are included, in key order, only when present.
+//
+// Verification is verify-before-trust, in the TS order: trusted issuer, key
+// on file, signature; then, with the claims authenticated, expiry and
+// revocation.
+// This module is PURE: no Rocket, no PgPool.
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::{DateTime, NaiveDateTime};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Same shape as the TS `AttrClaims`, plus the optional backend claims.
+/// Timestamps are RFC 3339 UTC strings ("2026-06-01T12:00:00Z").
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttrClaims {
+    pub subject: String,
+    pub entity: String,
+    pub clearance: String,
+    pub compartments: Vec<String>,
+    pub issuer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issued_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+}
+
+/// Same shape as the TS `Credential`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Credential {
+    pub payload: AttrClaims,
+    pub sig: String, // base64 HMAC-SHA256 over canonical(payload)
+}
+
+/// Same shape as the TS `VerifyResult`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VerifyResult {
+    pub valid: bool,
+    pub reason: String,
+}
+
+/// What the verifier's registry knows about an issuer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssuerKey {
+    pub trusted: bool,
+    pub key: Option<String>,
+}
+
+// Fields in key order; serde writes struct fields in declaration order.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Canonical<'a> {
+    clearance: &'a str,
+    compartments: Vec<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    credential_id: Option<&'a str>,
+    entity: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_at: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    issued_at: Option<&'a str>,
+    issuer: &'a str,
+    subject: &'a str,
+}
+
+/// Stable, key-sorted serialization so signing is deterministic (canonical).
+pub fn canonical(claims: &AttrClaims) -> String {
+    let mut compartments: Vec<&str> = claims.compartments.iter().map(String::as_str).collect();
+    compartments.sort_unstable();
+    serde_json::to_string(&Canonical {
+        clearance: &claims.clearance,
+        compartments,
+        credential_id: claims.credential_id.as_deref(),
+        entity: &claims.entity,
+        expires_at: claims.expires_at.as_deref(),
+        issued_at: claims.issued_at.as_deref(),
+        issuer: &claims.issuer,
+        subject: &claims.subject,
+    })
+    .expect("string fields always serialize")
+}
+
+fn mac(claims: &AttrClaims, secret: &str) -> HmacSha256 {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(canonical(claims).as_bytes());
+    mac
+}
+
+pub fn sign(claims: &AttrClaims, secret: &str) -> String {
+    STANDARD.encode(mac(claims, secret).finalize().into_bytes())
+}
+
+pub fn issue_credential(claims: AttrClaims, issuer_secret: &str) -> Credential {
+    let sig = sign(&claims, issuer_secret);
+    Credential {
+        payload: claims,
+        sig,
+    }
+}
+
+/// RFC 3339 UTC form used in the claims, to the second.
+pub fn claim_timestamp(at: NaiveDateTime) -> String {
+    at.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+fn invalid(reason: String) -> VerifyResult {
+    VerifyResult {
+        valid: false,
+        reason,
+    }
+}
+
+/// Verify a presented credential (verifyCredential). `issuer` is the
+/// registry entry for the payload's issuer, None when unknown; `revoked` is
+/// the revocation reason when the credential id is known to be revoked.
+pub fn verify_credential(
+    cred: &Credential,
+    issuer: Option<&IssuerKey>,
+    now: NaiveDateTime,
+    revoked: Option<&str>,
+) -> VerifyResult {
+    let issuer_id = &cred.payload.issuer;
+    let Some(entry) = issuer.filter(|i| i.trusted) else {
+        return invalid(format!("issuer \"{}\" is not trusted", issuer_id));
+    };
+    let Some(key) = entry.key.as_deref() else {
+        return invalid(format!("no key on file for issuer \"{}\"", issuer_id));
+    };
+    let signature_ok = STANDARD
+        .decode(&cred.sig)
+        .is_ok_and(|sig| mac(&cred.payload, key).verify_slice(&sig).is_ok());
+    if !signature_ok {
+        return invalid("signature mismatch — payload tampered or wrong key".to_string());
+    }
+
+    if let Some(expires_at) = &cred.payload.expires_at {
+        match DateTime::parse_from_rfc3339(expires_at) {
+            Ok(at) if at.naive_utc() <= now => {
+                return invalid(format!("credential expired at {}", expires_at));
+            }
+            Ok(_) => {}
+            Err(_) => return invalid(format!("malformed expiry \"{}\"", expires_at)),
+        }
+    }
+    if let Some(reason) = revoked {
+        return invalid(format!("credential revoked: {}", reason));
+    }
+
+    VerifyResult {
+        valid: true,
+        reason: format!("verified signature from {}", issuer_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    const NCA: &str = "NATIONAL-CLEARANCE-AUTHORITY";
+    const NCA_KEY: &str = "nca-demo-secret-key";
+
+    fn base_claims() -> AttrClaims {
+        AttrClaims {
+            subject: "officer-1".to_string(),
+            entity: "ENTITY_A".to_string(),
+            clearance: "SECRET".to_string(),
+            compartments: vec!["NATO".to_string(), "AURORA".to_string()],
+            issuer: NCA.to_string(),
+            credential_id: None,
+            issued_at: None,
+            expires_at: None,
+        }
+    }
+
+    fn trusted() -> IssuerKey {
+        IssuerKey {
+            trusted: true,
+            key: Some(NCA_KEY.to_string()),
+        }
+    }
+
+    fn at(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 6, 1)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_canonical_and_signature_match_ts() {
+        // canonical() and HMAC-SHA256 of credential.ts for the same claims
+        assert_eq!(
+            canonical(&base_claims()),
+            r#"{"clearance":"SECRET","compartments":["AURORA","NATO"],"entity":"ENTITY_A","issuer":"NATIONAL-CLEARANCE-AUTHORITY","subject":"officer-1"}"#
+        );
+        assert_eq!(
+            sign(&base_claims(), NCA_KEY),
+            "rBCughA5B/bv8ML2qCEdr07ejsyG5QJjVWj4UiDQNDs="
+        );
+
+        let mut extended = base_claims();
+        extended.credential_id = Some("c-1".to_string());
+        extended.expires_at = Some("2026-06-01T12:00:00Z".to_string());
+        assert_eq!(
+            canonical(&extended),
+            r#"{"clearance":"SECRET","compartments":["AURORA","NATO"],"credentialId":"c-1","entity":"ENTITY_A","expiresAt":"2026-06-01T12:00:00Z","issuer":"NATIONAL-CLEARANCE-AUTHORITY","subject":"officer-1"}"#
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_untrusted_tampered_and_wrong_key() {
+        let cred = issue_credential(base_claims(), NCA_KEY);
+        assert!(verify_credential(&cred, Some(&trusted()), at(0), None).valid);
+
+        let mut tampered = cred.clone();
+        tampered.payload.clearance = "TOP_SECRET".to_string();
+        let result = verify_credential(&tampered, Some(&trusted()), at(0), None);
+        assert!(!result.valid);
+        assert_eq!(
+            result.reason,
+            "signature mismatch — payload tampered or wrong key"
+        );
+
+        let untrusted = IssuerKey {
+            trusted: false,
+            key: Some(NCA_KEY.to_string()),
+        };
+        assert_eq!(
+            verify_credential(&cred, Some(&untrusted), at(0), None).reason,
+            "issuer \"NATIONAL-CLEARANCE-AUTHORITY\" is not trusted"
+        );
+        assert!(!verify_credential(&cred, None, at(0), None).valid);
+
+        let forged = issue_credential(base_claims(), "attacker-guessed-key");
+        assert!(!verify_credential(&forged, Some(&trusted()), at(0), None).valid);
+    }
+
+    #[test]
+    fn test_verify_checks_expiry_then_revocation() {
+        let mut claims = base_claims();
+        claims.expires_at = Some(claim_timestamp(at(12)));
+        let cred = issue_credential(claims, NCA_KEY);
+        assert!(verify_credential(&cred, Some(&trusted()), at(11), None).valid);
+        assert_eq!(
+            verify_credential(&cred, Some(&trusted()), at(12), None).reason,
+            "credential expired at 2026-06-01T12:00:00Z"
+        );
+        assert_eq!(
+            verify_credential(&cred, Some(&trusted()), at(11), Some("clearance suspended")).reason,
+            "credential revoked: clearance suspended"
+        );
+    }
+}
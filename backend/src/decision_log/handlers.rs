@@ -0,0 +1,122 @@
+// Decision log HTTP handlers (mounted at /api/decision-log).
+//
+//   GET /          — recorded decisions, filtered by subject, resource and outcome
+//   GET /settings  — the retention setting
+//   PUT /settings  — change it, decision_log.manage
+use rocket::serde::json::Json;
+use rocket::{get, http::Status, put, State};
+use sqlx::PgPool;
+use validator::Validate;
+
+use super::models::{DecisionLogEntry, DecisionLogSettings, SetRetentionRequest};
+use crate::audit::handlers::audit;
+use crate::auth::middleware::AuthGuard;
+use crate::shared::rbac::require_permission;
+use crate::shared::response::ApiResponse;
+
+const DEFAULT_LIMIT: i64 = 100;
+const MAX_LIMIT: i64 = 500;
+
+const ENTRY_COLUMNS: &str = "id, source, person_id, resource_id, org_id, decided_at, allow, \
+     reason, gates, policy_version, caller_person_id, caller_role, recorded_at";
+
+fn db_error(e: sqlx::Error) -> Status {
+    eprintln!("Database error: {:?}", e);
+    Status::InternalServerError
+}
+
+async fn load_settings(db: &PgPool) -> Result<DecisionLogSettings, Status> {
+    sqlx::query_as::<_, DecisionLogSettings>(
+        "SELECT retention_days, updated_by_person_id, updated_at FROM decision_log_settings",
+    )
+    .fetch_one(db)
+    .await
+    .map_err(db_error)
+}
+
+/// Recorded decisions, newest first (default 100, at most 500). Every filter
+/// is optional: `person_id` is the subject, `allow=false` lists the denials.
+/// Pages backwards with `before_id`, the smallest id of the previous page.
+/// audit.read.
+#[get("/?<person_id>&<resource_id>&<allow>&<before_id>&<limit>")]
+pub async fn list_decisions(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    person_id: Option<i32>,
+    resource_id: Option<&str>,
+    allow: Option<bool>,
+    before_id: Option<i64>,
+    limit: Option<i64>,
+) -> Result<Json<ApiResponse<Vec<DecisionLogEntry>>>, Status> {
+    require_permission(db.inner(), &auth, "audit.read").await?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let entries = sqlx::query_as::<_, DecisionLogEntry>(&format!(
+        "SELECT {} FROM resource_decision_log \
+         WHERE ($1::int IS NULL OR person_id = $1) \
+           AND ($2::text IS NULL OR resource_id = $2) \
+           AND ($3::bool IS NULL OR allow = $3) \
+           AND ($4::bigint IS NULL OR id < $4) \
+         ORDER BY id DESC LIMIT $5",
+        ENTRY_COLUMNS
+    ))
+    .bind(person_id)
+    .bind(resource_id)
+    .bind(allow)
+    .bind(before_id)
+    .bind(limit)
+    .fetch_all(db.inner())
+    .await
+    .map_err(db_error)?;
+    Ok(Json(ApiResponse::success(entries)))
+}
+
+/// The retention setting. audit.read or decision_log.manage.
+#[get("/settings")]
+pub async fn get_settings(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+) -> Result<Json<ApiResponse<DecisionLogSettings>>, Status> {
+    if require_permission(db.inner(), &auth, "decision_log.manage")
+        .await
+        .is_err()
+    {
+        require_permission(db.inner(), &auth, "audit.read").await?;
+    }
+    Ok(Json(ApiResponse::success(load_settings(db.inner()).await?)))
+}
+
+/// Change how many days records are kept; the next retention sweep applies
+/// it. decision_log.manage; 400 outside 1..=3650 days.
+#[put("/settings", data = "<data>")]
+pub async fn set_settings(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    data: Json<SetRetentionRequest>,
+) -> Result<Json<ApiResponse<DecisionLogSettings>>, Status> {
+    let actor = require_permission(db.inner(), &auth, "decision_log.manage").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    let previous = load_settings(db.inner()).await?;
+    let settings = sqlx::query_as::<_, DecisionLogSettings>(
+        "UPDATE decision_log_settings \
+         SET retention_days = $1, updated_by_person_id = $2, updated_at = CURRENT_TIMESTAMP \
+         RETURNING retention_days, updated_by_person_id, updated_at",
+    )
+    .bind(data.retention_days)
+    .bind(actor)
+    .fetch_one(db.inner())
+    .await
+    .map_err(db_error)?;
+    audit(
+        db.inner(),
+        actor,
+        "DECISION_LOG_RETENTION_SET",
+        "decision_log_settings",
+        None,
+        format!(
+            "Decision log retention changed from {} to {} days",
+            previous.retention_days, settings.retention_days
+        ),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(settings)))
+}
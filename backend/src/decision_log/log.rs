@@ -0,0 +1,234 @@
+// Recording decisions and enforcing retention.
+//
+// Handlers record a decision before they return it; a decision that cannot
+// be recorded is not served. Records are written in one statement however
+// many there are. The log is append-only in the database (see the
+// resource_decision_log migration); the retention sweep is the one writer
+// allowed to delete, and only rows past the retention setting.
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+
+use super::models::DecisionRecord;
+use crate::abac::models::{AbacDecision, DECISION_ALLOW};
+use crate::audit::handlers::create_audit_log;
+use crate::audit::models::CreateAuditLogRequest;
+use crate::auth::jwt::Claims;
+use crate::deployments::context::ContextDecision;
+use crate::digital_resources::models::ResourceAccessResult;
+
+/// A GET /access decision.
+pub const SOURCE_ACCESS: &str = "ACCESS";
+/// A subject listed by GET /resources/<id>/who-can-access.
+pub const SOURCE_WHO_CAN_ACCESS: &str = "WHO_CAN_ACCESS";
+/// A POST /api/abac/decide decision.
+pub const SOURCE_ABAC_DECIDE: &str = "ABAC_DECIDE";
+/// A POST /api/abac/release decision.
+pub const SOURCE_ABAC_RELEASE: &str = "ABAC_RELEASE";
+/// A GET /api/deployments/subunits/<id>/access decision.
+pub const SOURCE_SUBUNIT_ACCESS: &str = "SUBUNIT_ACCESS";
+/// A holder's decision on POST /api/federation/detail-requests.
+pub const SOURCE_FEDERATION_DETAIL: &str = "FEDERATION_DETAIL";
+
+/// The longest reason the log keeps.
+const MAX_REASON_CHARS: usize = 100;
+
+/// A rule-based decision (ABAC, subunit context) as the log records it.
+pub trait RuledDecision: Serialize {
+    fn allowed(&self) -> bool;
+    /// Names of what denied it: the deny overrides that fired, and the base
+    /// rules that failed.
+    fn denied_by(&self) -> Vec<String>;
+}
+
+impl RuledDecision for AbacDecision {
+    fn allowed(&self) -> bool {
+        self.decision == DECISION_ALLOW
+    }
+
+    fn denied_by(&self) -> Vec<String> {
+        self.overrides
+            .iter()
+            .map(|o| o.name.clone())
+            .chain(self.failed.iter().cloned())
+            .collect()
+    }
+}
+
+impl RuledDecision for ContextDecision {
+    fn allowed(&self) -> bool {
+        self.decision == DECISION_ALLOW
+    }
+
+    // A context rule that did not apply only denies when nothing else granted.
+    fn denied_by(&self) -> Vec<String> {
+        let overrides = self.overrides.iter().map(|o| o.name.clone());
+        if self.allowed() {
+            return overrides.collect();
+        }
+        overrides
+            .chain(
+                self.rules
+                    .iter()
+                    .filter(|r| !r.active)
+                    .map(|r| r.name.clone()),
+            )
+            .collect()
+    }
+}
+
+/// The record of `result` for `person_id` on `resource_id`, decided at
+/// `decided_at` for the caller in `claims`.
+pub fn decision_record(
+    source: &'static str,
+    person_id: i32,
+    resource_id: &str,
+    org_id: Option<&str>,
+    decided_at: DateTime<Utc>,
+    result: &ResourceAccessResult,
+    claims: &Claims,
+) -> DecisionRecord {
+    DecisionRecord {
+        source,
+        person_id,
+        resource_id: resource_id.to_string(),
+        org_id: org_id.filter(|o| !o.is_empty()).map(str::to_string),
+        decided_at,
+        allow: result.allow,
+        reason: result.reason.clone(),
+        gates: serde_json::to_value(&result.gates).unwrap_or_default(),
+        policy_version: result
+            .policy_version
+            .as_ref()
+            .and_then(|v| serde_json::to_value(v).ok()),
+        caller_person_id: claims.sub.parse().ok(),
+        caller_role: claims.role.clone(),
+    }
+}
+
+/// The record of a rule-based `decision` for `person_id` on `target` (see
+/// the extend_decision_log_sources migration for the target names), decided
+/// now for the caller in `claims`. The gates hold the whole decision; the
+/// reason names what denied it.
+pub fn ruled_decision_record(
+    source: &'static str,
+    person_id: i32,
+    target: String,
+    org_id: Option<i32>,
+    decision: &impl RuledDecision,
+    claims: &Claims,
+) -> DecisionRecord {
+    let allow = decision.allowed();
+    let denied_by = decision.denied_by().join(", ");
+    DecisionRecord {
+        source,
+        person_id,
+        resource_id: target,
+        org_id: org_id.map(|id| id.to_string()),
+        decided_at: Utc::now(),
+        allow,
+        reason: (!allow && !denied_by.is_empty())
+            .then(|| denied_by.chars().take(MAX_REASON_CHARS).collect()),
+        gates: serde_json::to_value(decision).unwrap_or_default(),
+        policy_version: None,
+        caller_person_id: claims.sub.parse().ok(),
+        caller_role: claims.role.clone(),
+    }
+}
+
+/// Append `records` to the log in one statement.
+pub async fn record_decisions(db: &PgPool, records: &[DecisionRecord]) -> Result<(), sqlx::Error> {
+    if records.is_empty() {
+        return Ok(());
+    }
+    sqlx::query(
+        "INSERT INTO resource_decision_log \
+         (source, person_id, resource_id, org_id, decided_at, allow, reason, gates, \
+          policy_version, caller_person_id, caller_role) \
+         SELECT * FROM UNNEST($1::varchar[], $2::int[], $3::text[], $4::text[], \
+                              $5::timestamptz[], $6::bool[], $7::varchar[], $8::jsonb[], \
+                              $9::jsonb[], $10::int[], $11::varchar[])",
+    )
+    .bind(records.iter().map(|r| r.source).collect::<Vec<_>>())
+    .bind(records.iter().map(|r| r.person_id).collect::<Vec<_>>())
+    .bind(
+        records
+            .iter()
+            .map(|r| r.resource_id.clone())
+            .collect::<Vec<_>>(),
+    )
+    .bind(records.iter().map(|r| r.org_id.clone()).collect::<Vec<_>>())
+    .bind(records.iter().map(|r| r.decided_at).collect::<Vec<_>>())
+    .bind(records.iter().map(|r| r.allow).collect::<Vec<_>>())
+    .bind(records.iter().map(|r| r.reason.clone()).collect::<Vec<_>>())
+    .bind(records.iter().map(|r| r.gates.clone()).collect::<Vec<_>>())
+    .bind(
+        records
+            .iter()
+            .map(|r| r.policy_version.clone())
+            .collect::<Vec<_>>(),
+    )
+    .bind(
+        records
+            .iter()
+            .map(|r| r.caller_person_id)
+            .collect::<Vec<_>>(),
+    )
+    .bind(
+        records
+            .iter()
+            .map(|r| r.caller_role.clone())
+            .collect::<Vec<_>>(),
+    )
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// Delete the records older than the retention setting at `now`, audited
+/// when any are removed. The delete runs in purge_resource_decision_log, the
+/// only path past the table's append-only trigger. Returns the number deleted.
+pub async fn purge_expired(db: &PgPool, now: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+    let purged: i64 = sqlx::query_scalar("SELECT purge_resource_decision_log($1)")
+        .bind(now)
+        .fetch_one(db)
+        .await?;
+    let purged = purged as u64;
+
+    if purged > 0 {
+        let _ = create_audit_log(
+            &CreateAuditLogRequest {
+                person_id: None,
+                username: "system".to_string(),
+                action: "DECISION_LOG_PURGED".to_string(),
+                resource_type: "resource_decision_log".to_string(),
+                resource_id: None,
+                details: Some(format!(
+                    "Purged {} decision record(s) past retention",
+                    purged
+                )),
+                ip_address: None,
+                user_agent: None,
+            },
+            db,
+        )
+        .await;
+    }
+    Ok(purged)
+}
+
+/// Run purge_expired forever on a fixed interval. Errors are logged and the
+/// next tick retries.
+pub async fn run_retention_task(db: PgPool, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        match purge_expired(&db, Utc::now()).await {
+            Ok(0) => {}
+            Ok(n) => println!("⏳ Purged {} decision record(s)", n),
+            Err(e) => eprintln!("Decision log retention sweep failed: {:?}", e),
+        }
+    }
+}
@@ -0,0 +1,16 @@
+// Decision log module
+// Append-only record of every access decision served over HTTP (digital
+// resources, ABAC, subunits, federation details), its query endpoint, and the
+// retention setting with its purge sweep
+
+pub mod handlers;
+pub mod log;
+pub mod models;
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![
+        handlers::list_decisions,
+        handlers::get_settings,
+        handlers::set_settings,
+    ]
+}
@@ -0,0 +1,51 @@
+// Decision log data models
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// One recorded decision, as the query endpoint returns it
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DecisionLogEntry {
+    pub id: i64,
+    pub source: String,
+    pub person_id: i32,
+    pub resource_id: String,
+    pub org_id: Option<String>,
+    pub decided_at: DateTime<Utc>,
+    pub allow: bool,
+    pub reason: Option<String>,
+    pub gates: serde_json::Value,
+    pub policy_version: Option<serde_json::Value>,
+    pub caller_person_id: Option<i32>,
+    pub caller_role: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// A decision to record; see log::decision_record
+#[derive(Debug, Clone)]
+pub struct DecisionRecord {
+    pub source: &'static str,
+    pub person_id: i32,
+    pub resource_id: String,
+    pub org_id: Option<String>,
+    pub decided_at: DateTime<Utc>,
+    pub allow: bool,
+    pub reason: Option<String>,
+    pub gates: serde_json::Value,
+    pub policy_version: Option<serde_json::Value>,
+    pub caller_person_id: Option<i32>,
+    pub caller_role: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DecisionLogSettings {
+    pub retention_days: i32,
+    pub updated_by_person_id: Option<i32>,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetRetentionRequest {
+    #[validate(range(min = 1, max = 3650))]
+    pub retention_days: i32,
+}
@@ -0,0 +1,251 @@
+// Pure context-based subunit access.
+//
+// Ported from evaluateSubunitAccess / hasObligation in
+// frontend/src/demo/lib/obligations.ts. A requester organization with
+// standing access to the subunit's organization is allowed; otherwise the
+// support obligation rule grants access while the subunit is deployed ABROAD
+// and the requester has an obligation to support its organization. Nothing
+// is stored: the decision is computed from the deployment and obligations
+// at the time of the request. Standing access in the backend is being the
+// same organization or holding a sharing agreement in force (the pairs come
+// from sharing_agreements::affiliation, as for the ABAC affiliation rule).
+//
+// Deny overrides (revoked, security hold) and a non-AUTHORIZED managerial
+// authorization deny on top, as in every other decision path.
+// This module is PURE: no Rocket, no PgPool.
+use serde::Serialize;
+
+use crate::abac::evaluator::{has_agreement, SubjectFlags, AUTHORIZATION_AUTHORIZED};
+use crate::abac::models::{DECISION_ALLOW, DECISION_DENY};
+
+pub const DEPLOYMENT_HOME: &str = "HOME";
+pub const DEPLOYMENT_ABROAD: &str = "ABROAD";
+
+pub const EFFECT_BASE: &str = "BASE";
+pub const EFFECT_GRANT: &str = "GRANT";
+pub const EFFECT_DENY: &str = "DENY";
+
+/// Same shape as the TS `ContextRule`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContextRule {
+    pub name: String,
+    pub effect: String, // BASE, GRANT, DENY
+    pub active: bool,
+    pub detail: String,
+}
+
+/// Same shape as the TS `ContextDecision`, plus the deny overrides that fired
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContextDecision {
+    pub decision: String, // ALLOW, DENY
+    pub rules: Vec<ContextRule>,
+    pub overrides: Vec<ContextRule>,
+}
+
+/// The subunit being reached, with its organization as an entity id.
+#[derive(Debug, Clone)]
+pub struct SubunitTarget<'a> {
+    pub name: &'a str,
+    pub unit: &'a str,
+    pub deployment: &'a str,
+}
+
+pub fn valid_deployment(deployment: &str) -> bool {
+    deployment == DEPLOYMENT_HOME || deployment == DEPLOYMENT_ABROAD
+}
+
+/// Whether `requester` must support `target_unit` (hasObligation);
+/// `obligations` holds (from, to) pairs.
+pub fn has_obligation(
+    obligations: &[(String, String)],
+    requester: &str,
+    target_unit: &str,
+) -> bool {
+    obligations
+        .iter()
+        .any(|(from, to)| from == requester && to == target_unit)
+}
+
+fn rule(name: &str, effect: &str, active: bool, detail: String) -> ContextRule {
+    ContextRule {
+        name: name.to_string(),
+        effect: effect.to_string(),
+        active,
+        detail,
+    }
+}
+
+/// evaluateSubunitAccess: standing access, else the support obligation rule.
+pub fn evaluate_subunit_access(
+    requester: &str,
+    target: &SubunitTarget,
+    agreements: &[(String, String)],
+    obligations: &[(String, String)],
+) -> ContextDecision {
+    let mut rules = Vec::new();
+    let standing = has_agreement(agreements, requester, target.unit);
+    rules.push(rule(
+        "Standing access",
+        EFFECT_BASE,
+        standing,
+        if standing {
+            format!("{} has standing access to {}", requester, target.unit)
+        } else {
+            format!("{} has no standing access to {}", requester, target.unit)
+        },
+    ));
+
+    let mut allow = standing;
+    if !allow {
+        let abroad = target.deployment == DEPLOYMENT_ABROAD;
+        let obliged = has_obligation(obligations, requester, target.unit);
+        let grant = abroad && obliged;
+        rules.push(rule(
+            "Support obligation",
+            EFFECT_GRANT,
+            grant,
+            if grant {
+                format!(
+                    "{} deployed ABROAD + {} has a support obligation → access granted",
+                    target.name, requester
+                )
+            } else if !abroad {
+                format!("{} is at HOME — obligation does not trigger", target.name)
+            } else {
+                format!("{} has no support obligation to {}", requester, target.unit)
+            },
+        ));
+        allow = grant;
+    }
+
+    ContextDecision {
+        decision: if allow { DECISION_ALLOW } else { DECISION_DENY }.to_string(),
+        rules,
+        overrides: Vec::new(),
+    }
+}
+
+/// Apply the subject's deny overrides and managerial authorization to a
+/// context decision; the rules are kept.
+pub fn with_overrides(
+    mut decision: ContextDecision,
+    flags: &SubjectFlags,
+    authorization: Option<&str>,
+) -> ContextDecision {
+    if flags.revoked {
+        decision.overrides.push(rule(
+            "Revoked",
+            EFFECT_DENY,
+            true,
+            "subject access has been revoked".to_string(),
+        ));
+    }
+    if flags.security_hold {
+        decision.overrides.push(rule(
+            "Security hold",
+            EFFECT_DENY,
+            true,
+            "flagged by Security Officer".to_string(),
+        ));
+    }
+    if let Some(status) = authorization.filter(|s| *s != AUTHORIZATION_AUTHORIZED) {
+        decision.overrides.push(rule(
+            "Authorization valid",
+            EFFECT_DENY,
+            true,
+            format!("authorization.status={} (requires AUTHORIZED)", status),
+        ));
+    }
+    if !decision.overrides.is_empty() {
+        decision.decision = DECISION_DENY.to_string();
+    }
+    decision
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(list: &[(&str, &str)]) -> Vec<(String, String)> {
+        list.iter()
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+            .collect()
+    }
+
+    // SUBUNITS / SUPPORT_OBLIGATIONS from obligations.ts; MILITARY_2 has
+    // standing access to MILITARY_1 there, INFRA does not.
+    fn hospital(deployment: &str) -> SubunitTarget<'_> {
+        SubunitTarget {
+            name: "Field Hospital",
+            unit: "MILITARY_1",
+            deployment,
+        }
+    }
+
+    #[test]
+    fn test_obligation_grants_only_while_abroad() {
+        let obligations = pairs(&[("INFRA", "MILITARY_1"), ("INFRA", "MILITARY_2")]);
+        let abroad = evaluate_subunit_access("INFRA", &hospital("ABROAD"), &[], &obligations);
+        assert_eq!(abroad.decision, "ALLOW");
+        assert_eq!(
+            abroad.rules[1].detail,
+            "Field Hospital deployed ABROAD + INFRA has a support obligation → access granted"
+        );
+
+        let home = evaluate_subunit_access("INFRA", &hospital("HOME"), &[], &obligations);
+        assert_eq!(home.decision, "DENY");
+        assert_eq!(
+            home.rules[1].detail,
+            "Field Hospital is at HOME — obligation does not trigger"
+        );
+
+        let unobliged = evaluate_subunit_access("INTEL", &hospital("ABROAD"), &[], &obligations);
+        assert_eq!(unobliged.decision, "DENY");
+        assert_eq!(
+            unobliged.rules[1].detail,
+            "INTEL has no support obligation to MILITARY_1"
+        );
+    }
+
+    #[test]
+    fn test_standing_access_skips_obligation_rule() {
+        let agreements = pairs(&[("MILITARY_1", "MILITARY_2")]);
+        let decision = evaluate_subunit_access("MILITARY_2", &hospital("HOME"), &agreements, &[]);
+        assert_eq!(decision.decision, "ALLOW");
+        assert_eq!(decision.rules.len(), 1);
+        assert_eq!(
+            decision.rules[0].detail,
+            "MILITARY_2 has standing access to MILITARY_1"
+        );
+        let own = evaluate_subunit_access("MILITARY_1", &hospital("HOME"), &[], &[]);
+        assert_eq!(own.decision, "ALLOW");
+    }
+
+    #[test]
+    fn test_overrides_deny_and_keep_rules() {
+        let allowed = evaluate_subunit_access("MILITARY_1", &hospital("HOME"), &[], &[]);
+        let held = with_overrides(
+            allowed.clone(),
+            &SubjectFlags {
+                revoked: false,
+                security_hold: true,
+            },
+            None,
+        );
+        assert_eq!(held.decision, "DENY");
+        assert_eq!(held.rules, allowed.rules);
+        assert_eq!(held.overrides[0].name, "Security hold");
+
+        let withdrawn =
+            with_overrides(allowed.clone(), &SubjectFlags::default(), Some("WITHDRAWN"));
+        assert_eq!(withdrawn.decision, "DENY");
+        assert_eq!(
+            withdrawn.overrides[0].detail,
+            "authorization.status=WITHDRAWN (requires AUTHORIZED)"
+        );
+        let clean = with_overrides(allowed, &SubjectFlags::default(), Some("AUTHORIZED"));
+        assert_eq!(clean.decision, "ALLOW");
+        assert!(clean.overrides.is_empty());
+        assert!(!valid_deployment("AWAY"));
+    }
+}
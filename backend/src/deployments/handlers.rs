@@ -0,0 +1,446 @@
+// Deployment HTTP handlers (mounted at /api/deployments)
+//
+// Subunits and support obligations are readable under organizations.read and
+// managed under deployments.manage. Changing a subunit's deployment needs a
+// reason and is audited, as are obligation changes: both change who reaches
+// the subunits at the next decision. GET /subunits/<id>/access decides for a
+// stored person under person.read (see context) and records the decision in
+// the decision log.
+use rocket::serde::json::Json;
+use rocket::{delete, get, http::Status, post, put, State};
+use sqlx::PgPool;
+use validator::Validate;
+
+use super::context::{
+    evaluate_subunit_access, valid_deployment, with_overrides, SubunitTarget, DEPLOYMENT_HOME,
+};
+use super::models::{
+    CreateSubunitRequest, CreateSupportObligationRequest, SetDeploymentRequest, Subunit,
+    SubunitAccessResponse, SupportObligation,
+};
+use crate::abac::models::DECISION_ALLOW;
+use crate::abac::principal::{person_organization_ids, UNAFFILIATED};
+use crate::audit::handlers::{audit, insert_audit_log};
+use crate::audit::models::CreateAuditLogRequest;
+use crate::auth::middleware::AuthGuard;
+use crate::authorizations::lifecycle::load_authorization_status;
+use crate::decision_log::log::{record_decisions, ruled_decision_record, SOURCE_SUBUNIT_ACCESS};
+use crate::security_flags::flags::load_subject_flags;
+use crate::shared::rbac::require_permission;
+use crate::shared::response::ApiResponse;
+use crate::sharing_agreements::affiliation::load_affiliation_agreements;
+
+const SUBUNIT_COLUMNS: &str = "id, organization_id, name, deployment, \
+                               deployment_changed_by_person_id, deployment_changed_at, \
+                               created_by_person_id, created_at, updated_at";
+const OBLIGATION_COLUMNS: &str =
+    "id, from_organization_id, to_organization_id, created_by_person_id, created_at";
+
+fn db_error(e: sqlx::Error) -> Status {
+    eprintln!("Database error: {:?}", e);
+    Status::InternalServerError
+}
+
+async fn fetch_subunit(db: &PgPool, id: i32) -> Result<Subunit, Status> {
+    sqlx::query_as::<_, Subunit>(&format!(
+        "SELECT {} FROM subunits WHERE id = $1",
+        SUBUNIT_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(db)
+    .await
+    .map_err(db_error)?
+    .ok_or(Status::NotFound)
+}
+
+/// How many of `ids` are organizations that exist and are not deleted.
+async fn count_organizations(db: &PgPool, ids: &[i32]) -> Result<i64, Status> {
+    sqlx::query_scalar(
+        "SELECT COUNT(*) FROM organizations WHERE id = ANY($1) AND deleted_at IS NULL",
+    )
+    .bind(ids)
+    .fetch_one(db)
+    .await
+    .map_err(db_error)
+}
+
+/// Subunits, optionally of one organization.
+#[get("/subunits?<organization_id>")]
+pub async fn list_subunits(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    organization_id: Option<i32>,
+) -> Result<Json<ApiResponse<Vec<Subunit>>>, Status> {
+    require_permission(db.inner(), &auth, "organizations.read").await?;
+    let subunits = sqlx::query_as::<_, Subunit>(&format!(
+        "SELECT {} FROM subunits WHERE ($1::int IS NULL OR organization_id = $1) ORDER BY id",
+        SUBUNIT_COLUMNS
+    ))
+    .bind(organization_id)
+    .fetch_all(db.inner())
+    .await
+    .map_err(db_error)?;
+    Ok(Json(ApiResponse::success(subunits)))
+}
+
+#[get("/subunits/<id>")]
+pub async fn get_subunit(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<Subunit>>, Status> {
+    require_permission(db.inner(), &auth, "organizations.read").await?;
+    Ok(Json(ApiResponse::success(
+        fetch_subunit(db.inner(), id).await?,
+    )))
+}
+
+/// Register a subunit, at HOME unless a deployment is given. 400 for an
+/// unknown deployment, 404 for an unknown or deleted organization.
+#[post("/subunits", data = "<data>")]
+pub async fn create_subunit(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    data: Json<CreateSubunitRequest>,
+) -> Result<Json<ApiResponse<Subunit>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "deployments.manage").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    let deployment = data.deployment.as_deref().unwrap_or(DEPLOYMENT_HOME);
+    if !valid_deployment(deployment) {
+        return Err(Status::BadRequest);
+    }
+    if count_organizations(db.inner(), &[data.organization_id]).await? != 1 {
+        return Err(Status::NotFound);
+    }
+
+    let subunit = sqlx::query_as::<_, Subunit>(&format!(
+        "INSERT INTO subunits (organization_id, name, deployment, created_by_person_id) \
+         VALUES ($1, $2, $3, $4) RETURNING {}",
+        SUBUNIT_COLUMNS
+    ))
+    .bind(data.organization_id)
+    .bind(data.name.trim())
+    .bind(deployment)
+    .bind(caller)
+    .fetch_one(db.inner())
+    .await
+    .map_err(db_error)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "SUBUNIT_CREATED",
+        "subunits",
+        Some(subunit.id),
+        format!(
+            "'{}' of organization {} at {}",
+            subunit.name, subunit.organization_id, subunit.deployment
+        ),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(subunit)))
+}
+
+/// Move a subunit HOME or ABROAD, with a reason for the audit trail. 400 for
+/// an unknown deployment or a missing reason, 404 for an unknown subunit,
+/// 409 when it is already there. The change and its audit entry commit
+/// together.
+#[put("/subunits/<id>/deployment", data = "<data>")]
+pub async fn set_deployment(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    data: Json<SetDeploymentRequest>,
+) -> Result<Json<ApiResponse<Subunit>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "deployments.manage").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    if !valid_deployment(&data.deployment) || data.reason.trim().is_empty() {
+        return Err(Status::BadRequest);
+    }
+    let previous = fetch_subunit(db.inner(), id).await?;
+    if previous.deployment == data.deployment {
+        return Err(Status::Conflict);
+    }
+
+    // Conditional on the deployment read above, so a concurrent change is a
+    // 409 rather than an unaudited overwrite. The audit entry is written in
+    // the same transaction: a change that cannot be recorded is not made.
+    let mut tx = db.inner().begin().await.map_err(db_error)?;
+    let subunit = sqlx::query_as::<_, Subunit>(&format!(
+        "UPDATE subunits SET deployment = $2, deployment_changed_by_person_id = $3, \
+           deployment_changed_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP \
+         WHERE id = $1 AND deployment = $4 RETURNING {}",
+        SUBUNIT_COLUMNS
+    ))
+    .bind(id)
+    .bind(&data.deployment)
+    .bind(caller)
+    .bind(&previous.deployment)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(db_error)?
+    .ok_or(Status::Conflict)?;
+
+    insert_audit_log(
+        &CreateAuditLogRequest {
+            person_id: Some(caller),
+            username: caller.to_string(),
+            action: "SUBUNIT_DEPLOYMENT_CHANGED".to_string(),
+            resource_type: "subunits".to_string(),
+            resource_id: Some(id),
+            details: Some(format!(
+                "'{}' of organization {}: {} -> {}: {}",
+                subunit.name,
+                subunit.organization_id,
+                previous.deployment,
+                subunit.deployment,
+                data.reason.trim()
+            )),
+            ip_address: None,
+            user_agent: None,
+        },
+        &mut *tx,
+    )
+    .await
+    .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
+    Ok(Json(ApiResponse::success(subunit)))
+}
+
+/// Delete a subunit; the audit log keeps its name and last deployment.
+#[delete("/subunits/<id>")]
+pub async fn delete_subunit(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<Subunit>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "deployments.manage").await?;
+    let subunit = sqlx::query_as::<_, Subunit>(&format!(
+        "DELETE FROM subunits WHERE id = $1 RETURNING {}",
+        SUBUNIT_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(db_error)?
+    .ok_or(Status::NotFound)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "SUBUNIT_DELETED",
+        "subunits",
+        Some(id),
+        format!(
+            "'{}' of organization {} (was {})",
+            subunit.name, subunit.organization_id, subunit.deployment
+        ),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(subunit)))
+}
+
+/// Decide whether a person reaches a subunit now: standing access (same
+/// organization or a sharing agreement in force), else a support obligation
+/// while the subunit is ABROAD, then the deny overrides. A person in several
+/// organizations is evaluated as the first, by id, that is allowed, otherwise
+/// as their lowest-id organization. The decision is recorded. 404 for an
+/// unknown subunit or an unknown or offboarded person.
+#[get("/subunits/<id>/access?<person_id>")]
+pub async fn subunit_access(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    person_id: i32,
+) -> Result<Json<ApiResponse<SubunitAccessResponse>>, Status> {
+    require_permission(db.inner(), &auth, "person.read").await?;
+    let subunit = fetch_subunit(db.inner(), id).await?;
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM person WHERE id = $1 AND deleted_at IS NULL)",
+    )
+    .bind(person_id)
+    .fetch_one(db.inner())
+    .await
+    .map_err(db_error)?;
+    if !exists {
+        return Err(Status::NotFound);
+    }
+
+    let now = chrono::Utc::now().naive_utc();
+    // Only agreements covering every domain give standing access to a unit.
+    let agreements = load_affiliation_agreements(
+        db.inner(),
+        subunit.organization_id,
+        None,
+        "UNCLASSIFIED",
+        now,
+    )
+    .await
+    .map_err(db_error)?;
+    let obligations: Vec<(String, String)> = sqlx::query_as::<_, (i32, i32)>(
+        "SELECT from_organization_id, to_organization_id FROM support_obligations \
+         WHERE to_organization_id = $1 ORDER BY id",
+    )
+    .bind(subunit.organization_id)
+    .fetch_all(db.inner())
+    .await
+    .map_err(db_error)?
+    .into_iter()
+    .map(|(from, to)| (from.to_string(), to.to_string()))
+    .collect();
+    let orgs = person_organization_ids(db.inner(), person_id)
+        .await
+        .map_err(db_error)?;
+    let flags = load_subject_flags(db.inner(), person_id)
+        .await
+        .map_err(db_error)?;
+    let authorization = load_authorization_status(db.inner(), person_id, now)
+        .await
+        .map_err(db_error)?;
+
+    let unit = subunit.organization_id.to_string();
+    let target = SubunitTarget {
+        name: &subunit.name,
+        unit: &unit,
+        deployment: &subunit.deployment,
+    };
+    let evaluate = |org: Option<i32>| {
+        let requester = org.map_or_else(|| UNAFFILIATED.to_string(), |o| o.to_string());
+        (
+            org,
+            evaluate_subunit_access(&requester, &target, &agreements, &obligations),
+        )
+    };
+    let (organization_id, decision) = orgs
+        .iter()
+        .map(|&org| evaluate(Some(org)))
+        .find(|(_, d)| d.decision == DECISION_ALLOW)
+        .unwrap_or_else(|| evaluate(orgs.first().copied()));
+    let decision = with_overrides(decision, &flags, authorization.as_deref());
+    let record = ruled_decision_record(
+        SOURCE_SUBUNIT_ACCESS,
+        person_id,
+        format!("subunit:{}", subunit.id),
+        organization_id,
+        &decision,
+        &auth.claims,
+    );
+    record_decisions(db.inner(), &[record])
+        .await
+        .map_err(db_error)?;
+
+    Ok(Json(ApiResponse::success(SubunitAccessResponse {
+        subunit_id: subunit.id,
+        person_id,
+        organization_id,
+        deployment: subunit.deployment.clone(),
+        decision,
+    })))
+}
+
+/// Support obligations, optionally those one organization is party to.
+#[get("/obligations?<organization_id>")]
+pub async fn list_obligations(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    organization_id: Option<i32>,
+) -> Result<Json<ApiResponse<Vec<SupportObligation>>>, Status> {
+    require_permission(db.inner(), &auth, "organizations.read").await?;
+    let obligations = sqlx::query_as::<_, SupportObligation>(&format!(
+        "SELECT {} FROM support_obligations \
+         WHERE ($1::int IS NULL OR from_organization_id = $1 OR to_organization_id = $1) \
+         ORDER BY id",
+        OBLIGATION_COLUMNS
+    ))
+    .bind(organization_id)
+    .fetch_all(db.inner())
+    .await
+    .map_err(db_error)?;
+    Ok(Json(ApiResponse::success(obligations)))
+}
+
+/// Record that one organization must support another's deployed subunits.
+/// 400 for the same organization on both sides, 404 when either is unknown
+/// or deleted, 409 when the obligation already exists.
+#[post("/obligations", data = "<data>")]
+pub async fn create_obligation(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    data: Json<CreateSupportObligationRequest>,
+) -> Result<Json<ApiResponse<SupportObligation>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "deployments.manage").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    if data.from_organization_id == data.to_organization_id {
+        return Err(Status::BadRequest);
+    }
+    if count_organizations(
+        db.inner(),
+        &[data.from_organization_id, data.to_organization_id],
+    )
+    .await?
+        != 2
+    {
+        return Err(Status::NotFound);
+    }
+
+    let obligation = sqlx::query_as::<_, SupportObligation>(&format!(
+        "INSERT INTO support_obligations (from_organization_id, to_organization_id, \
+           created_by_person_id) \
+         VALUES ($1, $2, $3) ON CONFLICT (from_organization_id, to_organization_id) DO NOTHING \
+         RETURNING {}",
+        OBLIGATION_COLUMNS
+    ))
+    .bind(data.from_organization_id)
+    .bind(data.to_organization_id)
+    .bind(caller)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(db_error)?
+    .ok_or(Status::Conflict)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "SUPPORT_OBLIGATION_CREATED",
+        "support_obligations",
+        Some(obligation.id),
+        format!(
+            "{} supports {}",
+            obligation.from_organization_id, obligation.to_organization_id
+        ),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(obligation)))
+}
+
+#[delete("/obligations/<id>")]
+pub async fn delete_obligation(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<SupportObligation>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "deployments.manage").await?;
+    let obligation = sqlx::query_as::<_, SupportObligation>(&format!(
+        "DELETE FROM support_obligations WHERE id = $1 RETURNING {}",
+        OBLIGATION_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(db_error)?
+    .ok_or(Status::NotFound)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "SUPPORT_OBLIGATION_DELETED",
+        "support_obligations",
+        Some(id),
+        format!(
+            "{} no longer supports {}",
+            obligation.from_organization_id, obligation.to_organization_id
+        ),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(obligation)))
+}
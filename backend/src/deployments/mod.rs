@@ -0,0 +1,21 @@
+// Deployments module
+// Subunits with deployment status, support obligations between organizations
+// and the context-based subunit access they drive
+
+pub mod context;
+pub mod handlers;
+pub mod models;
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![
+        handlers::list_subunits,
+        handlers::get_subunit,
+        handlers::create_subunit,
+        handlers::set_deployment,
+        handlers::delete_subunit,
+        handlers::subunit_access,
+        handlers::list_obligations,
+        handlers::create_obligation,
+        handlers::delete_obligation,
+    ]
+}
@@ -0,0 +1,62 @@
+// Deployment data models
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::context::ContextDecision;
+
+/// A subunit of an organization and where it is deployed
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Subunit {
+    pub id: i32,
+    pub organization_id: i32,
+    pub name: String,
+    pub deployment: String, // HOME, ABROAD
+    pub deployment_changed_by_person_id: Option<i32>,
+    pub deployment_changed_at: Option<NaiveDateTime>,
+    pub created_by_person_id: i32,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// `from_organization_id` must support `to_organization_id`'s deployed
+/// subunits
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SupportObligation {
+    pub id: i32,
+    pub from_organization_id: i32,
+    pub to_organization_id: i32,
+    pub created_by_person_id: i32,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateSubunitRequest {
+    pub organization_id: i32,
+    #[validate(length(min = 1, max = 200))]
+    pub name: String,
+    pub deployment: Option<String>, // defaults to HOME
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetDeploymentRequest {
+    pub deployment: String,
+    #[validate(length(min = 1, max = 500))]
+    pub reason: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateSupportObligationRequest {
+    pub from_organization_id: i32,
+    pub to_organization_id: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SubunitAccessResponse {
+    pub subunit_id: i32,
+    pub person_id: i32,
+    pub organization_id: Option<i32>, // the requester's organization evaluated
+    pub deployment: String,
+    #[serde(flatten)]
+    pub decision: ContextDecision,
+}
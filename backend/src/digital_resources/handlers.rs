@@ -1,25 +1,58 @@
 // Digital-resource HTTP handlers (Phase 11, Plan 03 — RSRC-BE-03, RSRC-BE-04).
 //
-// Three endpoints, all relative-path macros (domain mounted at /api/digital-resources):
+// Endpoints, all relative-path macros (domain mounted at /api/digital-resources):
 //   GET  /world          — aggregate read, AuthGuard (_auth = only used for 401 rejection)
+//   GET  /access         — resolver decision for one stored person on one resource
+//   GET  /resources/<id>/who-can-access — allowed subjects and near misses on one resource
+//   POST /simulations    — who gains or loses access under proposed policy changes
+//   GET  /lint           — policy coverage gaps and configuration mistakes
 //   POST /grants         — issue a resource access grant, re-validates authority server-side
 //   POST /delegates      — issue an org delegate, re-validates authority server-side
+//   GET  /resources/<id>/shielding — a node's shielded flag and org allowlist
+//   PUT  /resources/<id>/shielding — replace both, ADMIN-only
+//   GET  /persons/<id>/org — the resource organization a person is evaluated as
+//   PUT  /persons/<id>/org — set or clear it, ADMIN-only
 //
 // Route mounts: no /api/... in macros — the mount point handles the prefix (D-09).
 // All handlers return Result<Json<T>, Status>; never panic (CLAUDE.md convention).
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
 use rocket::http::Status;
 use rocket::serde::json::Json;
-use rocket::{get, post, State};
+use rocket::{get, post, put, State};
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use super::inputs::{
+    load_candidate_subjects, load_person_resource_org, load_resolver_platforms,
+    load_resolver_resource, load_resource_grants, load_shield_allowlists, load_subject_grants,
+};
+use super::lint::lint_world;
 use super::models::{
-    DigitalResourceWorldResponse, IssueDelegateRequest, IssueGrantRequest, ResourceAccessDelegate,
-    ResourceAccessGrant, ResourceApplication, ResourceNetwork, ResourceOrgLink, ResourcePlatform,
-    ResourcePolicy, ResourcePolicyAssignment,
+    DigitalResourceWorldResponse, IssueDelegateRequest, IssueGrantRequest, LintReport,
+    PersonResourceOrg, ResourceAccessDelegate, ResourceAccessGrant, ResourceAccessResult,
+    ResourceApplication, ResourceNetwork, ResourceOrgLink, ResourcePlatform, ResourcePolicy,
+    ResourcePolicyAssignment, ResourceShielding, SetPersonResourceOrgRequest, SetShieldingRequest,
+    SimulatePolicyRequest, SimulationResponse, WhoCanAccessResponse,
 };
+use super::resolver::{apply_authorization, apply_deny_overrides, resolve_resource_access};
+use super::simulation::{apply_modifications, simulate, ModificationError};
+use super::who_can_access::who_can_access;
+use crate::access::clearance::load_effective_clearance;
+use crate::access::grants::ACCESS_RESOURCE;
+use crate::audit::handlers::create_audit_log;
+use crate::audit::models::CreateAuditLogRequest;
 use crate::auth::middleware::AuthGuard;
+use crate::authorizations::lifecycle::load_authorization_status;
+use crate::compartments::checks::enforce_compartments;
+use crate::decision_log::log::{
+    decision_record, record_decisions, SOURCE_ACCESS, SOURCE_WHO_CAN_ACCESS,
+};
+use crate::security_flags::flags::load_subject_flags;
+use crate::shared::rbac::role_has_permission;
 use crate::shared::response::ApiResponse;
+use crate::sod::enforce::enforce_no_self_action;
 
 // ---------------------------------------------------------------------------
 // GET /world
@@ -33,10 +66,16 @@ pub async fn get_world(
     db: &State<PgPool>,
     _auth: AuthGuard,
 ) -> Result<Json<ApiResponse<DigitalResourceWorldResponse>>, Status> {
+    Ok(Json(ApiResponse::success(load_world(db.inner()).await?)))
+}
+
+// The aggregate GET /world returns, also the base copy POST /simulations
+// modifies.
+async fn load_world(db: &PgPool) -> Result<DigitalResourceWorldResponse, Status> {
     // 8 parallel flat queries — load everything, assemble in memory.
     let networks: Vec<ResourceNetwork> =
-        sqlx::query_as::<_, ResourceNetwork>("SELECT id, name, classification, created_at, updated_at FROM resource_networks ORDER BY id")
-            .fetch_all(db.inner())
+        sqlx::query_as::<_, ResourceNetwork>("SELECT id, name, classification, shielded, created_at, updated_at FROM resource_networks ORDER BY id")
+            .fetch_all(db)
             .await
             .map_err(|e| {
                 eprintln!("DB error loading resource_networks: {:?}", e);
@@ -44,8 +83,8 @@ pub async fn get_world(
             })?;
 
     let platforms: Vec<ResourcePlatform> =
-        sqlx::query_as::<_, ResourcePlatform>("SELECT id, name, classification, network_id, created_at, updated_at FROM resource_platforms ORDER BY id")
-            .fetch_all(db.inner())
+        sqlx::query_as::<_, ResourcePlatform>("SELECT id, name, classification, network_id, shielded, created_at, updated_at FROM resource_platforms ORDER BY id")
+            .fetch_all(db)
             .await
             .map_err(|e| {
                 eprintln!("DB error loading resource_platforms: {:?}", e);
@@ -53,8 +92,8 @@ pub async fn get_world(
             })?;
 
     let applications: Vec<ResourceApplication> =
-        sqlx::query_as::<_, ResourceApplication>("SELECT id, name, platform_id, created_at, updated_at FROM resource_applications ORDER BY id")
-            .fetch_all(db.inner())
+        sqlx::query_as::<_, ResourceApplication>("SELECT id, name, platform_id, shielded, created_at, updated_at FROM resource_applications ORDER BY id")
+            .fetch_all(db)
             .await
             .map_err(|e| {
                 eprintln!("DB error loading resource_applications: {:?}", e);
@@ -63,7 +102,7 @@ pub async fn get_world(
 
     let org_links: Vec<ResourceOrgLink> =
         sqlx::query_as::<_, ResourceOrgLink>("SELECT id, resource_id, resource_tier, org_id, role, valid_from, valid_until FROM resource_org_links ORDER BY id")
-            .fetch_all(db.inner())
+            .fetch_all(db)
             .await
             .map_err(|e| {
                 eprintln!("DB error loading resource_org_links: {:?}", e);
@@ -73,7 +112,7 @@ pub async fn get_world(
     let policies: Vec<ResourcePolicy> = sqlx::query_as::<_, ResourcePolicy>(
         "SELECT id, label, gates, zone_prereq_id FROM resource_policies ORDER BY id",
     )
-    .fetch_all(db.inner())
+    .fetch_all(db)
     .await
     .map_err(|e| {
         eprintln!("DB error loading resource_policies: {:?}", e);
@@ -82,7 +121,7 @@ pub async fn get_world(
 
     let policy_assignments: Vec<ResourcePolicyAssignment> =
         sqlx::query_as::<_, ResourcePolicyAssignment>("SELECT id, resource_id, resource_tier, policy_id, valid_from, valid_until FROM resource_policy_assignments ORDER BY id")
-            .fetch_all(db.inner())
+            .fetch_all(db)
             .await
             .map_err(|e| {
                 eprintln!("DB error loading resource_policy_assignments: {:?}", e);
@@ -91,7 +130,7 @@ pub async fn get_world(
 
     let grants: Vec<ResourceAccessGrant> =
         sqlx::query_as::<_, ResourceAccessGrant>("SELECT id, person_id, resource_id, valid_from, valid_until FROM resource_access_grants ORDER BY id")
-            .fetch_all(db.inner())
+            .fetch_all(db)
             .await
             .map_err(|e| {
                 eprintln!("DB error loading resource_access_grants: {:?}", e);
@@ -100,14 +139,14 @@ pub async fn get_world(
 
     let delegates: Vec<ResourceAccessDelegate> =
         sqlx::query_as::<_, ResourceAccessDelegate>("SELECT id, resource_id, delegate_type, delegate_person_id, delegate_org_id, granted_by_org_id, valid_from, valid_until FROM resource_access_delegates ORDER BY id")
-            .fetch_all(db.inner())
+            .fetch_all(db)
             .await
             .map_err(|e| {
                 eprintln!("DB error loading resource_access_delegates: {:?}", e);
                 Status::InternalServerError
             })?;
 
-    let world = DigitalResourceWorldResponse {
+    Ok(DigitalResourceWorldResponse {
         networks,
         platforms,
         applications,
@@ -116,9 +155,258 @@ pub async fn get_world(
         policy_assignments,
         grants,
         delegates,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// GET /access
+// ---------------------------------------------------------------------------
+//
+// Server-side resolver decision for a stored person on one resource node.
+// REQUIRED_ROLE and SHIELDING gates see the person's stored resource
+// organization (PUT /persons/<id>/org); a person without one fails them.
+// Clearance gates see the effective clearance now. Uncleared security flags
+// are applied as deny overrides after the gate chain, then the managerial
+// authorization rule. The decision is written to the decision log before it
+// is returned. person.read; 404 for an unknown or offboarded person or an
+// unknown resource.
+#[get("/access?<person_id>&<resource_id>")]
+pub async fn resolve_access(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    person_id: i32,
+    resource_id: &str,
+) -> Result<Json<ApiResponse<ResourceAccessResult>>, Status> {
+    if !role_has_permission(db.inner(), &auth.claims.role, "person.read")
+        .await
+        .unwrap_or(false)
+    {
+        return Err(Status::Forbidden);
+    }
+    let db_error = |e: sqlx::Error| {
+        eprintln!("DB error resolving resource access: {:?}", e);
+        Status::InternalServerError
+    };
+    let clearance = load_effective_clearance(db.inner(), person_id, Utc::now().naive_utc())
+        .await
+        .map_err(db_error)?
+        .ok_or(Status::NotFound)?;
+    let resource = load_resolver_resource(db.inner(), resource_id)
+        .await
+        .map_err(db_error)?
+        .ok_or(Status::NotFound)?;
+    let platforms = load_resolver_platforms(db.inner())
+        .await
+        .map_err(db_error)?;
+    let subject = person_id.to_string();
+    let grants = load_subject_grants(db.inner(), &subject)
+        .await
+        .map_err(db_error)?;
+    let flags = load_subject_flags(db.inner(), person_id)
+        .await
+        .map_err(db_error)?;
+    let authorization = load_authorization_status(db.inner(), person_id, Utc::now().naive_utc())
+        .await
+        .map_err(db_error)?;
+    let org_id = load_person_resource_org(db.inner(), person_id)
+        .await
+        .map_err(db_error)?;
+
+    let now = Utc::now();
+    let result = resolve_resource_access(
+        &subject,
+        clearance.as_deref().unwrap_or("UNCLASSIFIED"),
+        org_id.as_deref().unwrap_or_default(),
+        &resource,
+        &platforms,
+        &grants,
+        now,
+    );
+    let result = apply_deny_overrides(result, flags.revoked, flags.security_hold);
+    let result = apply_authorization(result, authorization.as_deref());
+    let record = decision_record(
+        SOURCE_ACCESS,
+        person_id,
+        &resource.id,
+        org_id.as_deref(),
+        now,
+        &result,
+        &auth.claims,
+    );
+    record_decisions(db.inner(), &[record])
+        .await
+        .map_err(db_error)?;
+    Ok(Json(ApiResponse::success(result)))
+}
+
+// An optional RFC 3339 `at` query parameter, default now; 400 when malformed.
+fn parse_at(at: Option<&str>) -> Result<DateTime<Utc>, Status> {
+    match at {
+        Some(at) => Ok(DateTime::parse_from_rfc3339(at)
+            .map_err(|_| Status::BadRequest)?
+            .with_timezone(&Utc)),
+        None => Ok(Utc::now()),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// GET /resources/<resource_id>/who-can-access
+// ---------------------------------------------------------------------------
+//
+// Reverse of GET /access: runs the same decision for every person who is not
+// offboarded and lists the allowed subjects plus the near misses (denied by
+// exactly one gate). `at` is an RFC 3339 timestamp, default now; clearance,
+// authorization and policy windows are evaluated at it, against the records
+// as stored now. Each candidate is evaluated as their stored resource
+// organization, as on GET /access. Loads everything in four queries, whatever
+// the number of persons. Every listed decision is written to the decision
+// log when `at` is omitted; a listing at another time is a what-if, not a
+// decision served, and like a simulation is not logged. person.read; 400 for
+// a malformed `at`, 404 for an unknown resource.
+#[get("/resources/<resource_id>/who-can-access?<at>")]
+pub async fn get_who_can_access(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    resource_id: &str,
+    at: Option<&str>,
+) -> Result<Json<ApiResponse<WhoCanAccessResponse>>, Status> {
+    if !role_has_permission(db.inner(), &auth.claims.role, "person.read")
+        .await
+        .unwrap_or(false)
+    {
+        return Err(Status::Forbidden);
+    }
+    let hypothetical = at.is_some();
+    let at = parse_at(at)?;
+    let db_error = |e: sqlx::Error| {
+        eprintln!("DB error resolving who can access: {:?}", e);
+        Status::InternalServerError
+    };
+    let resource = load_resolver_resource(db.inner(), resource_id)
+        .await
+        .map_err(db_error)?
+        .ok_or(Status::NotFound)?;
+    let platforms = load_resolver_platforms(db.inner())
+        .await
+        .map_err(db_error)?;
+    let grant_targets: Vec<String> = std::iter::once(resource.id.clone())
+        .chain(resource.parent_id.clone())
+        .collect();
+    let grants = load_resource_grants(db.inner(), &grant_targets)
+        .await
+        .map_err(db_error)?;
+    let candidates = load_candidate_subjects(db.inner(), at.naive_utc())
+        .await
+        .map_err(db_error)?;
+
+    let (allowed, near_misses) = who_can_access(&resource, &platforms, &grants, &candidates, at);
+    if !hypothetical {
+        let orgs: HashMap<i32, Option<&str>> = candidates
+            .iter()
+            .map(|c| (c.person_id, c.org_id.as_deref()))
+            .collect();
+        let records: Vec<_> = allowed
+            .iter()
+            .map(|row| (row.person_id, &row.decision))
+            .chain(near_misses.iter().map(|row| (row.person_id, &row.decision)))
+            .map(|(person_id, decision)| {
+                decision_record(
+                    SOURCE_WHO_CAN_ACCESS,
+                    person_id,
+                    &resource.id,
+                    orgs.get(&person_id).copied().flatten(),
+                    at,
+                    decision,
+                    &auth.claims,
+                )
+            })
+            .collect();
+        record_decisions(db.inner(), &records)
+            .await
+            .map_err(db_error)?;
+    }
+    Ok(Json(ApiResponse::success(WhoCanAccessResponse {
+        resource_id: resource.id,
+        at,
+        candidates: candidates.len(),
+        allowed,
+        near_misses,
+    })))
+}
+
+// ---------------------------------------------------------------------------
+// POST /simulations
+// ---------------------------------------------------------------------------
+//
+// What-if: applies the proposed gate lists, grant and org-link removals and
+// classification changes to an in-memory copy of GET /world, re-resolves the
+// nodes they reach for the subjects they can affect, and returns who gains
+// and who loses access at `at` (default now). Subjects are evaluated as on
+// GET /resources/<id>/who-can-access, or all as the request's `org_id` when
+// given: a what-if may assume an org nobody is stored as. Nothing is written.
+// person.read; 404
+// for an unknown policy, grant, org link or node; 400 for an unknown
+// classification or one set on an application.
+#[post("/simulations", data = "<body>")]
+pub async fn simulate_policy(
+    body: Json<SimulatePolicyRequest>,
+    db: &State<PgPool>,
+    auth: AuthGuard,
+) -> Result<Json<ApiResponse<SimulationResponse>>, Status> {
+    if !role_has_permission(db.inner(), &auth.claims.role, "person.read")
+        .await
+        .unwrap_or(false)
+    {
+        return Err(Status::Forbidden);
+    }
+    let request = body.into_inner();
+    let db_error = |e: sqlx::Error| {
+        eprintln!("DB error simulating policy changes: {:?}", e);
+        Status::InternalServerError
     };
+    let world = load_world(db.inner()).await?;
+    let modified = apply_modifications(&world, &request).map_err(|e| match e {
+        ModificationError::NotFound => Status::NotFound,
+        ModificationError::Invalid => Status::BadRequest,
+    })?;
+    let at = request.at.unwrap_or_else(Utc::now);
+    let allowlists = load_shield_allowlists(db.inner()).await.map_err(db_error)?;
+    let mut candidates = load_candidate_subjects(db.inner(), at.naive_utc())
+        .await
+        .map_err(db_error)?;
+    if let Some(org_id) = &request.org_id {
+        for candidate in &mut candidates {
+            candidate.org_id = Some(org_id.clone());
+        }
+    }
 
-    Ok(Json(ApiResponse::success(world)))
+    Ok(Json(ApiResponse::success(simulate(
+        &world,
+        &modified,
+        &allowlists,
+        &candidates,
+        at,
+    ))))
+}
+
+// ---------------------------------------------------------------------------
+// GET /lint
+// ---------------------------------------------------------------------------
+//
+// Scans every node and policy for future coverage gaps, overlapping
+// assignment windows, unknown gate kinds, REQUIRED_ROLE gates no org holds
+// and applications without their platform (see lint.rs). `at` is where the
+// scan for gaps and roles starts, RFC 3339, default now. Reads only what
+// GET /world returns, so AuthGuard alone, as there; 400 for a malformed `at`.
+#[get("/lint?<at>")]
+pub async fn get_lint(
+    db: &State<PgPool>,
+    _auth: AuthGuard,
+    at: Option<&str>,
+) -> Result<Json<ApiResponse<LintReport>>, Status> {
+    let at = parse_at(at)?;
+    let world = load_world(db.inner()).await?;
+    Ok(Json(ApiResponse::success(lint_world(&world, at))))
 }
 
 // ---------------------------------------------------------------------------
@@ -130,9 +418,10 @@ pub async fn get_world(
 //   1. Role gate: only ADMIN-role JWTs may issue (resolver::can_issue_resource_grant
 //      is NOT called — it is retained for SEED-012's org-based model).
 //   2. assert_resource_exists — 404 on unknown resource.
-//   3. INSERT ... ON CONFLICT DO NOTHING (idempotent — uq_grant / T-11-11;
+//   3. enforce_compartments — 403 when the grantee lacks a required compartment.
+//   4. INSERT ... ON CONFLICT DO NOTHING (idempotent — uq_grant / T-11-11;
 //      NULLS NOT DISTINCT since 20260601130003 so null-window grants dedupe too).
-//   4. Return the persisted row (or the existing row if it was a duplicate).
+//   5. Return the persisted row (or the existing row if it was a duplicate).
 #[post("/grants", data = "<body>")]
 pub async fn issue_grant(
     body: Json<IssueGrantRequest>,
@@ -148,10 +437,35 @@ pub async fn issue_grant(
     }
     let data = body.into_inner();
 
+    // SoD: the issuing principal may not grant a resource to themselves.
+    enforce_no_self_action(
+        db.inner(),
+        "access.write",
+        &auth.claims.sub,
+        &data.person_id,
+    )
+    .await?;
+
     // Validate the resource exists (404 otherwise). The org_links/policies loaded
     // here belong to the resolver's /world semantics, not the write-authz decision.
     assert_resource_exists(&data.resource_id, db.inner()).await?;
 
+    // Need-to-know: the grantee must hold every compartment the resource
+    // requires. Non-person grantee ids hold none.
+    let actor = auth
+        .claims
+        .sub
+        .parse::<i32>()
+        .map_err(|_| Status::InternalServerError)?;
+    enforce_compartments(
+        db.inner(),
+        actor,
+        data.person_id.parse::<i32>().ok(),
+        ACCESS_RESOURCE,
+        &data.resource_id,
+    )
+    .await?;
+
     // Generate a deterministic-enough TEXT id for the grant.
     let grant_id = Uuid::new_v4().to_string();
 
@@ -284,14 +598,276 @@ pub async fn issue_delegate(
     Ok(Json(ApiResponse::success(delegate)))
 }
 
+// ---------------------------------------------------------------------------
+// GET /resources/<resource_id>/shielding
+// ---------------------------------------------------------------------------
+//
+// A node's directional shielding: when shielded, the resolver's SHIELDING gate
+// admits only its ASSET_OWNER org and the allowlisted orgs. AuthGuard only,
+// like /world; 404 on an unknown resource.
+#[get("/resources/<resource_id>/shielding")]
+pub async fn get_shielding(
+    db: &State<PgPool>,
+    _auth: AuthGuard,
+    resource_id: &str,
+) -> Result<Json<ApiResponse<ResourceShielding>>, Status> {
+    let resource = load_resolver_resource(db.inner(), resource_id)
+        .await
+        .map_err(|e| {
+            eprintln!("DB error loading resource shielding: {:?}", e);
+            Status::InternalServerError
+        })?
+        .ok_or(Status::NotFound)?;
+    Ok(Json(ApiResponse::success(ResourceShielding {
+        resource_id: resource.id,
+        shielded: resource.shielded,
+        allowlist: resource.allowlist,
+    })))
+}
+
+// ---------------------------------------------------------------------------
+// PUT /resources/<resource_id>/shielding
+// ---------------------------------------------------------------------------
+//
+// Replaces the shielded flag and the whole allowlist in one transaction.
+// ADMIN-only (Option B, as for grants). Blank org ids are a 400; duplicates
+// collapse. 409 when the id names nodes on more than one tier. Audited, since
+// it changes who the resolver admits.
+#[put("/resources/<resource_id>/shielding", data = "<body>")]
+pub async fn set_shielding(
+    body: Json<SetShieldingRequest>,
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    resource_id: &str,
+) -> Result<Json<ApiResponse<ResourceShielding>>, Status> {
+    if auth.claims.role != "admin" {
+        return Err(Status::Forbidden);
+    }
+    let actor = auth
+        .claims
+        .sub
+        .parse::<i32>()
+        .map_err(|_| Status::InternalServerError)?;
+    let data = body.into_inner();
+    let mut allowlist: Vec<String> = data
+        .allowlist
+        .iter()
+        .map(|org| org.trim().to_string())
+        .collect();
+    if allowlist.iter().any(|org| org.is_empty()) {
+        return Err(Status::BadRequest);
+    }
+    allowlist.sort();
+    allowlist.dedup();
+
+    let db_error = |e: sqlx::Error| {
+        eprintln!("DB error updating resource shielding: {:?}", e);
+        Status::InternalServerError
+    };
+    let mut tx = db.inner().begin().await.map_err(db_error)?;
+    // Ids are meant to be unique across the three tiers, but nothing in the
+    // schema enforces it; shielding several nodes at once is refused.
+    let mut updated = 0;
+    for table in [
+        "resource_networks",
+        "resource_platforms",
+        "resource_applications",
+    ] {
+        updated += sqlx::query(&format!(
+            "UPDATE {} SET shielded = $2, updated_at = NOW() WHERE id = $1",
+            table
+        ))
+        .bind(resource_id)
+        .bind(data.shielded)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?
+        .rows_affected();
+    }
+    match updated {
+        0 => return Err(Status::NotFound),
+        1 => {}
+        _ => return Err(Status::Conflict),
+    }
+    sqlx::query("DELETE FROM resource_shield_allowlist WHERE resource_id = $1")
+        .bind(resource_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+    for org in &allowlist {
+        sqlx::query(
+            "INSERT INTO resource_shield_allowlist (resource_id, org_id, added_by_person_id) \
+             VALUES ($1, $2, $3)",
+        )
+        .bind(resource_id)
+        .bind(org)
+        .bind(actor)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+    }
+    tx.commit().await.map_err(db_error)?;
+
+    let _ = create_audit_log(
+        &CreateAuditLogRequest {
+            person_id: Some(actor),
+            username: actor.to_string(),
+            action: "RESOURCE_SHIELDING_UPDATED".to_string(),
+            resource_type: "digital_resource".to_string(),
+            resource_id: None,
+            details: Some(format!(
+                "'{}' shielded={} allowlist={:?}",
+                resource_id, data.shielded, allowlist
+            )),
+            ip_address: None,
+            user_agent: None,
+        },
+        db.inner(),
+    )
+    .await;
+
+    Ok(Json(ApiResponse::success(ResourceShielding {
+        resource_id: resource_id.to_string(),
+        shielded: data.shielded,
+        allowlist,
+    })))
+}
+
+// ---------------------------------------------------------------------------
+// GET /persons/<person_id>/org
+// ---------------------------------------------------------------------------
+//
+// The resource organization the person is evaluated as on GET /access and
+// who-can-access; `org_id` is null when none is stored. person.read; 404 for
+// an unknown or offboarded person.
+#[get("/persons/<person_id>/org")]
+pub async fn get_person_org(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    person_id: i32,
+) -> Result<Json<ApiResponse<PersonResourceOrg>>, Status> {
+    if !role_has_permission(db.inner(), &auth.claims.role, "person.read")
+        .await
+        .unwrap_or(false)
+    {
+        return Err(Status::Forbidden);
+    }
+    let org_id: Option<Option<String>> = sqlx::query_scalar(
+        "SELECT ro.org_id FROM person p \
+         LEFT JOIN person_resource_orgs ro ON ro.person_id = p.id \
+         WHERE p.id = $1 AND p.deleted_at IS NULL",
+    )
+    .bind(person_id)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(|e| {
+        eprintln!("DB error loading person resource org: {:?}", e);
+        Status::InternalServerError
+    })?;
+    let org_id = org_id.ok_or(Status::NotFound)?;
+    Ok(Json(ApiResponse::success(PersonResourceOrg {
+        person_id,
+        org_id,
+    })))
+}
+
+// ---------------------------------------------------------------------------
+// PUT /persons/<person_id>/org
+// ---------------------------------------------------------------------------
+//
+// Sets the person's resource organization, or clears it with a null `org_id`.
+// ADMIN-only, like shielding, since it decides which SHIELDING and
+// REQUIRED_ROLE gates the person passes; audited. 400 for a blank org id, 404
+// for an unknown or offboarded person.
+#[put("/persons/<person_id>/org", data = "<body>")]
+pub async fn set_person_org(
+    body: Json<SetPersonResourceOrgRequest>,
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    person_id: i32,
+) -> Result<Json<ApiResponse<PersonResourceOrg>>, Status> {
+    if auth.claims.role != "admin" {
+        return Err(Status::Forbidden);
+    }
+    let actor = auth
+        .claims
+        .sub
+        .parse::<i32>()
+        .map_err(|_| Status::InternalServerError)?;
+    let org_id = body.into_inner().org_id.map(|org| org.trim().to_string());
+    if org_id.as_deref() == Some("") {
+        return Err(Status::BadRequest);
+    }
+
+    let db_error = |e: sqlx::Error| {
+        eprintln!("DB error updating person resource org: {:?}", e);
+        Status::InternalServerError
+    };
+    let mut tx = db.inner().begin().await.map_err(db_error)?;
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM person WHERE id = $1 AND deleted_at IS NULL)",
+    )
+    .bind(person_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(db_error)?;
+    if !exists {
+        return Err(Status::NotFound);
+    }
+    match &org_id {
+        Some(org) => sqlx::query(
+            "INSERT INTO person_resource_orgs (person_id, org_id, assigned_by_person_id) \
+             VALUES ($1, $2, $3) \
+             ON CONFLICT (person_id) DO UPDATE SET org_id = EXCLUDED.org_id, \
+               assigned_by_person_id = EXCLUDED.assigned_by_person_id, \
+               created_at = CURRENT_TIMESTAMP",
+        )
+        .bind(person_id)
+        .bind(org)
+        .bind(actor),
+        None => {
+            sqlx::query("DELETE FROM person_resource_orgs WHERE person_id = $1").bind(person_id)
+        }
+    }
+    .execute(&mut *tx)
+    .await
+    .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
+
+    let _ = create_audit_log(
+        &CreateAuditLogRequest {
+            person_id: Some(actor),
+            username: actor.to_string(),
+            action: "PERSON_RESOURCE_ORG_UPDATED".to_string(),
+            resource_type: "person".to_string(),
+            resource_id: Some(person_id),
+            details: Some(format!(
+                "person_id={} resource org={}",
+                person_id,
+                org_id.as_deref().unwrap_or("none")
+            )),
+            ip_address: None,
+            user_agent: None,
+        },
+        db.inner(),
+    )
+    .await;
+
+    Ok(Json(ApiResponse::success(PersonResourceOrg {
+        person_id,
+        org_id,
+    })))
+}
+
 // ---------------------------------------------------------------------------
 // Helper: assert a resource_id exists
 // ---------------------------------------------------------------------------
 //
 // Write endpoints only need existence validation (404 if unknown across all
 // three tiers). Authority is decided by the Option-B role gate in the handlers,
-// NOT the resolver — so no ResolverResource is built here.
-async fn assert_resource_exists(resource_id: &str, pool: &PgPool) -> Result<(), Status> {
+// NOT the resolver — so no ResolverResource is built here. Also used by access
+// bundles to validate resource templates.
+pub async fn assert_resource_exists(resource_id: &str, pool: &PgPool) -> Result<(), Status> {
     let exists: bool = sqlx::query_scalar(
         "SELECT EXISTS ( \
            SELECT 1 FROM resource_networks     WHERE id = $1 \
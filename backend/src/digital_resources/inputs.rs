@@ -0,0 +1,260 @@
+// Assemble the resolver's inputs from the digital-resource tables, for the
+// server-side decisions (GET /access, GET /resources/<id>/who-can-access).
+// The resolver itself stays pure.
+//
+// Policy gates are stored as JSON; a gate list that does not parse becomes a
+// single Unknown gate, so the decision fails closed rather than erroring.
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::PgPool;
+
+use super::models::{GateDescriptor, ResourceAccessGrant};
+use super::resolver::{
+    ResolverOrgLink, ResolverPlatform, ResolverPolicy, ResolverPolicyAssignment, ResolverResource,
+};
+use super::who_can_access::{group_by_subject, CandidateSubject};
+use crate::access::clearance::effective_clearance_sql;
+use crate::authorizations::lifecycle::effective_status;
+use crate::security_flags::flags::subject_flags;
+
+#[derive(sqlx::FromRow)]
+struct NodeRow {
+    id: String,
+    tier: String,
+    classification: Option<String>,
+    parent_id: Option<String>,
+    shielded: bool,
+}
+
+#[derive(sqlx::FromRow)]
+struct OrgLinkRow {
+    org_id: String,
+    role: String,
+    valid_from: Option<DateTime<Utc>>,
+    valid_until: Option<DateTime<Utc>>,
+}
+
+#[derive(sqlx::FromRow)]
+struct AssignmentRow {
+    gates: serde_json::Value,
+    zone_prereq_id: Option<String>,
+    valid_from: Option<DateTime<Utc>>,
+    valid_until: Option<DateTime<Utc>>,
+}
+
+/// A stored gate list; one Unknown gate when it does not parse.
+pub fn parse_gates(gates: serde_json::Value) -> Vec<GateDescriptor> {
+    serde_json::from_value(gates).unwrap_or_else(|_| vec![GateDescriptor::Unknown])
+}
+
+/// The resource node with its org links, policy assignments (in id order, as
+/// the resolver picks the first active one) and shielding allowlist. None when
+/// no network, platform or application has that id.
+pub async fn load_resolver_resource(
+    db: &PgPool,
+    resource_id: &str,
+) -> Result<Option<ResolverResource>, sqlx::Error> {
+    let Some(node) = sqlx::query_as::<_, NodeRow>(
+        "SELECT id, 'NETWORK' AS tier, classification, NULL::text AS parent_id, shielded \
+           FROM resource_networks WHERE id = $1 \
+         UNION ALL SELECT id, 'PLATFORM', classification, network_id, shielded \
+           FROM resource_platforms WHERE id = $1 \
+         UNION ALL SELECT id, 'APPLICATION', NULL, platform_id, shielded \
+           FROM resource_applications WHERE id = $1 \
+         LIMIT 1",
+    )
+    .bind(resource_id)
+    .fetch_optional(db)
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let org_links = sqlx::query_as::<_, OrgLinkRow>(
+        "SELECT org_id, role, valid_from, valid_until FROM resource_org_links \
+         WHERE resource_id = $1 ORDER BY id",
+    )
+    .bind(resource_id)
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|l| ResolverOrgLink {
+        org_id: l.org_id,
+        role: l.role,
+        valid_from: l.valid_from,
+        valid_until: l.valid_until,
+    })
+    .collect();
+
+    let policy_assignments = sqlx::query_as::<_, AssignmentRow>(
+        "SELECT p.gates, p.zone_prereq_id, a.valid_from, a.valid_until \
+         FROM resource_policy_assignments a JOIN resource_policies p ON p.id = a.policy_id \
+         WHERE a.resource_id = $1 ORDER BY a.id",
+    )
+    .bind(resource_id)
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|a| ResolverPolicyAssignment {
+        policy: ResolverPolicy {
+            gates: parse_gates(a.gates),
+            zone_prereq_id: a.zone_prereq_id,
+        },
+        valid_from: a.valid_from,
+        valid_until: a.valid_until,
+    })
+    .collect();
+
+    let allowlist = sqlx::query_scalar::<_, String>(
+        "SELECT org_id FROM resource_shield_allowlist WHERE resource_id = $1 ORDER BY org_id",
+    )
+    .bind(resource_id)
+    .fetch_all(db)
+    .await?;
+
+    Ok(Some(ResolverResource {
+        id: node.id,
+        tier: node.tier,
+        classification: node.classification,
+        parent_id: node.parent_id,
+        org_links,
+        policy_assignments,
+        shielded: node.shielded,
+        allowlist,
+    }))
+}
+
+/// Every platform, for the application classification lookup.
+pub async fn load_resolver_platforms(db: &PgPool) -> Result<Vec<ResolverPlatform>, sqlx::Error> {
+    Ok(sqlx::query_as::<_, (String, String)>(
+        "SELECT id, classification FROM resource_platforms ORDER BY id",
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|(id, classification)| ResolverPlatform { id, classification })
+    .collect())
+}
+
+/// The subject's resource grants (all windows; the resolver checks them).
+pub async fn load_subject_grants(
+    db: &PgPool,
+    subject: &str,
+) -> Result<Vec<ResourceAccessGrant>, sqlx::Error> {
+    sqlx::query_as::<_, ResourceAccessGrant>(
+        "SELECT id, person_id, resource_id, valid_from, valid_until \
+         FROM resource_access_grants WHERE person_id = $1 ORDER BY id",
+    )
+    .bind(subject)
+    .fetch_all(db)
+    .await
+}
+
+/// The person's stored resource organization, if any.
+pub async fn load_person_resource_org(
+    db: &PgPool,
+    person_id: i32,
+) -> Result<Option<String>, sqlx::Error> {
+    sqlx::query_scalar("SELECT org_id FROM person_resource_orgs WHERE person_id = $1")
+        .bind(person_id)
+        .fetch_optional(db)
+        .await
+}
+
+/// Every shielded node's allowlist, keyed by resource id; GET /world does
+/// not carry them.
+pub async fn load_shield_allowlists(
+    db: &PgPool,
+) -> Result<HashMap<String, Vec<String>>, sqlx::Error> {
+    let mut allowlists: HashMap<String, Vec<String>> = HashMap::new();
+    for (resource_id, org_id) in sqlx::query_as::<_, (String, String)>(
+        "SELECT resource_id, org_id FROM resource_shield_allowlist ORDER BY resource_id, org_id",
+    )
+    .fetch_all(db)
+    .await?
+    {
+        allowlists.entry(resource_id).or_default().push(org_id);
+    }
+    Ok(allowlists)
+}
+
+/// Grants on any of `resource_ids`, keyed by subject. The reverse query only
+/// needs the node's own and parent-tier grants.
+pub async fn load_resource_grants(
+    db: &PgPool,
+    resource_ids: &[String],
+) -> Result<HashMap<String, Vec<ResourceAccessGrant>>, sqlx::Error> {
+    let grants = sqlx::query_as::<_, ResourceAccessGrant>(
+        "SELECT id, person_id, resource_id, valid_from, valid_until \
+         FROM resource_access_grants WHERE resource_id = ANY($1) ORDER BY id",
+    )
+    .bind(resource_ids)
+    .fetch_all(db)
+    .await?;
+    Ok(group_by_subject(grants))
+}
+
+#[derive(sqlx::FromRow)]
+struct CandidateRow {
+    id: i32,
+    name: String,
+    clearance: Option<String>,
+    flags: Vec<String>,
+    authorization: Option<String>,
+    authorization_until: Option<NaiveDateTime>,
+    org_id: Option<String>,
+}
+
+/// Every person who is not offboarded, with the effective clearance and
+/// authorization status at `at`, their uncleared flags and their resource
+/// organization, in one query.
+pub async fn load_candidate_subjects(
+    db: &PgPool,
+    at: NaiveDateTime,
+) -> Result<Vec<CandidateSubject>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, CandidateRow>(&format!(
+        "SELECT p.id, \
+                COALESCE(NULLIF(CONCAT_WS(' ', p.first_name, p.last_name), ''), p.username, '') AS name, \
+                {} AS clearance, \
+                ARRAY(SELECT f.flag FROM person_security_flags f \
+                      WHERE f.person_id = p.id AND f.cleared_at IS NULL ORDER BY f.flag) AS flags, \
+                pa.status AS authorization, pa.valid_until AS authorization_until, \
+                ro.org_id \
+         FROM person p \
+         LEFT JOIN person_resource_orgs ro ON ro.person_id = p.id \
+         LEFT JOIN LATERAL (SELECT status, valid_until FROM person_authorizations \
+                            WHERE person_id = p.id ORDER BY created_at DESC, id DESC LIMIT 1) pa ON TRUE \
+         WHERE p.deleted_at IS NULL ORDER BY p.id",
+        effective_clearance_sql("p.id", "$1")
+    ))
+    .bind(at)
+    .fetch_all(db)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| CandidateSubject {
+            person_id: row.id,
+            name: row.name,
+            clearance: row.clearance,
+            flags: subject_flags(&row.flags),
+            authorization: row
+                .authorization
+                .map(|status| effective_status(&status, row.authorization_until, at)),
+            org_id: row.org_id,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unparseable_gates_fail_closed() {
+        let gates = parse_gates(serde_json::json!([{ "kind": "CLEARANCE" }]));
+        assert!(matches!(gates.as_slice(), [GateDescriptor::Clearance]));
+        let broken = parse_gates(serde_json::json!({ "kind": "CLEARANCE" }));
+        assert!(matches!(broken.as_slice(), [GateDescriptor::Unknown]));
+    }
+}
@@ -0,0 +1,374 @@
+// Policy coverage and lint report.
+//
+// select_active_policy fails closed (NO_ACTIVE_POLICY) whenever no assignment
+// window covers the decision time, so a gap between two windows denies
+// everyone without any error. The lint scans the world GET /world returns
+// for that and the other configuration mistakes that only show at decision
+// time:
+//   COVERAGE_GAP          — a node is uncovered at some point from `at` on
+//   OVERLAPPING_WINDOWS   — two assignments of a node overlap; the first by
+//                           id silently wins
+//   UNKNOWN_GATE_KIND     — a policy's gate list has a kind the resolver does
+//                           not know (or does not parse); every decision
+//                           under it is DENY
+//   UNHELD_REQUIRED_ROLE  — a REQUIRED_ROLE gate on a node where no org holds
+//                           that role now or later
+//   MISSING_PLATFORM      — an application whose host platform does not
+//                           exist, so its classification cannot be derived
+// Windows are inclusive at both ends, as in is_window_active.
+// This module is PURE: no Rocket, no PgPool.
+use chrono::{DateTime, Duration, Utc};
+
+use super::inputs::parse_gates;
+use super::models::{
+    CoverageGap, DigitalResourceWorldResponse, GateDescriptor, LintFinding, LintReport,
+    ResourcePolicyAssignment,
+};
+
+pub const LINT_COVERAGE_GAP: &str = "COVERAGE_GAP";
+pub const LINT_OVERLAPPING_WINDOWS: &str = "OVERLAPPING_WINDOWS";
+pub const LINT_UNKNOWN_GATE_KIND: &str = "UNKNOWN_GATE_KIND";
+pub const LINT_UNHELD_REQUIRED_ROLE: &str = "UNHELD_REQUIRED_ROLE";
+pub const LINT_MISSING_PLATFORM: &str = "MISSING_PLATFORM";
+
+type Window = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+fn contains((from, until): Window, at: DateTime<Utc>) -> bool {
+    from.is_none_or(|f| f <= at) && until.is_none_or(|u| u >= at)
+}
+
+fn overlaps((from_a, until_a): Window, (from_b, until_b): Window) -> bool {
+    let a_before_b_ends = match (from_a, until_b) {
+        (Some(f), Some(u)) => f <= u,
+        _ => true,
+    };
+    let b_before_a_ends = match (from_b, until_a) {
+        (Some(f), Some(u)) => f <= u,
+        _ => true,
+    };
+    a_before_b_ends && b_before_a_ends
+}
+
+/// Uncovered stretches from `at` on, in order. `from` is the first
+/// uncovered instant, `until` the first covered one again (None: never).
+pub fn coverage_gaps(windows: &[Window], at: DateTime<Utc>) -> Vec<CoverageGap> {
+    let mut gaps = Vec::new();
+    let mut cursor = at;
+    loop {
+        let covering: Vec<Option<DateTime<Utc>>> = windows
+            .iter()
+            .filter(|w| contains(**w, cursor))
+            .map(|(_, until)| *until)
+            .collect();
+        if covering.iter().any(Option::is_none) {
+            return gaps;
+        }
+        if let Some(until) = covering.into_iter().flatten().max() {
+            // Covered through `until`; the next instant needs a window too.
+            cursor = until + Duration::microseconds(1);
+            continue;
+        }
+        let next = windows
+            .iter()
+            .filter_map(|(from, _)| from.filter(|f| *f > cursor))
+            .min();
+        gaps.push(CoverageGap {
+            from: cursor,
+            until: next,
+        });
+        match next {
+            Some(next) => cursor = next,
+            None => return gaps,
+        }
+    }
+}
+
+fn finding(
+    kind: &str,
+    resource_id: Option<&str>,
+    policy_id: Option<&str>,
+    detail: String,
+) -> LintFinding {
+    LintFinding {
+        kind: kind.to_string(),
+        resource_id: resource_id.map(str::to_string),
+        policy_id: policy_id.map(str::to_string),
+        detail,
+        gap: None,
+    }
+}
+
+fn window(a: &ResourcePolicyAssignment) -> Window {
+    (a.valid_from, a.valid_until)
+}
+
+/// Every node id in the world, networks then platforms then applications.
+fn node_ids(world: &DigitalResourceWorldResponse) -> Vec<&str> {
+    let networks = world.networks.iter().map(|n| n.id.as_str());
+    let platforms = world.platforms.iter().map(|p| p.id.as_str());
+    let applications = world.applications.iter().map(|a| a.id.as_str());
+    networks.chain(platforms).chain(applications).collect()
+}
+
+/// Lint the world as of `at`.
+pub fn lint_world(world: &DigitalResourceWorldResponse, at: DateTime<Utc>) -> LintReport {
+    let mut findings = Vec::new();
+    let nodes = node_ids(world);
+
+    for policy in &world.policies {
+        let gates = parse_gates(policy.gates.clone());
+        if gates.iter().any(|g| matches!(g, GateDescriptor::Unknown)) {
+            findings.push(finding(
+                LINT_UNKNOWN_GATE_KIND,
+                None,
+                Some(&policy.id),
+                format!(
+                    "policy {} has a gate the resolver does not know; it denies everyone",
+                    policy.id
+                ),
+            ));
+        }
+    }
+
+    for resource_id in &nodes {
+        let assignments: Vec<&ResourcePolicyAssignment> = world
+            .policy_assignments
+            .iter()
+            .filter(|a| a.resource_id == *resource_id)
+            .collect();
+
+        let windows: Vec<Window> = assignments.iter().map(|a| window(a)).collect();
+        for gap in coverage_gaps(&windows, at) {
+            let detail = match gap.until {
+                Some(until) => format!("no active policy from {} until {}", gap.from, until),
+                None => format!("no active policy from {} on", gap.from),
+            };
+            let mut gap_finding = finding(LINT_COVERAGE_GAP, Some(resource_id), None, detail);
+            gap_finding.gap = Some(gap);
+            findings.push(gap_finding);
+        }
+
+        for (i, first) in assignments.iter().enumerate() {
+            for second in &assignments[i + 1..] {
+                if overlaps(window(first), window(second)) {
+                    findings.push(finding(
+                        LINT_OVERLAPPING_WINDOWS,
+                        Some(resource_id),
+                        Some(&second.policy_id),
+                        format!(
+                            "assignments {} ({}) and {} ({}) overlap; {} wins",
+                            first.id, first.policy_id, second.id, second.policy_id, first.id
+                        ),
+                    ));
+                }
+            }
+        }
+
+        // Roles matter only under assignments still in force from `at` on.
+        let mut required: Vec<(&str, String)> = Vec::new();
+        for assignment in assignments
+            .iter()
+            .filter(|a| a.valid_until.is_none_or(|u| u >= at))
+        {
+            let Some(policy) = world.policies.iter().find(|p| p.id == assignment.policy_id) else {
+                continue;
+            };
+            for gate in parse_gates(policy.gates.clone()) {
+                if let GateDescriptor::RequiredRole { role } = gate {
+                    if !required.iter().any(|(p, r)| *p == policy.id && *r == role) {
+                        required.push((&policy.id, role));
+                    }
+                }
+            }
+        }
+        for (policy_id, role) in required {
+            let held = world.org_links.iter().any(|l| {
+                l.resource_id == *resource_id
+                    && l.role == role
+                    && l.valid_until.is_none_or(|u| u >= at)
+            });
+            if !held {
+                findings.push(finding(
+                    LINT_UNHELD_REQUIRED_ROLE,
+                    Some(resource_id),
+                    Some(policy_id),
+                    format!(
+                        "policy {} requires role {} but no org holds it on {}",
+                        policy_id, role, resource_id
+                    ),
+                ));
+            }
+        }
+    }
+
+    for application in &world.applications {
+        if !world
+            .platforms
+            .iter()
+            .any(|p| p.id == application.platform_id)
+        {
+            findings.push(finding(
+                LINT_MISSING_PLATFORM,
+                Some(&application.id),
+                None,
+                format!(
+                    "application {} runs on platform {}, which does not exist",
+                    application.id, application.platform_id
+                ),
+            ));
+        }
+    }
+
+    LintReport {
+        at,
+        resources_scanned: nodes.len(),
+        policies_scanned: world.policies.len(),
+        findings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::digital_resources::models::{
+        ResourceApplication, ResourceNetwork, ResourceOrgLink, ResourcePolicy,
+    };
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn day(d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 6, d, 0, 0, 0).unwrap()
+    }
+
+    fn assignment(
+        id: i32,
+        policy_id: &str,
+        from: Option<u32>,
+        until: Option<u32>,
+    ) -> ResourcePolicyAssignment {
+        ResourcePolicyAssignment {
+            id,
+            resource_id: "net-1".to_string(),
+            resource_tier: "NETWORK".to_string(),
+            policy_id: policy_id.to_string(),
+            valid_from: from.map(day),
+            valid_until: until.map(day),
+        }
+    }
+
+    fn world(assignments: Vec<ResourcePolicyAssignment>) -> DigitalResourceWorldResponse {
+        DigitalResourceWorldResponse {
+            networks: vec![ResourceNetwork {
+                id: "net-1".to_string(),
+                name: "Net".to_string(),
+                classification: "SECRET".to_string(),
+                shielded: false,
+                created_at: None,
+                updated_at: None,
+            }],
+            platforms: vec![],
+            applications: vec![],
+            org_links: vec![],
+            policies: vec![
+                ResourcePolicy {
+                    id: "pol".to_string(),
+                    label: "Baseline".to_string(),
+                    gates: json!([{ "kind": "CLEARANCE" }]),
+                    zone_prereq_id: None,
+                },
+                ResourcePolicy {
+                    id: "pol-role".to_string(),
+                    label: "Role".to_string(),
+                    gates: json!([{ "kind": "REQUIRED_ROLE", "role": "SECURITY_APPROVAL" }]),
+                    zone_prereq_id: None,
+                },
+            ],
+            policy_assignments: assignments,
+            grants: vec![],
+            delegates: vec![],
+        }
+    }
+
+    fn kinds(report: &LintReport) -> Vec<&str> {
+        report.findings.iter().map(|f| f.kind.as_str()).collect()
+    }
+
+    #[test]
+    fn test_coverage_gaps_are_inclusive() {
+        let windows = [
+            (None, Some(day(5))),
+            (Some(day(5)), Some(day(10))),
+            (Some(day(20)), None),
+        ];
+        let gaps = coverage_gaps(&windows, day(1));
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].from, day(10) + Duration::microseconds(1));
+        assert_eq!(gaps[0].until, Some(day(20)));
+        // Past gaps are not reported; an ending window leaves an open gap.
+        assert!(coverage_gaps(&windows, day(21)).is_empty());
+        let gaps = coverage_gaps(&[(None, Some(day(5)))], day(1));
+        assert_eq!(gaps[0].until, None);
+        assert_eq!(coverage_gaps(&[], day(1))[0].from, day(1));
+    }
+
+    #[test]
+    fn test_clean_world_has_no_findings() {
+        let report = lint_world(
+            &world(vec![
+                assignment(1, "pol", None, Some(10)),
+                assignment(2, "pol", Some(11), None),
+            ]),
+            day(12),
+        );
+        assert!(report.findings.is_empty(), "{:?}", report.findings);
+        assert_eq!(report.resources_scanned, 1);
+    }
+
+    #[test]
+    fn test_findings_for_each_kind() {
+        let mut broken = world(vec![
+            assignment(1, "pol", None, Some(10)),
+            assignment(2, "pol-role", Some(8), Some(12)),
+        ]);
+        broken.policies.push(ResourcePolicy {
+            id: "pol-typo".to_string(),
+            label: "Typo".to_string(),
+            gates: json!([{ "kind": "CLEARANCEE" }]),
+            zone_prereq_id: None,
+        });
+        broken.applications.push(ResourceApplication {
+            id: "app-1".to_string(),
+            name: "Orphan".to_string(),
+            platform_id: "plat-gone".to_string(),
+            shielded: false,
+            created_at: None,
+            updated_at: None,
+        });
+        let report = lint_world(&broken, day(1));
+        assert_eq!(
+            kinds(&report),
+            vec![
+                LINT_UNKNOWN_GATE_KIND,
+                LINT_COVERAGE_GAP,
+                LINT_OVERLAPPING_WINDOWS,
+                LINT_UNHELD_REQUIRED_ROLE,
+                LINT_COVERAGE_GAP,
+                LINT_MISSING_PLATFORM,
+            ]
+        );
+        assert_eq!(report.findings[1].resource_id.as_deref(), Some("net-1"));
+        // app-1 has no assignments at all.
+        assert_eq!(report.findings[4].resource_id.as_deref(), Some("app-1"));
+
+        broken.org_links.push(ResourceOrgLink {
+            id: 1,
+            resource_id: "net-1".to_string(),
+            resource_tier: "NETWORK".to_string(),
+            org_id: "ORG".to_string(),
+            role: "SECURITY_APPROVAL".to_string(),
+            valid_from: None,
+            valid_until: None,
+        });
+        assert!(!kinds(&lint_world(&broken, day(1))).contains(&LINT_UNHELD_REQUIRED_ROLE));
+    }
+}
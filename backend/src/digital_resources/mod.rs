@@ -1,15 +1,29 @@
 // Digital-resource domain (Phase 11): networks / platforms / applications,
 // time-versioned policies, person↔resource grants, and the pure gate-chain
-// resolver ported from the TS source of truth.
+// resolver ported from the TS source of truth, also run server-side per person
+// and, in reverse, over every person for one resource; what-if simulation of
+// policy changes on an in-memory copy of the world, and a policy lint.
 
 pub mod handlers;
+pub mod inputs;
+pub mod lint;
 pub mod models;
 pub mod resolver;
+pub mod simulation;
+pub mod who_can_access;
 
 pub fn routes() -> Vec<rocket::Route> {
     routes![
         handlers::get_world,
+        handlers::resolve_access,
+        handlers::get_who_can_access,
+        handlers::simulate_policy,
+        handlers::get_lint,
         handlers::issue_grant,
         handlers::issue_delegate,
+        handlers::get_shielding,
+        handlers::set_shielding,
+        handlers::get_person_org,
+        handlers::set_person_org,
     ]
 }
@@ -20,38 +20,41 @@ use serde::{Deserialize, Serialize};
 
 // --- 8 sqlx domain structs (1:1 with the Plan 01 tables) ---
 
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct ResourceNetwork {
     pub id: String,
     pub name: String,
     pub classification: String,
+    pub shielded: bool,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct ResourcePlatform {
     pub id: String,
     pub name: String,
     pub classification: String,
     pub network_id: String,
+    pub shielded: bool,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
 // Application has NO classification column — derived from the parent platform at
 // resolution time (anti-pattern 2 / RSRC-02).
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct ResourceApplication {
     pub id: String,
     pub name: String,
     pub platform_id: String,
+    pub shielded: bool,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
 }
 
 // SERIAL PK — no natural text id.
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct ResourceOrgLink {
     pub id: i32,
     pub resource_id: String,
@@ -62,7 +65,7 @@ pub struct ResourceOrgLink {
     pub valid_until: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct ResourcePolicy {
     pub id: String,
     pub label: String,
@@ -71,7 +74,7 @@ pub struct ResourcePolicy {
 }
 
 // SERIAL PK.
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct ResourcePolicyAssignment {
     pub id: i32,
     pub resource_id: String,
@@ -81,7 +84,7 @@ pub struct ResourcePolicyAssignment {
     pub valid_until: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct ResourceAccessGrant {
     pub id: String,
     pub person_id: String,
@@ -90,7 +93,7 @@ pub struct ResourceAccessGrant {
     pub valid_until: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct ResourceAccessDelegate {
     pub id: String,
     pub resource_id: String,
@@ -124,9 +127,18 @@ pub struct IssueDelegateRequest {
     pub valid_until: Option<DateTime<Utc>>,
 }
 
+// Full replacement of a node's shielding: the flag and the org ids admitted
+// besides the ASSET_OWNER org.
+#[derive(Debug, Deserialize)]
+pub struct SetShieldingRequest {
+    pub shielded: bool,
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+}
+
 // --- Aggregate response (no FromRow — assembled in the handler) ---
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DigitalResourceWorldResponse {
     pub networks: Vec<ResourceNetwork>,
     pub platforms: Vec<ResourcePlatform>,
@@ -138,6 +150,57 @@ pub struct DigitalResourceWorldResponse {
     pub delegates: Vec<ResourceAccessDelegate>,
 }
 
+// A node's shielding as GET/PUT /resources/<id>/shielding return it.
+#[derive(Debug, Serialize)]
+pub struct ResourceShielding {
+    pub resource_id: String,
+    pub shielded: bool,
+    pub allowlist: Vec<String>,
+}
+
+// The resource organization a person is evaluated as; `org_id` is None when
+// they have none.
+#[derive(Debug, Serialize)]
+pub struct PersonResourceOrg {
+    pub person_id: i32,
+    pub org_id: Option<String>,
+}
+
+// Set (or, with null, clear) a person's resource organization.
+#[derive(Debug, Deserialize)]
+pub struct SetPersonResourceOrgRequest {
+    pub org_id: Option<String>,
+}
+
+// A subject the reverse query admits (AccessRow in auditlog.ts).
+#[derive(Debug, Serialize)]
+pub struct WhoCanAccessRow {
+    pub person_id: i32,
+    pub name: String,
+    pub decision: ResourceAccessResult,
+}
+
+// A subject denied by exactly one gate, which `failed_gate` names.
+#[derive(Debug, Serialize)]
+pub struct NearMissRow {
+    pub person_id: i32,
+    pub name: String,
+    pub failed_gate: String,
+    pub failed_reason: Option<String>,
+    pub decision: ResourceAccessResult,
+}
+
+// GET /resources/<id>/who-can-access. `candidates` counts the persons
+// evaluated; nobody is listed when no policy covers `at`.
+#[derive(Debug, Serialize)]
+pub struct WhoCanAccessResponse {
+    pub resource_id: String,
+    pub at: DateTime<Utc>,
+    pub candidates: usize,
+    pub allowed: Vec<WhoCanAccessRow>,
+    pub near_misses: Vec<NearMissRow>,
+}
+
 // --- GateDescriptor: tagged union mirroring TS, fail-closed Unknown arm ---
 //
 // Mirrors frontend/src/demo/lib/model.ts `GateDescriptor`. Baseline kinds carry
@@ -192,3 +255,88 @@ pub struct ResourceAccessResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reason: Option<String>,
 }
+
+// --- What-if simulation (POST /simulations) ---
+
+// A policy's proposed gate list, replacing the stored one.
+#[derive(Debug, Deserialize)]
+pub struct PolicyGatesChange {
+    pub policy_id: String,
+    pub gates: Vec<GateDescriptor>,
+}
+
+// A network's or platform's proposed classification. Applications take theirs
+// from the host platform and cannot be changed directly.
+#[derive(Debug, Deserialize)]
+pub struct ClassificationChange {
+    pub resource_id: String,
+    pub classification: String,
+}
+
+// Proposed modifications, applied to an in-memory copy of the world. `at`
+// defaults to now; `org_id`, when given, is the resource organization every
+// subject is evaluated as instead of their own.
+#[derive(Debug, Deserialize)]
+pub struct SimulatePolicyRequest {
+    pub at: Option<DateTime<Utc>>,
+    pub org_id: Option<String>,
+    #[serde(default)]
+    pub policy_gates: Vec<PolicyGatesChange>,
+    #[serde(default)]
+    pub removed_grants: Vec<String>,
+    #[serde(default)]
+    pub removed_org_links: Vec<i32>,
+    #[serde(default)]
+    pub classification_changes: Vec<ClassificationChange>,
+}
+
+// One subject whose decision on one node flips under the modifications.
+#[derive(Debug, Serialize)]
+pub struct AccessChange {
+    pub resource_id: String,
+    pub person_id: i32,
+    pub name: String,
+    pub before: ResourceAccessResult,
+    pub after: ResourceAccessResult,
+}
+
+// Who gains and who loses access. `resources` lists the nodes re-resolved;
+// `decisions` counts the subject-node pairs evaluated on each side.
+#[derive(Debug, Serialize)]
+pub struct SimulationResponse {
+    pub at: DateTime<Utc>,
+    pub resources: Vec<String>,
+    pub decisions: usize,
+    pub gained: Vec<AccessChange>,
+    pub lost: Vec<AccessChange>,
+}
+
+// --- Policy lint (GET /lint) ---
+
+// An uncovered stretch: `from` is the first instant with no active policy,
+// `until` the first covered one again (null: never).
+#[derive(Debug, Serialize)]
+pub struct CoverageGap {
+    pub from: DateTime<Utc>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+// One lint finding; `kind` is one of the LINT_* constants in lint.rs. `gap`
+// is set on COVERAGE_GAP only.
+#[derive(Debug, Serialize)]
+pub struct LintFinding {
+    pub kind: String,
+    pub resource_id: Option<String>,
+    pub policy_id: Option<String>,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gap: Option<CoverageGap>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LintReport {
+    pub at: DateTime<Utc>,
+    pub resources_scanned: usize,
+    pub policies_scanned: usize,
+    pub findings: Vec<LintFinding>,
+}
@@ -3,7 +3,9 @@
 // Ported byte-for-byte from the TS source of truth in
 // frontend/src/demo/lib/model.ts (resolveResourceAccess ~L1084,
 // canIssueResourceGrant ~L1163, isWindowActive ~L822, selectActivePolicy,
-// effectiveClassification, CLEARANCE_RANK ~L15). This module is PURE:
+// effectiveClassification, CLEARANCE_RANK ~L15), plus the directional
+// shielding gate from obligations.ts (evaluateResourceAccess). This module is
+// PURE:
 //   - no Rocket imports, no &State<PgPool>
 //   - every time-dependent fn takes an explicit `now: DateTime<Utc>`
 //   - NO Utc::now()/chrono::Utc::now() anywhere (determinism — T-11-07)
@@ -27,6 +29,7 @@ use chrono::{DateTime, Utc};
 use super::models::{
     GateDescriptor, PolicyVersion, ResourceAccessGrant, ResourceAccessResult, ResourceGateResult,
 };
+use crate::abac::evaluator::AUTHORIZATION_AUTHORIZED;
 
 // --- Plain (non-DB) input shapes the resolver operates on ---
 //
@@ -50,7 +53,8 @@ pub struct ResolverOrgLink {
 // A resource node as the resolver sees it. `classification` is the node's own
 // stored classification (None for APPLICATION — derived from the parent
 // platform). `parent_id` is network_id for PLATFORM, platform_id for
-// APPLICATION, None for NETWORK.
+// APPLICATION, None for NETWORK. `allowlist` holds the org ids a shielded
+// node admits besides its owner.
 #[derive(Debug, Clone)]
 pub struct ResolverResource {
     pub id: String,
@@ -59,6 +63,8 @@ pub struct ResolverResource {
     pub parent_id: Option<String>,
     pub org_links: Vec<ResolverOrgLink>,
     pub policy_assignments: Vec<ResolverPolicyAssignment>,
+    pub shielded: bool,
+    pub allowlist: Vec<String>,
 }
 
 // The active-policy selector reads the window from the assignment and the gate
@@ -86,7 +92,8 @@ pub struct ResolverPlatform {
 // --- Helpers (mirror the TS functions of the same name) ---
 
 // CLEARANCE_RANK (model.ts:15-21). RESTRICTED is rank 1; unknown = -1 (fail closed).
-fn clearance_rank(c: &str) -> i32 {
+// Public so the classic access grants rank clearances the same way.
+pub fn clearance_rank(c: &str) -> i32 {
     match c {
         "UNCLASSIFIED" => 0,
         "RESTRICTED" => 1,
@@ -256,6 +263,37 @@ fn evaluate_required_role_gate(
     }
 }
 
+// Directional shielding (evaluateResourceAccess in obligations.ts), for
+// shielded nodes only: pass iff the subject's org is the owner (an active
+// ASSET_OWNER org_link) or on the node's allowlist. An empty org id never
+// passes.
+fn evaluate_shielding_gate(
+    subject_org_id: &str,
+    resource: &ResolverResource,
+    now: DateTime<Utc>,
+) -> ResourceGateResult {
+    let known = !subject_org_id.is_empty();
+    let is_owner = known
+        && active_org_links_for_role(&resource.org_links, "ASSET_OWNER", now)
+            .iter()
+            .any(|l| l.org_id == subject_org_id);
+    let is_allowlisted = known && resource.allowlist.iter().any(|o| o == subject_org_id);
+    ResourceGateResult {
+        kind: "SHIELDING".to_string(),
+        pass: is_owner || is_allowlisted,
+        reason: Some(
+            if is_owner {
+                "SHIELDING_OWNER"
+            } else if is_allowlisted {
+                "SHIELDING_ALLOWLISTED"
+            } else {
+                "SHIELDED_NOT_ALLOWLISTED"
+            }
+            .to_string(),
+        ),
+    }
+}
+
 // Gate dispatcher (evaluateGate). The Unknown arm FAILS CLOSED — never a silent
 // ALLOW (T-11-04).
 fn evaluate_gate(
@@ -292,7 +330,8 @@ fn evaluate_gate(
 // resolveResourceAccess (model.ts:1084). Pure, explicit `now`.
 //   1. select_active_policy(now) — None => fail-closed NO_ACTIVE_POLICY DENY.
 //   2. effective_classification once (single-hop for Applications).
-//   3. Loop policy.gates IN LIST ORDER; allow = AND of every gate.pass.
+//   3. Loop policy.gates IN LIST ORDER, after the SHIELDING gate when the node
+//      is shielded; allow = AND of every gate.pass.
 //   5. policy_version = the selected assignment's window.
 #[allow(clippy::too_many_arguments)]
 pub fn resolve_resource_access(
@@ -325,11 +364,13 @@ pub fn resolve_resource_access(
     let effective_class = effective_classification(resource, all_platforms).unwrap_or_default();
 
     // Step 3: evaluate gates in list order; allow = AND of all gate pass values.
-    let gates: Vec<ResourceGateResult> = assignment
-        .policy
-        .gates
-        .iter()
-        .map(|gate| {
+    // A shielded node's SHIELDING gate comes first, whatever the policy says.
+    let shielding = resource
+        .shielded
+        .then(|| evaluate_shielding_gate(subject_org_id, resource, now));
+    let gates: Vec<ResourceGateResult> = shielding
+        .into_iter()
+        .chain(assignment.policy.gates.iter().map(|gate| {
             evaluate_gate(
                 gate,
                 subject,
@@ -340,7 +381,7 @@ pub fn resolve_resource_access(
                 all_grants,
                 now,
             )
-        })
+        }))
         .collect();
     let allow = gates.iter().all(|g| g.pass);
 
@@ -363,6 +404,42 @@ pub fn resolve_resource_access(
     }
 }
 
+// Deny overrides for the server-side decision (the TS resolver takes no
+// subject flags). A revoked or held subject is denied whatever the gates say;
+// the gate trace is kept and `reason` names the override, REVOKED first.
+pub fn apply_deny_overrides(
+    mut result: ResourceAccessResult,
+    revoked: bool,
+    security_hold: bool,
+) -> ResourceAccessResult {
+    let reason = if revoked {
+        "SUBJECT_REVOKED"
+    } else if security_hold {
+        "SECURITY_HOLD"
+    } else {
+        return result;
+    };
+    result.allow = false;
+    result.reason = Some(reason.to_string());
+    result
+}
+
+// The managerial "Authorization valid" rule for the server-side decision
+// (evaluateWithAuth in auditlog.ts): a subject whose authorization on record
+// is not AUTHORIZED is denied. An earlier deny reason is kept.
+pub fn apply_authorization(
+    mut result: ResourceAccessResult,
+    authorization: Option<&str>,
+) -> ResourceAccessResult {
+    if authorization.is_some_and(|status| status != AUTHORIZATION_AUTHORIZED) {
+        result.allow = false;
+        result
+            .reason
+            .get_or_insert_with(|| "AUTHORIZATION_NOT_VALID".to_string());
+    }
+    result
+}
+
 // canIssueResourceGrant (model.ts:1163). True iff the actor org holds an active
 // ADMIN org_link on the resource OR an active matching ORG delegate. Pure.
 //
@@ -0,0 +1,498 @@
+// What-if policy simulation.
+//
+// Proposed modifications (a policy's gate list, removed grants and org links,
+// a node's classification) are applied to an in-memory copy of the world
+// GET /world returns. Only the nodes a modification can reach are re-resolved
+// (a policy's assigned nodes; a grant's node and its children, for its holder
+// only; a link's node; a platform's applications with the platform), on both
+// copies, with the GET /access decision; the subjects whose decision flips
+// are reported. Nothing is written.
+// This module is PURE: no Rocket, no PgPool.
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use chrono::{DateTime, Utc};
+
+use super::inputs::parse_gates;
+use super::models::{
+    AccessChange, DigitalResourceWorldResponse, ResourceAccessGrant, SimulatePolicyRequest,
+    SimulationResponse,
+};
+use super::resolver::{
+    clearance_rank, ResolverOrgLink, ResolverPlatform, ResolverPolicy, ResolverPolicyAssignment,
+    ResolverResource, TIER_APPLICATION, TIER_NETWORK, TIER_PLATFORM,
+};
+use super::who_can_access::{decide, group_by_subject, CandidateSubject};
+
+#[derive(Debug, PartialEq)]
+pub enum ModificationError {
+    /// A policy, grant, org link or node named by the request does not exist.
+    NotFound,
+    /// An unknown classification, or one set on an application.
+    Invalid,
+}
+
+/// Which subjects a modification can affect on one node.
+#[derive(Debug, PartialEq)]
+enum Scope {
+    All,
+    Subjects(BTreeSet<String>),
+}
+
+/// The modified copy and the nodes to re-resolve.
+#[derive(Debug)]
+pub struct ModifiedWorld {
+    pub world: DigitalResourceWorldResponse,
+    affected: BTreeMap<String, Scope>,
+}
+
+fn affect_all(affected: &mut BTreeMap<String, Scope>, resource_id: &str) {
+    affected.insert(resource_id.to_string(), Scope::All);
+}
+
+fn affect_subject(affected: &mut BTreeMap<String, Scope>, resource_id: &str, subject: &str) {
+    let scope = affected
+        .entry(resource_id.to_string())
+        .or_insert_with(|| Scope::Subjects(BTreeSet::new()));
+    if let Scope::Subjects(subjects) = scope {
+        subjects.insert(subject.to_string());
+    }
+}
+
+/// Platforms of a network and applications of a platform.
+fn children(world: &DigitalResourceWorldResponse, resource_id: &str) -> Vec<String> {
+    let platforms = world
+        .platforms
+        .iter()
+        .filter(|p| p.network_id == resource_id)
+        .map(|p| p.id.clone());
+    let applications = world
+        .applications
+        .iter()
+        .filter(|a| a.platform_id == resource_id)
+        .map(|a| a.id.clone());
+    platforms.chain(applications).collect()
+}
+
+/// Apply the request's modifications to a copy of `world`.
+pub fn apply_modifications(
+    world: &DigitalResourceWorldResponse,
+    request: &SimulatePolicyRequest,
+) -> Result<ModifiedWorld, ModificationError> {
+    let mut modified = world.clone();
+    let mut affected = BTreeMap::new();
+
+    for change in &request.policy_gates {
+        let policy = modified
+            .policies
+            .iter_mut()
+            .find(|p| p.id == change.policy_id)
+            .ok_or(ModificationError::NotFound)?;
+        policy.gates =
+            serde_json::to_value(&change.gates).map_err(|_| ModificationError::Invalid)?;
+        for assignment in modified
+            .policy_assignments
+            .iter()
+            .filter(|a| a.policy_id == change.policy_id)
+        {
+            affect_all(&mut affected, &assignment.resource_id);
+        }
+    }
+
+    for grant_id in &request.removed_grants {
+        let index = modified
+            .grants
+            .iter()
+            .position(|g| &g.id == grant_id)
+            .ok_or(ModificationError::NotFound)?;
+        let grant = modified.grants.remove(index);
+        affect_subject(&mut affected, &grant.resource_id, &grant.person_id);
+        for child in children(world, &grant.resource_id) {
+            affect_subject(&mut affected, &child, &grant.person_id);
+        }
+    }
+
+    for link_id in &request.removed_org_links {
+        let index = modified
+            .org_links
+            .iter()
+            .position(|l| l.id == *link_id)
+            .ok_or(ModificationError::NotFound)?;
+        let link = modified.org_links.remove(index);
+        affect_all(&mut affected, &link.resource_id);
+    }
+
+    for change in &request.classification_changes {
+        if clearance_rank(&change.classification) < 0 {
+            return Err(ModificationError::Invalid);
+        }
+        if let Some(network) = modified
+            .networks
+            .iter_mut()
+            .find(|n| n.id == change.resource_id)
+        {
+            network.classification = change.classification.clone();
+            affect_all(&mut affected, &change.resource_id);
+        } else if let Some(platform) = modified
+            .platforms
+            .iter_mut()
+            .find(|p| p.id == change.resource_id)
+        {
+            platform.classification = change.classification.clone();
+            affect_all(&mut affected, &change.resource_id);
+            for application in children(world, &change.resource_id) {
+                affect_all(&mut affected, &application);
+            }
+        } else if world
+            .applications
+            .iter()
+            .any(|a| a.id == change.resource_id)
+        {
+            return Err(ModificationError::Invalid);
+        } else {
+            return Err(ModificationError::NotFound);
+        }
+    }
+
+    Ok(ModifiedWorld {
+        world: modified,
+        affected,
+    })
+}
+
+/// The resolver's view of one node, assembled from the world rows the same
+/// way load_resolver_resource assembles it from the tables.
+pub fn resolver_resource(
+    world: &DigitalResourceWorldResponse,
+    allowlists: &HashMap<String, Vec<String>>,
+    resource_id: &str,
+) -> Option<ResolverResource> {
+    let (tier, classification, parent_id, shielded) =
+        if let Some(n) = world.networks.iter().find(|n| n.id == resource_id) {
+            (
+                TIER_NETWORK,
+                Some(n.classification.clone()),
+                None,
+                n.shielded,
+            )
+        } else if let Some(p) = world.platforms.iter().find(|p| p.id == resource_id) {
+            (
+                TIER_PLATFORM,
+                Some(p.classification.clone()),
+                Some(p.network_id.clone()),
+                p.shielded,
+            )
+        } else {
+            let a = world.applications.iter().find(|a| a.id == resource_id)?;
+            (
+                TIER_APPLICATION,
+                None,
+                Some(a.platform_id.clone()),
+                a.shielded,
+            )
+        };
+
+    let org_links = world
+        .org_links
+        .iter()
+        .filter(|l| l.resource_id == resource_id)
+        .map(|l| ResolverOrgLink {
+            org_id: l.org_id.clone(),
+            role: l.role.clone(),
+            valid_from: l.valid_from,
+            valid_until: l.valid_until,
+        })
+        .collect();
+    let policy_assignments = world
+        .policy_assignments
+        .iter()
+        .filter(|a| a.resource_id == resource_id)
+        .filter_map(|a| {
+            let policy = world.policies.iter().find(|p| p.id == a.policy_id)?;
+            Some(ResolverPolicyAssignment {
+                policy: ResolverPolicy {
+                    gates: parse_gates(policy.gates.clone()),
+                    zone_prereq_id: policy.zone_prereq_id.clone(),
+                },
+                valid_from: a.valid_from,
+                valid_until: a.valid_until,
+            })
+        })
+        .collect();
+
+    Some(ResolverResource {
+        id: resource_id.to_string(),
+        tier: tier.to_string(),
+        classification,
+        parent_id,
+        org_links,
+        policy_assignments,
+        shielded,
+        allowlist: allowlists.get(resource_id).cloned().unwrap_or_default(),
+    })
+}
+
+pub fn resolver_platforms(world: &DigitalResourceWorldResponse) -> Vec<ResolverPlatform> {
+    world
+        .platforms
+        .iter()
+        .map(|p| ResolverPlatform {
+            id: p.id.clone(),
+            classification: p.classification.clone(),
+        })
+        .collect()
+}
+
+/// One side of the simulation for one node: its resolver input and the
+/// grants on it and its parent, keyed by subject.
+struct Side {
+    resource: ResolverResource,
+    grants: HashMap<String, Vec<ResourceAccessGrant>>,
+}
+
+fn side(
+    world: &DigitalResourceWorldResponse,
+    allowlists: &HashMap<String, Vec<String>>,
+    resource_id: &str,
+) -> Option<Side> {
+    let resource = resolver_resource(world, allowlists, resource_id)?;
+    let grants = group_by_subject(
+        world
+            .grants
+            .iter()
+            .filter(|g| {
+                g.resource_id == resource.id || Some(&g.resource_id) == resource.parent_id.as_ref()
+            })
+            .cloned(),
+    );
+    Some(Side { resource, grants })
+}
+
+/// Re-resolve the affected nodes on both copies and report the flips, in node
+/// then candidate order.
+pub fn simulate(
+    before: &DigitalResourceWorldResponse,
+    after: &ModifiedWorld,
+    allowlists: &HashMap<String, Vec<String>>,
+    candidates: &[CandidateSubject],
+    at: DateTime<Utc>,
+) -> SimulationResponse {
+    let platforms_before = resolver_platforms(before);
+    let platforms_after = resolver_platforms(&after.world);
+    let mut response = SimulationResponse {
+        at,
+        resources: Vec::new(),
+        decisions: 0,
+        gained: Vec::new(),
+        lost: Vec::new(),
+    };
+
+    for (resource_id, scope) in &after.affected {
+        let (Some(old), Some(new)) = (
+            side(before, allowlists, resource_id),
+            side(&after.world, allowlists, resource_id),
+        ) else {
+            continue;
+        };
+        response.resources.push(resource_id.clone());
+        for candidate in candidates {
+            if let Scope::Subjects(subjects) = scope {
+                if !subjects.contains(&candidate.person_id.to_string()) {
+                    continue;
+                }
+            }
+            let was = decide(candidate, &old.resource, &platforms_before, &old.grants, at);
+            let now = decide(candidate, &new.resource, &platforms_after, &new.grants, at);
+            response.decisions += 1;
+            if was.allow == now.allow {
+                continue;
+            }
+            let change = AccessChange {
+                resource_id: resource_id.clone(),
+                person_id: candidate.person_id,
+                name: candidate.name.clone(),
+                before: was,
+                after: now,
+            };
+            if change.after.allow {
+                response.gained.push(change);
+            } else {
+                response.lost.push(change);
+            }
+        }
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abac::evaluator::SubjectFlags;
+    use crate::digital_resources::models::{
+        ClassificationChange, GateDescriptor, PolicyGatesChange, ResourceApplication,
+        ResourceNetwork, ResourcePlatform, ResourcePolicy, ResourcePolicyAssignment,
+    };
+    use serde_json::json;
+
+    // net-1 (SECRET) > plat-1 (SECRET) > app-1; baseline policy on all three.
+    // Person 1 holds grants on net-1 and plat-1, person 2 on net-1 only.
+    fn world() -> DigitalResourceWorldResponse {
+        let assignment = |id: i32, resource_id: &str, tier: &str| ResourcePolicyAssignment {
+            id,
+            resource_id: resource_id.to_string(),
+            resource_tier: tier.to_string(),
+            policy_id: "pol".to_string(),
+            valid_from: None,
+            valid_until: None,
+        };
+        let grant = |id: &str, person: &str, resource_id: &str| ResourceAccessGrant {
+            id: id.to_string(),
+            person_id: person.to_string(),
+            resource_id: resource_id.to_string(),
+            valid_from: None,
+            valid_until: None,
+        };
+        DigitalResourceWorldResponse {
+            networks: vec![ResourceNetwork {
+                id: "net-1".to_string(),
+                name: "Net".to_string(),
+                classification: "SECRET".to_string(),
+                shielded: false,
+                created_at: None,
+                updated_at: None,
+            }],
+            platforms: vec![ResourcePlatform {
+                id: "plat-1".to_string(),
+                name: "Plat".to_string(),
+                classification: "SECRET".to_string(),
+                network_id: "net-1".to_string(),
+                shielded: false,
+                created_at: None,
+                updated_at: None,
+            }],
+            applications: vec![ResourceApplication {
+                id: "app-1".to_string(),
+                name: "App".to_string(),
+                platform_id: "plat-1".to_string(),
+                shielded: false,
+                created_at: None,
+                updated_at: None,
+            }],
+            org_links: vec![],
+            policies: vec![ResourcePolicy {
+                id: "pol".to_string(),
+                label: "Baseline".to_string(),
+                gates: json!([{ "kind": "CLEARANCE" }, { "kind": "OWN_TIER_GRANT" }]),
+                zone_prereq_id: None,
+            }],
+            policy_assignments: vec![
+                assignment(1, "net-1", TIER_NETWORK),
+                assignment(2, "plat-1", TIER_PLATFORM),
+            ],
+            grants: vec![
+                grant("g-1", "1", "net-1"),
+                grant("g-2", "1", "plat-1"),
+                grant("g-3", "2", "net-1"),
+            ],
+            delegates: vec![],
+        }
+    }
+
+    fn candidates() -> Vec<CandidateSubject> {
+        [1, 2]
+            .into_iter()
+            .map(|person_id| CandidateSubject {
+                person_id,
+                name: format!("Person {}", person_id),
+                clearance: Some("SECRET".to_string()),
+                flags: SubjectFlags::default(),
+                authorization: None,
+                org_id: None,
+            })
+            .collect()
+    }
+
+    fn request() -> SimulatePolicyRequest {
+        SimulatePolicyRequest {
+            at: None,
+            org_id: None,
+            policy_gates: vec![],
+            removed_grants: vec![],
+            removed_org_links: vec![],
+            classification_changes: vec![],
+        }
+    }
+
+    fn run(request: &SimulatePolicyRequest) -> SimulationResponse {
+        let before = world();
+        let after = apply_modifications(&before, request).expect("valid modifications");
+        simulate(&before, &after, &HashMap::new(), &candidates(), Utc::now())
+    }
+
+    fn flips(changes: &[AccessChange]) -> Vec<(&str, i32)> {
+        changes
+            .iter()
+            .map(|c| (c.resource_id.as_str(), c.person_id))
+            .collect()
+    }
+
+    #[test]
+    fn test_gate_list_and_classification_changes() {
+        let mut dropped_grant_gate = request();
+        dropped_grant_gate.policy_gates = vec![PolicyGatesChange {
+            policy_id: "pol".to_string(),
+            gates: vec![GateDescriptor::Clearance],
+        }];
+        let result = run(&dropped_grant_gate);
+        assert_eq!(result.resources, vec!["net-1", "plat-1"]);
+        assert_eq!(flips(&result.gained), vec![("plat-1", 2)]);
+        assert!(result.lost.is_empty());
+
+        let mut raised = request();
+        raised.classification_changes = vec![ClassificationChange {
+            resource_id: "net-1".to_string(),
+            classification: "TOP_SECRET".to_string(),
+        }];
+        let result = run(&raised);
+        assert_eq!(result.resources, vec!["net-1"]);
+        assert_eq!(flips(&result.lost), vec![("net-1", 1), ("net-1", 2)]);
+    }
+
+    #[test]
+    fn test_removed_grant_reaches_only_its_holder() {
+        let mut revoked = request();
+        revoked.removed_grants = vec!["g-2".to_string()];
+        let result = run(&revoked);
+        assert_eq!(result.resources, vec!["app-1", "plat-1"]);
+        // app-1 has no policy, so only plat-1 flips, for person 1 alone.
+        assert_eq!(result.decisions, 2);
+        assert_eq!(flips(&result.lost), vec![("plat-1", 1)]);
+    }
+
+    #[test]
+    fn test_invalid_modifications_are_rejected() {
+        let mut unknown = request();
+        unknown.removed_grants = vec!["g-404".to_string()];
+        assert_eq!(
+            apply_modifications(&world(), &unknown).unwrap_err(),
+            ModificationError::NotFound
+        );
+        let mut application = request();
+        application.classification_changes = vec![ClassificationChange {
+            resource_id: "app-1".to_string(),
+            classification: "SECRET".to_string(),
+        }];
+        assert_eq!(
+            apply_modifications(&world(), &application).unwrap_err(),
+            ModificationError::Invalid
+        );
+        let mut bogus = request();
+        bogus.classification_changes = vec![ClassificationChange {
+            resource_id: "net-1".to_string(),
+            classification: "COSMIC".to_string(),
+        }];
+        assert_eq!(
+            apply_modifications(&world(), &bogus).unwrap_err(),
+            ModificationError::Invalid
+        );
+    }
+}
@@ -0,0 +1,234 @@
+// Reverse resolver query: who can access one resource node.
+//
+// The server-side counterpart of whoCanAccess in auditlog.ts. Every candidate
+// goes through the same decision as GET /access (resolve_resource_access,
+// then the deny overrides, then the authorization rule), so the two endpoints
+// cannot disagree. Beyond the TS function, subjects that fail exactly one
+// gate of the trace, with no override denying them, are reported as near
+// misses together with the gate that stopped them.
+// This module is PURE: no Rocket, no PgPool.
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use super::models::{NearMissRow, ResourceAccessGrant, ResourceAccessResult, WhoCanAccessRow};
+use super::resolver::{
+    apply_authorization, apply_deny_overrides, resolve_resource_access, ResolverPlatform,
+    ResolverResource,
+};
+use crate::abac::evaluator::SubjectFlags;
+
+/// A person as the reverse query evaluates them.
+#[derive(Debug, Clone)]
+pub struct CandidateSubject {
+    pub person_id: i32,
+    pub name: String,
+    pub clearance: Option<String>,
+    pub flags: SubjectFlags,
+    pub authorization: Option<String>,
+    pub org_id: Option<String>, // stored resource organization
+}
+
+/// Where a decision leaves the subject.
+#[derive(Debug, PartialEq)]
+pub enum Standing {
+    Allowed,
+    /// Index of the one failing gate in the trace.
+    NearMiss(usize),
+    Denied,
+}
+
+pub fn standing(result: &ResourceAccessResult) -> Standing {
+    if result.allow {
+        return Standing::Allowed;
+    }
+    if result.reason.is_some() {
+        return Standing::Denied;
+    }
+    let mut failing = result.gates.iter().enumerate().filter(|(_, g)| !g.pass);
+    match (failing.next(), failing.next()) {
+        (Some((index, _)), None) => Standing::NearMiss(index),
+        _ => Standing::Denied,
+    }
+}
+
+/// Grants keyed by subject, in the given order.
+pub fn group_by_subject(
+    grants: impl IntoIterator<Item = ResourceAccessGrant>,
+) -> HashMap<String, Vec<ResourceAccessGrant>> {
+    let mut by_subject: HashMap<String, Vec<ResourceAccessGrant>> = HashMap::new();
+    for grant in grants {
+        by_subject
+            .entry(grant.person_id.clone())
+            .or_default()
+            .push(grant);
+    }
+    by_subject
+}
+
+/// The GET /access decision for one candidate, evaluated as their stored
+/// resource organization.
+pub fn decide(
+    candidate: &CandidateSubject,
+    resource: &ResolverResource,
+    platforms: &[ResolverPlatform],
+    grants: &HashMap<String, Vec<ResourceAccessGrant>>,
+    now: DateTime<Utc>,
+) -> ResourceAccessResult {
+    let subject = candidate.person_id.to_string();
+    let result = resolve_resource_access(
+        &subject,
+        candidate.clearance.as_deref().unwrap_or("UNCLASSIFIED"),
+        candidate.org_id.as_deref().unwrap_or_default(),
+        resource,
+        platforms,
+        grants.get(&subject).map(Vec::as_slice).unwrap_or_default(),
+        now,
+    );
+    let result = apply_deny_overrides(
+        result,
+        candidate.flags.revoked,
+        candidate.flags.security_hold,
+    );
+    apply_authorization(result, candidate.authorization.as_deref())
+}
+
+/// Allowed subjects and near misses among `candidates`, in candidate order.
+/// `grants` holds the grants on the node and its parent, keyed by subject.
+pub fn who_can_access(
+    resource: &ResolverResource,
+    platforms: &[ResolverPlatform],
+    grants: &HashMap<String, Vec<ResourceAccessGrant>>,
+    candidates: &[CandidateSubject],
+    now: DateTime<Utc>,
+) -> (Vec<WhoCanAccessRow>, Vec<NearMissRow>) {
+    let mut allowed = Vec::new();
+    let mut near_misses = Vec::new();
+    for candidate in candidates {
+        let decision = decide(candidate, resource, platforms, grants, now);
+        match standing(&decision) {
+            Standing::Allowed => allowed.push(WhoCanAccessRow {
+                person_id: candidate.person_id,
+                name: candidate.name.clone(),
+                decision,
+            }),
+            Standing::NearMiss(index) => near_misses.push(NearMissRow {
+                person_id: candidate.person_id,
+                name: candidate.name.clone(),
+                failed_gate: decision.gates[index].kind.clone(),
+                failed_reason: decision.gates[index].reason.clone(),
+                decision,
+            }),
+            Standing::Denied => {}
+        }
+    }
+    (allowed, near_misses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::digital_resources::models::GateDescriptor;
+    use crate::digital_resources::resolver::{
+        ResolverPolicy, ResolverPolicyAssignment, TIER_NETWORK,
+    };
+
+    fn network() -> ResolverResource {
+        ResolverResource {
+            id: "net-1".to_string(),
+            tier: TIER_NETWORK.to_string(),
+            classification: Some("SECRET".to_string()),
+            parent_id: None,
+            org_links: vec![],
+            policy_assignments: vec![ResolverPolicyAssignment {
+                policy: ResolverPolicy {
+                    gates: vec![GateDescriptor::Clearance, GateDescriptor::OwnTierGrant],
+                    zone_prereq_id: None,
+                },
+                valid_from: None,
+                valid_until: None,
+            }],
+            shielded: false,
+            allowlist: vec![],
+        }
+    }
+
+    fn candidate(person_id: i32, clearance: Option<&str>) -> CandidateSubject {
+        CandidateSubject {
+            person_id,
+            name: format!("Person {}", person_id),
+            clearance: clearance.map(str::to_string),
+            flags: SubjectFlags::default(),
+            authorization: None,
+            org_id: None,
+        }
+    }
+
+    fn grant(person_id: i32) -> (String, Vec<ResourceAccessGrant>) {
+        let subject = person_id.to_string();
+        let grant = ResourceAccessGrant {
+            id: format!("g-{}", person_id),
+            person_id: subject.clone(),
+            resource_id: "net-1".to_string(),
+            valid_from: None,
+            valid_until: None,
+        };
+        (subject, vec![grant])
+    }
+
+    #[test]
+    fn test_allowed_near_miss_and_denied() {
+        let grants: HashMap<_, _> = [grant(1), grant(2), grant(4)].into_iter().collect();
+        let mut held = candidate(4, Some("SECRET"));
+        held.flags.security_hold = true;
+        let candidates = vec![
+            candidate(1, Some("TOP_SECRET")),   // allowed
+            candidate(2, Some("CONFIDENTIAL")), // fails the clearance only
+            candidate(3, Some("SECRET")),       // fails the grant only
+            held,                               // denied by the override
+            candidate(5, None),                 // fails both
+        ];
+
+        let (allowed, near_misses) =
+            who_can_access(&network(), &[], &grants, &candidates, Utc::now());
+        let allowed: Vec<i32> = allowed.iter().map(|r| r.person_id).collect();
+        assert_eq!(allowed, vec![1]);
+        let misses: Vec<(i32, &str)> = near_misses
+            .iter()
+            .map(|r| (r.person_id, r.failed_gate.as_str()))
+            .collect();
+        assert_eq!(misses, vec![(2, "CLEARANCE"), (3, "OWN_TIER_GRANT")]);
+    }
+
+    #[test]
+    fn test_no_active_policy_has_no_near_misses() {
+        let mut resource = network();
+        resource.policy_assignments.clear();
+        let (allowed, near_misses) = who_can_access(
+            &resource,
+            &[],
+            &HashMap::new(),
+            &[candidate(1, Some("TOP_SECRET"))],
+            Utc::now(),
+        );
+        assert!(allowed.is_empty() && near_misses.is_empty());
+    }
+
+    #[test]
+    fn test_shielding_admits_the_stored_org_only() {
+        let mut resource = network();
+        resource.shielded = true;
+        resource.allowlist = vec!["PARTNER".to_string()];
+        let grants: HashMap<_, _> = [grant(1), grant(2)].into_iter().collect();
+        let mut partner = candidate(1, Some("TOP_SECRET"));
+        partner.org_id = Some("PARTNER".to_string());
+        let mut other = candidate(2, Some("TOP_SECRET"));
+        other.org_id = Some("OTHER".to_string());
+        let (allowed, near_misses) =
+            who_can_access(&resource, &[], &grants, &[partner, other], Utc::now());
+        let allowed: Vec<i32> = allowed.iter().map(|r| r.person_id).collect();
+        assert_eq!(allowed, vec![1]);
+        assert_eq!(near_misses[0].person_id, 2);
+        assert_eq!(near_misses[0].failed_gate, "SHIELDING");
+    }
+}
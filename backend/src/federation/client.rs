@@ -0,0 +1,171 @@
+// Federation configuration, inbound peer key and outbound envelope sending.
+//
+// An instance takes part in the federation when FEDERATION_ENTITY_ID (its
+// entity id towards peers) and FEDERATION_KEY (its own key, sent as
+// X-Federation-Key on every envelope it sends) are set. Each peer registers
+// that key against this instance's entity id, and this instance registers
+// each peer's key likewise (federation_peers.key_hash), so an envelope is
+// only accepted from the peer it claims to come from. FEDERATION_HUB_URL is
+// the base URL of the hub instance that keeps the pointers; an instance
+// without one is its own hub and keeps its pointers locally. Envelopes go to
+// <base_url>/api/federation/envelopes and the reply envelope, if any, comes
+// back in the response body.
+use std::time::Duration;
+
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome, Request};
+use sha2::{Digest, Sha256};
+
+use super::contract::Envelope;
+use crate::shared::response::ApiResponse;
+
+pub const FEDERATION_KEY_HEADER: &str = "X-Federation-Key";
+
+/// Seconds to wait for a peer before giving up on an envelope.
+const PEER_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FederationConfig {
+    pub entity_id: Option<String>,
+    pub hub_url: Option<String>,
+    pub key: Option<String>,
+}
+
+fn non_blank(raw: Option<String>) -> Option<String> {
+    raw.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+impl FederationConfig {
+    /// Config from the raw FEDERATION_ENTITY_ID, FEDERATION_HUB_URL and
+    /// FEDERATION_KEY values; blank values count as unset and a trailing
+    /// slash on the hub URL is dropped.
+    pub fn from_values(
+        entity_id: Option<String>,
+        hub_url: Option<String>,
+        key: Option<String>,
+    ) -> Self {
+        FederationConfig {
+            entity_id: non_blank(entity_id),
+            hub_url: non_blank(hub_url).map(|u| u.trim_end_matches('/').to_string()),
+            key: non_blank(key),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::from_values(
+            std::env::var("FEDERATION_ENTITY_ID").ok(),
+            std::env::var("FEDERATION_HUB_URL").ok(),
+            std::env::var("FEDERATION_KEY").ok(),
+        )
+    }
+
+    /// This instance's entity id and key; 503 when the instance is not part
+    /// of a federation.
+    pub fn identity(&self) -> Result<(&str, &str), Status> {
+        match (&self.entity_id, &self.key) {
+            (Some(entity), Some(key)) => Ok((entity, key)),
+            _ => Err(Status::ServiceUnavailable),
+        }
+    }
+}
+
+/// Hex SHA-256 of a peer key, as stored in federation_peers.key_hash.
+pub fn hash_key(key: &str) -> String {
+    Sha256::digest(key.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Whether `presented` is the key behind `key_hash`. The hashes are compared
+/// in constant time.
+pub fn key_matches(presented: &str, key_hash: &str) -> bool {
+    let presented = hash_key(presented);
+    let (a, b) = (presented.as_bytes(), key_hash.as_bytes());
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// The key presented on the inbound envelope endpoint. Which peer it must
+/// belong to is only known from the envelope, so the handler checks it. 503
+/// when federation is not configured here, 401 for a missing key.
+pub struct FederationPeerKey(pub String);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for FederationPeerKey {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let Some(config) = request.rocket().state::<FederationConfig>() else {
+            return Outcome::Error((Status::InternalServerError, ()));
+        };
+        if config.identity().is_err() {
+            return Outcome::Error((Status::ServiceUnavailable, ()));
+        }
+        match request.headers().get_one(FEDERATION_KEY_HEADER) {
+            Some(presented) if !presented.is_empty() => {
+                Outcome::Success(FederationPeerKey(presented.to_string()))
+            }
+            _ => Outcome::Error((Status::Unauthorized, ())),
+        }
+    }
+}
+
+/// Send `envelope` to the instance at `base_url` and return its reply
+/// envelope. 502 when the peer cannot be reached or refuses the envelope.
+pub async fn send_envelope(
+    base_url: &str,
+    key: &str,
+    envelope: &Envelope,
+) -> Result<Option<Envelope>, Status> {
+    let bad_gateway = |e: reqwest::Error| {
+        eprintln!("Federation peer error ({}): {:?}", base_url, e);
+        Status::BadGateway
+    };
+    let response = reqwest::Client::new()
+        .post(format!(
+            "{}/api/federation/envelopes",
+            base_url.trim_end_matches('/')
+        ))
+        .header(FEDERATION_KEY_HEADER, key)
+        .timeout(Duration::from_secs(PEER_TIMEOUT_SECS))
+        .json(envelope)
+        .send()
+        .await
+        .map_err(bad_gateway)?
+        .error_for_status()
+        .map_err(bad_gateway)?;
+    let body: ApiResponse<Option<Envelope>> = response.json().await.map_err(bad_gateway)?;
+    Ok(body.data.flatten())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_from_values() {
+        let config = FederationConfig::from_values(
+            Some(" JANUS_A ".to_string()),
+            Some("http://hub:8000/".to_string()),
+            Some("shared".to_string()),
+        );
+        assert_eq!(config.entity_id.as_deref(), Some("JANUS_A"));
+        assert_eq!(config.hub_url.as_deref(), Some("http://hub:8000"));
+        assert_eq!(config.identity(), Ok(("JANUS_A", "shared")));
+
+        let unset =
+            FederationConfig::from_values(Some("JANUS_A".to_string()), None, Some(" ".to_string()));
+        assert_eq!(unset.key, None);
+        assert_eq!(unset.identity(), Err(Status::ServiceUnavailable));
+    }
+
+    #[test]
+    fn test_key_matches_only_its_hash() {
+        let stored = hash_key("peer-key");
+        assert_eq!(stored.len(), 64);
+        assert!(key_matches("peer-key", &stored));
+        assert!(!key_matches("peer-kez", &stored));
+        assert!(!key_matches("", &stored));
+        assert!(!key_matches("peer-key", ""));
+    }
+}
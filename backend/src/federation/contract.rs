@@ -0,0 +1,443 @@
+// Pure inter-instance interchange contract.
+//
+// Ported from frontend/src/demo/lib/contract.ts (Envelope, Pointer,
+// buildDiscoverEnvelopes, computeDetailResponse). Instances communicate only
+// through these envelopes; the wire shape keeps the TS field names
+// (subjectId, domainAuth, securityHold) so either side of the contract can be
+// checked against the demo. computeDetailResponse decides under the holder's
+// release policy and forces DENY with a "Record hold" override when the
+// requested record is held or revoked, on top of the requester's own deny
+// overrides (D2-08).
+// This module is PURE: no Rocket, no PgPool.
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::abac::evaluator::{
+    evaluate_with_policy, release_requirement_for, EntityPolicy, Principal, SubjectFlags,
+};
+use crate::abac::models::{AbacDecision, AbacRule, DECISION_ALLOW, DECISION_DENY};
+
+pub const KIND_PUBLISH: &str = "PUBLISH";
+pub const KIND_DISCOVER: &str = "DISCOVER";
+pub const KIND_DISCOVER_RESULT: &str = "DISCOVER_RESULT";
+pub const KIND_REQUEST_DETAIL: &str = "REQUEST_DETAIL";
+pub const KIND_DETAIL_RESPONSE: &str = "DETAIL_RESPONSE";
+
+pub const RULE_RECORD_HOLD: &str = "Record hold";
+
+/// Same shape as the TS `SubjectFlags`; absent flags are not set
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WireFlags {
+    #[serde(default)]
+    pub revoked: bool,
+    #[serde(default)]
+    pub security_hold: bool,
+}
+
+/// Same shape as the TS `Principal`: the requester as its own instance
+/// vouches for it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WirePrincipal {
+    pub entity: String,
+    pub clearance: String,
+    #[serde(default)]
+    pub domain_auth: BTreeMap<String, String>,
+    #[serde(default)]
+    pub compartments: Vec<String>,
+    #[serde(default)]
+    pub flags: WireFlags,
+}
+
+/// Same shape as the TS `Subject` (id, name, unit and the evaluated
+/// attributes): the record a holder releases
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FederatedRecord {
+    pub id: String,
+    pub name: String,
+    pub unit: String,
+    pub clearance: String,
+    #[serde(default)]
+    pub domain_auth: BTreeMap<String, String>,
+    #[serde(default)]
+    pub compartments: Vec<String>,
+    #[serde(default)]
+    pub flags: WireFlags,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pointer {
+    pub holder: String,
+    pub domain: String,
+}
+
+/// Same shape as the TS `Envelope`, tagged by `kind`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Envelope {
+    #[serde(rename_all = "camelCase")]
+    Publish {
+        from: String,
+        subject_id: String,
+        domain: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Discover { from: String, subject_id: String },
+    #[serde(rename_all = "camelCase")]
+    DiscoverResult {
+        to: String,
+        subject_id: String,
+        pointers: Vec<Pointer>,
+    },
+    #[serde(rename_all = "camelCase")]
+    RequestDetail {
+        from: String,
+        to: String,
+        subject_id: String,
+        requester: WirePrincipal,
+    },
+    #[serde(rename_all = "camelCase")]
+    DetailResponse {
+        to: String,
+        subject_id: String,
+        granted: bool,
+        decision: Option<AbacDecision>,
+        record: Option<FederatedRecord>,
+    },
+}
+
+impl Envelope {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Envelope::Publish { .. } => KIND_PUBLISH,
+            Envelope::Discover { .. } => KIND_DISCOVER,
+            Envelope::DiscoverResult { .. } => KIND_DISCOVER_RESULT,
+            Envelope::RequestDetail { .. } => KIND_REQUEST_DETAIL,
+            Envelope::DetailResponse { .. } => KIND_DETAIL_RESPONSE,
+        }
+    }
+}
+
+/// Same shape as the TS `DetailResult`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DetailResult {
+    pub granted: bool,
+    pub decision: Option<AbacDecision>,
+    pub record: Option<FederatedRecord>,
+}
+
+impl From<&WireFlags> for SubjectFlags {
+    fn from(flags: &WireFlags) -> Self {
+        SubjectFlags {
+            revoked: flags.revoked,
+            security_hold: flags.security_hold,
+        }
+    }
+}
+
+impl From<&SubjectFlags> for WireFlags {
+    fn from(flags: &SubjectFlags) -> Self {
+        WireFlags {
+            revoked: flags.revoked,
+            security_hold: flags.security_hold,
+        }
+    }
+}
+
+impl WirePrincipal {
+    pub fn to_principal(&self) -> Principal {
+        Principal {
+            entity: self.entity.clone(),
+            clearance: self.clearance.clone(),
+            domain_auth: self
+                .domain_auth
+                .iter()
+                .map(|(d, t)| (d.clone(), t.clone()))
+                .collect(),
+            compartments: self.compartments.clone(),
+            flags: (&self.flags).into(),
+        }
+    }
+
+    /// The wire form of a principal, under the entity id of the sending
+    /// instance.
+    pub fn from_principal(principal: &Principal, entity: &str) -> Self {
+        WirePrincipal {
+            entity: entity.to_string(),
+            clearance: principal.clearance.clone(),
+            domain_auth: principal.domain_auth.iter().cloned().collect(),
+            compartments: principal.compartments.clone(),
+            flags: (&principal.flags).into(),
+        }
+    }
+}
+
+impl FederatedRecord {
+    fn to_principal(&self) -> Principal {
+        Principal {
+            entity: self.unit.clone(),
+            clearance: self.clearance.clone(),
+            domain_auth: self
+                .domain_auth
+                .iter()
+                .map(|(d, t)| (d.clone(), t.clone()))
+                .collect(),
+            compartments: self.compartments.clone(),
+            flags: (&self.flags).into(),
+        }
+    }
+}
+
+/// The DISCOVER envelope and its DISCOVER_RESULT for `subject_id`, from the
+/// hub's (subject id, holder, domain) pointers (buildDiscoverEnvelopes).
+pub fn build_discover_envelopes(
+    from: &str,
+    subject_id: &str,
+    hub_index: &[(String, String, String)],
+) -> (Envelope, Envelope) {
+    let pointers = hub_index
+        .iter()
+        .filter(|(subject, _, _)| subject == subject_id)
+        .map(|(_, holder, domain)| Pointer {
+            holder: holder.clone(),
+            domain: domain.clone(),
+        })
+        .collect();
+    (
+        Envelope::Discover {
+            from: from.to_string(),
+            subject_id: subject_id.to_string(),
+        },
+        Envelope::DiscoverResult {
+            to: from.to_string(),
+            subject_id: subject_id.to_string(),
+            pointers,
+        },
+    )
+}
+
+/// Whether `requester` may receive `subject`'s record held by `holder`
+/// (computeDetailResponse) under the holder's release `policy`. `agreements`
+/// are the holder's (owner, recipient) pairs; an unknown record is refused
+/// without a decision.
+pub fn compute_detail_response(
+    requester: &Principal,
+    subject: Option<FederatedRecord>,
+    holder: &str,
+    agreements: &[(String, String)],
+    policy: &EntityPolicy,
+) -> DetailResult {
+    let Some(subject) = subject else {
+        return DetailResult {
+            granted: false,
+            decision: None,
+            record: None,
+        };
+    };
+
+    let mut decision = evaluate_with_policy(
+        requester,
+        &release_requirement_for(&subject.to_principal(), holder),
+        agreements,
+        policy,
+    );
+    if subject.flags.security_hold || subject.flags.revoked {
+        decision.decision = DECISION_DENY.to_string();
+        decision.overrides.push(AbacRule {
+            name: RULE_RECORD_HOLD.to_string(),
+            pass: false,
+            detail: "target record is held/revoked".to_string(),
+        });
+    }
+
+    let granted = decision.decision == DECISION_ALLOW;
+    DetailResult {
+        granted,
+        decision: Some(decision),
+        record: if granted { Some(subject) } else { None },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn record(clearance: &str, flags: WireFlags) -> FederatedRecord {
+        FederatedRecord {
+            id: "S-1".to_string(),
+            name: "Kari Nordmann".to_string(),
+            unit: "JANUS_A".to_string(),
+            clearance: clearance.to_string(),
+            domain_auth: BTreeMap::new(),
+            compartments: vec!["NATO".to_string()],
+            flags,
+        }
+    }
+
+    fn requester(entity: &str) -> Principal {
+        Principal {
+            entity: entity.to_string(),
+            clearance: "TOP_SECRET".to_string(),
+            compartments: vec!["NATO".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_detail_released_only_under_agreement() {
+        let agreements = vec![("JANUS_A".to_string(), "JANUS_B".to_string())];
+        let granted = compute_detail_response(
+            &requester("JANUS_B"),
+            Some(record("SECRET", WireFlags::default())),
+            "JANUS_A",
+            &agreements,
+            &EntityPolicy::default(),
+        );
+        assert!(granted.granted);
+        assert_eq!(granted.record.expect("record").name, "Kari Nordmann");
+
+        let refused = compute_detail_response(
+            &requester("JANUS_C"),
+            Some(record("SECRET", WireFlags::default())),
+            "JANUS_A",
+            &agreements,
+            &EntityPolicy::default(),
+        );
+        assert!(!refused.granted);
+        assert!(refused.record.is_none());
+        assert_eq!(
+            refused.decision.expect("decision").failed,
+            vec!["Affiliation"]
+        );
+
+        let unknown = compute_detail_response(
+            &requester("JANUS_B"),
+            None,
+            "JANUS_A",
+            &agreements,
+            &EntityPolicy::default(),
+        );
+        assert_eq!(
+            unknown,
+            DetailResult {
+                granted: false,
+                decision: None,
+                record: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_detail_follows_holder_policy() {
+        let agreements = vec![("JANUS_A".to_string(), "JANUS_B".to_string())];
+        let policy = EntityPolicy {
+            min_clearance_floor: Some("TOP_SECRET".to_string()),
+            ..Default::default()
+        };
+        let mut junior = requester("JANUS_B");
+        junior.clearance = "SECRET".to_string();
+        let refused = compute_detail_response(
+            &junior,
+            Some(record("CONFIDENTIAL", WireFlags::default())),
+            "JANUS_A",
+            &agreements,
+            &policy,
+        );
+        assert!(!refused.granted);
+        assert_eq!(
+            refused.decision.expect("decision").failed,
+            vec!["Clearance floor"]
+        );
+
+        let granted = compute_detail_response(
+            &requester("JANUS_B"),
+            Some(record("CONFIDENTIAL", WireFlags::default())),
+            "JANUS_A",
+            &agreements,
+            &policy,
+        );
+        assert!(granted.granted);
+    }
+
+    #[test]
+    fn test_record_hold_forces_deny() {
+        let agreements = vec![("JANUS_A".to_string(), "JANUS_B".to_string())];
+        let held = compute_detail_response(
+            &requester("JANUS_B"),
+            Some(record(
+                "SECRET",
+                WireFlags {
+                    revoked: false,
+                    security_hold: true,
+                },
+            )),
+            "JANUS_A",
+            &agreements,
+            &EntityPolicy::default(),
+        );
+        assert!(!held.granted);
+        assert!(held.record.is_none());
+        let decision = held.decision.expect("decision");
+        assert_eq!(decision.decision, "DENY");
+        assert!(decision.failed.is_empty());
+        assert_eq!(decision.overrides[0].name, "Record hold");
+        assert_eq!(
+            decision.overrides[0].detail,
+            "target record is held/revoked"
+        );
+    }
+
+    #[test]
+    fn test_envelope_wire_shape() {
+        let (discover, result) = build_discover_envelopes(
+            "JANUS_B",
+            "S-1",
+            &[
+                ("S-1".to_string(), "JANUS_A".to_string(), "DATA".to_string()),
+                ("S-2".to_string(), "JANUS_A".to_string(), "DATA".to_string()),
+            ],
+        );
+        assert_eq!(
+            serde_json::to_value(&discover).expect("json"),
+            json!({ "kind": "DISCOVER", "from": "JANUS_B", "subjectId": "S-1" })
+        );
+        assert_eq!(
+            serde_json::to_value(&result).expect("json"),
+            json!({
+                "kind": "DISCOVER_RESULT",
+                "to": "JANUS_B",
+                "subjectId": "S-1",
+                "pointers": [{ "holder": "JANUS_A", "domain": "DATA" }]
+            })
+        );
+
+        let request: Envelope = serde_json::from_value(json!({
+            "kind": "REQUEST_DETAIL",
+            "from": "JANUS_B",
+            "to": "JANUS_A",
+            "subjectId": "S-1",
+            "requester": {
+                "entity": "JANUS_B",
+                "clearance": "SECRET",
+                "domainAuth": { "DATA": "RESTRICTED" },
+                "compartments": [],
+                "flags": { "securityHold": true }
+            }
+        }))
+        .expect("envelope");
+        assert_eq!(request.kind(), KIND_REQUEST_DETAIL);
+        let Envelope::RequestDetail { requester, .. } = request else {
+            panic!("not a detail request");
+        };
+        let principal = requester.to_principal();
+        assert!(principal.flags.security_hold);
+        assert_eq!(
+            principal.domain_auth,
+            vec![("DATA".to_string(), "RESTRICTED".to_string())]
+        );
+    }
+}
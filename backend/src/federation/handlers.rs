@@ -0,0 +1,797 @@
+// Federation HTTP handlers (mounted at /api/federation)
+//
+// POST /envelopes is the only endpoint peers call, each under its own key
+// (see client): as hub it stores PUBLISH pointers and answers DISCOVER with
+// DISCOVER_RESULT; as holder it answers REQUEST_DETAIL with DETAIL_RESPONSE,
+// releasing the record only if compute_detail_response allows it for the
+// remote requester. A release needs the peer's release agreement flag and a
+// sharing agreement in force from the organization of the person held to
+// the peer's organization, and follows that organization's release policy.
+// The other endpoints are for local users: peers and publications are
+// managed under federation.manage, discovering holders needs person.read,
+// and requesting details goes out on behalf of the caller, or of another
+// local person under federation.manage.
+// Every envelope sent or received is written to the transcript; releases,
+// refusals and publications are audited.
+use chrono::NaiveDateTime;
+use rocket::serde::json::Json;
+use rocket::{delete, get, http::Status, post, put, State};
+use sqlx::{PgExecutor, PgPool};
+use validator::Validate;
+
+use super::client::{hash_key, key_matches, send_envelope, FederationConfig, FederationPeerKey};
+use super::contract::{
+    build_discover_envelopes, compute_detail_response, DetailResult, Envelope, FederatedRecord,
+    Pointer, WirePrincipal,
+};
+use super::models::{
+    DetailRequest, FederationPeer, FederationPublication, PublishSubjectRequest, SetPeerRequest,
+    TranscriptEntry,
+};
+use crate::abac::evaluator::tiers;
+use crate::abac::principal::load_principal;
+use crate::audit::handlers::{audit, create_audit_log};
+use crate::audit::models::CreateAuditLogRequest;
+use crate::auth::middleware::AuthGuard;
+use crate::decision_log::log::{record_decisions, ruled_decision_record, SOURCE_FEDERATION_DETAIL};
+use crate::release_policies::policy::load_release_policy;
+use crate::shared::rbac::{require_permission, role_has_permission};
+use crate::shared::response::ApiResponse;
+use crate::sharing_agreements::affiliation::{permits, AGREEMENT_COLUMNS};
+use crate::sharing_agreements::models::SharingAgreement;
+
+const PEER_COLUMNS: &str = "entity_id, base_url, release_agreement, organization_id, \
+                            created_by_person_id, created_at, updated_at";
+const PUBLICATION_COLUMNS: &str =
+    "id, person_id, subject_id, domain, published_by_person_id, published_at";
+
+const DEFAULT_TRANSCRIPT_LIMIT: i64 = 100;
+const MAX_TRANSCRIPT_LIMIT: i64 = 500;
+
+fn db_error(e: sqlx::Error) -> Status {
+    eprintln!("Database error: {:?}", e);
+    Status::InternalServerError
+}
+
+async fn record_transcript(db: &PgPool, direction: &str, peer: &str, envelope: &Envelope) {
+    let _ = sqlx::query(
+        "INSERT INTO federation_transcript (direction, peer, kind, envelope) VALUES ($1, $2, $3, $4)",
+    )
+    .bind(direction)
+    .bind(peer)
+    .bind(envelope.kind())
+    .bind(serde_json::to_value(envelope).unwrap_or_default())
+    .execute(db)
+    .await;
+}
+
+/// Send an envelope to `peer` at `base_url`, writing both directions to the
+/// transcript.
+async fn exchange(
+    db: &PgPool,
+    peer: &str,
+    base_url: &str,
+    key: &str,
+    envelope: Envelope,
+) -> Result<Option<Envelope>, Status> {
+    record_transcript(db, "OUT", peer, &envelope).await;
+    let reply = send_envelope(base_url, key, &envelope).await?;
+    if let Some(reply) = &reply {
+        record_transcript(db, "IN", peer, reply).await;
+    }
+    Ok(reply)
+}
+
+async fn fetch_peer(db: &PgPool, entity_id: &str) -> Result<Option<FederationPeer>, Status> {
+    sqlx::query_as::<_, FederationPeer>(&format!(
+        "SELECT {} FROM federation_peers WHERE entity_id = $1",
+        PEER_COLUMNS
+    ))
+    .bind(entity_id)
+    .fetch_optional(db)
+    .await
+    .map_err(db_error)
+}
+
+/// Authenticate the sender of an inbound envelope: 403 unless `from` is a
+/// registered peer, which this instance never is itself; 401 unless
+/// `presented` is the key registered for that peer.
+async fn authenticate_sender(
+    db: &PgPool,
+    own: &str,
+    from: &str,
+    presented: &str,
+) -> Result<(), Status> {
+    if from == own {
+        return Err(Status::Forbidden);
+    }
+    let key_hash: Option<Option<String>> =
+        sqlx::query_scalar("SELECT key_hash FROM federation_peers WHERE entity_id = $1")
+            .bind(from)
+            .fetch_optional(db)
+            .await
+            .map_err(db_error)?;
+    match key_hash {
+        None => Err(Status::Forbidden),
+        Some(Some(key_hash)) if key_matches(presented, &key_hash) => Ok(()),
+        Some(_) => Err(Status::Unauthorized),
+    }
+}
+
+/// Hub side: keep (or refresh) `holder`'s pointer on a subject.
+async fn store_pointer<'e>(
+    executor: impl PgExecutor<'e>,
+    holder: &str,
+    subject_id: &str,
+    domain: &str,
+) -> Result<(), Status> {
+    sqlx::query(
+        "INSERT INTO federation_pointers (subject_id, holder, domain) VALUES ($1, $2, $3) \
+         ON CONFLICT (subject_id, holder, domain) DO UPDATE SET published_at = CURRENT_TIMESTAMP",
+    )
+    .bind(subject_id)
+    .bind(holder)
+    .bind(domain)
+    .execute(executor)
+    .await
+    .map_err(db_error)?;
+    Ok(())
+}
+
+/// Hub side: the (subject id, holder, domain) pointers on a subject.
+async fn load_hub_index(
+    db: &PgPool,
+    subject_id: &str,
+) -> Result<Vec<(String, String, String)>, Status> {
+    sqlx::query_as(
+        "SELECT subject_id, holder, domain FROM federation_pointers \
+         WHERE subject_id = $1 ORDER BY holder, domain",
+    )
+    .bind(subject_id)
+    .fetch_all(db)
+    .await
+    .map_err(db_error)
+}
+
+/// The holder's (owner, recipient) agreement pair with `peer` for a record
+/// at `classification` held for `holder_org`: present only when the peer
+/// has a release agreement and an organization, and a sharing agreement in
+/// force permits `holder_org` sharing with it. A person record belongs to
+/// no authorization domain, so only agreements covering every domain count.
+async fn load_release_agreements(
+    db: &PgPool,
+    own: &str,
+    peer: &str,
+    holder_org: Option<i32>,
+    classification: &str,
+    now: NaiveDateTime,
+) -> Result<Vec<(String, String)>, Status> {
+    let peer_org: Option<Option<i32>> = sqlx::query_scalar(
+        "SELECT organization_id FROM federation_peers WHERE entity_id = $1 AND release_agreement",
+    )
+    .bind(peer)
+    .fetch_optional(db)
+    .await
+    .map_err(db_error)?;
+    let (Some(holder_org), Some(Some(peer_org))) = (holder_org, peer_org) else {
+        return Ok(Vec::new());
+    };
+    let agreements = sqlx::query_as::<_, SharingAgreement>(&format!(
+        "SELECT {} FROM sharing_agreements \
+         WHERE from_organization_id IN ($1, $2) AND to_organization_id IN ($1, $2)",
+        AGREEMENT_COLUMNS
+    ))
+    .bind(holder_org)
+    .bind(peer_org)
+    .fetch_all(db)
+    .await
+    .map_err(db_error)?;
+    if agreements
+        .iter()
+        .any(|a| permits(a, holder_org, peer_org, None, classification, now))
+    {
+        Ok(vec![(own.to_string(), peer.to_string())])
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+/// The record this instance holds on `subject_id`, the local person it is
+/// and the organization holding it for them (their lowest-id one); None when
+/// nothing is published under it or the person is offboarded.
+async fn load_record(
+    db: &PgPool,
+    own: &str,
+    subject_id: &str,
+) -> Result<Option<(i32, Option<i32>, FederatedRecord)>, Status> {
+    let published: Option<(i32, Option<String>, Option<String>)> = sqlx::query_as(
+        "SELECT p.id, p.first_name, p.last_name FROM federation_publications fp \
+         JOIN person p ON p.id = fp.person_id \
+         WHERE fp.subject_id = $1 AND p.deleted_at IS NULL LIMIT 1",
+    )
+    .bind(subject_id)
+    .fetch_optional(db)
+    .await
+    .map_err(db_error)?;
+    let Some((person_id, first_name, last_name)) = published else {
+        return Ok(None);
+    };
+    let Some((principal, holder_org)) = load_principal(db, person_id, 0, &[])
+        .await
+        .map_err(db_error)?
+    else {
+        return Ok(None);
+    };
+    let name = [first_name, last_name]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let wire = WirePrincipal::from_principal(&principal, own);
+    Ok(Some((
+        person_id,
+        holder_org,
+        FederatedRecord {
+            id: subject_id.to_string(),
+            name,
+            unit: own.to_string(),
+            clearance: wire.clearance,
+            domain_auth: wire.domain_auth,
+            compartments: wire.compartments,
+            flags: wire.flags,
+        },
+    )))
+}
+
+/// Receive an envelope from a peer and return the reply envelope, if the
+/// kind has one. Replies (DISCOVER_RESULT, DETAIL_RESPONSE) are never sent
+/// here: 400. 403 when the sender is not a registered peer (this instance
+/// included) or vouches for a requester of another entity, 401 when the key
+/// is not the sender's; 400 for a detail request meant for another instance
+/// or a publication in an unknown domain.
+#[post("/envelopes", data = "<data>")]
+pub async fn receive_envelope(
+    db: &State<PgPool>,
+    config: &State<FederationConfig>,
+    peer_key: FederationPeerKey,
+    data: Json<Envelope>,
+) -> Result<Json<ApiResponse<Option<Envelope>>>, Status> {
+    let (own, _) = config.identity()?;
+    let envelope = data.into_inner();
+
+    let reply = match &envelope {
+        Envelope::Publish {
+            from,
+            subject_id,
+            domain,
+        } => {
+            authenticate_sender(db.inner(), own, from, &peer_key.0).await?;
+            if tiers(domain).is_none() || subject_id.trim().is_empty() || subject_id.len() > 100 {
+                return Err(Status::BadRequest);
+            }
+            record_transcript(db.inner(), "IN", from, &envelope).await;
+            store_pointer(db.inner(), from, subject_id, domain).await?;
+            None
+        }
+        Envelope::Discover { from, subject_id } => {
+            authenticate_sender(db.inner(), own, from, &peer_key.0).await?;
+            let hub_index = load_hub_index(db.inner(), subject_id).await?;
+            let (discover, result) = build_discover_envelopes(from, subject_id, &hub_index);
+            record_transcript(db.inner(), "IN", from, &discover).await;
+            record_transcript(db.inner(), "OUT", from, &result).await;
+            Some(result)
+        }
+        Envelope::RequestDetail {
+            from,
+            to,
+            subject_id,
+            requester,
+        } => {
+            authenticate_sender(db.inner(), own, from, &peer_key.0).await?;
+            if to != own {
+                return Err(Status::BadRequest);
+            }
+            if requester.entity != *from {
+                return Err(Status::Forbidden);
+            }
+            record_transcript(db.inner(), "IN", from, &envelope).await;
+
+            let now = chrono::Utc::now().naive_utc();
+            let record = load_record(db.inner(), own, subject_id).await?;
+            let person_id = record.as_ref().map(|(id, _, _)| *id);
+            let (agreements, policy) = match &record {
+                Some((_, holder_org, record)) => (
+                    load_release_agreements(
+                        db.inner(),
+                        own,
+                        from,
+                        *holder_org,
+                        &record.clearance,
+                        now,
+                    )
+                    .await?,
+                    match holder_org {
+                        Some(org) => load_release_policy(db.inner(), *org)
+                            .await
+                            .map_err(db_error)?,
+                        None => None,
+                    },
+                ),
+                None => (Vec::new(), None),
+            };
+            let result = compute_detail_response(
+                &requester.to_principal(),
+                record.map(|(_, _, r)| r),
+                own,
+                &agreements,
+                &policy
+                    .as_ref()
+                    .map(|p| p.entity_policy())
+                    .unwrap_or_default(),
+            );
+            let (action, outcome) = if result.granted {
+                ("FEDERATION_DETAIL_RELEASED", "released")
+            } else {
+                ("FEDERATION_DETAIL_REFUSED", "refused")
+            };
+            let failed = result
+                .decision
+                .as_ref()
+                .map(|d| {
+                    d.failed
+                        .iter()
+                        .chain(d.overrides.iter().map(|o| &o.name))
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .unwrap_or_else(|| "no record".to_string());
+            // A peer's envelope has no local actor; it is recorded under the
+            // peer's entity id.
+            let _ = create_audit_log(
+                &CreateAuditLogRequest {
+                    person_id: None,
+                    username: from.clone(),
+                    action: action.to_string(),
+                    resource_type: "person".to_string(),
+                    resource_id: person_id,
+                    details: Some(if result.granted {
+                        format!("Record on subject '{}' {} to {}", subject_id, outcome, from)
+                    } else {
+                        format!(
+                            "Record on subject '{}' {} to {}: {}",
+                            subject_id, outcome, from, failed
+                        )
+                    }),
+                    ip_address: None,
+                    user_agent: None,
+                },
+                db.inner(),
+            )
+            .await;
+
+            let response = Envelope::DetailResponse {
+                to: from.clone(),
+                subject_id: subject_id.clone(),
+                granted: result.granted,
+                decision: result.decision,
+                record: result.record,
+            };
+            record_transcript(db.inner(), "OUT", from, &response).await;
+            Some(response)
+        }
+        Envelope::DiscoverResult { .. } | Envelope::DetailResponse { .. } => {
+            return Err(Status::BadRequest);
+        }
+    };
+
+    Ok(Json(ApiResponse::success(reply)))
+}
+
+#[get("/peers")]
+pub async fn list_peers(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+) -> Result<Json<ApiResponse<Vec<FederationPeer>>>, Status> {
+    require_permission(db.inner(), &auth, "federation.manage").await?;
+    let peers = sqlx::query_as::<_, FederationPeer>(&format!(
+        "SELECT {} FROM federation_peers ORDER BY entity_id",
+        PEER_COLUMNS
+    ))
+    .fetch_all(db.inner())
+    .await
+    .map_err(db_error)?;
+    Ok(Json(ApiResponse::success(peers)))
+}
+
+/// Register a peer or replace its URL, key, release agreement and
+/// organization. 400 for a blank or overlong entity id, this instance's own
+/// entity id, an invalid URL or a key shorter than 16 characters; 404 for an
+/// unknown organization.
+#[put("/peers/<entity_id>", data = "<data>")]
+pub async fn set_peer(
+    db: &State<PgPool>,
+    config: &State<FederationConfig>,
+    auth: AuthGuard,
+    entity_id: &str,
+    data: Json<SetPeerRequest>,
+) -> Result<Json<ApiResponse<FederationPeer>>, Status> {
+    let actor = require_permission(db.inner(), &auth, "federation.manage").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    let entity_id = entity_id.trim();
+    if entity_id.is_empty()
+        || entity_id.len() > 100
+        || config.entity_id.as_deref() == Some(entity_id)
+    {
+        return Err(Status::BadRequest);
+    }
+    if let Some(organization_id) = data.organization_id {
+        sqlx::query_scalar::<_, i32>(
+            "SELECT id FROM organizations WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(organization_id)
+        .fetch_optional(db.inner())
+        .await
+        .map_err(db_error)?
+        .ok_or(Status::NotFound)?;
+    }
+
+    let peer = sqlx::query_as::<_, FederationPeer>(&format!(
+        "INSERT INTO federation_peers \
+             (entity_id, base_url, release_agreement, organization_id, created_by_person_id, \
+              key_hash) \
+         VALUES ($1, $2, $3, $4, $5, $6) \
+         ON CONFLICT (entity_id) DO UPDATE SET base_url = EXCLUDED.base_url, \
+             release_agreement = EXCLUDED.release_agreement, \
+             organization_id = EXCLUDED.organization_id, key_hash = EXCLUDED.key_hash, \
+             updated_at = CURRENT_TIMESTAMP \
+         RETURNING {}",
+        PEER_COLUMNS
+    ))
+    .bind(entity_id)
+    .bind(data.base_url.trim_end_matches('/'))
+    .bind(data.release_agreement)
+    .bind(data.organization_id)
+    .bind(actor)
+    .bind(hash_key(&data.key))
+    .fetch_one(db.inner())
+    .await
+    .map_err(db_error)?;
+
+    audit(
+        db.inner(),
+        actor,
+        "FEDERATION_PEER_UPDATED",
+        "federation_peer",
+        None,
+        format!(
+            "Peer {} at {} (release agreement: {}, organization: {})",
+            peer.entity_id,
+            peer.base_url,
+            peer.release_agreement,
+            peer.organization_id
+                .map_or_else(|| "none".to_string(), |id| id.to_string())
+        ),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(peer)))
+}
+
+#[delete("/peers/<entity_id>")]
+pub async fn delete_peer(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    entity_id: &str,
+) -> Result<Json<ApiResponse<String>>, Status> {
+    let actor = require_permission(db.inner(), &auth, "federation.manage").await?;
+    let deleted = sqlx::query("DELETE FROM federation_peers WHERE entity_id = $1")
+        .bind(entity_id)
+        .execute(db.inner())
+        .await
+        .map_err(db_error)?;
+    if deleted.rows_affected() == 0 {
+        return Err(Status::NotFound);
+    }
+    audit(
+        db.inner(),
+        actor,
+        "FEDERATION_PEER_DELETED",
+        "federation_peer",
+        None,
+        format!("Peer {} removed", entity_id),
+    )
+    .await;
+    Ok(Json(ApiResponse::success("Peer removed".to_string())))
+}
+
+#[get("/publications")]
+pub async fn list_publications(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+) -> Result<Json<ApiResponse<Vec<FederationPublication>>>, Status> {
+    require_permission(db.inner(), &auth, "federation.manage").await?;
+    let publications = sqlx::query_as::<_, FederationPublication>(&format!(
+        "SELECT {} FROM federation_publications ORDER BY subject_id, domain",
+        PUBLICATION_COLUMNS
+    ))
+    .fetch_all(db.inner())
+    .await
+    .map_err(db_error)?;
+    Ok(Json(ApiResponse::success(publications)))
+}
+
+/// Publish a local person to the hub under `subject_id` (PUBLISH). Stored
+/// only once the hub has accepted the pointer; an instance without a hub URL
+/// is its own hub and stores the pointer with the publication. 400 for an
+/// unknown domain, 404 for an unknown person, 409 when the subject id is
+/// already published in the domain or names another person, 502 when the
+/// hub refuses.
+#[post("/publications", data = "<data>")]
+pub async fn publish_subject(
+    db: &State<PgPool>,
+    config: &State<FederationConfig>,
+    auth: AuthGuard,
+    data: Json<PublishSubjectRequest>,
+) -> Result<Json<ApiResponse<FederationPublication>>, Status> {
+    let actor = require_permission(db.inner(), &auth, "federation.manage").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    if tiers(&data.domain).is_none() {
+        return Err(Status::BadRequest);
+    }
+    let (own, key) = config.identity()?;
+
+    sqlx::query_scalar::<_, i32>("SELECT id FROM person WHERE id = $1 AND deleted_at IS NULL")
+        .bind(data.person_id)
+        .fetch_optional(db.inner())
+        .await
+        .map_err(db_error)?
+        .ok_or(Status::NotFound)?;
+    let taken: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM federation_publications \
+         WHERE subject_id = $1 AND (person_id <> $2 OR domain = $3))",
+    )
+    .bind(&data.subject_id)
+    .bind(data.person_id)
+    .bind(&data.domain)
+    .fetch_one(db.inner())
+    .await
+    .map_err(db_error)?;
+    if taken {
+        return Err(Status::Conflict);
+    }
+
+    // The hub is told first, with no transaction open across the call: a
+    // pointer left behind by a racing publication only leads to detail
+    // requests answered as for an unknown record.
+    if let Some(hub_url) = config.hub_url.as_deref() {
+        exchange(
+            db.inner(),
+            "hub",
+            hub_url,
+            key,
+            Envelope::Publish {
+                from: own.to_string(),
+                subject_id: data.subject_id.clone(),
+                domain: data.domain.clone(),
+            },
+        )
+        .await?;
+    }
+
+    let mut tx = db.inner().begin().await.map_err(db_error)?;
+    let publication = sqlx::query_as::<_, FederationPublication>(&format!(
+        "INSERT INTO federation_publications (person_id, subject_id, domain, published_by_person_id) \
+         VALUES ($1, $2, $3, $4) ON CONFLICT (subject_id, domain) DO NOTHING RETURNING {}",
+        PUBLICATION_COLUMNS
+    ))
+    .bind(data.person_id)
+    .bind(&data.subject_id)
+    .bind(&data.domain)
+    .bind(actor)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(db_error)?
+    .ok_or(Status::Conflict)?;
+    if config.hub_url.is_none() {
+        store_pointer(&mut *tx, own, &publication.subject_id, &publication.domain).await?;
+    }
+    tx.commit().await.map_err(db_error)?;
+
+    audit(
+        db.inner(),
+        actor,
+        "FEDERATION_SUBJECT_PUBLISHED",
+        "person",
+        Some(publication.person_id),
+        format!(
+            "Published as subject '{}' ({}) to the hub",
+            publication.subject_id, publication.domain
+        ),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(publication)))
+}
+
+/// Stop holding a record for the federation: detail requests for the
+/// subject are answered as for an unknown record from now on. The hub's
+/// pointer is left in place; the contract has no withdrawal envelope.
+#[delete("/publications/<id>")]
+pub async fn delete_publication(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<String>>, Status> {
+    let actor = require_permission(db.inner(), &auth, "federation.manage").await?;
+    let (person_id, subject_id, domain): (i32, String, String) = sqlx::query_as(
+        "DELETE FROM federation_publications WHERE id = $1 RETURNING person_id, subject_id, domain",
+    )
+    .bind(id)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(db_error)?
+    .ok_or(Status::NotFound)?;
+    audit(
+        db.inner(),
+        actor,
+        "FEDERATION_SUBJECT_UNPUBLISHED",
+        "person",
+        Some(person_id),
+        format!("Subject '{}' ({}) no longer held", subject_id, domain),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(
+        "Publication removed".to_string(),
+    )))
+}
+
+/// Ask the hub which instances hold records on `subject_id` (DISCOVER); an
+/// instance without a hub URL answers from its own pointers. 502 when the
+/// hub does not answer with a DISCOVER_RESULT.
+#[get("/discover?<subject_id>")]
+pub async fn discover(
+    db: &State<PgPool>,
+    config: &State<FederationConfig>,
+    auth: AuthGuard,
+    subject_id: &str,
+) -> Result<Json<ApiResponse<Vec<Pointer>>>, Status> {
+    require_permission(db.inner(), &auth, "person.read").await?;
+    let (own, key) = config.identity()?;
+    let Some(hub_url) = config.hub_url.as_deref() else {
+        let pointers = load_hub_index(db.inner(), subject_id)
+            .await?
+            .into_iter()
+            .map(|(_, holder, domain)| Pointer { holder, domain })
+            .collect();
+        return Ok(Json(ApiResponse::success(pointers)));
+    };
+
+    let reply = exchange(
+        db.inner(),
+        "hub",
+        hub_url,
+        key,
+        Envelope::Discover {
+            from: own.to_string(),
+            subject_id: subject_id.to_string(),
+        },
+    )
+    .await?;
+    match reply {
+        Some(Envelope::DiscoverResult { pointers, .. }) => Ok(Json(ApiResponse::success(pointers))),
+        _ => Err(Status::BadGateway),
+    }
+}
+
+/// Request a holder's record on a subject for the caller, or for another
+/// local person under federation.manage (REQUEST_DETAIL). The person goes
+/// out as this instance's principal with their effective clearance,
+/// compartments, domain tiers and flags; the holder decides, and its decision
+/// is recorded in the decision log (a refusal for an unknown record carries
+/// none). 403 when naming another person without federation.manage, 404 for
+/// an unknown peer or person, 502 when the holder does not answer with a
+/// DETAIL_RESPONSE.
+#[post("/detail-requests", data = "<data>")]
+pub async fn request_detail(
+    db: &State<PgPool>,
+    config: &State<FederationConfig>,
+    auth: AuthGuard,
+    data: Json<DetailRequest>,
+) -> Result<Json<ApiResponse<DetailResult>>, Status> {
+    let actor = require_permission(db.inner(), &auth, "person.read").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    let requester_person_id = data.requester_person_id.unwrap_or(actor);
+    if requester_person_id != actor
+        && !role_has_permission(db.inner(), &auth.claims.role, "federation.manage")
+            .await
+            .map_err(db_error)?
+    {
+        return Err(Status::Forbidden);
+    }
+    let (own, key) = config.identity()?;
+    let peer = fetch_peer(db.inner(), &data.holder)
+        .await?
+        .ok_or(Status::NotFound)?;
+    let (principal, _) = load_principal(db.inner(), requester_person_id, 0, &[])
+        .await
+        .map_err(db_error)?
+        .ok_or(Status::NotFound)?;
+
+    let reply = exchange(
+        db.inner(),
+        &peer.entity_id,
+        &peer.base_url,
+        key,
+        Envelope::RequestDetail {
+            from: own.to_string(),
+            to: peer.entity_id.clone(),
+            subject_id: data.subject_id.clone(),
+            requester: WirePrincipal::from_principal(&principal, own),
+        },
+    )
+    .await?;
+    let Some(Envelope::DetailResponse {
+        granted,
+        decision,
+        record,
+        ..
+    }) = reply
+    else {
+        return Err(Status::BadGateway);
+    };
+    if let Some(decision) = &decision {
+        let record = ruled_decision_record(
+            SOURCE_FEDERATION_DETAIL,
+            requester_person_id,
+            format!("federation:{}/{}", peer.entity_id, data.subject_id),
+            None,
+            decision,
+            &auth.claims,
+        );
+        record_decisions(db.inner(), &[record])
+            .await
+            .map_err(db_error)?;
+    }
+
+    audit(
+        db.inner(),
+        actor,
+        "FEDERATION_DETAIL_REQUESTED",
+        "person",
+        Some(requester_person_id),
+        format!(
+            "Record on subject '{}' requested from {}: {}",
+            data.subject_id,
+            peer.entity_id,
+            if granted { "granted" } else { "refused" }
+        ),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(DetailResult {
+        granted,
+        decision,
+        record,
+    })))
+}
+
+/// The most recent envelopes sent and received, newest first (default 100,
+/// at most 500).
+#[get("/transcript?<limit>")]
+pub async fn list_transcript(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    limit: Option<i64>,
+) -> Result<Json<ApiResponse<Vec<TranscriptEntry>>>, Status> {
+    require_permission(db.inner(), &auth, "federation.manage").await?;
+    let limit = limit
+        .unwrap_or(DEFAULT_TRANSCRIPT_LIMIT)
+        .clamp(1, MAX_TRANSCRIPT_LIMIT);
+    let entries = sqlx::query_as::<_, TranscriptEntry>(
+        "SELECT id, direction, peer, kind, envelope, created_at FROM federation_transcript \
+         ORDER BY id DESC LIMIT $1",
+    )
+    .bind(limit)
+    .fetch_all(db.inner())
+    .await
+    .map_err(db_error)?;
+    Ok(Json(ApiResponse::success(entries)))
+}
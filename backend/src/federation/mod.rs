@@ -0,0 +1,24 @@
+// Federation module
+// Discovery and detail exchange between Janus instances through the typed
+// envelopes of the interchange contract, with this instance as hub, holder
+// or requester
+
+pub mod client;
+pub mod contract;
+pub mod handlers;
+pub mod models;
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![
+        handlers::receive_envelope,
+        handlers::list_peers,
+        handlers::set_peer,
+        handlers::delete_peer,
+        handlers::list_publications,
+        handlers::publish_subject,
+        handlers::delete_publication,
+        handlers::discover,
+        handlers::request_detail,
+        handlers::list_transcript,
+    ]
+}
@@ -0,0 +1,71 @@
+// Federation data models
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Another Janus instance, by its entity id in the federation
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FederationPeer {
+    pub entity_id: String,
+    pub base_url: String,
+    pub release_agreement: bool, // this instance releases records to the peer
+    pub organization_id: Option<i32>, // the organization sharing agreements name
+    pub created_by_person_id: i32,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetPeerRequest {
+    #[validate(url, length(max = 500))]
+    pub base_url: String,
+    /// The key the peer presents on its envelopes; only its hash is stored
+    #[validate(length(min = 16, max = 200))]
+    pub key: String,
+    #[serde(default)]
+    pub release_agreement: bool,
+    #[serde(default)]
+    pub organization_id: Option<i32>,
+}
+
+/// A local person published to the hub under a federation-wide subject id
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct FederationPublication {
+    pub id: i32,
+    pub person_id: i32,
+    pub subject_id: String,
+    pub domain: String, // COMPUTER, DATA, PHYSICAL
+    pub published_by_person_id: i32,
+    pub published_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct PublishSubjectRequest {
+    pub person_id: i32,
+    #[validate(length(min = 1, max = 100))]
+    pub subject_id: String,
+    pub domain: String,
+}
+
+/// Ask a holder for its record on a subject on behalf of a local person; the
+/// caller themselves unless they hold federation.manage
+#[derive(Debug, Deserialize, Validate)]
+pub struct DetailRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub holder: String,
+    #[validate(length(min = 1, max = 100))]
+    pub subject_id: String,
+    #[serde(default)]
+    pub requester_person_id: Option<i32>,
+}
+
+/// One envelope sent to or received from a peer
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TranscriptEntry {
+    pub id: i64,
+    pub direction: String, // IN, OUT
+    pub peer: String,
+    pub kind: String,
+    pub envelope: serde_json::Value,
+    pub created_at: NaiveDateTime,
+}
@@ -4,7 +4,15 @@ use rocket::{delete, get, http::Status, post, put, State};
 use sqlx::PgPool;
 use validator::Validate;
 
-use super::models::{CreateInfoSystemRequest, InfoSystem, UpdateInfoSystemRequest};
+use super::models::{
+    CreateInfoSystemRequest, InfoSystem, InfoSystemShielding, SetInfoSystemShieldingRequest,
+    SystemAccessHolder, UpdateInfoSystemRequest,
+};
+use crate::access::grants::revoke_system_grants;
+use crate::access::revocation::audit_revoked_grants;
+use crate::access::systems::revoke_unadmitted_grants;
+use crate::audit::handlers::{create_audit_log, insert_audit_log};
+use crate::audit::models::CreateAuditLogRequest;
 use crate::auth::middleware::AuthGuard;
 use crate::shared::pagination::PaginationParams;
 use crate::shared::rbac::role_has_permission;
@@ -35,7 +43,7 @@ pub async fn list_info_systems(
         r#"
         SELECT id, system_name, description, environment, status,
                ip_address, domain, managed_by, last_audit_date,
-               created_at, updated_at
+               classification, created_at, updated_at
         FROM info_systems
         ORDER BY system_name
         LIMIT $1 OFFSET $2
@@ -69,7 +77,7 @@ pub async fn get_info_system(
         r#"
         SELECT id, system_name, description, environment, status,
                ip_address, domain, managed_by, last_audit_date,
-               created_at, updated_at
+               classification, created_at, updated_at
         FROM info_systems
         WHERE id = $1
         "#,
@@ -83,6 +91,212 @@ pub async fn get_info_system(
     Ok(Json(system))
 }
 
+/// Who currently holds computer access to a system. Holders with an uncleared
+/// security flag are left out, as in the person's own access listing.
+#[get("/api/info-systems/<id>/access")]
+pub async fn list_info_system_access(
+    id: i32,
+    db: &State<PgPool>,
+    auth: AuthGuard,
+) -> Result<Json<Vec<SystemAccessHolder>>, Status> {
+    if !role_has_permission(db.inner(), &auth.claims.role, "person.read")
+        .await
+        .unwrap_or(false)
+    {
+        return Err(Status::Forbidden);
+    }
+    let exists = sqlx::query_scalar!(
+        "SELECT EXISTS(SELECT 1 FROM info_systems WHERE id = $1)",
+        id
+    )
+    .fetch_one(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?
+    .unwrap_or(false);
+    if !exists {
+        return Err(Status::NotFound);
+    }
+
+    let holders = sqlx::query_as::<_, SystemAccessHolder>(
+        r#"
+        SELECT ca.id AS access_id, ca.person_id, p.username, p.first_name, p.last_name,
+               ca.access_level, ca.granted_at, ca.expires_at
+        FROM computer_access ca
+        JOIN person p ON p.id = ca.person_id
+        WHERE ca.info_system_id = $1 AND ca.status = 'ACTIVE'
+          AND (ca.expires_at IS NULL OR ca.expires_at > $2)
+          AND NOT EXISTS (SELECT 1 FROM person_security_flags f
+                          WHERE f.person_id = ca.person_id AND f.cleared_at IS NULL)
+        ORDER BY p.last_name, p.first_name, ca.person_id
+        "#,
+    )
+    .bind(id)
+    .bind(chrono::Utc::now().naive_utc())
+    .fetch_all(db.inner())
+    .await
+    .map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?;
+
+    Ok(Json(holders))
+}
+
+async fn load_shielding(db: &PgPool, id: i32) -> Result<InfoSystemShielding, Status> {
+    let (shielded, owner_organization_id) = sqlx::query_as::<_, (bool, Option<i32>)>(
+        "SELECT shielded, owner_organization_id FROM info_systems WHERE id = $1",
+    )
+    .bind(id)
+    .fetch_optional(db)
+    .await
+    .map_err(|_| Status::InternalServerError)?
+    .ok_or(Status::NotFound)?;
+    let allowlist = sqlx::query_scalar::<_, i32>(
+        "SELECT organization_id FROM info_system_shield_allowlist \
+         WHERE info_system_id = $1 ORDER BY organization_id",
+    )
+    .bind(id)
+    .fetch_all(db)
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    Ok(InfoSystemShielding {
+        info_system_id: id,
+        shielded,
+        owner_organization_id,
+        allowlist,
+    })
+}
+
+/// A system's shielded flag, owning organization and allowlist
+#[get("/api/info-systems/<id>/shielding")]
+pub async fn get_info_system_shielding(
+    id: i32,
+    db: &State<PgPool>,
+    _auth: AuthGuard,
+) -> Result<Json<ApiResponse<InfoSystemShielding>>, Status> {
+    Ok(Json(ApiResponse::success(
+        load_shielding(db.inner(), id).await?,
+    )))
+}
+
+/// Replace a system's shielding. Grants held by persons the new shielding
+/// does not admit are revoked in the same transaction.
+#[put("/api/info-systems/<id>/shielding", data = "<request>")]
+pub async fn set_info_system_shielding(
+    id: i32,
+    request: Json<SetInfoSystemShieldingRequest>,
+    db: &State<PgPool>,
+    auth: AuthGuard,
+) -> Result<Json<ApiResponse<InfoSystemShielding>>, Status> {
+    if !role_has_permission(db.inner(), &auth.claims.role, "info_systems.write")
+        .await
+        .unwrap_or(false)
+    {
+        return Err(Status::Forbidden);
+    }
+    let caller = auth
+        .claims
+        .sub
+        .parse::<i32>()
+        .map_err(|_| Status::InternalServerError)?;
+    let mut allowlist = request.allowlist.clone();
+    allowlist.sort_unstable();
+    allowlist.dedup();
+
+    // Every named organization must exist (404 otherwise)
+    let mut named = allowlist.clone();
+    named.extend(request.owner_organization_id);
+    named.sort_unstable();
+    named.dedup();
+    let found: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM organizations WHERE id = ANY($1) AND deleted_at IS NULL",
+    )
+    .bind(&named)
+    .fetch_one(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    if found != named.len() as i64 {
+        return Err(Status::NotFound);
+    }
+
+    let mut tx = db
+        .inner()
+        .begin()
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let updated = sqlx::query(
+        "UPDATE info_systems SET shielded = $2, owner_organization_id = $3, \
+         updated_at = CURRENT_TIMESTAMP WHERE id = $1",
+    )
+    .bind(id)
+    .bind(request.shielded)
+    .bind(request.owner_organization_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| Status::InternalServerError)?
+    .rows_affected();
+    if updated == 0 {
+        return Err(Status::NotFound);
+    }
+    sqlx::query("DELETE FROM info_system_shield_allowlist WHERE info_system_id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    sqlx::query(
+        "INSERT INTO info_system_shield_allowlist (info_system_id, organization_id, added_by_person_id) \
+         SELECT $1, org, $3 FROM UNNEST($2::int[]) AS org",
+    )
+    .bind(id)
+    .bind(&allowlist)
+    .bind(caller)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?;
+    let revoked = revoke_unadmitted_grants(&mut tx, id, caller)
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            Status::InternalServerError
+        })?;
+    tx.commit().await.map_err(|_| Status::InternalServerError)?;
+
+    for (person_id, grant) in &revoked {
+        audit_revoked_grants(
+            db.inner(),
+            caller,
+            person_id,
+            std::slice::from_ref(grant),
+            "Not admitted by the system's shielding",
+        )
+        .await;
+    }
+    let _ = create_audit_log(
+        &CreateAuditLogRequest {
+            person_id: Some(caller),
+            username: caller.to_string(),
+            action: "INFO_SYSTEM_SHIELDING_UPDATED".to_string(),
+            resource_type: "info_system".to_string(),
+            resource_id: Some(id),
+            details: Some(format!(
+                "shielded={} owner_organization_id={:?} allowlist={:?}",
+                request.shielded, request.owner_organization_id, allowlist
+            )),
+            ip_address: None,
+            user_agent: None,
+        },
+        db.inner(),
+    )
+    .await;
+
+    Ok(Json(ApiResponse::success(
+        load_shielding(db.inner(), id).await?,
+    )))
+}
+
 #[post("/api/info-systems", data = "<request>")]
 pub async fn create_info_system(
     request: Json<CreateInfoSystemRequest>,
@@ -109,11 +323,12 @@ pub async fn create_info_system(
         InfoSystem,
         r#"
         INSERT INTO info_systems (system_name, description, environment, status, 
-                                  ip_address, domain, managed_by, last_audit_date)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                                  ip_address, domain, managed_by, last_audit_date,
+                                  classification)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
         RETURNING id, system_name, description, environment, status,
                   ip_address, domain, managed_by, last_audit_date,
-                  created_at, updated_at
+                  classification, created_at, updated_at
         "#,
         request.system_name,
         request.description,
@@ -122,7 +337,8 @@ pub async fn create_info_system(
         request.ip_address,
         request.domain,
         request.managed_by,
-        audit_date
+        audit_date,
+        request.classification
     )
     .fetch_one(db.inner())
     .await
@@ -146,19 +362,26 @@ pub async fn update_info_system(
     }
     request.validate().map_err(|_| Status::BadRequest)?;
 
-    // Check if system exists
-    let exists = sqlx::query_scalar!(
-        "SELECT EXISTS(SELECT 1 FROM info_systems WHERE id = $1)",
-        id
+    let caller = auth
+        .claims
+        .sub
+        .parse::<i32>()
+        .map_err(|_| Status::InternalServerError)?;
+
+    // Lock the system so the audited classification change is the one applied
+    let mut tx = db
+        .inner()
+        .begin()
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let previous_classification = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT classification FROM info_systems WHERE id = $1 FOR UPDATE",
     )
-    .fetch_one(db.inner())
+    .bind(id)
+    .fetch_optional(&mut *tx)
     .await
     .map_err(|_| Status::InternalServerError)?
-    .unwrap_or(false);
-
-    if !exists {
-        return Err(Status::NotFound);
-    }
+    .ok_or(Status::NotFound)?;
 
     // Parse last_audit_date if provided
     let audit_date = request
@@ -168,7 +391,8 @@ pub async fn update_info_system(
         .transpose()
         .map_err(|_| Status::BadRequest)?;
 
-    // Use COALESCE for optional fields to update only what's provided
+    // Use COALESCE for optional fields to update only what's provided;
+    // classification is set whenever it is present, so null removes it
     let system = sqlx::query_as!(
         InfoSystem,
         r#"
@@ -181,11 +405,12 @@ pub async fn update_info_system(
             domain = COALESCE($6, domain),
             managed_by = COALESCE($7, managed_by),
             last_audit_date = COALESCE($8, last_audit_date),
+            classification = CASE WHEN $9 THEN $10 ELSE classification END,
             updated_at = CURRENT_TIMESTAMP
-        WHERE id = $9
+        WHERE id = $11
         RETURNING id, system_name, description, environment, status,
                   ip_address, domain, managed_by, last_audit_date,
-                  created_at, updated_at
+                  classification, created_at, updated_at
         "#,
         request.system_name.as_ref(),
         request.description.as_ref(),
@@ -195,15 +420,46 @@ pub async fn update_info_system(
         request.domain.as_ref(),
         request.managed_by.as_ref(),
         audit_date,
+        request.classification.is_some(),
+        request.classification.clone().flatten(),
         id
     )
-    .fetch_one(db.inner())
+    .fetch_one(&mut *tx)
     .await
     .map_err(|e| {
         eprintln!("Database error: {:?}", e);
         Status::InternalServerError
     })?;
 
+    // The classification decides who may hold grants, so its change is
+    // audited with the update
+    if system.classification != previous_classification {
+        insert_audit_log(
+            &CreateAuditLogRequest {
+                person_id: Some(caller),
+                username: caller.to_string(),
+                action: "INFO_SYSTEM_CLASSIFICATION_CHANGED".to_string(),
+                resource_type: "info_system".to_string(),
+                resource_id: Some(id),
+                details: Some(format!(
+                    "'{}' classification {} -> {}",
+                    system.system_name,
+                    previous_classification.as_deref().unwrap_or("none"),
+                    system.classification.as_deref().unwrap_or("none")
+                )),
+                ip_address: None,
+                user_agent: None,
+            },
+            &mut *tx,
+        )
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            Status::InternalServerError
+        })?;
+    }
+    tx.commit().await.map_err(|_| Status::InternalServerError)?;
+
     Ok(Json(system))
 }
 
@@ -219,15 +475,54 @@ pub async fn delete_info_system(
     {
         return Err(Status::Forbidden);
     }
-    let rows_affected = sqlx::query!("DELETE FROM info_systems WHERE id = $1", id)
-        .execute(db.inner())
+    let caller = auth
+        .claims
+        .sub
+        .parse::<i32>()
+        .map_err(|_| Status::InternalServerError)?;
+
+    // Revoke the system's active grants and delete it in one transaction; the
+    // revoked rows stay as history with info_system_id cleared by the FK.
+    let mut tx = db
+        .inner()
+        .begin()
         .await
-        .map_err(|_| Status::InternalServerError)?
-        .rows_affected();
+        .map_err(|_| Status::InternalServerError)?;
+    let revoked = revoke_system_grants(&mut *tx, id, Some(caller), "Information system deleted")
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            Status::InternalServerError
+        })?;
+    let system_name = sqlx::query_scalar!(
+        "DELETE FROM info_systems WHERE id = $1 RETURNING system_name",
+        id
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|_| Status::InternalServerError)?
+    .ok_or(Status::NotFound)?;
+    tx.commit().await.map_err(|_| Status::InternalServerError)?;
 
-    if rows_affected == 0 {
-        return Err(Status::NotFound);
-    }
+    let _ = create_audit_log(
+        &CreateAuditLogRequest {
+            person_id: Some(caller),
+            username: caller.to_string(),
+            action: "INFO_SYSTEM_DELETED".to_string(),
+            resource_type: "info_system".to_string(),
+            resource_id: Some(id),
+            details: Some(format!(
+                "'{}' deleted; {} active computer grant(s) revoked: {:?}",
+                system_name,
+                revoked.len(),
+                revoked
+            )),
+            ip_address: None,
+            user_agent: None,
+        },
+        db.inner(),
+    )
+    .await;
 
     Ok(Json(ApiResponse::success("Deleted".to_string())))
 }
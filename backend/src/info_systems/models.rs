@@ -1,5 +1,5 @@
 // Information Systems data models
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use validator::Validate;
 
 #[derive(Debug, Serialize, sqlx::FromRow)]
@@ -13,10 +13,43 @@ pub struct InfoSystem {
     pub domain: Option<String>,
     pub managed_by: Option<String>,
     pub last_audit_date: Option<chrono::NaiveDate>,
+    pub classification: Option<String>, // None = not classified
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
 }
 
+/// Someone holding active computer access to a system
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct SystemAccessHolder {
+    pub access_id: i32,
+    pub person_id: i32,
+    pub username: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub access_level: String,
+    pub granted_at: chrono::NaiveDateTime,
+    pub expires_at: Option<chrono::NaiveDateTime>,
+}
+
+/// A system's directional shielding: when shielded, new computer grants need
+/// the grantee to belong to the owner or an allowlisted organization
+#[derive(Debug, Serialize)]
+pub struct InfoSystemShielding {
+    pub info_system_id: i32,
+    pub shielded: bool,
+    pub owner_organization_id: Option<i32>,
+    pub allowlist: Vec<i32>,
+}
+
+/// Replaces a system's shielding wholesale (flag, owner and allowlist)
+#[derive(Debug, Deserialize)]
+pub struct SetInfoSystemShieldingRequest {
+    pub shielded: bool,
+    pub owner_organization_id: Option<i32>,
+    #[serde(default)]
+    pub allowlist: Vec<i32>,
+}
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct CreateInfoSystemRequest {
     #[validate(length(min = 1, max = 100))]
@@ -37,6 +70,9 @@ pub struct CreateInfoSystemRequest {
     pub managed_by: Option<String>,
 
     pub last_audit_date: Option<String>,
+
+    #[validate(custom = "validate_classification")]
+    pub classification: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -59,6 +95,21 @@ pub struct UpdateInfoSystemRequest {
     pub managed_by: Option<String>,
 
     pub last_audit_date: Option<String>,
+
+    /// Absent keeps the classification; `null` removes it
+    #[serde(default, deserialize_with = "present")]
+    #[validate(custom = "validate_classification")]
+    pub classification: Option<Option<String>>,
+}
+
+/// Deserialize a field that is present, even as `null`, to `Some`, so an
+/// absent field (`None` through `#[serde(default)]`) stays distinguishable.
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
 fn validate_environment(env: &str) -> Result<(), validator::ValidationError> {
@@ -75,6 +126,13 @@ fn validate_status(status: &str) -> Result<(), validator::ValidationError> {
     }
 }
 
+fn validate_classification(classification: &str) -> Result<(), validator::ValidationError> {
+    match classification {
+        "UNCLASSIFIED" | "CONFIDENTIAL" | "SECRET" | "TOP_SECRET" => Ok(()),
+        _ => Err(validator::ValidationError::new("invalid_classification")),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,6 +165,13 @@ mod tests {
         assert!(validate_status("active").is_err()); // Case sensitive
     }
 
+    #[test]
+    fn test_validate_classification() {
+        assert!(validate_classification("SECRET").is_ok());
+        assert!(validate_classification("RESTRICTED").is_err());
+        assert!(validate_classification("secret").is_err());
+    }
+
     #[test]
     fn test_create_info_system_request_validation() {
         let valid_request = CreateInfoSystemRequest {
@@ -118,6 +183,7 @@ mod tests {
             domain: Some("test.example.com".to_string()),
             managed_by: Some("IT Operations".to_string()),
             last_audit_date: Some("2024-01-15".to_string()),
+            classification: None,
         };
 
         assert!(valid_request.validate().is_ok());
@@ -134,6 +200,7 @@ mod tests {
             domain: None,
             managed_by: None,
             last_audit_date: None,
+            classification: None,
         };
 
         assert!(invalid_request.validate().is_err());
@@ -150,6 +217,7 @@ mod tests {
             domain: None,
             managed_by: None,
             last_audit_date: None,
+            classification: None,
         };
 
         assert!(invalid_request.validate().is_err());
@@ -166,6 +234,7 @@ mod tests {
             domain: None,
             managed_by: None,
             last_audit_date: None,
+            classification: None,
         };
 
         assert!(invalid_request.validate().is_err());
@@ -182,6 +251,7 @@ mod tests {
             domain: None,
             managed_by: None,
             last_audit_date: None,
+            classification: None,
         };
 
         assert!(valid_request.validate().is_ok());
@@ -199,6 +269,7 @@ mod tests {
             domain: None,
             managed_by: None,
             last_audit_date: None,
+            classification: None,
         };
 
         assert!(partial_request.validate().is_ok());
@@ -215,11 +286,31 @@ mod tests {
             domain: None,
             managed_by: None,
             last_audit_date: None,
+            classification: None,
         };
 
         assert!(invalid_request.validate().is_err());
     }
 
+    #[test]
+    fn test_update_info_system_request_classification_absent_or_null() {
+        let absent: UpdateInfoSystemRequest = serde_json::from_str("{}").unwrap();
+        assert_eq!(absent.classification, None);
+
+        let cleared: UpdateInfoSystemRequest =
+            serde_json::from_str(r#"{"classification": null}"#).unwrap();
+        assert_eq!(cleared.classification, Some(None));
+        assert!(cleared.validate().is_ok());
+
+        let set: UpdateInfoSystemRequest =
+            serde_json::from_str(r#"{"classification": "SECRET"}"#).unwrap();
+        assert_eq!(set.classification, Some(Some("SECRET".to_string())));
+
+        let invalid: UpdateInfoSystemRequest =
+            serde_json::from_str(r#"{"classification": "RESTRICTED"}"#).unwrap();
+        assert!(invalid.validate().is_err());
+    }
+
     #[test]
     fn test_update_info_system_request_invalid_environment() {
         let invalid_request = UpdateInfoSystemRequest {
@@ -231,6 +322,7 @@ mod tests {
             domain: None,
             managed_by: None,
             last_audit_date: None,
+            classification: None,
         };
 
         assert!(invalid_request.validate().is_err());
@@ -4,20 +4,36 @@
 #[macro_use]
 extern crate rocket;
 
+pub mod abac;
 pub mod access;
+pub mod access_bulk;
+pub mod access_bundles;
+pub mod access_requests;
 pub mod audit;
 pub mod auth;
+pub mod authorizations;
+pub mod clearances;
+pub mod compartments;
+pub mod credentials;
+pub mod decision_log;
+pub mod deployments;
 pub mod digital_resources;
 pub mod discussions;
 pub mod document_references;
+pub mod federation;
 pub mod info_systems;
 pub mod messaging;
 pub mod nda;
 pub mod organizations;
 pub mod person;
+pub mod recertification;
 pub mod relations;
+pub mod release_policies;
 pub mod roles;
+pub mod security_flags;
 pub mod shared;
+pub mod sharing_agreements;
+pub mod sod;
 pub mod vendor_relations;
 
 // Re-export routes and handlers needed for rocket setup
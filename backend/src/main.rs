@@ -1,20 +1,36 @@
 #[macro_use]
 extern crate rocket;
 
+mod abac;
 mod access;
+mod access_bulk;
+mod access_bundles;
+mod access_requests;
 mod audit;
 mod auth;
+mod authorizations;
+mod clearances;
+mod compartments;
+mod credentials;
+mod decision_log;
+mod deployments;
 mod digital_resources;
 mod discussions;
 mod document_references;
+mod federation;
 mod info_systems;
 mod messaging;
 mod nda;
 mod organizations;
 mod person;
+mod recertification;
 mod relations;
+mod release_policies;
 mod roles;
+mod security_flags;
 mod shared;
+mod sharing_agreements;
+mod sod;
 mod vendor_relations;
 
 use rocket::serde::json::Json;
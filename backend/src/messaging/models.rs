@@ -18,6 +18,14 @@ pub enum WebSocketMessage {
         created_by: i32,
         created_at: String,
     },
+    #[serde(rename = "access_request")]
+    AccessRequestUpdate {
+        request_id: i32,
+        person_id: i32,
+        access_type: String,
+        target: String,
+        status: String,
+    },
     #[serde(rename = "read")]
     MarkRead { discussion_id: i32, user_id: i32 },
     #[serde(rename = "ping")]
@@ -9,6 +9,7 @@ use crate::audit::models::CreateAuditLogRequest;
 use crate::auth::middleware::AuthGuard;
 use crate::nda::models::*;
 use crate::shared::rbac::role_has_permission;
+use crate::sod::enforce::{enforce_no_self_action, enforce_requester_not_approver};
 
 /// List NDAs for person or by person email
 #[get("/?<person_id>&<status>&<email>")]
@@ -118,6 +119,13 @@ pub async fn create_nda(
         return Err(Status::Forbidden);
     }
     let issued_by_person_id = auth.claims.sub.parse::<i32>().unwrap_or(0);
+    enforce_no_self_action(
+        db.inner(),
+        "nda.write",
+        &issued_by_person_id,
+        &data.person_id,
+    )
+    .await?;
     let version = data.version.clone().unwrap_or_else(|| "1.0".to_string());
 
     let expires_at = match &data.expires_at {
@@ -182,6 +190,23 @@ pub async fn sign_nda(
     {
         return Err(Status::Forbidden);
     }
+    // SoD: the issuer of an NDA may not also sign it.
+    let issued_by_person_id: i32 =
+        sqlx::query_scalar("SELECT issued_by_person_id FROM nda WHERE id = $1")
+            .bind(id)
+            .fetch_optional(db.inner())
+            .await
+            .map_err(|_| Status::InternalServerError)?
+            .ok_or(Status::NotFound)?;
+    let signer_person_id = auth.claims.sub.parse::<i32>().unwrap_or(0);
+    enforce_requester_not_approver(
+        db.inner(),
+        "nda.sign",
+        &issued_by_person_id,
+        &signer_person_id,
+    )
+    .await?;
+
     let now = Utc::now().naive_utc();
 
     let nda = sqlx::query_as::<sqlx::Postgres, NDA>(
@@ -5,11 +5,20 @@ use rocket::{delete, get, http::Status, post, put, State};
 use sqlx::PgPool;
 use validator::Validate;
 
-use super::models::{CreatePersonRequest, Person, UpdatePersonRequest};
+use super::models::{CreatePersonRequest, OffboardPersonRequest, Person, UpdatePersonRequest};
+use crate::access::models::OffboardingSummary;
+use crate::access::revocation::{
+    audit_revoked_grants, offboard_person, revoke_exceeding_clearance,
+};
+use crate::audit::handlers::create_audit_log;
+use crate::audit::models::CreateAuditLogRequest;
 use crate::auth::middleware::AuthGuard;
 use crate::shared::pagination::PaginationParams;
 use crate::shared::rbac::role_has_permission;
-use crate::shared::response::PaginatedResponse;
+use crate::shared::response::{ApiResponse, PaginatedResponse};
+use crate::sod::enforce::enforce_no_self_action;
+
+const DEFAULT_OFFBOARDING_REASON: &str = "Person offboarded";
 
 #[get("/?<page>&<per_page>&<search>")]
 pub async fn list_persons(
@@ -142,6 +151,16 @@ pub async fn create_person(
     // Validate input
     person_request.validate().map_err(|_| Status::BadRequest)?;
 
+    // An initial clearance becomes a granted clearance record (see the
+    // clearance_records trigger), so it needs clearance.manage like any grant.
+    if person_request.clearance_level.is_some()
+        && !role_has_permission(db.inner(), &auth.claims.role, "clearance.manage")
+            .await
+            .unwrap_or(false)
+    {
+        return Err(Status::Forbidden);
+    }
+
     // Validate that person has at least some identity
     if person_request.first_name.is_none()
         && person_request.last_name.is_none()
@@ -226,20 +245,37 @@ pub async fn update_person(
     {
         return Err(Status::Forbidden);
     }
+    let actor = auth
+        .claims
+        .sub
+        .parse::<i32>()
+        .map_err(|_| Status::InternalServerError)?;
     // Validate input
     person_request.validate().map_err(|_| Status::BadRequest)?;
 
     // Check if person exists and is not deleted
-    let exists: bool = sqlx::query_scalar::<_, bool>(
-        "SELECT EXISTS(SELECT 1 FROM person WHERE id = $1 AND deleted_at IS NULL)",
+    let current_clearance: Option<String> = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT clearance_level FROM person WHERE id = $1 AND deleted_at IS NULL",
     )
     .bind(id)
-    .fetch_one(db.inner())
+    .fetch_optional(db.inner())
     .await
-    .map_err(|_| Status::InternalServerError)?;
+    .map_err(|_| Status::InternalServerError)?
+    .ok_or(Status::NotFound)?;
 
-    if !exists {
-        return Err(Status::NotFound);
+    // Changing the clearance grants a new clearance record (see the
+    // clearance_records trigger), so it needs clearance.manage and falls under
+    // the no-self-clearance rule. Resubmitting the unchanged level needs neither.
+    if person_request.clearance_level.is_some()
+        && person_request.clearance_level != current_clearance
+    {
+        if !role_has_permission(db.inner(), &auth.claims.role, "clearance.manage")
+            .await
+            .unwrap_or(false)
+        {
+            return Err(Status::Forbidden);
+        }
+        enforce_no_self_action(db.inner(), "clearance.manage", &actor, &id).await?;
     }
 
     // Validate department if it's being updated
@@ -353,39 +389,129 @@ pub async fn update_person(
 
     query_builder = query_builder.bind(id);
 
+    // A clearance change re-evaluates classified grants in the same
+    // transaction, so a downgrade never leaves access the new level forbids.
+    let mut tx = db
+        .inner()
+        .begin()
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let previous_clearance: Option<String> =
+        sqlx::query_scalar("SELECT clearance_level FROM person WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|_| Status::InternalServerError)?;
+
     let person = query_builder
-        .fetch_one(db.inner())
+        .fetch_one(&mut *tx)
         .await
         .map_err(|_| Status::InternalServerError)?;
 
+    if person.clearance_level == previous_clearance {
+        tx.commit().await.map_err(|_| Status::InternalServerError)?;
+        return Ok(Json(person));
+    }
+    let revoked = revoke_exceeding_clearance(&mut tx, id, actor, chrono::Utc::now().naive_utc())
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            Status::InternalServerError
+        })?;
+    tx.commit().await.map_err(|_| Status::InternalServerError)?;
+    audit_revoked_grants(
+        db.inner(),
+        actor,
+        id,
+        &revoked,
+        &format!(
+            "Clearance changed from {} to {}",
+            previous_clearance.as_deref().unwrap_or("none"),
+            person.clearance_level.as_deref().unwrap_or("none")
+        ),
+    )
+    .await;
+
     Ok(Json(person))
 }
 
-#[delete("/<id>")]
-pub async fn delete_person(id: i32, db: &State<PgPool>, auth: AuthGuard) -> Result<Status, Status> {
-    if !role_has_permission(db.inner(), &auth.claims.role, "person.write")
+/// Offboard a person in one transaction: revoke every access and resource
+/// grant, revoke NDAs awaiting signature, deny pending access requests and
+/// soft-delete the person (which also invalidates their tokens).
+async fn run_offboarding(
+    db: &PgPool,
+    auth: &AuthGuard,
+    id: i32,
+    reason: &str,
+) -> Result<OffboardingSummary, Status> {
+    if !role_has_permission(db, &auth.claims.role, "person.write")
         .await
         .unwrap_or(false)
     {
         return Err(Status::Forbidden);
     }
-    // Soft delete by setting deleted_at timestamp
-    let result = sqlx::query(
-        r#"
-        UPDATE person 
-        SET deleted_at = CURRENT_TIMESTAMP 
-        WHERE id = $1 AND deleted_at IS NULL
-        "#,
+    let actor = auth
+        .claims
+        .sub
+        .parse::<i32>()
+        .map_err(|_| Status::InternalServerError)?;
+
+    let mut tx = db.begin().await.map_err(|_| Status::InternalServerError)?;
+    let summary = offboard_person(&mut tx, id, actor, reason)
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            Status::InternalServerError
+        })?
+        .ok_or(Status::NotFound)?;
+    tx.commit().await.map_err(|_| Status::InternalServerError)?;
+
+    audit_revoked_grants(db, actor, id, &summary.revoked_grants, reason).await;
+    let _ = create_audit_log(
+        &CreateAuditLogRequest {
+            person_id: Some(actor),
+            username: actor.to_string(),
+            action: "PERSON_OFFBOARDED".to_string(),
+            resource_type: "person".to_string(),
+            resource_id: Some(id),
+            details: Some(format!(
+                "{}: {} grant(s) revoked, NDAs revoked {:?}, access requests denied {:?}",
+                reason,
+                summary.revoked_grants.len(),
+                summary.revoked_nda_ids,
+                summary.denied_request_ids
+            )),
+            ip_address: None,
+            user_agent: None,
+        },
+        db,
     )
-    .bind(id)
-    .execute(db.inner())
-    .await
-    .map_err(|_| Status::InternalServerError)?;
+    .await;
 
-    if result.rows_affected() == 0 {
-        return Err(Status::NotFound);
-    }
+    Ok(summary)
+}
+
+/// Offboard a person and report everything that was revoked
+#[post("/<id>/offboard", data = "<request>")]
+pub async fn offboard(
+    id: i32,
+    request: Json<OffboardPersonRequest>,
+    db: &State<PgPool>,
+    auth: AuthGuard,
+) -> Result<Json<ApiResponse<OffboardingSummary>>, Status> {
+    request.validate().map_err(|_| Status::BadRequest)?;
+    let reason = request
+        .reason
+        .as_deref()
+        .unwrap_or(DEFAULT_OFFBOARDING_REASON);
+    let summary = run_offboarding(db.inner(), &auth, id, reason).await?;
+    Ok(Json(ApiResponse::success(summary)))
+}
 
+/// Delete (offboard) a person
+#[delete("/<id>")]
+pub async fn delete_person(id: i32, db: &State<PgPool>, auth: AuthGuard) -> Result<Status, Status> {
+    run_offboarding(db.inner(), &auth, id, DEFAULT_OFFBOARDING_REASON).await?;
     Ok(Status::NoContent)
 }
 
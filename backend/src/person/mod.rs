@@ -14,5 +14,6 @@ pub fn routes() -> Vec<rocket::Route> {
         handlers::create_person,
         handlers::update_person,
         handlers::delete_person,
+        handlers::offboard,
     ]
 }
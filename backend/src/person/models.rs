@@ -97,6 +97,12 @@ pub struct UpdatePersonRequest {
     pub position: Option<String>,
 }
 
+#[derive(Debug, Deserialize, Validate)]
+pub struct OffboardPersonRequest {
+    #[validate(length(min = 1, max = 500))]
+    pub reason: Option<String>,
+}
+
 // Helper methods for Person
 impl Person {
     /// Check if this person is a user (has username/password)
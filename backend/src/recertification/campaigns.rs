@@ -0,0 +1,345 @@
+// Recertification campaign logic shared by the handlers and the deadline sweep.
+//
+// snapshot_scope copies the in-scope active grants into recert_items when a
+// campaign opens; revoke_item ends the underlying grant; close_campaign
+// auto-revokes whatever is still pending and marks the campaign CLOSED.
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use chrono::{NaiveDateTime, Utc};
+use sqlx::{PgPool, Postgres, Transaction};
+
+use super::models::{
+    RecertCampaign, RecertCounts, RecertItem, DECISION_AUTO_REVOKED, DECISION_KEEP,
+    DECISION_REVOKE, SCOPE_ALL, SCOPE_CLASSIFICATION, SCOPE_ORGANIZATION, SCOPE_SYSTEM,
+};
+use crate::access::grants::{end_resource_grant, revoke_access_grant, ACCESS_RESOURCE};
+use crate::audit::handlers::create_audit_log;
+use crate::audit::models::CreateAuditLogRequest;
+
+pub const CAMPAIGN_COLUMNS: &str = "id, name, scope_type, scope_value, deadline, status, \
+    created_by_person_id, created_at, closed_at";
+
+pub const ITEM_COLUMNS: &str = "id, campaign_id, grant_type, grant_id, person_id, target, \
+    access_level, decision, reviewed_by_person_id, reviewed_at, note";
+
+// Person belongs to organization $1 via an employee/consultant relation in
+// either direction. Organizations are stored as 'vendor' in relations.
+const PERSON_IN_ORG: &str = "EXISTS (SELECT 1 FROM relations r \
+     WHERE r.relation_type IN ('employee', 'consultant') \
+       AND ((r.entity_type IN ('vendor', 'organization') AND r.entity_id::text = $1 \
+             AND r.related_entity_type = 'person' AND r.related_entity_id = g.person_id) \
+         OR (r.entity_type = 'person' AND r.entity_id = g.person_id \
+             AND r.related_entity_type IN ('vendor', 'organization') \
+             AND r.related_entity_id::text = $1)))";
+
+/// Scope predicate per grant source. $1 is scope_value (NULL for ALL) and $2
+/// the snapshot time. None means the source never matches the scope.
+fn scope_predicate(source: &str, scope_type: &str) -> Option<&'static str> {
+    match (scope_type, source) {
+        (SCOPE_ALL, _) => Some("$1::text IS NULL"),
+        (SCOPE_ORGANIZATION, "resource") => Some(
+            "EXISTS (SELECT 1 FROM resource_org_links l \
+             WHERE l.resource_id = g.resource_id AND l.org_id = $1)",
+        ),
+        (SCOPE_ORGANIZATION, _) => Some(PERSON_IN_ORG),
+        (SCOPE_CLASSIFICATION, "data") => Some("g.data_classification = $1"),
+        (SCOPE_CLASSIFICATION, "resource") => {
+            Some("COALESCE(n.classification, p.classification, ap.classification) = $1")
+        }
+        (SCOPE_SYSTEM, "computer") => Some("g.system_name = $1"),
+        (SCOPE_SYSTEM, "resource") => Some("g.resource_id = $1"),
+        _ => None,
+    }
+}
+
+/// Snapshot every active grant matching the campaign scope. Returns the number
+/// of items created.
+pub async fn snapshot_scope(
+    tx: &mut Transaction<'_, Postgres>,
+    campaign: &RecertCampaign,
+    now: NaiveDateTime,
+) -> Result<u64, sqlx::Error> {
+    // (source, SELECT list and FROM for the grant, active-grant condition)
+    let sources = [
+        (
+            "computer",
+            "'computer', g.id::text, g.person_id::text, g.system_name, g.access_level \
+             FROM computer_access g",
+            "g.status = 'ACTIVE' AND (g.expires_at IS NULL OR g.expires_at > $2)",
+        ),
+        (
+            "data",
+            "'data', g.id::text, g.person_id::text, g.data_classification, g.access_level \
+             FROM data_access g",
+            "g.status = 'ACTIVE' AND (g.expires_at IS NULL OR g.expires_at > $2)",
+        ),
+        (
+            "physical",
+            "'physical', g.id::text, g.person_id::text, g.zone_name, g.access_level \
+             FROM physical_access g",
+            "g.status = 'ACTIVE' AND (g.valid_until IS NULL OR g.valid_until > $2)",
+        ),
+        (
+            "resource",
+            "'resource', g.id, g.person_id, g.resource_id, NULL \
+             FROM resource_access_grants g \
+             LEFT JOIN resource_networks n ON n.id = g.resource_id \
+             LEFT JOIN resource_platforms p ON p.id = g.resource_id \
+             LEFT JOIN resource_applications a ON a.id = g.resource_id \
+             LEFT JOIN resource_platforms ap ON ap.id = a.platform_id",
+            "(g.valid_from IS NULL OR g.valid_from <= $2::timestamp AT TIME ZONE 'UTC') \
+             AND (g.valid_until IS NULL OR g.valid_until > $2::timestamp AT TIME ZONE 'UTC')",
+        ),
+    ];
+
+    let mut created = 0;
+    for (source, select_from, active) in sources {
+        let Some(predicate) = scope_predicate(source, &campaign.scope_type) else {
+            continue;
+        };
+        let sql = format!(
+            "INSERT INTO recert_items \
+             (campaign_id, grant_type, grant_id, person_id, target, access_level) \
+             SELECT $3, {} WHERE {} AND {} \
+             ON CONFLICT (campaign_id, grant_type, grant_id) DO NOTHING",
+            select_from, active, predicate
+        );
+        created += sqlx::query(&sql)
+            .bind(&campaign.scope_value)
+            .bind(now)
+            .bind(campaign.id)
+            .execute(&mut **tx)
+            .await?
+            .rows_affected();
+    }
+    Ok(created)
+}
+
+/// End the grant behind an item. Returns false when it was already inactive.
+pub async fn revoke_item(
+    tx: &mut Transaction<'_, Postgres>,
+    item: &RecertItem,
+    revoked_by_person_id: Option<i32>,
+    reason: &str,
+) -> Result<bool, sqlx::Error> {
+    if item.grant_type == ACCESS_RESOURCE {
+        return end_resource_grant(&mut **tx, &item.grant_id).await;
+    }
+    match item.grant_id.parse::<i32>() {
+        Ok(id) => {
+            revoke_access_grant(
+                &mut **tx,
+                &item.grant_type,
+                id,
+                revoked_by_person_id,
+                Some(reason),
+            )
+            .await
+        }
+        Err(_) => Ok(false),
+    }
+}
+
+async fn audit(db: &PgPool, actor: Option<i32>, action: &str, id: i32, details: String) {
+    let _ = create_audit_log(
+        &CreateAuditLogRequest {
+            person_id: actor,
+            username: actor.map_or_else(|| "system".to_string(), |a| a.to_string()),
+            action: action.to_string(),
+            resource_type: "recert_campaign".to_string(),
+            resource_id: Some(id),
+            details: Some(details),
+            ip_address: None,
+            user_agent: None,
+        },
+        db,
+    )
+    .await;
+}
+
+/// Close an OPEN campaign: revoke every pending item's grant, mark those items
+/// AUTO_REVOKED and the campaign CLOSED. `actor` is None for the deadline
+/// sweep. Returns None when the campaign was not open.
+pub async fn close_campaign(
+    db: &PgPool,
+    campaign_id: i32,
+    actor: Option<i32>,
+) -> Result<Option<RecertCampaign>, sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    let campaign = sqlx::query_as::<_, RecertCampaign>(&format!(
+        "SELECT {} FROM recert_campaigns WHERE id = $1 AND status = 'OPEN' FOR UPDATE",
+        CAMPAIGN_COLUMNS
+    ))
+    .bind(campaign_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    if campaign.is_none() {
+        return Ok(None);
+    }
+
+    let pending = sqlx::query_as::<_, RecertItem>(&format!(
+        "SELECT {} FROM recert_items WHERE campaign_id = $1 AND decision IS NULL ORDER BY id",
+        ITEM_COLUMNS
+    ))
+    .bind(campaign_id)
+    .fetch_all(&mut *tx)
+    .await?;
+    let reason = format!("Not recertified before campaign {} closed", campaign_id);
+    for item in &pending {
+        revoke_item(&mut tx, item, actor, &reason).await?;
+    }
+    sqlx::query(
+        "UPDATE recert_items SET decision = $1, reviewed_at = CURRENT_TIMESTAMP, \
+         note = 'Not reviewed before the campaign closed' \
+         WHERE campaign_id = $2 AND decision IS NULL",
+    )
+    .bind(DECISION_AUTO_REVOKED)
+    .bind(campaign_id)
+    .execute(&mut *tx)
+    .await?;
+
+    let campaign = sqlx::query_as::<_, RecertCampaign>(&format!(
+        "UPDATE recert_campaigns SET status = 'CLOSED', closed_at = CURRENT_TIMESTAMP \
+         WHERE id = $1 RETURNING {}",
+        CAMPAIGN_COLUMNS
+    ))
+    .bind(campaign_id)
+    .fetch_one(&mut *tx)
+    .await?;
+    tx.commit().await?;
+
+    audit(
+        db,
+        actor,
+        "RECERT_CAMPAIGN_CLOSED",
+        campaign.id,
+        format!(
+            "Campaign '{}' closed; {} unreviewed grant(s) auto-revoked",
+            campaign.name,
+            pending.len()
+        ),
+    )
+    .await;
+    Ok(Some(campaign))
+}
+
+/// Close every OPEN campaign whose deadline has passed. Returns how many closed.
+pub async fn close_due_campaigns(db: &PgPool, now: NaiveDateTime) -> Result<usize, sqlx::Error> {
+    let due: Vec<i32> = sqlx::query_scalar(
+        "SELECT id FROM recert_campaigns WHERE status = 'OPEN' AND deadline <= $1 ORDER BY id",
+    )
+    .bind(now)
+    .fetch_all(db)
+    .await?;
+
+    let mut closed = 0;
+    for id in due {
+        if close_campaign(db, id, None).await?.is_some() {
+            closed += 1;
+        }
+    }
+    Ok(closed)
+}
+
+/// Run close_due_campaigns forever on a fixed interval.
+pub async fn run_deadline_task(db: PgPool, interval: Duration) {
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        match close_due_campaigns(&db, Utc::now().naive_utc()).await {
+            Ok(0) => {}
+            Ok(n) => println!("⏳ Closed {} recertification campaign(s)", n),
+            Err(e) => eprintln!("Recertification deadline sweep failed: {:?}", e),
+        }
+    }
+}
+
+fn count(counts: &mut RecertCounts, decision: Option<&str>) {
+    counts.total += 1;
+    match decision {
+        Some(DECISION_KEEP) => counts.kept += 1,
+        Some(DECISION_REVOKE) => counts.revoked += 1,
+        Some(DECISION_AUTO_REVOKED) => counts.auto_revoked += 1,
+        _ => counts.pending += 1,
+    }
+}
+
+/// Totals, per-grant-type counts, and the persons who lost access.
+pub fn summarize(
+    items: &[RecertItem],
+) -> (RecertCounts, BTreeMap<String, RecertCounts>, Vec<String>) {
+    let mut totals = RecertCounts::default();
+    let mut by_type: BTreeMap<String, RecertCounts> = BTreeMap::new();
+    let mut revoked = BTreeSet::new();
+    for item in items {
+        let decision = item.decision.as_deref();
+        count(&mut totals, decision);
+        count(
+            by_type.entry(item.grant_type.clone()).or_default(),
+            decision,
+        );
+        if matches!(
+            decision,
+            Some(DECISION_REVOKE) | Some(DECISION_AUTO_REVOKED)
+        ) {
+            revoked.insert(item.person_id.clone());
+        }
+    }
+    (totals, by_type, revoked.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(grant_type: &str, person_id: &str, decision: Option<&str>) -> RecertItem {
+        RecertItem {
+            id: 0,
+            campaign_id: 1,
+            grant_type: grant_type.to_string(),
+            grant_id: "1".to_string(),
+            person_id: person_id.to_string(),
+            target: "File Server".to_string(),
+            access_level: None,
+            decision: decision.map(str::to_string),
+            reviewed_by_person_id: None,
+            reviewed_at: None,
+            note: None,
+        }
+    }
+
+    #[test]
+    fn test_summarize_counts_by_decision_and_type() {
+        let items = vec![
+            item("computer", "5", Some(DECISION_KEEP)),
+            item("computer", "6", Some(DECISION_REVOKE)),
+            item("data", "6", Some(DECISION_AUTO_REVOKED)),
+            item("resource", "subj-1", None),
+        ];
+        let (totals, by_type, revoked) = summarize(&items);
+        assert_eq!(
+            totals,
+            RecertCounts {
+                total: 4,
+                kept: 1,
+                revoked: 1,
+                auto_revoked: 1,
+                pending: 1
+            }
+        );
+        assert_eq!(by_type["computer"].total, 2);
+        assert_eq!(by_type["resource"].pending, 1);
+        assert_eq!(revoked, vec!["6".to_string()]);
+    }
+
+    #[test]
+    fn test_scope_predicate_coverage() {
+        assert!(scope_predicate("physical", SCOPE_ALL).is_some());
+        assert!(scope_predicate("physical", SCOPE_ORGANIZATION).is_some());
+        assert!(scope_predicate("physical", SCOPE_CLASSIFICATION).is_none());
+        assert!(scope_predicate("data", SCOPE_SYSTEM).is_none());
+        assert!(scope_predicate("resource", SCOPE_SYSTEM).is_some());
+    }
+}
@@ -0,0 +1,323 @@
+// Recertification HTTP handlers (mounted at /api/recertification)
+//
+// Lifecycle: OPEN (items snapshotted, reviewers decide KEEP/REVOKE)
+//         -> CLOSED (deadline sweep or manual close; pending items AUTO_REVOKED)
+use rocket::serde::json::Json;
+use rocket::{get, http::Status, post, State};
+use sqlx::PgPool;
+use validator::Validate;
+
+use super::campaigns::{
+    close_campaign, revoke_item, snapshot_scope, summarize, CAMPAIGN_COLUMNS, ITEM_COLUMNS,
+};
+use super::models::{
+    CreateRecertCampaignRequest, RecertCampaign, RecertCampaignDetail, RecertDecisionRequest,
+    RecertItem, RecertReport, DECISION_REVOKE, SCOPE_ALL,
+};
+use crate::audit::handlers::create_audit_log;
+use crate::audit::models::CreateAuditLogRequest;
+use crate::auth::middleware::AuthGuard;
+use crate::shared::rbac::role_has_permission;
+use crate::shared::response::ApiResponse;
+use crate::sod::enforce::enforce_no_self_action;
+
+fn caller_id(auth: &AuthGuard) -> Result<i32, Status> {
+    auth.claims
+        .sub
+        .parse::<i32>()
+        .map_err(|_| Status::InternalServerError)
+}
+
+async fn require(db: &PgPool, auth: &AuthGuard, key: &str) -> Result<(), Status> {
+    if role_has_permission(db, &auth.claims.role, key)
+        .await
+        .unwrap_or(false)
+    {
+        Ok(())
+    } else {
+        Err(Status::Forbidden)
+    }
+}
+
+async fn fetch_campaign(db: &PgPool, id: i32) -> Result<RecertCampaign, Status> {
+    sqlx::query_as::<_, RecertCampaign>(&format!(
+        "SELECT {} FROM recert_campaigns WHERE id = $1",
+        CAMPAIGN_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(db)
+    .await
+    .map_err(|_| Status::InternalServerError)?
+    .ok_or(Status::NotFound)
+}
+
+async fn fetch_items(db: &PgPool, campaign_id: i32) -> Result<Vec<RecertItem>, Status> {
+    sqlx::query_as::<_, RecertItem>(&format!(
+        "SELECT {} FROM recert_items WHERE campaign_id = $1 ORDER BY grant_type, person_id, id",
+        ITEM_COLUMNS
+    ))
+    .bind(campaign_id)
+    .fetch_all(db)
+    .await
+    .map_err(|_| Status::InternalServerError)
+}
+
+async fn audit(db: &PgPool, actor: i32, action: &str, campaign_id: i32, details: String) {
+    let _ = create_audit_log(
+        &CreateAuditLogRequest {
+            person_id: Some(actor),
+            username: actor.to_string(),
+            action: action.to_string(),
+            resource_type: "recert_campaign".to_string(),
+            resource_id: Some(campaign_id),
+            details: Some(details),
+            ip_address: None,
+            user_agent: None,
+        },
+        db,
+    )
+    .await;
+}
+
+#[get("/")]
+pub async fn list_campaigns(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+) -> Result<Json<ApiResponse<Vec<RecertCampaign>>>, Status> {
+    require(db.inner(), &auth, "recert.review").await?;
+    let campaigns = sqlx::query_as::<_, RecertCampaign>(&format!(
+        "SELECT {} FROM recert_campaigns ORDER BY created_at DESC",
+        CAMPAIGN_COLUMNS
+    ))
+    .fetch_all(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    Ok(Json(ApiResponse::success(campaigns)))
+}
+
+/// Open a campaign and snapshot the in-scope active grants in one transaction.
+#[post("/", data = "<data>")]
+pub async fn create_campaign(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    data: Json<CreateRecertCampaignRequest>,
+) -> Result<Json<ApiResponse<RecertCampaignDetail>>, Status> {
+    require(db.inner(), &auth, "recert.manage").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    // ALL takes no value; every other scope needs one.
+    if (data.scope_type == SCOPE_ALL) != data.scope_value.is_none() {
+        return Err(Status::BadRequest);
+    }
+    let now = chrono::Utc::now().naive_utc();
+    if data.deadline <= now {
+        return Err(Status::BadRequest);
+    }
+    let caller = caller_id(&auth)?;
+
+    let mut tx = db
+        .inner()
+        .begin()
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let campaign = sqlx::query_as::<_, RecertCampaign>(&format!(
+        "INSERT INTO recert_campaigns (name, scope_type, scope_value, deadline, created_by_person_id) \
+         VALUES ($1, $2, $3, $4, $5) RETURNING {}",
+        CAMPAIGN_COLUMNS
+    ))
+    .bind(&data.name)
+    .bind(&data.scope_type)
+    .bind(&data.scope_value)
+    .bind(data.deadline)
+    .bind(caller)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?;
+    let item_count = snapshot_scope(&mut tx, &campaign, now).await.map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })? as usize;
+    tx.commit().await.map_err(|_| Status::InternalServerError)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "RECERT_CAMPAIGN_OPENED",
+        campaign.id,
+        format!(
+            "Campaign '{}' over {} {} with {} grant(s), deadline {}",
+            campaign.name,
+            campaign.scope_type,
+            campaign.scope_value.as_deref().unwrap_or("-"),
+            item_count,
+            campaign.deadline
+        ),
+    )
+    .await;
+
+    Ok(Json(ApiResponse::success(RecertCampaignDetail {
+        campaign,
+        item_count,
+    })))
+}
+
+#[get("/<id>")]
+pub async fn get_campaign(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<RecertCampaignDetail>>, Status> {
+    require(db.inner(), &auth, "recert.review").await?;
+    let campaign = fetch_campaign(db.inner(), id).await?;
+    let item_count = fetch_items(db.inner(), id).await?.len();
+    Ok(Json(ApiResponse::success(RecertCampaignDetail {
+        campaign,
+        item_count,
+    })))
+}
+
+/// Items to review; `pending=true` limits to undecided ones.
+#[get("/<id>/items?<pending>")]
+pub async fn list_items(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    pending: Option<bool>,
+) -> Result<Json<ApiResponse<Vec<RecertItem>>>, Status> {
+    require(db.inner(), &auth, "recert.review").await?;
+    fetch_campaign(db.inner(), id).await?;
+    let mut items = fetch_items(db.inner(), id).await?;
+    if pending.unwrap_or(false) {
+        items.retain(|i| i.decision.is_none());
+    }
+    Ok(Json(ApiResponse::success(items)))
+}
+
+/// Record KEEP or REVOKE for a pending item. REVOKE ends the grant immediately.
+#[post("/<id>/items/<item_id>/decision", data = "<data>")]
+pub async fn decide_item(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    item_id: i32,
+    data: Json<RecertDecisionRequest>,
+) -> Result<Json<ApiResponse<RecertItem>>, Status> {
+    require(db.inner(), &auth, "recert.review").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    let caller = caller_id(&auth)?;
+
+    let mut tx = db
+        .inner()
+        .begin()
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let open: Option<bool> =
+        sqlx::query_scalar("SELECT status = 'OPEN' FROM recert_campaigns WHERE id = $1 FOR SHARE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|_| Status::InternalServerError)?;
+    match open {
+        None => return Err(Status::NotFound),
+        Some(false) => return Err(Status::Conflict),
+        Some(true) => {}
+    }
+    let item = sqlx::query_as::<_, RecertItem>(&format!(
+        "SELECT {} FROM recert_items WHERE id = $1 AND campaign_id = $2 FOR UPDATE",
+        ITEM_COLUMNS
+    ))
+    .bind(item_id)
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|_| Status::InternalServerError)?
+    .ok_or(Status::NotFound)?;
+    if item.decision.is_some() {
+        return Err(Status::Conflict);
+    }
+    enforce_no_self_action(
+        db.inner(),
+        "recert.review",
+        &caller.to_string(),
+        &item.person_id,
+    )
+    .await?;
+
+    if data.decision == DECISION_REVOKE {
+        let reason = data
+            .note
+            .clone()
+            .unwrap_or_else(|| format!("Revoked in recertification campaign {}", id));
+        revoke_item(&mut tx, &item, Some(caller), &reason)
+            .await
+            .map_err(|_| Status::InternalServerError)?;
+    }
+    let item = sqlx::query_as::<_, RecertItem>(&format!(
+        "UPDATE recert_items \
+         SET decision = $1, reviewed_by_person_id = $2, reviewed_at = CURRENT_TIMESTAMP, note = $3 \
+         WHERE id = $4 RETURNING {}",
+        ITEM_COLUMNS
+    ))
+    .bind(&data.decision)
+    .bind(caller)
+    .bind(&data.note)
+    .bind(item_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    tx.commit().await.map_err(|_| Status::InternalServerError)?;
+
+    audit(
+        db.inner(),
+        caller,
+        &format!("RECERT_{}", item.decision.as_deref().unwrap_or_default()),
+        id,
+        format!(
+            "{} grant {} ('{}') of person {}",
+            item.grant_type, item.grant_id, item.target, item.person_id
+        ),
+    )
+    .await;
+
+    Ok(Json(ApiResponse::success(item)))
+}
+
+/// Close a campaign before its deadline; pending items are auto-revoked.
+#[post("/<id>/close")]
+pub async fn close(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<RecertCampaign>>, Status> {
+    require(db.inner(), &auth, "recert.manage").await?;
+    let caller = caller_id(&auth)?;
+    fetch_campaign(db.inner(), id).await?;
+    let campaign = close_campaign(db.inner(), id, Some(caller))
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            Status::InternalServerError
+        })?
+        .ok_or(Status::Conflict)?;
+    Ok(Json(ApiResponse::success(campaign)))
+}
+
+#[get("/<id>/report")]
+pub async fn get_report(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<RecertReport>>, Status> {
+    require(db.inner(), &auth, "recert.review").await?;
+    let campaign = fetch_campaign(db.inner(), id).await?;
+    let items = fetch_items(db.inner(), id).await?;
+    let (totals, by_grant_type, revoked_person_ids) = summarize(&items);
+    Ok(Json(ApiResponse::success(RecertReport {
+        campaign,
+        totals,
+        by_grant_type,
+        revoked_person_ids,
+    })))
+}
@@ -0,0 +1,18 @@
+// Access recertification module
+// Periodic review campaigns over current grants, with deadline auto-revocation
+
+pub mod campaigns;
+pub mod handlers;
+pub mod models;
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![
+        handlers::list_campaigns,
+        handlers::create_campaign,
+        handlers::get_campaign,
+        handlers::list_items,
+        handlers::decide_item,
+        handlers::close,
+        handlers::get_report,
+    ]
+}
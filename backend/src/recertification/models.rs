@@ -0,0 +1,103 @@
+// Recertification campaign data models
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+pub const SCOPE_ALL: &str = "ALL";
+pub const SCOPE_ORGANIZATION: &str = "ORGANIZATION";
+pub const SCOPE_CLASSIFICATION: &str = "CLASSIFICATION";
+pub const SCOPE_SYSTEM: &str = "SYSTEM";
+
+pub const DECISION_KEEP: &str = "KEEP";
+pub const DECISION_REVOKE: &str = "REVOKE";
+pub const DECISION_AUTO_REVOKED: &str = "AUTO_REVOKED";
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RecertCampaign {
+    pub id: i32,
+    pub name: String,
+    pub scope_type: String, // ALL, ORGANIZATION, CLASSIFICATION, SYSTEM
+    pub scope_value: Option<String>, // None only for ALL
+    pub deadline: chrono::NaiveDateTime,
+    pub status: String, // OPEN, CLOSED
+    pub created_by_person_id: i32,
+    pub created_at: chrono::NaiveDateTime,
+    pub closed_at: Option<chrono::NaiveDateTime>,
+}
+
+/// One grant snapshotted into a campaign for review
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct RecertItem {
+    pub id: i32,
+    pub campaign_id: i32,
+    pub grant_type: String, // computer, data, physical, resource
+    pub grant_id: String,
+    pub person_id: String,
+    pub target: String,
+    pub access_level: Option<String>,
+    pub decision: Option<String>, // None = pending; KEEP, REVOKE, AUTO_REVOKED
+    pub reviewed_by_person_id: Option<i32>,
+    pub reviewed_at: Option<chrono::NaiveDateTime>,
+    pub note: Option<String>,
+}
+
+fn validate_scope_type(scope_type: &str) -> Result<(), ValidationError> {
+    match scope_type {
+        SCOPE_ALL | SCOPE_ORGANIZATION | SCOPE_CLASSIFICATION | SCOPE_SYSTEM => Ok(()),
+        _ => Err(ValidationError::new("invalid_scope_type")),
+    }
+}
+
+fn validate_decision(decision: &str) -> Result<(), ValidationError> {
+    match decision {
+        DECISION_KEEP | DECISION_REVOKE => Ok(()),
+        _ => Err(ValidationError::new("invalid_decision")),
+    }
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateRecertCampaignRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub name: String,
+
+    #[validate(custom = "validate_scope_type")]
+    pub scope_type: String,
+
+    #[validate(length(min = 1, max = 100))]
+    pub scope_value: Option<String>,
+
+    pub deadline: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RecertDecisionRequest {
+    #[validate(custom = "validate_decision")]
+    pub decision: String,
+
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecertCampaignDetail {
+    pub campaign: RecertCampaign,
+    pub item_count: usize,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct RecertCounts {
+    pub total: usize,
+    pub kept: usize,
+    pub revoked: usize,
+    pub auto_revoked: usize,
+    pub pending: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecertReport {
+    pub campaign: RecertCampaign,
+    pub totals: RecertCounts,
+    pub by_grant_type: BTreeMap<String, RecertCounts>,
+    /// Persons who lost at least one grant in this campaign
+    pub revoked_person_ids: Vec<String>,
+}
@@ -0,0 +1,148 @@
+// Release policy HTTP handlers (mounted at /api/release-policies)
+//
+// An organization's policy history is readable under organizations.read;
+// setting a policy needs policies.manage, records the next version and is
+// audited. Releases read the policy in force directly (see policy), so a new
+// version applies to the next release decision.
+use rocket::serde::json::Json;
+use rocket::{get, http::Status, put, State};
+use sqlx::PgPool;
+use validator::Validate;
+
+use super::models::{ReleasePolicy, ReleasePolicyHistory, SetReleasePolicyRequest};
+use super::policy::POLICY_COLUMNS;
+use crate::audit::handlers::audit;
+use crate::auth::middleware::AuthGuard;
+use crate::clearances::lifecycle::valid_level;
+use crate::shared::rbac::require_permission;
+use crate::shared::response::ApiResponse;
+
+/// Audit details for a new policy version: its rules and floor.
+fn describe(policy: &ReleasePolicy) -> String {
+    let disabled: Vec<&str> = [
+        ("clearance", policy.clearance_rule),
+        ("domain tier", policy.domain_tier_rule),
+        ("need-to-know", policy.need_to_know_rule),
+        ("affiliation", policy.affiliation_rule),
+    ]
+    .iter()
+    .filter(|(_, enabled)| !enabled)
+    .map(|(name, _)| *name)
+    .collect();
+    format!(
+        "v{} \"{}\": {}{}",
+        policy.version,
+        policy.label,
+        if disabled.is_empty() {
+            "all rules".to_string()
+        } else {
+            format!("without {}", disabled.join(", "))
+        },
+        policy
+            .min_clearance_floor
+            .as_deref()
+            .map(|floor| format!(", floor {}", floor))
+            .unwrap_or_default()
+    )
+}
+
+fn db_error(e: sqlx::Error) -> Status {
+    eprintln!("Database error: {:?}", e);
+    Status::InternalServerError
+}
+
+/// An organization's release policy in force and every version, newest
+/// first. 404 for an unknown or deleted organization.
+#[get("/organizations/<id>")]
+pub async fn get_release_policy(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<ReleasePolicyHistory>>, Status> {
+    require_permission(db.inner(), &auth, "organizations.read").await?;
+    sqlx::query_scalar::<_, i32>(
+        "SELECT id FROM organizations WHERE id = $1 AND deleted_at IS NULL",
+    )
+    .bind(id)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(db_error)?
+    .ok_or(Status::NotFound)?;
+    let versions = sqlx::query_as::<_, ReleasePolicy>(&format!(
+        "SELECT {} FROM release_policies WHERE organization_id = $1 ORDER BY version DESC",
+        POLICY_COLUMNS
+    ))
+    .bind(id)
+    .fetch_all(db.inner())
+    .await
+    .map_err(db_error)?;
+    Ok(Json(ApiResponse::success(ReleasePolicyHistory {
+        organization_id: id,
+        current: versions.first().cloned(),
+        versions,
+    })))
+}
+
+/// Record the next version of an organization's release policy. 400 for an
+/// unknown clearance floor, 404 for an unknown or deleted organization.
+#[put("/organizations/<id>", data = "<data>")]
+pub async fn set_release_policy(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    data: Json<SetReleasePolicyRequest>,
+) -> Result<Json<ApiResponse<ReleasePolicy>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "policies.manage").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    if data
+        .min_clearance_floor
+        .as_deref()
+        .is_some_and(|floor| !valid_level(floor))
+    {
+        return Err(Status::BadRequest);
+    }
+
+    // Locking the organization serializes concurrent changes, so versions
+    // stay consecutive.
+    let mut tx = db.inner().begin().await.map_err(db_error)?;
+    sqlx::query_scalar::<_, i32>(
+        "SELECT id FROM organizations WHERE id = $1 AND deleted_at IS NULL FOR UPDATE",
+    )
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(db_error)?
+    .ok_or(Status::NotFound)?;
+    let policy = sqlx::query_as::<_, ReleasePolicy>(&format!(
+        "INSERT INTO release_policies \
+           (organization_id, version, label, clearance_rule, domain_tier_rule, \
+            need_to_know_rule, affiliation_rule, min_clearance_floor, created_by_person_id) \
+         SELECT $1, COALESCE(MAX(version), 0) + 1, $2, $3, $4, $5, $6, $7, $8 \
+         FROM release_policies WHERE organization_id = $1 \
+         RETURNING {}",
+        POLICY_COLUMNS
+    ))
+    .bind(id)
+    .bind(&data.label)
+    .bind(data.clearance_rule)
+    .bind(data.domain_tier_rule)
+    .bind(data.need_to_know_rule)
+    .bind(data.affiliation_rule)
+    .bind(data.min_clearance_floor.as_deref())
+    .bind(caller)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(db_error)?;
+    tx.commit().await.map_err(db_error)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "RELEASE_POLICY_CHANGED",
+        "release_policies",
+        Some(policy.organization_id),
+        describe(&policy),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(policy)))
+}
@@ -0,0 +1,11 @@
+// Release policies module
+// Versioned per-organization release policies (rule toggles and clearance
+// floor) applied when another organization asks for a person's record
+
+pub mod handlers;
+pub mod models;
+pub mod policy;
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![handlers::get_release_policy, handlers::set_release_policy]
+}
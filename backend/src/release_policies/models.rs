@@ -0,0 +1,42 @@
+// Release policy data models
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// One version of an organization's release policy; the highest version is
+/// in force
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ReleasePolicy {
+    pub id: i32,
+    pub organization_id: i32,
+    pub version: i32,
+    pub label: String,
+    pub clearance_rule: bool,
+    pub domain_tier_rule: bool,
+    pub need_to_know_rule: bool,
+    pub affiliation_rule: bool,
+    pub min_clearance_floor: Option<String>,
+    pub created_by_person_id: i32,
+    pub created_at: NaiveDateTime,
+}
+
+/// An organization's policy in force (None = the standard policy) and every
+/// version, newest first
+#[derive(Debug, Serialize)]
+pub struct ReleasePolicyHistory {
+    pub organization_id: i32,
+    pub current: Option<ReleasePolicy>,
+    pub versions: Vec<ReleasePolicy>,
+}
+
+/// The next version of an organization's release policy
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetReleasePolicyRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub label: String,
+    pub clearance_rule: bool,
+    pub domain_tier_rule: bool,
+    pub need_to_know_rule: bool,
+    pub affiliation_rule: bool,
+    pub min_clearance_floor: Option<String>,
+}
@@ -0,0 +1,42 @@
+// Release policy lookup for decisions.
+//
+// The policy in force is an organization's highest version; decisions turn it
+// into the evaluator's EntityPolicy. No policy on record means the standard
+// policy (EntityPolicy::default()).
+use sqlx::PgPool;
+
+use super::models::ReleasePolicy;
+use crate::abac::evaluator::{EntityPolicy, PolicyRules};
+
+pub const POLICY_COLUMNS: &str = "id, organization_id, version, label, clearance_rule, \
+                                  domain_tier_rule, need_to_know_rule, affiliation_rule, \
+                                  min_clearance_floor, created_by_person_id, created_at";
+
+impl ReleasePolicy {
+    pub fn entity_policy(&self) -> EntityPolicy {
+        EntityPolicy {
+            rules: PolicyRules {
+                clearance: self.clearance_rule,
+                domain_tier: self.domain_tier_rule,
+                need_to_know: self.need_to_know_rule,
+                affiliation: self.affiliation_rule,
+            },
+            min_clearance_floor: self.min_clearance_floor.clone(),
+        }
+    }
+}
+
+/// The release policy in force for `organization_id`, if one was ever set.
+pub async fn load_release_policy(
+    db: &PgPool,
+    organization_id: i32,
+) -> Result<Option<ReleasePolicy>, sqlx::Error> {
+    sqlx::query_as::<_, ReleasePolicy>(&format!(
+        "SELECT {} FROM release_policies WHERE organization_id = $1 \
+         ORDER BY version DESC LIMIT 1",
+        POLICY_COLUMNS
+    ))
+    .bind(organization_id)
+    .fetch_optional(db)
+    .await
+}
@@ -8,6 +8,7 @@ use super::models::{
 use crate::auth::middleware::AuthGuard;
 use crate::shared::error::AppError;
 use crate::shared::rbac::role_has_permission;
+use crate::sod::enforce::conflicting_rule_names;
 
 #[get("/")]
 pub async fn list_roles(db: &State<PgPool>, auth: AuthGuard) -> Result<Json<Vec<Role>>, AppError> {
@@ -160,6 +161,15 @@ pub async fn set_role_permissions(
     {
         return Err(AppError::Forbidden);
     }
+    // SoD: refuse a permission set that breaks a CONFLICTING_PERMISSIONS rule.
+    let conflicts = conflicting_rule_names(db.inner(), &req.permissions).await?;
+    if !conflicts.is_empty() {
+        eprintln!(
+            "SoD: role {} permission set rejected by {:?}",
+            id, conflicts
+        );
+        return Err(AppError::BadRequest);
+    }
     let mut tx = db.inner().begin().await.map_err(|_| AppError::Internal)?;
     sqlx::query!("DELETE FROM role_permissions WHERE role_id = $1", id)
         .execute(&mut *tx)
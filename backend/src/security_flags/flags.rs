@@ -0,0 +1,54 @@
+// Loading a person's uncleared security flags for the decision paths.
+use sqlx::PgExecutor;
+
+use super::models::{FLAG_REVOKED, FLAG_SECURITY_HOLD};
+use crate::abac::evaluator::SubjectFlags;
+
+/// Whether `flag` is one of the known flags.
+pub fn valid_flag(flag: &str) -> bool {
+    flag == FLAG_SECURITY_HOLD || flag == FLAG_REVOKED
+}
+
+/// The evaluator's flags for a set of uncleared flag names.
+pub fn subject_flags(active: &[String]) -> SubjectFlags {
+    SubjectFlags {
+        revoked: active.iter().any(|f| f == FLAG_REVOKED),
+        security_hold: active.iter().any(|f| f == FLAG_SECURITY_HOLD),
+    }
+}
+
+/// The person's uncleared flags, ordered by name.
+pub async fn active_flags<'e>(
+    executor: impl PgExecutor<'e>,
+    person_id: i32,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT flag FROM person_security_flags \
+         WHERE person_id = $1 AND cleared_at IS NULL ORDER BY flag",
+    )
+    .bind(person_id)
+    .fetch_all(executor)
+    .await
+}
+
+/// The evaluator's flags for the person as stored now.
+pub async fn load_subject_flags<'e>(
+    executor: impl PgExecutor<'e>,
+    person_id: i32,
+) -> Result<SubjectFlags, sqlx::Error> {
+    Ok(subject_flags(&active_flags(executor, person_id).await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subject_flags_from_names() {
+        let none = subject_flags(&[]);
+        assert!(!none.revoked && !none.security_hold);
+        let both = subject_flags(&["REVOKED".to_string(), "SECURITY_HOLD".to_string()]);
+        assert!(both.revoked && both.security_hold);
+        assert!(!valid_flag("FROZEN"));
+    }
+}
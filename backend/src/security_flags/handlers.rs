@@ -0,0 +1,184 @@
+// Security flag HTTP handlers (mounted at /api/security-flags)
+//
+// Setting and clearing flags needs security.flags; nobody may flag or unflag
+// themselves (SoD). A person's flags are readable under person.read. Flags
+// take effect on the next decision: nothing is cached. Attribute credentials
+// are checked offline, so setting a flag revokes the person's open ones; they
+// stay revoked when the flag is cleared.
+use rocket::serde::json::Json;
+use rocket::{delete, get, http::Status, post, State};
+use sqlx::PgPool;
+use validator::Validate;
+
+use super::flags::valid_flag;
+use super::models::{PersonSecurityFlag, SetSecurityFlagRequest};
+use crate::access::revocation::{audit_revoked_grants, revoke_open_credentials};
+use crate::audit::handlers::audit;
+use crate::auth::middleware::AuthGuard;
+use crate::shared::rbac::require_permission;
+use crate::shared::response::ApiResponse;
+use crate::sod::enforce::enforce_no_self_action;
+
+const FLAG_COLUMNS: &str = "id, person_id, flag, reason, set_by_person_id, set_at, \
+                            cleared_by_person_id, cleared_at, clear_reason";
+
+/// Every uncleared flag, newest first.
+#[get("/")]
+pub async fn list_active_flags(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+) -> Result<Json<ApiResponse<Vec<PersonSecurityFlag>>>, Status> {
+    require_permission(db.inner(), &auth, "security.flags").await?;
+    let flags = sqlx::query_as::<_, PersonSecurityFlag>(&format!(
+        "SELECT {} FROM person_security_flags WHERE cleared_at IS NULL \
+         ORDER BY set_at DESC, id DESC",
+        FLAG_COLUMNS
+    ))
+    .fetch_all(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    Ok(Json(ApiResponse::success(flags)))
+}
+
+/// A person's uncleared flags, or their full flag history with
+/// `include_cleared=true`.
+#[get("/persons/<id>?<include_cleared>")]
+pub async fn list_person_flags(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    include_cleared: Option<bool>,
+) -> Result<Json<ApiResponse<Vec<PersonSecurityFlag>>>, Status> {
+    require_permission(db.inner(), &auth, "person.read").await?;
+    let flags = sqlx::query_as::<_, PersonSecurityFlag>(&format!(
+        "SELECT {} FROM person_security_flags \
+         WHERE person_id = $1 AND ($2 OR cleared_at IS NULL) \
+         ORDER BY set_at DESC, id DESC",
+        FLAG_COLUMNS
+    ))
+    .bind(id)
+    .bind(include_cleared.unwrap_or(false))
+    .fetch_all(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    Ok(Json(ApiResponse::success(flags)))
+}
+
+/// Set a flag and revoke the person's open attribute credentials. 400 for an
+/// unknown flag or missing reason, 404 for an unknown or offboarded person,
+/// 409 when the flag is already set.
+#[post("/persons/<id>", data = "<data>")]
+pub async fn set_flag(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    data: Json<SetSecurityFlagRequest>,
+) -> Result<Json<ApiResponse<PersonSecurityFlag>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "security.flags").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    if !valid_flag(&data.flag) {
+        return Err(Status::BadRequest);
+    }
+    enforce_no_self_action(db.inner(), "security.flags", &caller, &id).await?;
+
+    let person_exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM person WHERE id = $1 AND deleted_at IS NULL)",
+    )
+    .bind(id)
+    .fetch_one(db.inner())
+    .await
+    .map_err(|_| Status::InternalServerError)?;
+    if !person_exists {
+        return Err(Status::NotFound);
+    }
+
+    let mut tx = db
+        .inner()
+        .begin()
+        .await
+        .map_err(|_| Status::InternalServerError)?;
+    let flag = sqlx::query_as::<_, PersonSecurityFlag>(&format!(
+        "INSERT INTO person_security_flags (person_id, flag, reason, set_by_person_id) \
+         VALUES ($1, $2, $3, $4) \
+         ON CONFLICT (person_id, flag) WHERE cleared_at IS NULL DO NOTHING \
+         RETURNING {}",
+        FLAG_COLUMNS
+    ))
+    .bind(id)
+    .bind(&data.flag)
+    .bind(&data.reason)
+    .bind(caller)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?
+    .ok_or(Status::Conflict)?;
+    let reason = format!("{} set: {}", flag.flag, flag.reason);
+    let revoked = revoke_open_credentials(&mut tx, id, caller, &reason, None)
+        .await
+        .map_err(|e| {
+            eprintln!("Database error: {:?}", e);
+            Status::InternalServerError
+        })?;
+    tx.commit().await.map_err(|_| Status::InternalServerError)?;
+
+    audit_revoked_grants(db.inner(), caller, id, &revoked, &reason).await;
+    audit(
+        db.inner(),
+        caller,
+        "SECURITY_FLAG_SET",
+        "person",
+        Some(id),
+        reason,
+    )
+    .await;
+    Ok(Json(ApiResponse::success(flag)))
+}
+
+/// Clear a flag, recording who cleared it and why. 404 when it is not set.
+#[delete("/persons/<id>/<flag>?<reason>")]
+pub async fn clear_flag(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    flag: &str,
+    reason: Option<String>,
+) -> Result<Json<ApiResponse<PersonSecurityFlag>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "security.flags").await?;
+    enforce_no_self_action(db.inner(), "security.flags", &caller, &id).await?;
+
+    let cleared = sqlx::query_as::<_, PersonSecurityFlag>(&format!(
+        "UPDATE person_security_flags SET cleared_at = CURRENT_TIMESTAMP, \
+           cleared_by_person_id = $3, clear_reason = $4 \
+         WHERE person_id = $1 AND flag = $2 AND cleared_at IS NULL RETURNING {}",
+        FLAG_COLUMNS
+    ))
+    .bind(id)
+    .bind(flag)
+    .bind(caller)
+    .bind(reason.as_deref())
+    .fetch_optional(db.inner())
+    .await
+    .map_err(|e| {
+        eprintln!("Database error: {:?}", e);
+        Status::InternalServerError
+    })?
+    .ok_or(Status::NotFound)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "SECURITY_FLAG_CLEARED",
+        "person",
+        Some(id),
+        format!(
+            "{} cleared: {}",
+            cleared.flag,
+            reason.as_deref().unwrap_or("no reason given")
+        ),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(cleared)))
+}
@@ -0,0 +1,15 @@
+// Security flags module
+// Security hold / revocation flags that force every access decision to DENY
+
+pub mod flags;
+pub mod handlers;
+pub mod models;
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![
+        handlers::list_active_flags,
+        handlers::list_person_flags,
+        handlers::set_flag,
+        handlers::clear_flag,
+    ]
+}
@@ -0,0 +1,28 @@
+// Security flag data models
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+pub const FLAG_SECURITY_HOLD: &str = "SECURITY_HOLD";
+pub const FLAG_REVOKED: &str = "REVOKED";
+
+/// One flag on a person; uncleared while `cleared_at` is None
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PersonSecurityFlag {
+    pub id: i32,
+    pub person_id: i32,
+    pub flag: String, // SECURITY_HOLD, REVOKED
+    pub reason: String,
+    pub set_by_person_id: i32,
+    pub set_at: NaiveDateTime,
+    pub cleared_by_person_id: Option<i32>,
+    pub cleared_at: Option<NaiveDateTime>,
+    pub clear_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SetSecurityFlagRequest {
+    pub flag: String,
+    #[validate(length(min = 1, max = 1000))]
+    pub reason: String,
+}
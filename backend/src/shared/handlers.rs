@@ -1,4 +1,5 @@
 // Shared HTTP handlers (stats, health, etc.)
+use chrono::Utc;
 use rocket::serde::json::Json;
 use rocket::{get, http::Status, State};
 use serde::Serialize;
@@ -52,15 +53,20 @@ pub async fn get_stats(
     .map_err(|_| Status::InternalServerError)?
     .unwrap_or(0);
 
-    // Get active access grants
+    // Get active access grants (past-expiry rows are inactive even before the sweep runs)
+    let now = Utc::now().naive_utc();
     let active_access_grants: i64 = sqlx::query_scalar!(
         "SELECT COUNT(*) FROM (
-            SELECT id FROM computer_access WHERE status = 'ACTIVE'
+            SELECT id FROM computer_access
+            WHERE status = 'ACTIVE' AND (expires_at IS NULL OR expires_at > $1)
             UNION ALL
-            SELECT id FROM data_access WHERE status = 'ACTIVE'
+            SELECT id FROM data_access
+            WHERE status = 'ACTIVE' AND (expires_at IS NULL OR expires_at > $1)
             UNION ALL
-            SELECT id FROM physical_access WHERE status = 'ACTIVE'
-        ) as all_active_access"
+            SELECT id FROM physical_access
+            WHERE status = 'ACTIVE' AND (valid_until IS NULL OR valid_until > $1)
+        ) as all_active_access",
+        now
     )
     .fetch_one(db.inner())
     .await
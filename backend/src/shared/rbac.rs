@@ -1,5 +1,8 @@
+use rocket::http::Status;
 use sqlx::PgPool;
 
+use crate::auth::middleware::AuthGuard;
+
 pub async fn role_has_permission(
     db: &PgPool,
     role_name: &str,
@@ -24,3 +27,22 @@ pub async fn role_has_permission(
 
     Ok(exists)
 }
+
+/// Require `perm_key` for the caller's role (403 otherwise) and return the
+/// caller's person id.
+pub async fn require_permission(
+    db: &PgPool,
+    auth: &AuthGuard,
+    perm_key: &str,
+) -> Result<i32, Status> {
+    if !role_has_permission(db, &auth.claims.role, perm_key)
+        .await
+        .unwrap_or(false)
+    {
+        return Err(Status::Forbidden);
+    }
+    auth.claims
+        .sub
+        .parse::<i32>()
+        .map_err(|_| Status::InternalServerError)
+}
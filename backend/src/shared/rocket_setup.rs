@@ -6,8 +6,11 @@ use std::env;
 
 // Import all needed modules - these must be available when compiled as lib
 use crate::{
-    access, audit, auth, digital_resources, discussions, document_references, info_systems,
-    messaging, nda, organizations, person, relations, roles, shared, vendor_relations,
+    abac, access, access_bulk, access_bundles, access_requests, audit, auth, authorizations,
+    clearances, compartments, credentials, decision_log, deployments, digital_resources,
+    discussions, document_references, federation, info_systems, messaging, nda, organizations,
+    person, recertification, relations, release_policies, roles, security_flags, shared,
+    sharing_agreements, sod, vendor_relations,
 };
 
 /// Validate a raw JWT secret value: present and non-empty after trimming (SEC-03).
@@ -28,6 +31,16 @@ pub fn read_jwt_secret() -> Result<String, String> {
 pub async fn create_rocket() -> rocket::Rocket<rocket::Build> {
     // Load environment variables
     dotenvy::dotenv().ok();
+    create_rocket_with_federation(federation::client::FederationConfig::from_env()).await
+}
+
+/// As create_rocket, but taking part in the federation as `federation_config`
+/// rather than as configured in the environment (a second instance in one
+/// process).
+pub async fn create_rocket_with_federation(
+    federation_config: federation::client::FederationConfig,
+) -> rocket::Rocket<rocket::Build> {
+    dotenvy::dotenv().ok();
 
     // Get database URL (with test fallback)
     let database_url = env::var("DATABASE_URL").unwrap_or_else(|_| {
@@ -68,6 +81,24 @@ pub async fn create_rocket() -> rocket::Rocket<rocket::Build> {
         }
     });
 
+    // Background sweep that moves lapsed access grants to EXPIRED
+    let expiry_interval =
+        access::expiry::expiry_interval(env::var("ACCESS_EXPIRY_INTERVAL_SECS").ok());
+    tokio::spawn(access::expiry::run_expiry_task(
+        db_pool.clone(),
+        expiry_interval,
+    ));
+    // Recertification campaigns past their deadline are closed on the same cadence
+    tokio::spawn(recertification::campaigns::run_deadline_task(
+        db_pool.clone(),
+        expiry_interval,
+    ));
+    // Decision log records past their retention are purged on the same cadence
+    tokio::spawn(decision_log::log::run_retention_task(
+        db_pool.clone(),
+        expiry_interval,
+    ));
+
     if cfg!(not(test)) {
         println!("✅ Database connected");
         println!("✅ JWT secret loaded");
@@ -107,6 +138,7 @@ pub async fn create_rocket() -> rocket::Rocket<rocket::Build> {
         .manage(db_pool)
         .manage(jwt_secret)
         .manage(ws_manager.clone())
+        .manage(federation_config)
         .attach(cors)
         .mount(
             "/",
@@ -126,9 +158,14 @@ pub async fn create_rocket() -> rocket::Rocket<rocket::Build> {
                 access::handlers::grant_data_access,
                 access::handlers::grant_physical_access,
                 access::handlers::list_person_access,
+                access::handlers::get_person_access_timeline,
                 access::handlers::revoke_access,
+                access::handlers::list_clearance_violations,
                 info_systems::handlers::list_info_systems,
                 info_systems::handlers::get_info_system,
+                info_systems::handlers::list_info_system_access,
+                info_systems::handlers::get_info_system_shielding,
+                info_systems::handlers::set_info_system_shielding,
                 info_systems::handlers::create_info_system,
                 info_systems::handlers::update_info_system,
                 info_systems::handlers::delete_info_system,
@@ -141,5 +178,21 @@ pub async fn create_rocket() -> rocket::Rocket<rocket::Build> {
         .mount("/api/discussions", discussions::routes())
         .mount("/api/document-references", document_references::routes())
         .mount("/api/digital-resources", digital_resources::routes())
+        .mount("/api/sod", sod::routes())
+        .mount("/api/access-requests", access_requests::routes())
+        .mount("/api/recertification", recertification::routes())
+        .mount("/api/access/bulk", access_bulk::routes())
+        .mount("/api/access-bundles", access_bundles::routes())
+        .mount("/api/abac", abac::routes())
+        .mount("/api/authorizations", authorizations::routes())
+        .mount("/api/clearances", clearances::routes())
+        .mount("/api/compartments", compartments::routes())
+        .mount("/api/credentials", credentials::routes())
+        .mount("/api/decision-log", decision_log::routes())
+        .mount("/api/deployments", deployments::routes())
+        .mount("/api/federation", federation::routes())
+        .mount("/api/release-policies", release_policies::routes())
+        .mount("/api/security-flags", security_flags::routes())
+        .mount("/api/sharing-agreements", sharing_agreements::routes())
         .mount("/api", relations::routes())
 }
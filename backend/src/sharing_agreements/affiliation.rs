@@ -0,0 +1,145 @@
+// Affiliation check for decisions.
+//
+// An agreement permits sharing `owner`'s records with `recipient` when it is
+// in force at the time of the decision, runs in that direction (UNILATERAL:
+// from owner to recipient only), covers the domain and reaches the
+// classification. Decisions pass the permitted pairs to the evaluator as its
+// agreements, so the affiliation rule stays the ported hasAgreement.
+use chrono::NaiveDateTime;
+use sqlx::PgPool;
+
+use super::models::{SharingAgreement, DIRECTION_BILATERAL, DIRECTION_UNILATERAL};
+use crate::abac::evaluator::tiers;
+use crate::clearances::lifecycle::valid_level;
+use crate::digital_resources::resolver::clearance_rank;
+
+pub const AGREEMENT_COLUMNS: &str = "id, from_organization_id, to_organization_id, direction, \
+                                     domain, max_classification, valid_from, valid_until, \
+                                     document_reference, created_by_person_id, created_at, \
+                                     updated_at";
+
+/// Whether direction, domain and max classification are known values.
+pub fn valid_terms(direction: &str, domain: Option<&str>, max_classification: &str) -> bool {
+    [DIRECTION_BILATERAL, DIRECTION_UNILATERAL].contains(&direction)
+        && domain.is_none_or(|d| tiers(d).is_some())
+        && valid_level(max_classification)
+}
+
+/// Whether `agreement` permits sharing `owner`'s records at `classification`
+/// in `domain` (None = no particular domain) with `recipient` at `now`.
+pub fn permits(
+    agreement: &SharingAgreement,
+    owner: i32,
+    recipient: i32,
+    domain: Option<&str>,
+    classification: &str,
+    now: NaiveDateTime,
+) -> bool {
+    let forward =
+        agreement.from_organization_id == owner && agreement.to_organization_id == recipient;
+    let backward = agreement.direction == DIRECTION_BILATERAL
+        && agreement.from_organization_id == recipient
+        && agreement.to_organization_id == owner;
+    let in_domain = match (agreement.domain.as_deref(), domain) {
+        (None, _) => true,
+        (Some(scope), Some(requested)) => scope == requested,
+        (Some(_), None) => false,
+    };
+    let rank = clearance_rank(classification);
+    (forward || backward)
+        && in_domain
+        && rank >= 0
+        && rank <= clearance_rank(&agreement.max_classification)
+        && agreement.valid_from <= now
+        && agreement.valid_until.is_none_or(|until| until > now)
+}
+
+/// (owner, recipient) pairs, as evaluator entity ids, for every organization
+/// `owner_org_id` may share with under the given scope at `now`.
+pub async fn load_affiliation_agreements(
+    db: &PgPool,
+    owner_org_id: i32,
+    domain: Option<&str>,
+    classification: &str,
+    now: NaiveDateTime,
+) -> Result<Vec<(String, String)>, sqlx::Error> {
+    let agreements = sqlx::query_as::<_, SharingAgreement>(&format!(
+        "SELECT {} FROM sharing_agreements \
+         WHERE from_organization_id = $1 OR to_organization_id = $1 ORDER BY id",
+        AGREEMENT_COLUMNS
+    ))
+    .bind(owner_org_id)
+    .fetch_all(db)
+    .await?;
+    Ok(agreements
+        .iter()
+        .filter_map(|a| {
+            let recipient = if a.from_organization_id == owner_org_id {
+                a.to_organization_id
+            } else {
+                a.from_organization_id
+            };
+            permits(a, owner_org_id, recipient, domain, classification, now)
+                .then(|| (owner_org_id.to_string(), recipient.to_string()))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32) -> NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2026, 6, day)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    }
+
+    fn agreement(direction: &str, domain: Option<&str>) -> SharingAgreement {
+        SharingAgreement {
+            id: 1,
+            from_organization_id: 1,
+            to_organization_id: 2,
+            direction: direction.to_string(),
+            domain: domain.map(str::to_string),
+            max_classification: "SECRET".to_string(),
+            valid_from: at(1),
+            valid_until: Some(at(20)),
+            document_reference: None,
+            created_by_person_id: 1,
+            created_at: at(1),
+            updated_at: at(1),
+        }
+    }
+
+    #[test]
+    fn test_unilateral_runs_one_way() {
+        let a = agreement(DIRECTION_UNILATERAL, None);
+        assert!(permits(&a, 1, 2, Some("DATA"), "SECRET", at(10)));
+        assert!(!permits(&a, 2, 1, Some("DATA"), "SECRET", at(10)));
+        let b = agreement(DIRECTION_BILATERAL, None);
+        assert!(permits(&b, 2, 1, Some("DATA"), "SECRET", at(10)));
+        assert!(!permits(&b, 1, 3, Some("DATA"), "SECRET", at(10)));
+    }
+
+    #[test]
+    fn test_scope_and_window() {
+        let a = agreement(DIRECTION_BILATERAL, Some("DATA"));
+        assert!(!permits(&a, 1, 2, Some("COMPUTER"), "SECRET", at(10)));
+        assert!(!permits(&a, 1, 2, None, "SECRET", at(10)));
+        assert!(!permits(&a, 1, 2, Some("DATA"), "TOP_SECRET", at(10)));
+        assert!(permits(&a, 1, 2, Some("DATA"), "UNCLASSIFIED", at(10)));
+        assert!(!permits(&a, 1, 2, Some("DATA"), "COSMIC", at(10)));
+        assert!(!permits(&a, 1, 2, Some("DATA"), "SECRET", at(20)));
+    }
+
+    #[test]
+    fn test_valid_terms() {
+        assert!(valid_terms("UNILATERAL", Some("PHYSICAL"), "TOP_SECRET"));
+        assert!(valid_terms("BILATERAL", None, "UNCLASSIFIED"));
+        assert!(!valid_terms("MUTUAL", None, "SECRET"));
+        assert!(!valid_terms("BILATERAL", Some("NETWORK"), "SECRET"));
+        assert!(!valid_terms("BILATERAL", None, "RESTRICTED"));
+    }
+}
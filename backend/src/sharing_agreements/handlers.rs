@@ -0,0 +1,238 @@
+// Sharing agreement HTTP handlers (mounted at /api/sharing-agreements)
+//
+// Agreements are readable under organizations.read and created, changed or
+// deleted under agreements.manage; every change is audited. Decisions read
+// them directly (see affiliation), so a change applies to the next decision.
+use chrono::NaiveDateTime;
+use rocket::serde::json::Json;
+use rocket::{delete, get, http::Status, post, put, State};
+use sqlx::PgPool;
+use validator::Validate;
+
+use super::affiliation::{valid_terms, AGREEMENT_COLUMNS};
+use super::models::{
+    CreateSharingAgreementRequest, SharingAgreement, UpdateSharingAgreementRequest,
+};
+use crate::audit::handlers::audit;
+use crate::auth::middleware::AuthGuard;
+use crate::shared::rbac::require_permission;
+use crate::shared::response::ApiResponse;
+
+/// Audit details for an agreement: direction, parties, scope and reference.
+fn describe(agreement: &SharingAgreement) -> String {
+    format!(
+        "{} {} -> {} ({}, up to {}){}",
+        agreement.direction,
+        agreement.from_organization_id,
+        agreement.to_organization_id,
+        agreement.domain.as_deref().unwrap_or("all domains"),
+        agreement.max_classification,
+        agreement
+            .document_reference
+            .as_deref()
+            .map(|d| format!(", ref {}", d))
+            .unwrap_or_default()
+    )
+}
+
+fn db_error(e: sqlx::Error) -> Status {
+    eprintln!("Database error: {:?}", e);
+    Status::InternalServerError
+}
+
+fn valid_window(valid_from: NaiveDateTime, valid_until: Option<NaiveDateTime>) -> bool {
+    valid_until.is_none_or(|until| until > valid_from)
+}
+
+/// Agreements, optionally those one organization is party to, and with
+/// `active_only=true` only those in force now.
+#[get("/?<organization_id>&<active_only>")]
+pub async fn list_agreements(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    organization_id: Option<i32>,
+    active_only: Option<bool>,
+) -> Result<Json<ApiResponse<Vec<SharingAgreement>>>, Status> {
+    require_permission(db.inner(), &auth, "organizations.read").await?;
+    let agreements = sqlx::query_as::<_, SharingAgreement>(&format!(
+        "SELECT {} FROM sharing_agreements \
+         WHERE ($1::int IS NULL OR from_organization_id = $1 OR to_organization_id = $1) \
+           AND (NOT $2 OR (valid_from <= $3 AND (valid_until IS NULL OR valid_until > $3))) \
+         ORDER BY id",
+        AGREEMENT_COLUMNS
+    ))
+    .bind(organization_id)
+    .bind(active_only.unwrap_or(false))
+    .bind(chrono::Utc::now().naive_utc())
+    .fetch_all(db.inner())
+    .await
+    .map_err(db_error)?;
+    Ok(Json(ApiResponse::success(agreements)))
+}
+
+#[get("/<id>")]
+pub async fn get_agreement(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<SharingAgreement>>, Status> {
+    require_permission(db.inner(), &auth, "organizations.read").await?;
+    let agreement = sqlx::query_as::<_, SharingAgreement>(&format!(
+        "SELECT {} FROM sharing_agreements WHERE id = $1",
+        AGREEMENT_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(db_error)?
+    .ok_or(Status::NotFound)?;
+    Ok(Json(ApiResponse::success(agreement)))
+}
+
+/// Record an agreement. 400 for unknown terms, the same organization on both
+/// sides or an empty window; 404 when either organization is unknown or
+/// deleted.
+#[post("/", data = "<data>")]
+pub async fn create_agreement(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    data: Json<CreateSharingAgreementRequest>,
+) -> Result<Json<ApiResponse<SharingAgreement>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "agreements.manage").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    let valid_from = data
+        .valid_from
+        .unwrap_or_else(|| chrono::Utc::now().naive_utc());
+    if !valid_terms(
+        &data.direction,
+        data.domain.as_deref(),
+        &data.max_classification,
+    ) || data.from_organization_id == data.to_organization_id
+        || !valid_window(valid_from, data.valid_until)
+    {
+        return Err(Status::BadRequest);
+    }
+    let organizations: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM organizations WHERE id IN ($1, $2) AND deleted_at IS NULL",
+    )
+    .bind(data.from_organization_id)
+    .bind(data.to_organization_id)
+    .fetch_one(db.inner())
+    .await
+    .map_err(db_error)?;
+    if organizations != 2 {
+        return Err(Status::NotFound);
+    }
+
+    let agreement = sqlx::query_as::<_, SharingAgreement>(&format!(
+        "INSERT INTO sharing_agreements \
+           (from_organization_id, to_organization_id, direction, domain, max_classification, \
+            valid_from, valid_until, document_reference, created_by_person_id) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) RETURNING {}",
+        AGREEMENT_COLUMNS
+    ))
+    .bind(data.from_organization_id)
+    .bind(data.to_organization_id)
+    .bind(&data.direction)
+    .bind(data.domain.as_deref())
+    .bind(&data.max_classification)
+    .bind(valid_from)
+    .bind(data.valid_until)
+    .bind(data.document_reference.as_deref())
+    .bind(caller)
+    .fetch_one(db.inner())
+    .await
+    .map_err(db_error)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "SHARING_AGREEMENT_CREATED",
+        "sharing_agreements",
+        Some(agreement.id),
+        describe(&agreement),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(agreement)))
+}
+
+/// Replace an agreement's terms. 400 for unknown terms or an empty window,
+/// 404 for an unknown agreement.
+#[put("/<id>", data = "<data>")]
+pub async fn update_agreement(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+    data: Json<UpdateSharingAgreementRequest>,
+) -> Result<Json<ApiResponse<SharingAgreement>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "agreements.manage").await?;
+    data.validate().map_err(|_| Status::BadRequest)?;
+    if !valid_terms(
+        &data.direction,
+        data.domain.as_deref(),
+        &data.max_classification,
+    ) || !valid_window(data.valid_from, data.valid_until)
+    {
+        return Err(Status::BadRequest);
+    }
+
+    let agreement = sqlx::query_as::<_, SharingAgreement>(&format!(
+        "UPDATE sharing_agreements SET direction = $2, domain = $3, max_classification = $4, \
+           valid_from = $5, valid_until = $6, document_reference = $7, \
+           updated_at = CURRENT_TIMESTAMP \
+         WHERE id = $1 RETURNING {}",
+        AGREEMENT_COLUMNS
+    ))
+    .bind(id)
+    .bind(&data.direction)
+    .bind(data.domain.as_deref())
+    .bind(&data.max_classification)
+    .bind(data.valid_from)
+    .bind(data.valid_until)
+    .bind(data.document_reference.as_deref())
+    .fetch_optional(db.inner())
+    .await
+    .map_err(db_error)?
+    .ok_or(Status::NotFound)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "SHARING_AGREEMENT_UPDATED",
+        "sharing_agreements",
+        Some(agreement.id),
+        describe(&agreement),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(agreement)))
+}
+
+/// Delete an agreement; the audit log keeps its terms.
+#[delete("/<id>")]
+pub async fn delete_agreement(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<ApiResponse<SharingAgreement>>, Status> {
+    let caller = require_permission(db.inner(), &auth, "agreements.manage").await?;
+    let agreement = sqlx::query_as::<_, SharingAgreement>(&format!(
+        "DELETE FROM sharing_agreements WHERE id = $1 RETURNING {}",
+        AGREEMENT_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(db.inner())
+    .await
+    .map_err(db_error)?
+    .ok_or(Status::NotFound)?;
+
+    audit(
+        db.inner(),
+        caller,
+        "SHARING_AGREEMENT_DELETED",
+        "sharing_agreements",
+        Some(agreement.id),
+        describe(&agreement),
+    )
+    .await;
+    Ok(Json(ApiResponse::success(agreement)))
+}
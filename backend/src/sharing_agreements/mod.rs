@@ -0,0 +1,17 @@
+// Sharing agreements module
+// Inter-organization agreements (direction, domain, max classification,
+// validity window) and the affiliation check decisions use
+
+pub mod affiliation;
+pub mod handlers;
+pub mod models;
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![
+        handlers::list_agreements,
+        handlers::get_agreement,
+        handlers::create_agreement,
+        handlers::update_agreement,
+        handlers::delete_agreement,
+    ]
+}
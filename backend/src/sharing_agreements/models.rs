@@ -0,0 +1,50 @@
+// Sharing agreement data models
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+pub const DIRECTION_BILATERAL: &str = "BILATERAL";
+pub const DIRECTION_UNILATERAL: &str = "UNILATERAL";
+
+/// Records of `from_organization_id` may be shared with `to_organization_id`
+/// (and the other way round when BILATERAL)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SharingAgreement {
+    pub id: i32,
+    pub from_organization_id: i32,
+    pub to_organization_id: i32,
+    pub direction: String,      // BILATERAL, UNILATERAL
+    pub domain: Option<String>, // COMPUTER, DATA, PHYSICAL; None = every domain
+    pub max_classification: String,
+    pub valid_from: NaiveDateTime,
+    pub valid_until: Option<NaiveDateTime>,
+    pub document_reference: Option<String>,
+    pub created_by_person_id: i32,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateSharingAgreementRequest {
+    pub from_organization_id: i32,
+    pub to_organization_id: i32,
+    pub direction: String,
+    pub domain: Option<String>,
+    pub max_classification: String,
+    pub valid_from: Option<NaiveDateTime>, // defaults to now
+    pub valid_until: Option<NaiveDateTime>,
+    #[validate(length(min = 1, max = 200))]
+    pub document_reference: Option<String>,
+}
+
+/// Replaces the terms of an agreement; the organizations cannot change
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateSharingAgreementRequest {
+    pub direction: String,
+    pub domain: Option<String>,
+    pub max_classification: String,
+    pub valid_from: NaiveDateTime,
+    pub valid_until: Option<NaiveDateTime>,
+    #[validate(length(min = 1, max = 200))]
+    pub document_reference: Option<String>,
+}
@@ -0,0 +1,97 @@
+// Central SoD enforcement for mutating handlers.
+//
+// Handlers call these after their RBAC gate and before writing. A violation is
+// a 403 plus a SOD_VIOLATION_BLOCKED audit entry; failing to load the rules is
+// a 500 (fail closed — never skip the check).
+use std::fmt::Display;
+
+use rocket::http::Status;
+use sqlx::PgPool;
+
+use super::models::SodRule;
+use super::rules::{conflicting_rules, requester_approver_rule, self_action_rule};
+use crate::audit::handlers::create_audit_log;
+use crate::audit::models::CreateAuditLogRequest;
+
+/// All enabled rules, in id order.
+pub async fn load_enabled_rules(db: &PgPool) -> Result<Vec<SodRule>, sqlx::Error> {
+    sqlx::query_as::<_, SodRule>(
+        "SELECT id, name, rule_type, action_key, conflicting_key, description, enabled, created_at \
+         FROM sod_rules WHERE enabled ORDER BY id",
+    )
+    .fetch_all(db)
+    .await
+}
+
+async fn load_or_fail(db: &PgPool) -> Result<Vec<SodRule>, Status> {
+    load_enabled_rules(db).await.map_err(|e| {
+        eprintln!("DB error loading sod_rules: {:?}", e);
+        Status::InternalServerError
+    })
+}
+
+async fn audit_blocked<T: Display>(db: &PgPool, actor: &T, rule: &SodRule, details: String) {
+    let _ = create_audit_log(
+        &CreateAuditLogRequest {
+            person_id: actor.to_string().parse::<i32>().ok(),
+            username: actor.to_string(),
+            action: "SOD_VIOLATION_BLOCKED".to_string(),
+            resource_type: "sod_rule".to_string(),
+            resource_id: Some(rule.id),
+            details: Some(format!("{}: {}", rule.name, details)),
+            ip_address: None,
+            user_agent: None,
+        },
+        db,
+    )
+    .await;
+}
+
+/// Reject `actor` performing `action_key` on themselves (NO_SELF_ACTION).
+pub async fn enforce_no_self_action<T: PartialEq + Display>(
+    db: &PgPool,
+    action_key: &str,
+    actor: &T,
+    subject: &T,
+) -> Result<(), Status> {
+    let rules = load_or_fail(db).await?;
+    if let Some(rule) = self_action_rule(&rules, action_key, actor, subject) {
+        audit_blocked(db, actor, rule, format!("{} on self", action_key)).await;
+        return Err(Status::Forbidden);
+    }
+    Ok(())
+}
+
+/// Reject `approver` completing `action_key` on something they initiated
+/// (REQUESTER_NOT_APPROVER).
+pub async fn enforce_requester_not_approver<T: PartialEq + Display>(
+    db: &PgPool,
+    action_key: &str,
+    requester: &T,
+    approver: &T,
+) -> Result<(), Status> {
+    let rules = load_or_fail(db).await?;
+    if let Some(rule) = requester_approver_rule(&rules, action_key, requester, approver) {
+        audit_blocked(
+            db,
+            approver,
+            rule,
+            format!("{} by its own requester", action_key),
+        )
+        .await;
+        return Err(Status::Forbidden);
+    }
+    Ok(())
+}
+
+/// Names of the CONFLICTING_PERMISSIONS rules a proposed permission set breaks.
+pub async fn conflicting_rule_names(
+    db: &PgPool,
+    permissions: &[String],
+) -> Result<Vec<String>, sqlx::Error> {
+    let rules = load_enabled_rules(db).await?;
+    Ok(conflicting_rules(&rules, permissions)
+        .into_iter()
+        .map(|r| r.name.clone())
+        .collect())
+}
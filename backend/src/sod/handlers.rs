@@ -0,0 +1,139 @@
+// Separation-of-duties HTTP handlers (mounted at /api/sod)
+use std::collections::BTreeMap;
+
+use rocket::serde::json::Json;
+use rocket::{delete, get, post, State};
+use sqlx::PgPool;
+use validator::Validate;
+
+use super::enforce::load_enabled_rules;
+use super::models::{CreateSodRuleRequest, SodRule, SodViolation, RULE_CONFLICTING_PERMISSIONS};
+use super::rules::role_conflicts;
+use crate::auth::middleware::AuthGuard;
+use crate::shared::error::AppError;
+use crate::shared::rbac::role_has_permission;
+
+#[get("/rules")]
+pub async fn list_rules(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+) -> Result<Json<Vec<SodRule>>, AppError> {
+    if !role_has_permission(db.inner(), &auth.claims.role, "roles.read")
+        .await
+        .unwrap_or(false)
+    {
+        return Err(AppError::Forbidden);
+    }
+    let items = sqlx::query_as::<_, SodRule>(
+        "SELECT id, name, rule_type, action_key, conflicting_key, description, enabled, created_at \
+         FROM sod_rules ORDER BY id",
+    )
+    .fetch_all(db.inner())
+    .await?;
+    Ok(Json(items))
+}
+
+#[post("/rules", data = "<req>")]
+pub async fn create_rule(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    req: Json<CreateSodRuleRequest>,
+) -> Result<Json<SodRule>, AppError> {
+    if !role_has_permission(db.inner(), &auth.claims.role, "roles.write")
+        .await
+        .unwrap_or(false)
+    {
+        return Err(AppError::Forbidden);
+    }
+    req.validate()?;
+    // conflicting_key is required for, and only allowed on, permission conflicts
+    // (mirrors chk_sod_conflicting_key so the client gets a 400, not a 500).
+    if (req.rule_type == RULE_CONFLICTING_PERMISSIONS) != req.conflicting_key.is_some() {
+        return Err(AppError::BadRequest);
+    }
+    let item = sqlx::query_as::<_, SodRule>(
+        "INSERT INTO sod_rules (name, rule_type, action_key, conflicting_key, description) \
+         VALUES ($1, $2, $3, $4, $5) \
+         RETURNING id, name, rule_type, action_key, conflicting_key, description, enabled, created_at",
+    )
+    .bind(&req.name)
+    .bind(&req.rule_type)
+    .bind(&req.action_key)
+    .bind(&req.conflicting_key)
+    .bind(&req.description)
+    .fetch_one(db.inner())
+    .await?;
+    Ok(Json(item))
+}
+
+#[delete("/rules/<id>")]
+pub async fn delete_rule(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+    id: i32,
+) -> Result<Json<()>, AppError> {
+    if !role_has_permission(db.inner(), &auth.claims.role, "roles.write")
+        .await
+        .unwrap_or(false)
+    {
+        return Err(AppError::Forbidden);
+    }
+    let result = sqlx::query("DELETE FROM sod_rules WHERE id = $1")
+        .bind(id)
+        .execute(db.inner())
+        .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(Json(()))
+}
+
+/// Report every role whose current permission set breaks a CONFLICTING_PERMISSIONS
+/// rule, with the (non-deleted) persons assigned that role.
+#[get("/violations")]
+pub async fn list_violations(
+    db: &State<PgPool>,
+    auth: AuthGuard,
+) -> Result<Json<Vec<SodViolation>>, AppError> {
+    if !role_has_permission(db.inner(), &auth.claims.role, "roles.read")
+        .await
+        .unwrap_or(false)
+    {
+        return Err(AppError::Forbidden);
+    }
+    let rules = load_enabled_rules(db.inner()).await?;
+
+    let rows: Vec<(String, String)> = sqlx::query_as(
+        "SELECT r.name, p.key FROM roles r \
+         JOIN role_permissions rp ON rp.role_id = r.id \
+         JOIN permissions p ON p.id = rp.permission_id \
+         ORDER BY r.name, p.key",
+    )
+    .fetch_all(db.inner())
+    .await?;
+    let mut role_permissions: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (role, key) in rows {
+        role_permissions.entry(role).or_default().push(key);
+    }
+
+    let mut violations = Vec::new();
+    for (rule, role) in role_conflicts(&rules, &role_permissions) {
+        let person_ids: Vec<i32> = sqlx::query_scalar(
+            "SELECT id FROM person WHERE role = $1 AND deleted_at IS NULL ORDER BY id",
+        )
+        .bind(role)
+        .fetch_all(db.inner())
+        .await?;
+        violations.push(SodViolation {
+            rule_id: rule.id,
+            rule_name: rule.name.clone(),
+            role_name: role.to_string(),
+            permissions: vec![
+                rule.action_key.clone(),
+                rule.conflicting_key.clone().unwrap_or_default(),
+            ],
+            person_ids,
+        });
+    }
+    Ok(Json(violations))
+}
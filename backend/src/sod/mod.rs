@@ -0,0 +1,16 @@
+// Separation-of-duties module
+// Declarative SoD rules, central enforcement, and the violations report
+
+pub mod enforce;
+pub mod handlers;
+pub mod models;
+pub mod rules;
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![
+        handlers::list_rules,
+        handlers::create_rule,
+        handlers::delete_rule,
+        handlers::list_violations,
+    ]
+}
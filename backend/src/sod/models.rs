@@ -0,0 +1,53 @@
+// Separation-of-duties data models
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+pub const RULE_CONFLICTING_PERMISSIONS: &str = "CONFLICTING_PERMISSIONS";
+pub const RULE_NO_SELF_ACTION: &str = "NO_SELF_ACTION";
+pub const RULE_REQUESTER_NOT_APPROVER: &str = "REQUESTER_NOT_APPROVER";
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SodRule {
+    pub id: i32,
+    pub name: String,
+    pub rule_type: String, // CONFLICTING_PERMISSIONS, NO_SELF_ACTION, REQUESTER_NOT_APPROVER
+    pub action_key: String,
+    pub conflicting_key: Option<String>, // only set for CONFLICTING_PERMISSIONS
+    pub description: Option<String>,
+    pub enabled: bool,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateSodRuleRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+
+    #[validate(custom = "validate_rule_type")]
+    pub rule_type: String,
+
+    #[validate(length(min = 1, max = 100))]
+    pub action_key: String,
+
+    #[validate(length(min = 1, max = 100))]
+    pub conflicting_key: Option<String>,
+
+    pub description: Option<String>,
+}
+
+/// One role assignment that breaks a CONFLICTING_PERMISSIONS rule.
+#[derive(Debug, Serialize)]
+pub struct SodViolation {
+    pub rule_id: i32,
+    pub rule_name: String,
+    pub role_name: String,
+    pub permissions: Vec<String>,
+    pub person_ids: Vec<i32>,
+}
+
+fn validate_rule_type(rule_type: &str) -> Result<(), validator::ValidationError> {
+    match rule_type {
+        RULE_CONFLICTING_PERMISSIONS | RULE_NO_SELF_ACTION | RULE_REQUESTER_NOT_APPROVER => Ok(()),
+        _ => Err(validator::ValidationError::new("invalid_rule_type")),
+    }
+}
@@ -0,0 +1,157 @@
+// Pure separation-of-duties rule evaluation.
+//
+// No DB, no Rocket: callers load the enabled rules (and, for the violations
+// report, each role's permission keys) and pass them in. Disabled rules never
+// match. Rule lookup is by exact action_key.
+use std::collections::BTreeMap;
+
+use super::models::{
+    SodRule, RULE_CONFLICTING_PERMISSIONS, RULE_NO_SELF_ACTION, RULE_REQUESTER_NOT_APPROVER,
+};
+
+fn enabled_rules<'a>(
+    rules: &'a [SodRule],
+    rule_type: &'a str,
+) -> impl Iterator<Item = &'a SodRule> + 'a {
+    rules
+        .iter()
+        .filter(move |r| r.enabled && r.rule_type == rule_type)
+}
+
+/// CONFLICTING_PERMISSIONS rules broken by holding every key in `held`.
+pub fn conflicting_rules<'a>(rules: &'a [SodRule], held: &[String]) -> Vec<&'a SodRule> {
+    let holds = |key: &str| held.iter().any(|h| h == key);
+    enabled_rules(rules, RULE_CONFLICTING_PERMISSIONS)
+        .filter(|r| holds(&r.action_key) && r.conflicting_key.as_deref().is_some_and(holds))
+        .collect()
+}
+
+/// The NO_SELF_ACTION rule forbidding `actor` to perform `action_key` on
+/// `subject`, if any.
+pub fn self_action_rule<'a, T: PartialEq>(
+    rules: &'a [SodRule],
+    action_key: &str,
+    actor: &T,
+    subject: &T,
+) -> Option<&'a SodRule> {
+    if actor != subject {
+        return None;
+    }
+    enabled_rules(rules, RULE_NO_SELF_ACTION).find(|r| r.action_key == action_key)
+}
+
+/// The REQUESTER_NOT_APPROVER rule forbidding `approver` to complete
+/// `action_key` on something `requester` initiated, if any.
+pub fn requester_approver_rule<'a, T: PartialEq>(
+    rules: &'a [SodRule],
+    action_key: &str,
+    requester: &T,
+    approver: &T,
+) -> Option<&'a SodRule> {
+    if requester != approver {
+        return None;
+    }
+    enabled_rules(rules, RULE_REQUESTER_NOT_APPROVER).find(|r| r.action_key == action_key)
+}
+
+/// Every (rule, role) pair where the role's current permission set breaks a
+/// CONFLICTING_PERMISSIONS rule. Ordered by rule id, then role name.
+pub fn role_conflicts<'a>(
+    rules: &'a [SodRule],
+    role_permissions: &'a BTreeMap<String, Vec<String>>,
+) -> Vec<(&'a SodRule, &'a str)> {
+    let mut out = Vec::new();
+    for rule in enabled_rules(rules, RULE_CONFLICTING_PERMISSIONS) {
+        for (role, held) in role_permissions {
+            if !conflicting_rules(std::slice::from_ref(rule), held).is_empty() {
+                out.push((rule, role.as_str()));
+            }
+        }
+    }
+    out.sort_by_key(|(r, role)| (r.id, *role));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(id: i32, rule_type: &str, action: &str, conflicting: Option<&str>) -> SodRule {
+        SodRule {
+            id,
+            name: format!("rule-{}", id),
+            rule_type: rule_type.to_string(),
+            action_key: action.to_string(),
+            conflicting_key: conflicting.map(str::to_string),
+            description: None,
+            enabled: true,
+            created_at: chrono::NaiveDateTime::default(),
+        }
+    }
+
+    fn keys(k: &[&str]) -> Vec<String> {
+        k.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_conflicting_rules_needs_both_keys() {
+        let rules = vec![rule(
+            1,
+            RULE_CONFLICTING_PERMISSIONS,
+            "access.request",
+            Some("access.approve"),
+        )];
+        assert!(conflicting_rules(&rules, &keys(&["access.request"])).is_empty());
+        assert_eq!(
+            conflicting_rules(&rules, &keys(&["access.request", "access.approve"])).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_disabled_rule_never_matches() {
+        let mut r = rule(1, RULE_NO_SELF_ACTION, "access.write", None);
+        r.enabled = false;
+        let rules = vec![r];
+        assert!(self_action_rule(&rules, "access.write", &7, &7).is_none());
+    }
+
+    #[test]
+    fn test_self_action_only_on_same_subject() {
+        let rules = vec![rule(1, RULE_NO_SELF_ACTION, "access.write", None)];
+        assert!(self_action_rule(&rules, "access.write", &7, &7).is_some());
+        assert!(self_action_rule(&rules, "access.write", &7, &8).is_none());
+        assert!(self_action_rule(&rules, "nda.write", &7, &7).is_none());
+    }
+
+    #[test]
+    fn test_requester_not_approver() {
+        let rules = vec![rule(1, RULE_REQUESTER_NOT_APPROVER, "nda.sign", None)];
+        let issuer = "1".to_string();
+        assert!(requester_approver_rule(&rules, "nda.sign", &issuer, &issuer).is_some());
+        assert!(requester_approver_rule(&rules, "nda.sign", &issuer, &"2".to_string()).is_none());
+    }
+
+    #[test]
+    fn test_role_conflicts_reports_each_offending_role() {
+        let rules = vec![
+            rule(
+                1,
+                RULE_CONFLICTING_PERMISSIONS,
+                "access.request",
+                Some("access.approve"),
+            ),
+            rule(2, RULE_NO_SELF_ACTION, "access.write", None),
+        ];
+        let mut roles = BTreeMap::new();
+        roles.insert(
+            "admin".to_string(),
+            keys(&["access.approve", "access.request", "access.write"]),
+        );
+        roles.insert("manager".to_string(), keys(&["access.request"]));
+        let found = role_conflicts(&rules, &roles);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0.id, 1);
+        assert_eq!(found[0].1, "admin");
+    }
+}
@@ -0,0 +1,295 @@
+// ABAC evaluator parity test.
+//
+// Plain #[test] (no DB): the evaluator is pure. Loads the committed golden
+// JSON emitted by the TS exporter
+// (frontend/src/demo/lib/abac-golden-export.test.ts) and asserts the ported
+// Rust evaluator produces equal serde output over the SAME seed subjects,
+// resources and AGREEMENTS (frontend/src/demo/lib/seed.ts), rebuilt here.
+use serde_json::Value;
+
+use janus_backend::abac::evaluator::{
+    evaluate, evaluate_with_auth, evaluate_with_policy, release_requirement_for, EntityPolicy,
+    PolicyRules, Principal, Requirement, SubjectFlags,
+};
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|v| v.to_string()).collect()
+}
+
+fn agreements() -> Vec<(String, String)> {
+    [
+        ("MILITARY_1", "MILITARY_2"),
+        ("MILITARY_1", "INFRA"),
+        ("MILITARY_1", "HOME_GUARD"),
+        ("MILITARY_1", "INDUSTRY"),
+        ("MILITARY_2", "INTEL"),
+        ("MILITARY_2", "INFRA"),
+        ("MILITARY_2", "HOME_GUARD"),
+        ("MILITARY_2", "INDUSTRY"),
+        ("INTEL", "INFRA"),
+        ("INTEL", "HOME_GUARD"),
+        ("INTEL", "INDUSTRY"),
+        ("INFRA", "HOME_GUARD"),
+        ("INFRA", "INDUSTRY"),
+        ("HOME_GUARD", "INDUSTRY"),
+        // MILITARY_1 <-> INTEL intentionally absent (Affiliation DENY).
+    ]
+    .iter()
+    .map(|(a, b)| (a.to_string(), b.to_string()))
+    .collect()
+}
+
+fn subject(id: &str) -> Principal {
+    let (entity, clearance, domain_auth, compartments): (&str, &str, &[(&str, &str)], &[&str]) =
+        match id {
+            "subj-1" => (
+                "MILITARY_1",
+                "SECRET",
+                &[("COMPUTER", "PRIVILEGED"), ("DATA", "RESTRICTED")],
+                &["AURORA"],
+            ),
+            "subj-2" => (
+                "MILITARY_2",
+                "TOP_SECRET",
+                &[
+                    ("COMPUTER", "ROOT"),
+                    ("DATA", "CLASSIFIED"),
+                    ("PHYSICAL", "SECURE_VAULT"),
+                ],
+                &["AURORA", "BLACKWING"],
+            ),
+            "subj-3" => ("INTEL", "CONFIDENTIAL", &[("COMPUTER", "STANDARD")], &[]),
+            "subj-4" => (
+                "MILITARY_1",
+                "TOP_SECRET",
+                &[("DATA", "CLASSIFIED"), ("PHYSICAL", "SECURE_VAULT")],
+                &["CITADEL"],
+            ),
+            _ => panic!("unknown subject {id}"),
+        };
+    Principal {
+        entity: entity.to_string(),
+        clearance: clearance.to_string(),
+        domain_auth: domain_auth
+            .iter()
+            .map(|(d, t)| (d.to_string(), t.to_string()))
+            .collect(),
+        compartments: strings(compartments),
+        flags: SubjectFlags::default(),
+    }
+}
+
+fn resource(id: &str) -> Requirement {
+    let (domain, tier, min_clearance, compartments, owner): (&str, &str, &str, &[&str], &str) =
+        match id {
+            "res-1" => ("DATA", "RESTRICTED", "SECRET", &["AURORA"], "MILITARY_1"),
+            "res-2" => (
+                "PHYSICAL",
+                "SECURE_VAULT",
+                "TOP_SECRET",
+                &["BLACKWING"],
+                "MILITARY_2",
+            ),
+            "res-3" => ("COMPUTER", "PRIVILEGED", "CONFIDENTIAL", &[], "INTEL"),
+            "res-4" => (
+                "DATA",
+                "CLASSIFIED",
+                "TOP_SECRET",
+                &["CITADEL"],
+                "MILITARY_1",
+            ),
+            _ => panic!("unknown resource {id}"),
+        };
+    Requirement {
+        min_clearance: min_clearance.to_string(),
+        required_compartments: strings(compartments),
+        owner_unit: owner.to_string(),
+        domain: Some(domain.to_string()),
+        required_tier: Some(tier.to_string()),
+    }
+}
+
+fn decide(principal: &Principal, requirement: &Requirement) -> Value {
+    serde_json::to_value(evaluate(principal, requirement, &agreements())).expect("serde")
+}
+
+#[test]
+fn abac_parity_against_golden_fixtures() {
+    let golden: Value =
+        serde_json::from_str(include_str!("fixtures/abac-golden.json")).expect("golden json");
+
+    let cases = [
+        ("allow_subj1_res1", "subj-1", "res-1"),
+        ("allow_subj4_res4", "subj-4", "res-4"),
+        ("tier_deny_subj3_res3", "subj-3", "res-3"),
+        ("ntk_deny_subj4_res1", "subj-4", "res-1"),
+        ("affiliation_deny_subj1_res3", "subj-1", "res-3"),
+    ];
+    for (name, s, r) in cases {
+        assert_eq!(
+            decide(&subject(s), &resource(r)),
+            golden[name],
+            "{name} parity"
+        );
+    }
+
+    // Cross-entity release: an upgraded subj-1 asks MILITARY_2 for its record on subj-2.
+    let mut upgraded = subject("subj-1");
+    upgraded.clearance = "TOP_SECRET".to_string();
+    upgraded.compartments = strings(&["AURORA", "BLACKWING"]);
+    let release = release_requirement_for(&subject("subj-2"), "MILITARY_2");
+    assert_eq!(
+        decide(&upgraded, &release),
+        golden["release_subj2_record_to_subj1"],
+        "release parity"
+    );
+    assert_eq!(golden["release_subj2_record_to_subj1"]["decision"], "ALLOW");
+
+    // Deny overrides flip an otherwise-ALLOW and keep the base rules passing.
+    let mut held = subject("subj-1");
+    held.flags.security_hold = true;
+    let overridden = decide(&held, &resource("res-1"));
+    assert_eq!(
+        overridden, golden["security_hold_subj1_res1"],
+        "hold parity"
+    );
+    assert_eq!(overridden["decision"], "DENY");
+    assert_eq!(overridden["failed"], Value::Array(vec![]));
+
+    let mut revoked = subject("subj-2");
+    revoked.flags = SubjectFlags {
+        revoked: true,
+        security_hold: true,
+    };
+    assert_eq!(
+        decide(&revoked, &resource("res-2")),
+        golden["revoked_subj2_res2"],
+        "revoked parity"
+    );
+}
+
+// evaluateWithAuth cases from frontend/src/demo/lib/auditlog.test.ts: no
+// authorization or AUTHORIZED leaves the base decision untouched; any other
+// status appends the failing rule and denies.
+#[test]
+fn authorization_rule_matches_evaluate_with_auth() {
+    let requirement = resource("res-1");
+    let base = evaluate(&subject("subj-1"), &requirement, &agreements());
+    for status in [None, Some("AUTHORIZED")] {
+        assert_eq!(
+            evaluate_with_auth(&subject("subj-1"), &requirement, &agreements(), status),
+            base
+        );
+    }
+
+    let withdrawn = serde_json::to_value(evaluate_with_auth(
+        &subject("subj-1"),
+        &requirement,
+        &agreements(),
+        Some("WITHDRAWN"),
+    ))
+    .expect("serde");
+    assert_eq!(withdrawn["decision"], "DENY");
+    assert_eq!(
+        withdrawn["rules"].as_array().unwrap().last().unwrap(),
+        &serde_json::json!({
+            "name": "Authorization valid",
+            "pass": false,
+            "detail": "authorization.status=WITHDRAWN (requires AUTHORIZED)"
+        })
+    );
+    assert_eq!(
+        withdrawn["failed"],
+        serde_json::json!(["Authorization valid"])
+    );
+}
+
+fn policy(need_to_know: bool, affiliation: bool, floor: Option<&str>) -> EntityPolicy {
+    EntityPolicy {
+        rules: PolicyRules {
+            clearance: true,
+            domain_tier: true,
+            need_to_know,
+            affiliation,
+        },
+        min_clearance_floor: floor.map(str::to_string),
+    }
+}
+
+fn rule_names(decision: &janus_backend::abac::models::AbacDecision) -> Vec<&str> {
+    decision.rules.iter().map(|r| r.name.as_str()).collect()
+}
+
+// evaluateWithPolicy cases from frontend/src/demo/lib/policy.test.ts, with the
+// standard, strict (TOP_SECRET floor) and relaxed (no NTK / no affiliation)
+// policies, plus the policy.ts detail strings.
+#[test]
+fn policy_cases_match_evaluate_with_policy() {
+    let standard = EntityPolicy::default();
+    assert_eq!(standard, policy(true, true, None));
+    let strict = policy(true, true, Some("TOP_SECRET"));
+    let relaxed = policy(false, false, None);
+    let dana = subject("subj-1");
+    let file_share = resource("res-1");
+    let foreign = Principal {
+        entity: "INDUSTRY".to_string(),
+        compartments: Vec::new(),
+        ..subject("subj-1")
+    };
+
+    let allowed = evaluate_with_policy(&dana, &file_share, &agreements(), &standard);
+    assert_eq!(allowed.decision, "ALLOW");
+    assert_eq!(
+        serde_json::to_value(&allowed.rules).expect("serde"),
+        serde_json::json!([
+            { "name": "Clearance", "pass": true, "detail": "SECRET ≥ SECRET" },
+            { "name": "Domain tier", "pass": true, "detail": "DATA:RESTRICTED ≥ RESTRICTED" },
+            { "name": "Need-to-know", "pass": true, "detail": "all compartments held" },
+            { "name": "Affiliation", "pass": true, "detail": "agreement present" }
+        ])
+    );
+
+    let low = Principal {
+        clearance: "UNCLASSIFIED".to_string(),
+        ..subject("subj-1")
+    };
+    let denied = evaluate_with_policy(&low, &file_share, &agreements(), &standard);
+    assert_eq!(denied.decision, "DENY");
+    assert_eq!(denied.failed, vec!["Clearance".to_string()]);
+
+    let floored = evaluate_with_policy(&dana, &file_share, &agreements(), &strict);
+    assert_eq!(floored.decision, "DENY");
+    assert_eq!(floored.failed, vec!["Clearance floor".to_string()]);
+    assert_eq!(floored.rules[1].detail, "SECRET < entity floor TOP_SECRET");
+    let top_secret = Principal {
+        clearance: "TOP_SECRET".to_string(),
+        ..subject("subj-1")
+    };
+    assert_eq!(
+        evaluate_with_policy(&top_secret, &file_share, &agreements(), &strict).decision,
+        "ALLOW"
+    );
+
+    let foreign_standard = evaluate_with_policy(&foreign, &file_share, &[], &standard);
+    assert_eq!(foreign_standard.decision, "DENY");
+    assert_eq!(
+        foreign_standard.failed,
+        vec!["Need-to-know".to_string(), "Affiliation".to_string()]
+    );
+    assert_eq!(
+        foreign_standard.rules[3].detail,
+        "no agreement INDUSTRY↔MILITARY_1"
+    );
+    let foreign_relaxed = evaluate_with_policy(&foreign, &file_share, &[], &relaxed);
+    assert_eq!(foreign_relaxed.decision, "ALLOW");
+    assert_eq!(
+        rule_names(&foreign_relaxed),
+        vec!["Clearance", "Domain tier"]
+    );
+
+    let mut revoked = subject("subj-1");
+    revoked.flags.revoked = true;
+    let overridden = evaluate_with_policy(&revoked, &file_share, &agreements(), &standard);
+    assert_eq!(overridden.decision, "DENY");
+    assert_eq!(overridden.overrides[0].detail, "access revoked");
+}
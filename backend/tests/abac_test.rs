@@ -0,0 +1,239 @@
+// ABAC decision endpoint integration tests.
+//
+// Test map:
+//   POST /api/abac/decide                     — no token -> 401                 [no DB]
+//   POST /api/abac/decide                     — unknown domain / cross-domain   [DB: login]
+//                                               tier -> 400; unknown person -> 404
+//   PUT  /api/abac/persons/<id>/domain-tiers  — tier set, then decide ALLOWs     [DB: login]
+//                                               for the person's organization and
+//                                               fails Domain tier above it
+//   PUT  /api/abac/persons/<id>/domain-tiers  — viewer -> 403                    [DB: login]
+//
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test abac_test -- --include-ignored
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn send(
+    client: &Client,
+    token: &str,
+    put: bool,
+    uri: String,
+    body: Value,
+) -> (Status, Value) {
+    let request = if put {
+        client.put(uri)
+    } else {
+        client.post(uri)
+    };
+    let response = request
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+async fn create_organization(client: &Client, admin: &str) -> i64 {
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    let (status, body) = send(
+        client,
+        admin,
+        false,
+        "/api/organizations".to_string(),
+        json!({
+            "company_name": format!("ABAC Org {}", &suffix[..12]),
+            "contact_name": "ABAC Contact",
+            "contact_email": "abac@example.com",
+            "clearance_level": "SECRET",
+            "contract_number": format!("ABAC-{}", &suffix[..8])
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    body["id"].as_i64().expect("organization id")
+}
+
+/// A SECRET-cleared employee of `org_id`.
+async fn create_member(client: &Client, admin: &str, org_id: i64) -> i64 {
+    let (status, body) = send(
+        client,
+        admin,
+        false,
+        "/api/person".to_string(),
+        json!({ "first_name": "Abac", "last_name": "Member", "clearance_level": "SECRET" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let person_id = body["id"].as_i64().expect("person id");
+    // Inserted directly: the relations API writes entity_type 'organization',
+    // which the table CHECK does not accept.
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    sqlx::query(
+        "INSERT INTO relations (entity_type, entity_id, related_entity_type, related_entity_id, relation_type) \
+         VALUES ('vendor', $1, 'person', $2, 'employee')",
+    )
+    .bind(org_id as i32)
+    .bind(person_id as i32)
+    .execute(pool)
+    .await
+    .expect("membership");
+    person_id
+}
+
+fn rule<'a>(decision: &'a Value, name: &str) -> &'a Value {
+    decision["rules"]
+        .as_array()
+        .expect("rules")
+        .iter()
+        .find(|r| r["name"] == name)
+        .expect("rule present")
+}
+
+#[rocket::async_test]
+async fn test_decide_requires_auth() {
+    let client = create_test_client().await;
+    let response = client
+        .post("/api/abac/decide")
+        .header(ContentType::JSON)
+        .body("{}")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_decide_validates_input() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let base = json!({
+        "person_id": 5,
+        "domain": "COMPUTER",
+        "required_tier": "PRIVILEGED",
+        "min_clearance": "SECRET",
+        "owner_organization_id": 1
+    });
+
+    let mut bad_domain = base.clone();
+    bad_domain["domain"] = json!("NETWORK");
+    let (status, _) = send(
+        &client,
+        &admin,
+        false,
+        "/api/abac/decide".into(),
+        bad_domain,
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+
+    // DATA tier on the COMPUTER ladder
+    let mut cross = base.clone();
+    cross["required_tier"] = json!("CLASSIFIED");
+    let (status, _) = send(&client, &admin, false, "/api/abac/decide".into(), cross).await;
+    assert_eq!(status, Status::BadRequest);
+
+    let mut unknown = base;
+    unknown["person_id"] = json!(999999);
+    let (status, _) = send(&client, &admin, false, "/api/abac/decide".into(), unknown).await;
+    assert_eq!(status, Status::NotFound);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_domain_tier_drives_decision() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let org_id = create_organization(&client, &admin).await;
+    let person_id = create_member(&client, &admin, org_id).await;
+
+    let (status, _) = send(
+        &client,
+        &admin,
+        true,
+        format!("/api/abac/persons/{}/domain-tiers", person_id),
+        json!({ "domain": "COMPUTER", "tier": "PRIVILEGED" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+
+    let request = json!({
+        "person_id": person_id,
+        "domain": "COMPUTER",
+        "required_tier": "PRIVILEGED",
+        "min_clearance": "SECRET",
+        "owner_organization_id": org_id
+    });
+    let (status, body) = send(
+        &client,
+        &admin,
+        false,
+        "/api/abac/decide".into(),
+        request.clone(),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let decision = &body["data"];
+    assert_eq!(decision["decision"], "ALLOW", "{decision}");
+    assert_eq!(decision["organization_id"], org_id);
+    assert_eq!(
+        rule(decision, "Affiliation")["detail"],
+        format!("same entity ({})", org_id)
+    );
+
+    let mut root = request;
+    root["required_tier"] = json!("ROOT");
+    let (_, body) = send(&client, &admin, false, "/api/abac/decide".into(), root).await;
+    let decision = &body["data"];
+    assert_eq!(decision["decision"], "DENY");
+    assert_eq!(decision["failed"], json!(["Domain tier"]));
+    assert_eq!(
+        rule(decision, "Domain tier")["detail"],
+        "COMPUTER:PRIVILEGED < required ROOT"
+    );
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_set_domain_tier_forbidden_for_viewer() {
+    let client = create_test_client().await;
+    let viewer = login(&client, "viewer").await;
+    let (status, _) = send(
+        &client,
+        &viewer,
+        true,
+        "/api/abac/persons/5/domain-tiers".to_string(),
+        json!({ "domain": "DATA", "tier": "INTERNAL" }),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+}
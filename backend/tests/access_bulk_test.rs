@@ -0,0 +1,285 @@
+// Bulk grant/revoke integration tests.
+//
+// Test map:
+//   POST /api/access/bulk/grant   — no token -> 401                             [no DB]
+//   POST /api/access/bulk/grant   — two selectors -> 400; enduser -> 403        [DB: login]
+//   POST /api/access/bulk/grant   — department selector: dry run plans one      [DB: login]
+//                                   GRANTED and one clearance BLOCKED and
+//                                   writes nothing; applying grants under a
+//                                   batch id readable at GET /<batch_id>
+//   POST /api/access/bulk/revoke  — holders_of a zone revokes every holder      [DB: login]
+//   POST /api/access/bulk/grant   — two concurrent applies grant once; the      [DB: login]
+//                                   later one reports SKIPPED
+//
+// Each test creates its own department, people and zone names.
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test access_bulk_test -- --include-ignored
+
+mod common;
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn get_json(client: &Client, token: &str, uri: String) -> (Status, Value) {
+    let response = client.get(uri).header(auth_header(token)).dispatch().await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+fn unique(prefix: &str) -> String {
+    format!(
+        "{} {}",
+        prefix,
+        &uuid::Uuid::new_v4().simple().to_string()[..12]
+    )
+}
+
+/// Register an organization owning a fresh department; returns its name.
+async fn create_department(client: &Client, admin: &str) -> String {
+    let department = unique("Bulk Dept");
+    let (status, _) = common::post_json(
+        client,
+        admin,
+        "/api/organizations",
+        json!({
+            "company_name": unique("Bulk Org"),
+            "contact_name": "Bulk Contact",
+            "contact_email": "bulk@example.com",
+            "clearance_level": "SECRET",
+            "contract_number": unique("C"),
+            "department": department
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    department
+}
+
+async fn active_data_grants(client: &Client, admin: &str, person_id: i64) -> usize {
+    let (_, body) = get_json(client, admin, format!("/api/persons/{}/access", person_id)).await;
+    body["data"]["data_access"].as_array().expect("array").len()
+}
+
+fn outcome_of(result: &Value, person_id: i64) -> Value {
+    result["items"]
+        .as_array()
+        .expect("items")
+        .iter()
+        .find(|i| i["person_id"] == person_id)
+        .expect("person planned")["outcome"]
+        .clone()
+}
+
+#[rocket::async_test]
+async fn test_bulk_grant_requires_auth() {
+    let client = create_test_client().await;
+    let response = client
+        .post("/api/access/bulk/grant")
+        .header(ContentType::JSON)
+        .body("{}")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_bulk_grant_rejects_bad_selector_and_enduser() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let body = json!({
+        "selector": { "person_ids": [7], "department": "Anything" },
+        "access_type": "data",
+        "target": "CONFIDENTIAL",
+        "access_level": "READ"
+    });
+    let (status, _) =
+        common::post_json(&client, &admin, "/api/access/bulk/grant", body.clone()).await;
+    assert_eq!(status, Status::BadRequest);
+
+    let enduser = login(&client, "enduser").await;
+    let (status, _) = common::post_json(&client, &enduser, "/api/access/bulk/grant", body).await;
+    assert_eq!(status, Status::Forbidden);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_bulk_grant_by_department_dry_run_then_apply() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let department = create_department(&client, &admin).await;
+    let cleared = common::create_person(
+        &client,
+        &admin,
+        json!({ "clearance_level": "SECRET", "department": department }),
+    )
+    .await;
+    let uncleared = common::create_person(
+        &client,
+        &admin,
+        json!({ "clearance_level": "CONFIDENTIAL", "department": department }),
+    )
+    .await;
+    let request = json!({
+        "selector": { "department": department },
+        "access_type": "data",
+        "target": "SECRET",
+        "access_level": "READ"
+    });
+
+    let (status, body) =
+        common::post_json(&client, &admin, "/api/access/bulk/grant", request.clone()).await;
+    assert_eq!(status, Status::Ok);
+    let plan = &body["data"];
+    assert_eq!(plan["dry_run"], true);
+    assert!(plan["batch_id"].is_null());
+    assert_eq!(plan["selected"], 2);
+    assert_eq!(outcome_of(plan, cleared), "GRANTED");
+    assert_eq!(outcome_of(plan, uncleared), "BLOCKED");
+    assert_eq!(active_data_grants(&client, &admin, cleared).await, 0);
+
+    let mut apply = request;
+    apply["dry_run"] = json!(false);
+    let (status, body) = common::post_json(&client, &admin, "/api/access/bulk/grant", apply).await;
+    assert_eq!(status, Status::Ok);
+    let batch_id = body["data"]["batch_id"]
+        .as_str()
+        .expect("batch id")
+        .to_string();
+    assert_eq!(active_data_grants(&client, &admin, cleared).await, 1);
+    assert_eq!(active_data_grants(&client, &admin, uncleared).await, 0);
+
+    let (status, body) = get_json(&client, &admin, format!("/api/access/bulk/{}", batch_id)).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["batch"]["operation"], "GRANT");
+    assert_eq!(body["data"]["items"].as_array().expect("items").len(), 2);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_bulk_revoke_holders_of_zone() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let zone = unique("Bulk Zone");
+    let first = common::create_person(
+        &client,
+        &admin,
+        json!({ "clearance_level": "CONFIDENTIAL" }),
+    )
+    .await;
+    let second = common::create_person(
+        &client,
+        &admin,
+        json!({ "clearance_level": "CONFIDENTIAL" }),
+    )
+    .await;
+
+    let (status, body) = common::post_json(
+        &client,
+        &admin,
+        "/api/access/bulk/grant",
+        json!({
+            "selector": { "person_ids": [first, second] },
+            "access_type": "physical",
+            "target": zone,
+            "access_level": "VISITOR",
+            "dry_run": false
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(outcome_of(&body["data"], second), "GRANTED");
+
+    let (status, body) = common::post_json(
+        &client,
+        &admin,
+        "/api/access/bulk/revoke",
+        json!({
+            "selector": { "holders_of": { "access_type": "physical", "target": zone } },
+            "access_type": "physical",
+            "target": zone,
+            "reason": "Zone closed",
+            "dry_run": false
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let result = &body["data"];
+    assert_eq!(result["selected"], 2);
+    assert_eq!(outcome_of(result, first), "REVOKED");
+    assert_eq!(outcome_of(result, second), "REVOKED");
+
+    let (_, body) = get_json(&client, &admin, format!("/api/persons/{}/access", first)).await;
+    assert!(body["data"]["physical_access"]
+        .as_array()
+        .expect("array")
+        .is_empty());
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_concurrent_bulk_grants_grant_once() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let zone = unique("Bulk Zone");
+    let person = common::create_person(
+        &client,
+        &admin,
+        json!({ "clearance_level": "CONFIDENTIAL" }),
+    )
+    .await;
+    let request = json!({
+        "selector": { "person_ids": [person] },
+        "access_type": "physical",
+        "target": zone,
+        "access_level": "VISITOR",
+        "dry_run": false
+    });
+
+    let ((first, a), (second, b)) = tokio::join!(
+        common::post_json(&client, &admin, "/api/access/bulk/grant", request.clone()),
+        common::post_json(&client, &admin, "/api/access/bulk/grant", request.clone())
+    );
+    assert_eq!(first, Status::Ok);
+    assert_eq!(second, Status::Ok);
+    let mut outcomes = vec![
+        outcome_of(&a["data"], person),
+        outcome_of(&b["data"], person),
+    ];
+    outcomes.sort_by_key(|o| o.to_string());
+    assert_eq!(outcomes, vec![json!("GRANTED"), json!("SKIPPED")]);
+
+    let (_, body) = get_json(&client, &admin, format!("/api/persons/{}/access", person)).await;
+    assert_eq!(
+        body["data"]["physical_access"]
+            .as_array()
+            .expect("array")
+            .len(),
+        1
+    );
+}
@@ -0,0 +1,303 @@
+// Access bundle integration tests.
+//
+// Test map:
+//   GET  /api/access-bundles               — unauthenticated -> 401
+//   POST /api/access-bundles               — bad access level -> 400          [DB: login]
+//   POST /api/access-bundles/<id>/assign   — all templates granted in one     [DB: login]
+//                                            call; revoke + template change
+//                                            show up as drift
+//   POST /api/access-bundles/<id>/assign   — clearance failure on one         [DB: login]
+//                                            template -> 403, nothing granted
+//   POST /api/access-bundles/<id>/assign   — two concurrent assigns issue     [DB: login]
+//                                            each template once
+//
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test access_bundles_test -- --include-ignored
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn send(
+    client: &Client,
+    token: &str,
+    method: &str,
+    uri: String,
+    body: Value,
+) -> (Status, Value) {
+    let request = match method {
+        "POST" => client
+            .post(uri)
+            .header(ContentType::JSON)
+            .body(body.to_string()),
+        "DELETE" => client.delete(uri),
+        _ => client.get(uri),
+    };
+    let response = request.header(auth_header(token)).dispatch().await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+fn unique(prefix: &str) -> String {
+    format!("{}-{}", prefix, uuid::Uuid::new_v4())
+}
+
+#[rocket::async_test]
+async fn test_list_bundles_requires_auth() {
+    let client = create_test_client().await;
+    let response = client.get("/api/access-bundles").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_create_bundle_rejects_bad_level() {
+    let client = create_test_client().await;
+    let token = login(&client, "admin").await;
+    let (status, _) = send(
+        &client,
+        &token,
+        "POST",
+        "/api/access-bundles".to_string(),
+        json!({
+            "name": unique("bad"),
+            "items": [{ "access_type": "physical", "target": "Lobby", "access_level": "READ" }]
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_assign_bundle_and_report_drift() {
+    let client = create_test_client().await;
+    let token = login(&client, "admin").await;
+    let (status, body) = send(
+        &client,
+        &token,
+        "POST",
+        "/api/access-bundles".to_string(),
+        json!({
+            "name": unique("onboarding"),
+            "items": [
+                { "access_type": "physical", "target": "Lobby", "access_level": "VISITOR" },
+                { "access_type": "data", "target": "CONFIDENTIAL", "access_level": "READ",
+                  "duration_days": 30 }
+            ]
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let id = body["data"]["bundle"]["id"].as_i64().expect("bundle id");
+
+    // Person 7 holds CONFIDENTIAL clearance.
+    let (status, body) = send(
+        &client,
+        &token,
+        "POST",
+        format!("/api/access-bundles/{}/assign", id),
+        json!({ "person_id": 7 }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let grants = body["data"]["grants"].as_array().expect("grants").clone();
+    assert_eq!(grants.len(), 2);
+    assert!(grants.iter().all(|g| g["active"] == true));
+
+    let (_, body) = send(
+        &client,
+        &token,
+        "GET",
+        format!("/api/access-bundles/{}/drift", id),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(body["data"][0]["in_sync"], true);
+
+    // Revoke the physical grant and add a template: both are drift.
+    let physical = grants
+        .iter()
+        .find(|g| g["access_type"] == "physical")
+        .expect("physical grant");
+    let (status, _) = send(
+        &client,
+        &token,
+        "DELETE",
+        format!(
+            "/api/access/physical/{}",
+            physical["grant_id"].as_str().expect("grant id")
+        ),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let (status, _) = send(
+        &client,
+        &token,
+        "POST",
+        format!("/api/access-bundles/{}/items", id),
+        json!({ "access_type": "computer", "target": "Email Server", "access_level": "READ" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+
+    let (_, body) = send(
+        &client,
+        &token,
+        "GET",
+        format!("/api/access-bundles/{}/drift", id),
+        Value::Null,
+    )
+    .await;
+    let report = &body["data"][0];
+    assert_eq!(report["in_sync"], false);
+    let mut kinds: Vec<&str> = report["drift"]
+        .as_array()
+        .expect("drift")
+        .iter()
+        .map(|d| d["kind"].as_str().unwrap())
+        .collect();
+    kinds.sort();
+    assert_eq!(kinds, vec!["GRANT_INACTIVE", "TEMPLATE_ADDED"]);
+
+    // Re-assigning tops the person back up.
+    let (status, _) = send(
+        &client,
+        &token,
+        "POST",
+        format!("/api/access-bundles/{}/assign", id),
+        json!({ "person_id": 7 }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let (_, body) = send(
+        &client,
+        &token,
+        "GET",
+        format!("/api/access-bundles/{}/drift", id),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(body["data"][0]["in_sync"], true);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_assign_bundle_is_all_or_nothing_on_clearance() {
+    let client = create_test_client().await;
+    let token = login(&client, "admin").await;
+    let zone = unique("zone");
+    let (status, body) = send(
+        &client,
+        &token,
+        "POST",
+        "/api/access-bundles".to_string(),
+        json!({
+            "name": unique("secret-team"),
+            "items": [
+                { "access_type": "physical", "target": zone, "access_level": "STANDARD" },
+                { "access_type": "data", "target": "SECRET", "access_level": "READ" }
+            ]
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let id = body["data"]["bundle"]["id"].as_i64().expect("bundle id");
+
+    // Person 7 (CONFIDENTIAL) may not hold SECRET data access.
+    let (status, _) = send(
+        &client,
+        &token,
+        "POST",
+        format!("/api/access-bundles/{}/assign", id),
+        json!({ "person_id": 7 }),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+
+    let (_, body) = send(
+        &client,
+        &token,
+        "GET",
+        "/api/persons/7/access".to_string(),
+        Value::Null,
+    )
+    .await;
+    let physical = body["data"]["physical_access"].as_array().expect("array");
+    assert!(!physical.iter().any(|g| g["zone_name"] == zone.as_str()));
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_concurrent_assigns_issue_each_template_once() {
+    let client = create_test_client().await;
+    let token = login(&client, "admin").await;
+    let zone = unique("Annex");
+    let (status, body) = send(
+        &client,
+        &token,
+        "POST",
+        "/api/access-bundles".to_string(),
+        json!({
+            "name": unique("annex"),
+            "items": [{ "access_type": "physical", "target": zone, "access_level": "VISITOR" }]
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let id = body["data"]["bundle"]["id"].as_i64().expect("bundle id");
+
+    let assign = || {
+        send(
+            &client,
+            &token,
+            "POST",
+            format!("/api/access-bundles/{}/assign", id),
+            json!({ "person_id": 7 }),
+        )
+    };
+    let ((first, _), (second, _)) = tokio::join!(assign(), assign());
+    assert_eq!(first, Status::Ok);
+    assert_eq!(second, Status::Ok);
+
+    let (_, body) = send(
+        &client,
+        &token,
+        "GET",
+        "/api/persons/7/access".to_string(),
+        Value::Null,
+    )
+    .await;
+    let issued = body["data"]["physical_access"]
+        .as_array()
+        .expect("array")
+        .iter()
+        .filter(|g| g["zone_name"] == zone.as_str())
+        .count();
+    assert_eq!(issued, 1);
+}
@@ -0,0 +1,121 @@
+// Access grant expiry integration tests.
+//
+// Test map:
+//   GET /api/persons/<id>/access — past-expiry grant is not listed    [DB: login]
+//   expire_grants sweep          — lapsed grant flips to EXPIRED       [DB: login]
+//
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test access_expiry_test -- --include-ignored
+
+use chrono::{Duration, Utc};
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use janus_backend::access::expiry::expire_grants;
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+/// Grant person 7 a computer grant and wait until it lapses; returns its id.
+/// The table CHECK requires expires_at > granted_at, so it cannot be back-dated.
+async fn grant_lapsed(client: &Client, token: &str) -> i64 {
+    let expires_at = (Utc::now() + Duration::seconds(1)).naive_utc();
+    let response = client
+        .post("/api/access/computer")
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(
+            json!({
+                "person_id": 7,
+                "system_name": "File Server",
+                "access_level": "READ",
+                "expires_at": expires_at
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    let id = body["data"]["id"].as_i64().expect("id");
+    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    id
+}
+
+async fn listed_computer_ids(client: &Client, token: &str) -> Vec<i64> {
+    let response = client
+        .get("/api/persons/7/access")
+        .header(auth_header(token))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["data"]["computer_access"]
+        .as_array()
+        .expect("computer_access array")
+        .iter()
+        .filter_map(|g| g["id"].as_i64())
+        .collect()
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_lapsed_grant_not_listed_before_sweep() {
+    let client = create_test_client().await;
+    let token = login(&client, "admin").await;
+
+    let id = grant_lapsed(&client, &token).await;
+    assert!(!listed_computer_ids(&client, &token).await.contains(&id));
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_sweep_marks_lapsed_grant_expired() {
+    let client = create_test_client().await;
+    let token = login(&client, "admin").await;
+    let id = grant_lapsed(&client, &token).await;
+
+    let pool = client.rocket().state::<PgPool>().expect("pool").clone();
+    expire_grants(&pool, Utc::now().naive_utc())
+        .await
+        .expect("sweep");
+
+    let status: String = sqlx::query_scalar("SELECT status FROM computer_access WHERE id = $1")
+        .bind(id as i32)
+        .fetch_one(&pool)
+        .await
+        .expect("grant row");
+    assert_eq!(status, "EXPIRED");
+
+    let audited: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM audit_log \
+         WHERE action = 'ACCESS_EXPIRED' AND resource_type = 'computer_access' AND resource_id = $1",
+    )
+    .bind(id as i32)
+    .fetch_one(&pool)
+    .await
+    .expect("audit count");
+    assert_eq!(audited, 1);
+}
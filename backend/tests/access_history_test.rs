@@ -0,0 +1,149 @@
+// Access history and timeline integration tests.
+//
+// Test map:
+//   DELETE /api/access/<type>/<id>?reason  — revoker/time/reason recorded,   [DB: login]
+//   GET /api/persons/<id>/access            active-only by default,
+//                                           ?include_inactive=true keeps it
+//   GET /api/persons/<id>/access/timeline  — grant before revoke, clearance  [DB: login]
+//                                           change listed
+//   GET /api/persons/<id>/access/timeline  — enduser on someone else -> 403  [DB: login]
+//
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test access_history_test -- --include-ignored
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn get_json(client: &Client, token: &str, uri: String) -> (Status, Value) {
+    let response = client.get(uri).header(auth_header(token)).dispatch().await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+async fn grant_and_revoke(client: &Client, token: &str) -> i64 {
+    let response = client
+        .post("/api/access/physical")
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(
+            json!({ "person_id": 7, "zone_name": "Archive Vault", "access_level": "VISITOR" })
+                .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    let id = body["data"]["id"].as_i64().expect("grant id");
+
+    let response = client
+        .delete(format!(
+            "/api/access/physical/{}?reason=Project%20ended",
+            id
+        ))
+        .header(auth_header(token))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    id
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_revoked_grant_kept_with_history() {
+    let client = create_test_client().await;
+    let token = login(&client, "admin").await;
+    let id = grant_and_revoke(&client, &token).await;
+
+    let (status, body) = get_json(&client, &token, "/api/persons/7/access".to_string()).await;
+    assert_eq!(status, Status::Ok);
+    let active = body["data"]["physical_access"].as_array().expect("array");
+    assert!(!active.iter().any(|g| g["id"] == id));
+
+    let (status, body) = get_json(
+        &client,
+        &token,
+        "/api/persons/7/access?include_inactive=true".to_string(),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let grant = body["data"]["physical_access"]
+        .as_array()
+        .expect("array")
+        .iter()
+        .find(|g| g["id"] == id)
+        .expect("revoked grant still listed")
+        .clone();
+    assert_eq!(grant["status"], "REVOKED");
+    assert_eq!(grant["revoked_by_person_id"], 1);
+    assert_eq!(grant["revocation_reason"], "Project ended");
+    assert!(grant["revoked_at"].is_string());
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_timeline_orders_grant_before_revoke() {
+    let client = create_test_client().await;
+    let token = login(&client, "admin").await;
+    let id = grant_and_revoke(&client, &token).await.to_string();
+
+    let (status, body) = get_json(
+        &client,
+        &token,
+        "/api/persons/7/access/timeline".to_string(),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let events = body["data"].as_array().expect("events");
+
+    let position = |event: &str| {
+        events
+            .iter()
+            .position(|e| {
+                e["source"] == "physical_access" && e["source_id"] == id && e["event"] == event
+            })
+            .unwrap_or_else(|| panic!("{event} missing"))
+    };
+    assert!(position("ACCESS_GRANTED") < position("ACCESS_REVOKED"));
+
+    // Seeded CONFIDENTIAL clearance is backfilled as the first change.
+    assert!(events.iter().any(|e| e["event"] == "CLEARANCE_CHANGED"));
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_timeline_forbidden_for_other_enduser() {
+    let client = create_test_client().await;
+    let token = login(&client, "enduser").await;
+    let (status, _) = get_json(
+        &client,
+        &token,
+        "/api/persons/7/access/timeline".to_string(),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+}
@@ -0,0 +1,175 @@
+// Access request workflow integration tests.
+//
+// Test map:
+//   POST /api/access-requests           — 401 without a token              [DB: conn]
+//   POST /api/access-requests           — enduser for someone else -> 403  [DB: login]
+//   POST + /approve                     — manager files, admin approves,   [DB: login]
+//                                         grant row is created
+//   POST /<id>/approve                  — enduser cannot decide -> 403     [DB: login]
+//   POST /<id>/deny                     — reason is mandatory -> 400       [DB: login]
+//
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test access_requests_test -- --include-ignored
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+
+    assert_eq!(
+        response.status(),
+        Status::Ok,
+        "login must succeed for {username}"
+    );
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+// Seeded person 7 (Operations, CONFIDENTIAL) is the subject of every request.
+async fn file_request(client: &Client, token: &str) -> Value {
+    let response = client
+        .post("/api/access-requests")
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(
+            json!({
+                "person_id": 7,
+                "access_type": "physical",
+                "target": "Server Room B",
+                "access_level": "STANDARD",
+                "justification": "Rotating onto the on-call hardware team"
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["data"].clone()
+}
+
+#[rocket::async_test]
+async fn test_create_request_unauthenticated() {
+    let client = create_test_client().await;
+    let response = client
+        .post("/api/access-requests")
+        .header(ContentType::JSON)
+        .body("{}")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_enduser_cannot_request_for_others() {
+    let client = create_test_client().await;
+    let token = login(&client, "enduser").await;
+
+    let response = client
+        .post("/api/access-requests")
+        .header(ContentType::JSON)
+        .header(auth_header(&token))
+        .body(
+            json!({
+                "person_id": 7,
+                "access_type": "computer",
+                "target": "File Server",
+                "access_level": "READ",
+                "justification": "Needs the shared drive"
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users and the SoD seed migration
+async fn test_manager_requests_admin_approves() {
+    let client = create_test_client().await;
+    let manager = login(&client, "manager").await;
+    let admin = login(&client, "admin").await;
+
+    let request = file_request(&client, &manager).await;
+    assert_eq!(request["status"], "PENDING");
+    let id = request["id"].as_i64().expect("id");
+
+    let response = client
+        .post(format!("/api/access-requests/{}/approve", id))
+        .header(ContentType::JSON)
+        .header(auth_header(&admin))
+        .body(json!({}).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    assert_eq!(body["data"]["status"], "APPROVED");
+    assert!(body["data"]["granted_access_id"].is_number());
+
+    // A decided request cannot be decided again.
+    let again = client
+        .post(format!("/api/access-requests/{}/approve", id))
+        .header(ContentType::JSON)
+        .header(auth_header(&admin))
+        .body(json!({}).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(again.status(), Status::Conflict);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_enduser_cannot_decide() {
+    let client = create_test_client().await;
+    let manager = login(&client, "manager").await;
+    let enduser = login(&client, "enduser").await;
+
+    let request = file_request(&client, &manager).await;
+    let response = client
+        .post(format!("/api/access-requests/{}/approve", request["id"]))
+        .header(ContentType::JSON)
+        .header(auth_header(&enduser))
+        .body(json!({}).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Forbidden);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_deny_requires_reason() {
+    let client = create_test_client().await;
+    let manager = login(&client, "manager").await;
+    let admin = login(&client, "admin").await;
+
+    let request = file_request(&client, &manager).await;
+    let response = client
+        .post(format!("/api/access-requests/{}/deny", request["id"]))
+        .header(ContentType::JSON)
+        .header(auth_header(&admin))
+        .body(json!({}).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+}
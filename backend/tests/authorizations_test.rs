@@ -0,0 +1,259 @@
+// Managerial authorization integration tests.
+//
+// Test map:
+//   GET  /api/authorizations/reports                  — no token -> 401          [no DB]
+//   POST /api/authorizations/persons/<id>             — manager authorizes a     [DB: login]
+//   POST /api/authorizations/persons/<id>/withdraw      report: no rule before,
+//   POST /api/abac/decide                               none while AUTHORIZED;
+//   GET  /api/authorizations/reports                    withdrawn -> failing
+//                                                       "Authorization valid";
+//                                                       renewal restores it; a
+//                                                       lapsed one is EXPIRED
+//   POST /api/authorizations/persons/<id>             — not a report / admin     [DB: login]
+//                                                       -> 403; incomplete
+//                                                       authorization -> 400;
+//                                                       withdraw twice -> 409;
+//                                                       viewer -> 403
+//
+// Each test creates its own report and reporting line so seed users keep no
+// authorization records.
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test authorizations_test -- --include-ignored
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn post(client: &Client, token: &str, uri: String, body: Value) -> (Status, Value) {
+    let response = client
+        .post(uri)
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+async fn get(client: &Client, token: &str, uri: String) -> (Status, Value) {
+    let response = client.get(uri).header(auth_header(token)).dispatch().await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+/// A fresh person who reports to the seed manager.
+async fn create_report(client: &Client, admin: &str) -> i64 {
+    let (status, body) = post(
+        client,
+        admin,
+        "/api/person".to_string(),
+        json!({ "first_name": "Authorized", "last_name": "Report" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let person_id = body["id"].as_i64().expect("person id");
+
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    sqlx::query(
+        "INSERT INTO relations (entity_type, entity_id, related_entity_type, related_entity_id, \
+           relation_type, valid_from) \
+         SELECT 'person', $1, 'person', id, 'reports_to', NOW() - INTERVAL '1 day' \
+         FROM person WHERE username = 'manager'",
+    )
+    .bind(person_id as i32)
+    .execute(pool)
+    .await
+    .expect("reporting line");
+    person_id
+}
+
+fn authorization(days: i64) -> Value {
+    let today = chrono::Utc::now().naive_utc();
+    json!({
+        "conversation_date": today.date().to_string(),
+        "valid_until": (today + chrono::Duration::days(days)).format("%Y-%m-%dT%H:%M:%S").to_string()
+    })
+}
+
+async fn authorization_rule(client: &Client, token: &str, person_id: i64) -> Option<Value> {
+    let (status, body) = post(
+        client,
+        token,
+        "/api/abac/decide".to_string(),
+        json!({
+            "person_id": person_id,
+            "domain": "DATA",
+            "min_clearance": "UNCLASSIFIED",
+            "owner_organization_id": 1
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    body["data"]["rules"]
+        .as_array()
+        .expect("rules")
+        .iter()
+        .find(|r| r["name"] == "Authorization valid")
+        .cloned()
+}
+
+#[rocket::async_test]
+async fn test_list_reports_requires_auth() {
+    let client = create_test_client().await;
+    let response = client.get("/api/authorizations/reports").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_authorize_withdraw_renew_expire() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let manager = login(&client, "manager").await;
+    let person_id = create_report(&client, &admin).await;
+    let uri = format!("/api/authorizations/persons/{}", person_id);
+
+    assert!(authorization_rule(&client, &admin, person_id)
+        .await
+        .is_none());
+
+    let (status, body) = post(&client, &manager, uri.clone(), authorization(90)).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["effective_status"], "AUTHORIZED");
+    assert!(authorization_rule(&client, &admin, person_id)
+        .await
+        .is_none());
+
+    let (_, body) = get(&client, &manager, "/api/authorizations/reports".to_string()).await;
+    let report = body["data"]
+        .as_array()
+        .expect("reports")
+        .iter()
+        .find(|r| r["person_id"] == person_id)
+        .cloned()
+        .expect("listed as report");
+    assert_eq!(report["authorization"]["effective_status"], "AUTHORIZED");
+
+    let (status, body) = post(
+        &client,
+        &manager,
+        format!("{}/withdraw", uri),
+        json!({ "reason": "role change" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["status"], "WITHDRAWN");
+    let rule = authorization_rule(&client, &admin, person_id)
+        .await
+        .expect("rule added");
+    assert_eq!(rule["pass"], false);
+    assert_eq!(
+        rule["detail"],
+        "authorization.status=WITHDRAWN (requires AUTHORIZED)"
+    );
+
+    // Renewal is a new record; letting it lapse makes it EXPIRED.
+    let (status, _) = post(&client, &manager, uri.clone(), authorization(30)).await;
+    assert_eq!(status, Status::Ok);
+    assert!(authorization_rule(&client, &admin, person_id)
+        .await
+        .is_none());
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    sqlx::query(
+        "UPDATE person_authorizations SET valid_until = NOW() - INTERVAL '1 hour' \
+         WHERE person_id = $1 AND status = 'AUTHORIZED'",
+    )
+    .bind(person_id as i32)
+    .execute(pool)
+    .await
+    .expect("lapse");
+    let rule = authorization_rule(&client, &admin, person_id)
+        .await
+        .expect("rule added");
+    assert_eq!(
+        rule["detail"],
+        "authorization.status=EXPIRED (requires AUTHORIZED)"
+    );
+    let (_, body) = get(&client, &admin, uri).await;
+    assert_eq!(body["data"]["effective_status"], "EXPIRED");
+    assert_eq!(body["data"]["records"].as_array().unwrap().len(), 2);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_authorize_validation() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let manager = login(&client, "manager").await;
+    let person_id = create_report(&client, &admin).await;
+    let uri = format!("/api/authorizations/persons/{}", person_id);
+
+    // The admin is not the line manager.
+    let (status, _) = post(&client, &admin, uri.clone(), authorization(30)).await;
+    assert_eq!(status, Status::Forbidden);
+    // Person 5 does not report to the manager.
+    let (status, _) = post(
+        &client,
+        &manager,
+        "/api/authorizations/persons/5".to_string(),
+        authorization(30),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+
+    let (status, _) = post(
+        &client,
+        &manager,
+        uri.clone(),
+        json!({ "conversation_date": "2026-01-01" }),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+    let (status, _) = post(&client, &manager, uri.clone(), authorization(-1)).await;
+    assert_eq!(status, Status::BadRequest);
+
+    let (status, body) = post(&client, &manager, uri.clone(), json!({})).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["status"], "PENDING");
+    let withdraw = format!("{}/withdraw", uri);
+    let (status, _) = post(
+        &client,
+        &manager,
+        withdraw.clone(),
+        json!({ "reason": "x" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let (status, _) = post(&client, &manager, withdraw, json!({ "reason": "x" })).await;
+    assert_eq!(status, Status::Conflict);
+
+    let viewer = login(&client, "viewer").await;
+    let (status, _) = post(&client, &viewer, uri, authorization(30)).await;
+    assert_eq!(status, Status::Forbidden);
+}
@@ -0,0 +1,223 @@
+// Clearance check integration tests.
+//
+// Test map:
+//   POST /api/access/data                 — CONFIDENTIAL person, TOP_SECRET  [DB: login]
+//                                           data -> 403; SECRET person,
+//                                           SECRET data -> 200
+//   POST /api/access/computer             — system classified above the      [DB: login]
+//                                           holder's clearance -> 403
+//   GET  /api/access/clearance-violations — downgraded holder is listed      [DB: login]
+//   PUT  /api/info-systems/<id>           — classification null removes it;  [DB: login]
+//                                           the change is audited
+//
+// Seed persons: 5 = SECRET, 6 = TOP_SECRET, 7 = CONFIDENTIAL.
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test clearance_check_test -- --include-ignored
+
+use chrono::Utc;
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn post_json(client: &Client, token: &str, uri: &str, body: Value) -> (Status, Value) {
+    let response = client
+        .post(uri.to_string())
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_data_grant_requires_clearance() {
+    let client = create_test_client().await;
+    let token = login(&client, "admin").await;
+
+    let (status, _) = post_json(
+        &client,
+        &token,
+        "/api/access/data",
+        json!({ "person_id": 7, "data_classification": "TOP_SECRET", "access_level": "READ" }),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+
+    let (status, _) = post_json(
+        &client,
+        &token,
+        "/api/access/data",
+        json!({ "person_id": 5, "data_classification": "SECRET", "access_level": "READ" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_classified_computer_grant_requires_clearance() {
+    let client = create_test_client().await;
+    let token = login(&client, "admin").await;
+    let system = format!("Classified Enclave {}", Utc::now().timestamp_millis());
+
+    let (status, _) = post_json(
+        &client,
+        &token,
+        "/api/info-systems",
+        json!({
+            "system_name": system,
+            "environment": "PROD",
+            "status": "ACTIVE",
+            "classification": "SECRET"
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+
+    let (status, _) = post_json(
+        &client,
+        &token,
+        "/api/access/computer",
+        json!({ "person_id": 7, "system_name": system, "access_level": "READ" }),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+
+    let (status, _) = post_json(
+        &client,
+        &token,
+        "/api/access/computer",
+        json!({ "person_id": 6, "system_name": system, "access_level": "READ" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_violations_list_downgraded_holder() {
+    let client = create_test_client().await;
+    let token = login(&client, "admin").await;
+    let pool = client.rocket().state::<PgPool>().expect("pool").clone();
+
+    // Grant at the holder's clearance, then downgrade them directly.
+    let (status, body) = post_json(
+        &client,
+        &token,
+        "/api/access/data",
+        json!({ "person_id": 6, "data_classification": "TOP_SECRET", "access_level": "READ" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let grant_id = body["data"]["id"].as_i64().expect("grant id");
+    sqlx::query("UPDATE person SET clearance_level = 'SECRET' WHERE id = 6")
+        .execute(&pool)
+        .await
+        .expect("downgrade");
+
+    let response = client
+        .get("/api/access/clearance-violations")
+        .header(auth_header(&token))
+        .dispatch()
+        .await;
+    let status = response.status();
+    let body: Value = response.into_json().await.expect("valid json");
+
+    sqlx::query("UPDATE person SET clearance_level = 'TOP_SECRET' WHERE id = 6")
+        .execute(&pool)
+        .await
+        .expect("restore clearance");
+
+    assert_eq!(status, Status::Ok);
+    let listed = body["data"]
+        .as_array()
+        .expect("violations")
+        .iter()
+        .any(|v| v["access_type"] == "data" && v["access_id"] == grant_id);
+    assert!(listed, "downgraded TOP_SECRET grant must be reported");
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_system_classification_can_be_removed() {
+    let client = create_test_client().await;
+    let token = login(&client, "admin").await;
+    let pool = client.rocket().state::<PgPool>().expect("pool").clone();
+    let system = format!("Declassified Enclave {}", Utc::now().timestamp_millis());
+
+    let (status, body) = post_json(
+        &client,
+        &token,
+        "/api/info-systems",
+        json!({
+            "system_name": system,
+            "environment": "PROD",
+            "status": "ACTIVE",
+            "classification": "SECRET"
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let system_id = body["id"].as_i64().expect("system id");
+
+    // Omitting the field keeps the classification
+    let response = client
+        .put(format!("/api/info-systems/{}", system_id))
+        .header(ContentType::JSON)
+        .header(auth_header(&token))
+        .body(json!({ "status": "MAINTENANCE" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    assert_eq!(body["classification"], "SECRET");
+
+    let response = client
+        .put(format!("/api/info-systems/{}", system_id))
+        .header(ContentType::JSON)
+        .header(auth_header(&token))
+        .body(json!({ "classification": null }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    assert!(body["classification"].is_null());
+
+    let audited: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM audit_log \
+         WHERE action = 'INFO_SYSTEM_CLASSIFICATION_CHANGED' AND resource_id = $1",
+    )
+    .bind(system_id as i32)
+    .fetch_one(&pool)
+    .await
+    .expect("audit count");
+    assert_eq!(audited, 1);
+}
@@ -0,0 +1,346 @@
+// Clearance lifecycle integration tests.
+//
+// Test map:
+//   GET  /api/clearances/persons/<id>            — no token -> 401               [no DB]
+//   POST /api/clearances/persons/<id>            — officer grants TOP_SECRET;     [DB: login]
+//   POST /api/clearances/records/<id>/status       TOP_SECRET data grant allowed;
+//                                                  suspending revokes it and
+//                                                  clears the effective level;
+//                                                  reinstate restores the level
+//                                                  but not the revoked grant;
+//                                                  revoked is final (409)
+//   GET  /api/clearances/reinvestigations-due    — expired record: effective      [DB: login]
+//                                                  clearance none, classified
+//                                                  grant refused, listed overdue;
+//                                                  due date inside the window
+//                                                  listed, outside not
+//   POST /api/clearances/persons/<id>            — unknown level / past expiry    [DB: login]
+//                                                  -> 400; on self -> 403;
+//   POST /api/person                               viewer -> 403; manager
+//                                                  creating a person with a
+//                                                  clearance -> 403
+//   PUT  /api/person/<id>                        — own clearance change -> 403;   [DB: login]
+//                                                  unchanged level resubmitted
+//                                                  -> 200
+//
+// Each test creates its own person so seed clearances are never changed.
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test clearances_test -- --include-ignored
+
+mod common;
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn post(client: &Client, token: &str, uri: String, body: Value) -> (Status, Value) {
+    let response = client
+        .post(uri)
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+async fn get(client: &Client, token: &str, uri: String) -> (Status, Value) {
+    let response = client.get(uri).header(auth_header(token)).dispatch().await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+async fn set_status(client: &Client, token: &str, record_id: i64, status: &str) -> Status {
+    post(
+        client,
+        token,
+        format!("/api/clearances/records/{}/status", record_id),
+        json!({ "status": status, "reason": "test" }),
+    )
+    .await
+    .0
+}
+
+#[rocket::async_test]
+async fn test_get_clearance_requires_auth() {
+    let client = create_test_client().await;
+    let response = client.get("/api/clearances/persons/1").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_grant_suspend_reinstate_revoke() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let officer = login(&client, "security").await;
+    let person_id = common::create_person(&client, &admin, json!({})).await;
+    let clearance_uri = format!("/api/clearances/persons/{}", person_id);
+
+    // Creating the person recorded their SECRET clearance.
+    let (_, body) = get(&client, &admin, clearance_uri.clone()).await;
+    assert_eq!(body["data"]["effective_clearance"], "SECRET");
+    assert_eq!(body["data"]["records"].as_array().unwrap().len(), 1);
+
+    let (status, body) = post(
+        &client,
+        &officer,
+        clearance_uri.clone(),
+        json!({
+            "level": "TOP_SECRET",
+            "granting_authority": "Defence Vetting Agency",
+            "reinvestigation_due": "2031-01-01"
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["status"], "GRANTED");
+    let record_id = body["data"]["id"].as_i64().expect("record id");
+
+    let (status, _) = post(
+        &client,
+        &admin,
+        "/api/access/data".to_string(),
+        json!({ "person_id": person_id, "data_classification": "TOP_SECRET", "access_level": "READ" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+
+    assert_eq!(
+        set_status(&client, &officer, record_id, "SUSPENDED").await,
+        Status::Ok
+    );
+    let (_, body) = get(&client, &admin, clearance_uri.clone()).await;
+    assert_eq!(body["data"]["effective_clearance"], Value::Null);
+    let (_, body) = get(
+        &client,
+        &admin,
+        format!("/api/persons/{}/access", person_id),
+    )
+    .await;
+    assert_eq!(body["data"]["data_access"], json!([]));
+
+    assert_eq!(
+        set_status(&client, &officer, record_id, "GRANTED").await,
+        Status::Ok
+    );
+    let (_, body) = get(&client, &admin, clearance_uri.clone()).await;
+    assert_eq!(body["data"]["effective_clearance"], "TOP_SECRET");
+    // Reinstating restores the clearance, not the grants it lost.
+    let (_, body) = get(
+        &client,
+        &admin,
+        format!("/api/persons/{}/access", person_id),
+    )
+    .await;
+    assert_eq!(body["data"]["data_access"], json!([]));
+
+    assert_eq!(
+        set_status(&client, &officer, record_id, "REVOKED").await,
+        Status::Ok
+    );
+    assert_eq!(
+        set_status(&client, &officer, record_id, "GRANTED").await,
+        Status::Conflict
+    );
+
+    // The superseded SECRET record cannot be changed any more.
+    let (_, body) = get(&client, &admin, clearance_uri).await;
+    let older = body["data"]["records"][1]["id"].as_i64().expect("older id");
+    assert_eq!(
+        set_status(&client, &officer, older, "SUSPENDED").await,
+        Status::Conflict
+    );
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_expired_clearance_and_due_list() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let expired_person = common::create_person(&client, &admin, json!({})).await;
+    let due_person = common::create_person(&client, &admin, json!({})).await;
+
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    // Backdate the record created with the person so it has lapsed.
+    sqlx::query(
+        "UPDATE clearance_records SET granted_at = NOW() - INTERVAL '2 days', \
+           valid_until = NOW() - INTERVAL '1 day' WHERE person_id = $1",
+    )
+    .bind(expired_person as i32)
+    .execute(pool)
+    .await
+    .expect("expired record");
+
+    let (_, body) = get(
+        &client,
+        &admin,
+        format!("/api/clearances/persons/{}", expired_person),
+    )
+    .await;
+    assert_eq!(body["data"]["effective_clearance"], Value::Null);
+    let (status, _) = post(
+        &client,
+        &admin,
+        "/api/access/data".to_string(),
+        json!({ "person_id": expired_person, "data_classification": "SECRET", "access_level": "READ" }),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+
+    let due = (chrono::Utc::now().date_naive() + chrono::Duration::days(30)).to_string();
+    let (status, _) = post(
+        &client,
+        &admin,
+        format!("/api/clearances/persons/{}", due_person),
+        json!({ "level": "SECRET", "granting_authority": "test", "reinvestigation_due": due }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+
+    let listed = |body: &Value, person_id: i64| {
+        body["data"]
+            .as_array()
+            .expect("due list")
+            .iter()
+            .find(|d| d["person_id"] == person_id)
+            .cloned()
+    };
+    let (status, body) = get(
+        &client,
+        &admin,
+        "/api/clearances/reinvestigations-due?within_days=60".to_string(),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(
+        listed(&body, expired_person).expect("expired")["overdue"],
+        true
+    );
+    let upcoming = listed(&body, due_person).expect("upcoming");
+    assert_eq!(upcoming["overdue"], false);
+    assert_eq!(upcoming["due"], due);
+
+    let (_, body) = get(
+        &client,
+        &admin,
+        "/api/clearances/reinvestigations-due?within_days=10".to_string(),
+    )
+    .await;
+    assert!(listed(&body, due_person).is_none());
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_grant_clearance_validation() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let person_id = common::create_person(&client, &admin, json!({})).await;
+    let uri = format!("/api/clearances/persons/{}", person_id);
+
+    let (status, _) = post(
+        &client,
+        &admin,
+        uri.clone(),
+        json!({ "level": "COSMIC", "granting_authority": "test" }),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+    let (status, _) = post(
+        &client,
+        &admin,
+        uri,
+        json!({
+            "level": "SECRET",
+            "granting_authority": "test",
+            "valid_until": "2001-01-01T00:00:00"
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+
+    // admin is person 1: granting yourself a clearance breaks the SoD rule.
+    let (status, _) = post(
+        &client,
+        &admin,
+        "/api/clearances/persons/1".to_string(),
+        json!({ "level": "TOP_SECRET", "granting_authority": "self" }),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+
+    // The person API falls under the same rule when the level changes, and
+    // resubmitting the unchanged level is not a clearance change at all.
+    let (_, admin_person) = get(&client, &admin, "/api/person/1".to_string()).await;
+    let own_level = admin_person["clearance_level"].clone();
+    let changed = if own_level == "TOP_SECRET" {
+        "SECRET"
+    } else {
+        "TOP_SECRET"
+    };
+    for (level, expected) in [(json!(changed), Status::Forbidden), (own_level, Status::Ok)] {
+        let response = client
+            .put("/api/person/1")
+            .header(ContentType::JSON)
+            .header(auth_header(&admin))
+            .body(json!({ "clearance_level": level }).to_string())
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), expected);
+    }
+
+    // person.write alone may create a person, but not with a clearance.
+    let manager = login(&client, "manager").await;
+    let (status, _) = post(
+        &client,
+        &manager,
+        "/api/person".to_string(),
+        json!({ "first_name": "Uncleared", "clearance_level": "SECRET" }),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+    let (status, _) = post(
+        &client,
+        &manager,
+        "/api/person".to_string(),
+        json!({ "first_name": "Uncleared" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+
+    let viewer = login(&client, "viewer").await;
+    let (status, _) = post(
+        &client,
+        &viewer,
+        format!("/api/clearances/persons/{}", person_id),
+        json!({ "level": "SECRET", "granting_authority": "test" }),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+}
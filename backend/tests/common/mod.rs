@@ -0,0 +1,103 @@
+// Shared fixtures for the integration tests: persons created through the
+// API, and digital-resource networks inserted directly.
+//
+// Each test binary includes this with `mod common;`, so a binary may leave
+// some helpers unused.
+#![allow(dead_code)]
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+/// Tables whose rows hang off a resource id without a foreign key, so they do
+/// not cascade when the network goes.
+const RESOURCE_KEYED_TABLES: [&str; 5] = [
+    "resource_access_grants",
+    "resource_access_delegates",
+    "resource_policy_assignments",
+    "resource_org_links",
+    "resource_shield_allowlist",
+];
+
+/// POST `body` to `uri` as `token`; the status and the JSON reply (Null when
+/// there is none).
+pub async fn post_json(client: &Client, token: &str, uri: &str, body: Value) -> (Status, Value) {
+    let response = client
+        .post(uri.to_string())
+        .header(ContentType::JSON)
+        .header(Header::new("Authorization", format!("Bearer {}", token)))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+/// Create a person as `admin` and return their id. `fields` override the
+/// defaults, a SECRET-cleared "Test Subject"; a null clearance_level creates
+/// an uncleared person.
+pub async fn create_person(client: &Client, admin: &str, fields: Value) -> i64 {
+    let mut body = json!({
+        "first_name": "Test",
+        "last_name": "Subject",
+        "clearance_level": "SECRET"
+    });
+    if let (Some(body), Some(fields)) = (body.as_object_mut(), fields.as_object()) {
+        body.extend(fields.clone());
+    }
+    let (status, reply) = post_json(client, admin, "/api/person", body).await;
+    assert_eq!(status, Status::Ok, "{reply}");
+    reply["id"].as_i64().expect("person id")
+}
+
+/// Insert an UNCLASSIFIED network with a fresh `<prefix>-<uuid>` id, assigned
+/// to `policy_id` when given. Pair every call with drop_network.
+pub async fn create_network(client: &Client, prefix: &str, policy_id: Option<&str>) -> String {
+    let id = format!("{}-{}", prefix, uuid::Uuid::new_v4().simple());
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    sqlx::query(
+        "INSERT INTO resource_networks (id, name, classification) VALUES ($1, $1, 'UNCLASSIFIED')",
+    )
+    .bind(&id)
+    .execute(pool)
+    .await
+    .expect("network");
+    if let Some(policy_id) = policy_id {
+        sqlx::query(
+            "INSERT INTO resource_policy_assignments (resource_id, resource_tier, policy_id) \
+             VALUES ($1, 'NETWORK', $2)",
+        )
+        .bind(&id)
+        .bind(policy_id)
+        .execute(pool)
+        .await
+        .expect("policy assignment");
+    }
+    id
+}
+
+/// Remove a test network and every row keyed on it; /world tests count the
+/// seeded networks.
+pub async fn drop_network(client: &Client, id: &str) {
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    for table in RESOURCE_KEYED_TABLES {
+        sqlx::query(&format!("DELETE FROM {} WHERE resource_id = $1", table))
+            .bind(id)
+            .execute(pool)
+            .await
+            .expect("cleanup");
+    }
+    sqlx::query(
+        "DELETE FROM compartment_requirements WHERE target_type = 'resource' AND target = $1",
+    )
+    .bind(id)
+    .execute(pool)
+    .await
+    .expect("cleanup");
+    sqlx::query("DELETE FROM resource_networks WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await
+        .expect("cleanup");
+}
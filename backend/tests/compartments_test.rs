@@ -0,0 +1,255 @@
+// Compartment integration tests.
+//
+// Test map:
+//   GET    /api/compartments                         — no token -> 401              [no DB]
+//   POST   /api/compartments/persons/<id>/grants     — resource requiring a fresh    [DB: login]
+//          POST /api/digital-resources/grants          compartment: grant refused
+//          DELETE /api/compartments/grants/<id>        (403) until the person holds
+//                                                      it, then issued; revoking the
+//                                                      compartment ends the resource
+//                                                      grant; revoked grant leaves
+//                                                      the in-force list but stays
+//                                                      in the history
+//   POST   /api/compartments/persons/<id>/grants     — empty window -> 400; unknown  [DB: login]
+//                                                      compartment -> 404; second open
+//                                                      grant -> 409
+//   POST   /api/compartments/persons/<id>/grants     — viewer -> 403                  [DB: login]
+//
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test compartments_test -- --include-ignored
+
+mod common;
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn post(client: &Client, token: &str, uri: String, body: Value) -> (Status, Value) {
+    let response = client
+        .post(uri)
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+async fn get(client: &Client, token: &str, uri: String) -> (Status, Value) {
+    let response = client.get(uri).header(auth_header(token)).dispatch().await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+fn suffix() -> String {
+    uuid::Uuid::new_v4().simple().to_string()[..10].to_uppercase()
+}
+
+async fn create_compartment(client: &Client, admin: &str) -> String {
+    let code = format!("TEST_{}", suffix());
+    let (status, _) = post(
+        client,
+        admin,
+        "/api/compartments".to_string(),
+        json!({ "code": code, "name": "Test compartment" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    code
+}
+
+#[rocket::async_test]
+async fn test_list_compartments_requires_auth() {
+    let client = create_test_client().await;
+    let response = client.get("/api/compartments").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_resource_grant_requires_compartment() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let code = create_compartment(&client, &admin).await;
+    let person_id = common::create_person(&client, &admin, json!({})).await;
+
+    // A fresh network, so no other test's grants see the requirement.
+    let network_id = common::create_network(&client, "net-compartment", None).await;
+
+    let (status, _) = post(
+        &client,
+        &admin,
+        "/api/compartments/requirements".to_string(),
+        json!({ "target_type": "resource", "target": network_id, "compartment_code": code }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+
+    let issue = json!({
+        "resource_id": network_id,
+        "person_id": person_id.to_string(),
+        "actor_org_id": "ignored"
+    });
+    let (status, _) = post(
+        &client,
+        &admin,
+        "/api/digital-resources/grants".to_string(),
+        issue.clone(),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+
+    let (status, body) = post(
+        &client,
+        &admin,
+        format!("/api/compartments/persons/{}/grants", person_id),
+        json!({ "compartment_code": code }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let grant = &body["data"];
+    assert_eq!(grant["active"], true);
+    assert_eq!(grant["approved_by_person_id"], 1);
+    let grant_id = grant["id"].as_i64().expect("grant id");
+
+    let (status, _) = post(
+        &client,
+        &admin,
+        "/api/digital-resources/grants".to_string(),
+        issue,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+
+    let response = client
+        .delete(format!(
+            "/api/compartments/grants/{}?reason=rotation",
+            grant_id
+        ))
+        .header(auth_header(&admin))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    assert_eq!(body["data"]["active"], false);
+    assert_eq!(body["data"]["revocation_reason"], "rotation");
+
+    // The resource grant needed the compartment and ended with it.
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    let in_force: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM resource_access_grants WHERE resource_id = $1 \
+         AND person_id = $2 AND (valid_until IS NULL OR valid_until > NOW())",
+    )
+    .bind(&network_id)
+    .bind(person_id.to_string())
+    .fetch_one(pool)
+    .await
+    .expect("grant count");
+    assert_eq!(in_force, 0);
+
+    let (_, body) = get(
+        &client,
+        &admin,
+        format!("/api/compartments/persons/{}", person_id),
+    )
+    .await;
+    assert_eq!(body["data"], json!([]));
+    let (_, body) = get(
+        &client,
+        &admin,
+        format!(
+            "/api/compartments/persons/{}?include_inactive=true",
+            person_id
+        ),
+    )
+    .await;
+    assert_eq!(body["data"].as_array().expect("history").len(), 1);
+
+    common::drop_network(&client, &network_id).await;
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_grant_compartment_validation() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let code = create_compartment(&client, &admin).await;
+    let person_id = common::create_person(&client, &admin, json!({})).await;
+    let uri = format!("/api/compartments/persons/{}/grants", person_id);
+
+    let (status, _) = post(
+        &client,
+        &admin,
+        uri.clone(),
+        json!({
+            "compartment_code": code,
+            "valid_from": "2030-01-02T00:00:00",
+            "valid_until": "2030-01-01T00:00:00"
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+
+    let (status, _) = post(
+        &client,
+        &admin,
+        uri.clone(),
+        json!({ "compartment_code": "NO_SUCH_COMPARTMENT" }),
+    )
+    .await;
+    assert_eq!(status, Status::NotFound);
+
+    // A future window is not in force yet but still blocks a second grant.
+    let (status, body) = post(
+        &client,
+        &admin,
+        uri.clone(),
+        json!({ "compartment_code": code, "valid_from": "2030-01-01T00:00:00" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["active"], false);
+    let (status, _) = post(&client, &admin, uri, json!({ "compartment_code": code })).await;
+    assert_eq!(status, Status::Conflict);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_grant_compartment_forbidden_for_viewer() {
+    let client = create_test_client().await;
+    let viewer = login(&client, "viewer").await;
+    let (status, _) = post(
+        &client,
+        &viewer,
+        "/api/compartments/persons/5/grants".to_string(),
+        json!({ "compartment_code": "AURORA" }),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+}
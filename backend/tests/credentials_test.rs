@@ -0,0 +1,473 @@
+// Attribute credential integration tests.
+//
+// Test map:
+//   GET    /api/credentials/issuers          — no token -> 401                  [no DB]
+//   PUT    /api/credentials/issuers/<id>     — issued for a SECRET person:      [DB: login]
+//   POST   /api/credentials/issue              claims carry clearance, id and
+//   POST   /api/credentials/verify             expiry; verifies; tampered ->
+//   POST   /api/credentials/<id>/revoke        signature mismatch; revoked ->
+//   GET    /api/credentials/persons/<id>       rejected with the reason, listed
+//                                              as revoked; revoked twice -> 409
+//   POST   /api/credentials/verify          — untrusted / unknown issuer ->     [DB: login]
+//   PUT    /api/credentials/issuers/<id>       rejected; trusted again ->
+//                                              verifies; short key -> 400; new
+//                                              issuer without key -> 404
+//   POST   /api/clearances/persons/<id>     — downgrade SECRET -> CONFIDENTIAL  [DB: login]
+//   POST   /api/security-flags/persons/<id>   revokes the SECRET credential,
+//   POST   /api/person/<id>/offboard          keeps a CONFIDENTIAL one; flag
+//                                             raised -> revoked, stays revoked
+//                                             once cleared; offboarding ->
+//                                             revoked; verify rejects each
+//   POST   /api/credentials/issue           — valid_days 0 -> 400; unknown      [DB: login]
+//                                              person -> 404; no clearance /
+//                                              security hold -> 409; self /
+//                                              viewer -> 403
+//
+// The suite signs with the default issuer (JANUS); its key is registered by
+// the tests that issue. Foreign credentials are signed with the library's
+// signing module, as another system would.
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test credentials_test -- --include-ignored
+
+mod common;
+
+use rocket::http::{ContentType, Header, Method, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+
+use janus_backend::credentials::signing::{issue_credential, AttrClaims};
+use janus_backend::shared::rocket_setup::create_rocket;
+
+const JANUS_KEY: &str = "janus-test-signing-key";
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn send(
+    client: &Client,
+    token: &str,
+    method: Method,
+    uri: String,
+    body: Value,
+) -> (Status, Value) {
+    let response = client
+        .req(method, uri)
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+async fn set_issuer(client: &Client, admin: &str, issuer: &str, body: Value) -> Status {
+    send(
+        client,
+        admin,
+        Method::Put,
+        format!("/api/credentials/issuers/{}", issuer),
+        body,
+    )
+    .await
+    .0
+}
+
+async fn register_janus(client: &Client, admin: &str) {
+    let status = set_issuer(
+        client,
+        admin,
+        "JANUS",
+        json!({ "signing_key": JANUS_KEY, "trusted": true }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+}
+
+/// The verification result for a presented credential.
+async fn verify(client: &Client, token: &str, credential: Value) -> Value {
+    let (status, body) = send(
+        client,
+        token,
+        Method::Post,
+        "/api/credentials/verify".to_string(),
+        credential,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    body["data"].clone()
+}
+
+fn foreign_credential(issuer: &str, key: &str) -> Value {
+    let credential = issue_credential(
+        AttrClaims {
+            subject: "officer-1".to_string(),
+            entity: "ENTITY_A".to_string(),
+            clearance: "SECRET".to_string(),
+            compartments: vec!["AURORA".to_string()],
+            issuer: issuer.to_string(),
+            credential_id: None,
+            issued_at: None,
+            expires_at: None,
+        },
+        key,
+    );
+    serde_json::to_value(credential).expect("json")
+}
+
+#[rocket::async_test]
+async fn test_list_issuers_requires_auth() {
+    let client = create_test_client().await;
+    let response = client.get("/api/credentials/issuers").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_issue_verify_and_revoke() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let officer = login(&client, "security").await;
+    let viewer = login(&client, "viewer").await;
+    register_janus(&client, &admin).await;
+    let person_id = common::create_person(&client, &admin, json!({})).await;
+
+    let (status, body) = send(
+        &client,
+        &officer,
+        Method::Post,
+        "/api/credentials/issue".to_string(),
+        json!({ "person_id": person_id, "valid_days": 30 }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok, "{body}");
+    let issued = body["data"].clone();
+    let credential = issued["credential"].clone();
+    let payload = &credential["payload"];
+    assert_eq!(payload["subject"], person_id.to_string());
+    assert_eq!(payload["clearance"], "SECRET");
+    assert_eq!(payload["issuer"], "JANUS");
+    assert_eq!(payload["credentialId"], issued["id"]);
+    assert!(payload["expiresAt"]
+        .as_str()
+        .expect("expiry")
+        .ends_with('Z'));
+
+    let result = verify(&client, &viewer, credential.clone()).await;
+    assert_eq!(result["valid"], true, "{result}");
+    assert_eq!(result["reason"], "verified signature from JANUS");
+
+    let mut tampered = credential.clone();
+    tampered["payload"]["clearance"] = json!("TOP_SECRET");
+    let result = verify(&client, &viewer, tampered).await;
+    assert_eq!(result["valid"], false);
+    assert_eq!(
+        result["reason"],
+        "signature mismatch — payload tampered or wrong key"
+    );
+
+    let revoke_uri = format!(
+        "/api/credentials/{}/revoke",
+        issued["id"].as_str().expect("id")
+    );
+    let reason = json!({ "reason": "Clearance under review" });
+    let (status, _) = send(
+        &client,
+        &officer,
+        Method::Post,
+        revoke_uri.clone(),
+        reason.clone(),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let result = verify(&client, &viewer, credential).await;
+    assert_eq!(result["valid"], false);
+    assert_eq!(
+        result["reason"],
+        "credential revoked: Clearance under review"
+    );
+    let (status, _) = send(&client, &officer, Method::Post, revoke_uri, reason).await;
+    assert_eq!(status, Status::Conflict);
+
+    let (status, body) = send(
+        &client,
+        &viewer,
+        Method::Get,
+        format!("/api/credentials/persons/{}", person_id),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let listed = body["data"].as_array().expect("credentials");
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0]["revocation_reason"], "Clearance under review");
+}
+
+/// Issue a credential for `person_id` as `officer`; the presented credential
+/// and its id.
+async fn issue_for(client: &Client, officer: &str, person_id: i64) -> (Value, String) {
+    let (status, body) = send(
+        client,
+        officer,
+        Method::Post,
+        "/api/credentials/issue".to_string(),
+        json!({ "person_id": person_id }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok, "{body}");
+    let id = body["data"]["id"]
+        .as_str()
+        .expect("credential id")
+        .to_string();
+    (body["data"]["credential"].clone(), id)
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_lifecycle_revokes_open_credentials() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let officer = login(&client, "security").await;
+    register_janus(&client, &admin).await;
+    let person_id = common::create_person(&client, &admin, json!({})).await;
+
+    let (secret, _) = issue_for(&client, &officer, person_id).await;
+    let (status, _) = send(
+        &client,
+        &officer,
+        Method::Post,
+        format!("/api/clearances/persons/{}", person_id),
+        json!({ "level": "CONFIDENTIAL", "granting_authority": "Credential test" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let result = verify(&client, &admin, secret).await;
+    assert_eq!(result["valid"], false);
+    assert_eq!(
+        result["reason"],
+        "credential revoked: Clearance now CONFIDENTIAL"
+    );
+
+    // A credential the new level still backs survives the downgrade, but
+    // not a security flag, and clearing the flag does not restore it.
+    let (confidential, _) = issue_for(&client, &officer, person_id).await;
+    let result = verify(&client, &admin, confidential.clone()).await;
+    assert_eq!(result["valid"], true, "{result}");
+    let (status, _) = send(
+        &client,
+        &officer,
+        Method::Post,
+        format!("/api/security-flags/persons/{}", person_id),
+        json!({ "flag": "SECURITY_HOLD", "reason": "Credential test" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let (status, _) = send(
+        &client,
+        &officer,
+        Method::Delete,
+        format!(
+            "/api/security-flags/persons/{}/SECURITY_HOLD?reason=cleared",
+            person_id
+        ),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let result = verify(&client, &admin, confidential).await;
+    assert_eq!(result["valid"], false);
+    assert_eq!(
+        result["reason"],
+        "credential revoked: SECURITY_HOLD set: Credential test"
+    );
+
+    let (departing, departing_id) = issue_for(&client, &officer, person_id).await;
+    let (status, body) = send(
+        &client,
+        &admin,
+        Method::Post,
+        format!("/api/person/{}/offboard", person_id),
+        json!({ "reason": "Contract ended" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert!(body["data"]["revoked_grants"]
+        .as_array()
+        .expect("grants")
+        .iter()
+        .any(|g| g["access_type"] == "credential" && g["grant_id"] == departing_id.as_str()));
+    let result = verify(&client, &admin, departing).await;
+    assert_eq!(result["valid"], false);
+    assert_eq!(result["reason"], "credential revoked: Contract ended");
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_issuer_registry_controls_trust() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let issuer = format!(
+        "ISSUER-{}",
+        &uuid::Uuid::new_v4().simple().to_string()[..12]
+    );
+    let key = "foreign-issuer-signing-key";
+    let credential = foreign_credential(&issuer, key);
+
+    let result = verify(&client, &admin, credential.clone()).await;
+    assert_eq!(
+        result["reason"],
+        format!("issuer \"{}\" is not trusted", issuer)
+    );
+
+    assert_eq!(
+        set_issuer(&client, &admin, &issuer, json!({ "trusted": true })).await,
+        Status::NotFound
+    );
+    assert_eq!(
+        set_issuer(&client, &admin, &issuer, json!({ "signing_key": "short" })).await,
+        Status::BadRequest
+    );
+    assert_eq!(
+        set_issuer(
+            &client,
+            &admin,
+            &issuer,
+            json!({ "signing_key": key, "trusted": false })
+        )
+        .await,
+        Status::Ok
+    );
+    let result = verify(&client, &admin, credential.clone()).await;
+    assert_eq!(result["valid"], false);
+
+    assert_eq!(
+        set_issuer(&client, &admin, &issuer, json!({ "trusted": true })).await,
+        Status::Ok
+    );
+    let result = verify(&client, &admin, credential.clone()).await;
+    assert_eq!(result["valid"], true, "{result}");
+    let forged = foreign_credential(&issuer, "attacker-guessed-key");
+    let result = verify(&client, &admin, forged).await;
+    assert_eq!(result["valid"], false);
+
+    let (status, body) = send(
+        &client,
+        &admin,
+        Method::Get,
+        "/api/credentials/issuers".to_string(),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let listed = body["data"]
+        .as_array()
+        .expect("issuers")
+        .iter()
+        .find(|i| i["issuer_id"] == issuer.as_str())
+        .cloned()
+        .expect("issuer listed");
+    assert!(listed.get("signing_key").is_none());
+
+    let (status, _) = send(
+        &client,
+        &admin,
+        Method::Delete,
+        format!("/api/credentials/issuers/{}", issuer),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_issue_validation() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    register_janus(&client, &admin).await;
+    let cleared = common::create_person(
+        &client,
+        &admin,
+        json!({ "clearance_level": "CONFIDENTIAL" }),
+    )
+    .await;
+    let uncleared =
+        common::create_person(&client, &admin, json!({ "clearance_level": null })).await;
+    let issue = |token: String, body: Value| {
+        let client = &client;
+        async move {
+            send(
+                client,
+                &token,
+                Method::Post,
+                "/api/credentials/issue".to_string(),
+                body,
+            )
+            .await
+            .0
+        }
+    };
+
+    assert_eq!(
+        issue(
+            admin.clone(),
+            json!({ "person_id": cleared, "valid_days": 0 })
+        )
+        .await,
+        Status::BadRequest
+    );
+    assert_eq!(
+        issue(admin.clone(), json!({ "person_id": 999999 })).await,
+        Status::NotFound
+    );
+    assert_eq!(
+        issue(admin.clone(), json!({ "person_id": uncleared })).await,
+        Status::Conflict
+    );
+    // admin is person 1: issuing yourself a credential breaks the SoD rule.
+    assert_eq!(
+        issue(admin.clone(), json!({ "person_id": 1 })).await,
+        Status::Forbidden
+    );
+
+    let held = common::create_person(&client, &admin, json!({})).await;
+    let officer = login(&client, "security").await;
+    let (status, _) = send(
+        &client,
+        &officer,
+        Method::Post,
+        format!("/api/security-flags/persons/{}", held),
+        json!({ "flag": "SECURITY_HOLD", "reason": "Credential test" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(
+        issue(admin.clone(), json!({ "person_id": held })).await,
+        Status::Conflict
+    );
+
+    let viewer = login(&client, "viewer").await;
+    assert_eq!(
+        issue(viewer, json!({ "person_id": cleared })).await,
+        Status::Forbidden
+    );
+}
@@ -0,0 +1,346 @@
+// Decision log integration tests.
+//
+// Test map:
+//   GET  /api/decision-log          — no token -> 401                          [no DB]
+//   GET  /api/digital-resources/access, then
+//   GET  /api/decision-log          — the denial is recorded with its gates,   [DB: login]
+//                                     the caller and the subject; filters by
+//                                     subject, resource and outcome; viewer
+//                                     (no audit.read) -> 403
+//   GET  /api/digital-resources/resources/<id>/who-can-access, then
+//   GET  /api/decision-log          — listed subjects are recorded as          [DB: login]
+//                                     WHO_CAN_ACCESS; a listing at another
+//                                     time is not recorded
+//   POST /api/abac/decide, then
+//   GET  /api/decision-log          — the denial is recorded as ABAC_DECIDE    [DB: login]
+//                                     on organization:<id>/<domain> with the
+//                                     failed rules as reason
+//   resource_decision_log           — UPDATE, DELETE and TRUNCATE are          [DB]
+//                                     rejected
+//   GET/PUT /api/decision-log/settings — officer reads and sets retention;     [DB: login]
+//                                     out of range -> 400; viewer -> 403
+//
+// Each test creates its own network and person. Recorded decisions cannot be
+// deleted, so they are left behind; the filters keep the tests independent.
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test decision_log_test -- --include-ignored
+
+mod common;
+
+use rocket::http::{ContentType, Header, Method, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn send(
+    client: &Client,
+    token: &str,
+    method: Method,
+    uri: String,
+    body: Value,
+) -> (Status, Value) {
+    let response = client
+        .req(method, uri)
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+#[rocket::async_test]
+async fn test_decision_log_requires_auth() {
+    let client = create_test_client().await;
+    let response = client.get("/api/decision-log").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_access_decision_is_recorded() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let viewer = login(&client, "viewer").await;
+    let officer = login(&client, "security").await;
+    let resource_id =
+        common::create_network(&client, "rsrc-decision", Some("rsrc-pol-baseline")).await;
+    let person_id = common::create_person(&client, &admin, json!({})).await;
+
+    // No grant on the new network: denied.
+    let (status, body) = send(
+        &client,
+        &viewer,
+        Method::Get,
+        format!(
+            "/api/digital-resources/access?person_id={}&resource_id={}",
+            person_id, resource_id
+        ),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["allow"], false);
+
+    let uri = format!(
+        "/api/decision-log?person_id={}&resource_id={}",
+        person_id, resource_id
+    );
+    let (status, _) = send(&client, &viewer, Method::Get, uri.clone(), Value::Null).await;
+    assert_eq!(status, Status::Forbidden);
+
+    let (status, body) = send(
+        &client,
+        &officer,
+        Method::Get,
+        format!("{}&allow=false", uri),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let entries = body["data"].as_array().expect("entries");
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry["source"], "ACCESS");
+    assert_eq!(entry["person_id"], person_id);
+    assert_eq!(entry["resource_id"], resource_id.as_str());
+    assert_eq!(entry["allow"], false);
+    assert_eq!(entry["caller_person_id"], 4);
+    assert_eq!(entry["caller_role"], "viewer");
+    let failing: Vec<&Value> = entry["gates"]
+        .as_array()
+        .expect("gates")
+        .iter()
+        .filter(|g| g["pass"] == false)
+        .map(|g| &g["kind"])
+        .collect();
+    assert_eq!(failing, vec!["OWN_TIER_GRANT"]);
+
+    let (status, body) = send(
+        &client,
+        &officer,
+        Method::Get,
+        format!("{}&allow=true", uri),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"], json!([]));
+
+    // The reverse query records the subjects it lists, but not when asked
+    // about another time.
+    let (status, _) = send(
+        &client,
+        &viewer,
+        Method::Get,
+        format!(
+            "/api/digital-resources/resources/{}/who-can-access?at=2026-01-01T00:00:00Z",
+            resource_id
+        ),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let (status, _) = send(
+        &client,
+        &viewer,
+        Method::Get,
+        format!(
+            "/api/digital-resources/resources/{}/who-can-access",
+            resource_id
+        ),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let (status, body) = send(&client, &officer, Method::Get, uri, Value::Null).await;
+    assert_eq!(status, Status::Ok);
+    let sources: Vec<&Value> = body["data"]
+        .as_array()
+        .expect("entries")
+        .iter()
+        .map(|e| &e["source"])
+        .collect();
+    assert_eq!(sources, vec!["WHO_CAN_ACCESS", "ACCESS"]);
+    common::drop_network(&client, &resource_id).await;
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_abac_decision_is_recorded() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let viewer = login(&client, "viewer").await;
+    let officer = login(&client, "security").await;
+    let person_id = common::create_person(&client, &admin, json!({})).await;
+
+    let (status, body) = send(
+        &client,
+        &viewer,
+        Method::Post,
+        "/api/abac/decide".to_string(),
+        json!({
+            "person_id": person_id,
+            "domain": "DATA",
+            "min_clearance": "TOP_SECRET",
+            "owner_organization_id": 1
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["decision"], "DENY");
+
+    let (status, body) = send(
+        &client,
+        &officer,
+        Method::Get,
+        format!(
+            "/api/decision-log?person_id={}&resource_id=organization:1/DATA",
+            person_id
+        ),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let entries = body["data"].as_array().expect("entries");
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry["source"], "ABAC_DECIDE");
+    assert_eq!(entry["allow"], false);
+    assert_eq!(entry["caller_role"], "viewer");
+    assert!(entry["reason"]
+        .as_str()
+        .expect("reason")
+        .contains("Clearance"));
+    assert_eq!(entry["gates"]["decision"], "DENY");
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_decision_log_is_append_only() {
+    let client = create_test_client().await;
+    let viewer = login(&client, "viewer").await;
+    let (status, _) = send(
+        &client,
+        &viewer,
+        Method::Get,
+        "/api/digital-resources/access?person_id=4&resource_id=rsrc-milnet".to_string(),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    let id: i64 = sqlx::query_scalar("SELECT MAX(id) FROM resource_decision_log")
+        .fetch_one(pool)
+        .await
+        .expect("recorded decision");
+    let update = sqlx::query("UPDATE resource_decision_log SET allow = NOT allow WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await;
+    assert!(update.is_err());
+    let delete = sqlx::query("DELETE FROM resource_decision_log WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await;
+    assert!(delete.is_err());
+    let truncate = sqlx::query("TRUNCATE resource_decision_log")
+        .execute(pool)
+        .await;
+    assert!(truncate.is_err());
+    let still_there: bool =
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM resource_decision_log WHERE id = $1)")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .expect("decision lookup");
+    assert!(still_there);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_retention_settings() {
+    let client = create_test_client().await;
+    let officer = login(&client, "security").await;
+    let viewer = login(&client, "viewer").await;
+
+    let (status, body) = send(
+        &client,
+        &officer,
+        Method::Get,
+        "/api/decision-log/settings".to_string(),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let retention = body["data"]["retention_days"].as_i64().expect("retention");
+
+    let (status, _) = send(
+        &client,
+        &viewer,
+        Method::Get,
+        "/api/decision-log/settings".to_string(),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+    let (status, _) = send(
+        &client,
+        &viewer,
+        Method::Put,
+        "/api/decision-log/settings".to_string(),
+        json!({ "retention_days": 30 }),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+    for days in [0, 3651] {
+        let (status, _) = send(
+            &client,
+            &officer,
+            Method::Put,
+            "/api/decision-log/settings".to_string(),
+            json!({ "retention_days": days }),
+        )
+        .await;
+        assert_eq!(status, Status::BadRequest);
+    }
+
+    // Set it back to what it was.
+    let (status, body) = send(
+        &client,
+        &officer,
+        Method::Put,
+        "/api/decision-log/settings".to_string(),
+        json!({ "retention_days": retention }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["retention_days"], retention);
+    assert_eq!(body["data"]["updated_by_person_id"], 10);
+}
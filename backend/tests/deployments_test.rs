@@ -0,0 +1,294 @@
+// Subunit deployment and support obligation integration tests.
+//
+// Test map:
+//   GET    /api/deployments/subunits                — no token -> 401          [no DB]
+//   POST   /api/deployments/subunits                — subunit of A at HOME:    [DB: login]
+//   POST   /api/deployments/obligations               B's member is denied
+//   GET    /api/deployments/subunits/<id>/access      with an obligation B -> A;
+//   PUT    /api/deployments/subunits/<id>/deployment  ABROAD -> allowed; back
+//                                                     HOME -> denied again;
+//                                                     change is audited;
+//                                                     same deployment -> 409
+//   POST   /api/deployments/obligations             — same organization -> 400;[DB: login]
+//   POST   /api/deployments/subunits                  unknown organization
+//                                                     -> 404; duplicate -> 409;
+//                                                     bad deployment -> 400;
+//                                                     viewer -> 403
+//
+// Each test creates its own organizations and members.
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test deployments_test -- --include-ignored
+
+use rocket::http::{ContentType, Header, Method, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn send(
+    client: &Client,
+    token: &str,
+    method: Method,
+    uri: String,
+    body: Value,
+) -> (Status, Value) {
+    let response = client
+        .req(method, uri)
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+async fn create_organization(client: &Client, admin: &str) -> i64 {
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    let (status, body) = send(
+        client,
+        admin,
+        Method::Post,
+        "/api/organizations".to_string(),
+        json!({
+            "company_name": format!("Deployment Org {}", &suffix[..12]),
+            "contact_name": "Deployment Contact",
+            "contact_email": "deployment@example.com",
+            "clearance_level": "SECRET",
+            "contract_number": format!("DPL-{}", &suffix[..8])
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    body["id"].as_i64().expect("organization id")
+}
+
+/// A SECRET-cleared employee of `org_id`.
+async fn create_member(client: &Client, admin: &str, org_id: i64) -> i64 {
+    let (status, body) = send(
+        client,
+        admin,
+        Method::Post,
+        "/api/person".to_string(),
+        json!({ "first_name": "Deployment", "last_name": "Member", "clearance_level": "SECRET" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let person_id = body["id"].as_i64().expect("person id");
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    sqlx::query(
+        "INSERT INTO relations (entity_type, entity_id, related_entity_type, related_entity_id, relation_type) \
+         VALUES ('vendor', $1, 'person', $2, 'employee')",
+    )
+    .bind(org_id as i32)
+    .bind(person_id as i32)
+    .execute(pool)
+    .await
+    .expect("membership");
+    person_id
+}
+
+/// The decision and support obligation rule for `person_id` reaching the
+/// subunit.
+async fn subunit_access(client: &Client, token: &str, subunit_id: i64, person_id: i64) -> Value {
+    let (status, body) = send(
+        client,
+        token,
+        Method::Get,
+        format!(
+            "/api/deployments/subunits/{}/access?person_id={}",
+            subunit_id, person_id
+        ),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    body["data"].clone()
+}
+
+async fn set_deployment(client: &Client, token: &str, subunit_id: i64, deployment: &str) -> Status {
+    send(
+        client,
+        token,
+        Method::Put,
+        format!("/api/deployments/subunits/{}/deployment", subunit_id),
+        json!({ "deployment": deployment, "reason": "Exercise rotation" }),
+    )
+    .await
+    .0
+}
+
+#[rocket::async_test]
+async fn test_list_subunits_requires_auth() {
+    let client = create_test_client().await;
+    let response = client.get("/api/deployments/subunits").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_deployment_drives_obligation_access() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let officer = login(&client, "security").await;
+    let org_a = create_organization(&client, &admin).await;
+    let org_b = create_organization(&client, &admin).await;
+    let member_a = create_member(&client, &admin, org_a).await;
+    let member_b = create_member(&client, &admin, org_b).await;
+
+    let (status, body) = send(
+        &client,
+        &officer,
+        Method::Post,
+        "/api/deployments/subunits".to_string(),
+        json!({ "organization_id": org_a, "name": "Field Hospital" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["deployment"], "HOME");
+    let subunit_id = body["data"]["id"].as_i64().expect("subunit id");
+
+    let (status, _) = send(
+        &client,
+        &officer,
+        Method::Post,
+        "/api/deployments/obligations".to_string(),
+        json!({ "from_organization_id": org_b, "to_organization_id": org_a }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+
+    let own = subunit_access(&client, &admin, subunit_id, member_a).await;
+    assert_eq!(own["decision"], "ALLOW", "{own}");
+    let home = subunit_access(&client, &admin, subunit_id, member_b).await;
+    assert_eq!(home["decision"], "DENY", "{home}");
+    assert_eq!(home["organization_id"], org_b);
+    assert_eq!(
+        home["rules"][1]["detail"],
+        "Field Hospital is at HOME — obligation does not trigger"
+    );
+
+    assert_eq!(
+        set_deployment(&client, &officer, subunit_id, "ABROAD").await,
+        Status::Ok
+    );
+    assert_eq!(
+        set_deployment(&client, &officer, subunit_id, "ABROAD").await,
+        Status::Conflict
+    );
+    let abroad = subunit_access(&client, &admin, subunit_id, member_b).await;
+    assert_eq!(abroad["decision"], "ALLOW", "{abroad}");
+    assert_eq!(abroad["deployment"], "ABROAD");
+    assert_eq!(abroad["rules"][1]["active"], true);
+
+    assert_eq!(
+        set_deployment(&client, &officer, subunit_id, "HOME").await,
+        Status::Ok
+    );
+    let back = subunit_access(&client, &admin, subunit_id, member_b).await;
+    assert_eq!(back["decision"], "DENY", "{back}");
+
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    let audited: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM audit_log \
+         WHERE action = 'SUBUNIT_DEPLOYMENT_CHANGED' AND resource_id = $1",
+    )
+    .bind(subunit_id as i32)
+    .fetch_one(pool)
+    .await
+    .expect("audit count");
+    assert_eq!(audited, 2);
+
+    let (status, _) = send(
+        &client,
+        &officer,
+        Method::Delete,
+        format!("/api/deployments/subunits/{}", subunit_id),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_deployment_validation() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let org_a = create_organization(&client, &admin).await;
+    let org_b = create_organization(&client, &admin).await;
+    let obligation = |from: i64, to: i64| {
+        let client = &client;
+        let admin = &admin;
+        async move {
+            send(
+                client,
+                admin,
+                Method::Post,
+                "/api/deployments/obligations".to_string(),
+                json!({ "from_organization_id": from, "to_organization_id": to }),
+            )
+            .await
+            .0
+        }
+    };
+
+    assert_eq!(obligation(org_a, org_a).await, Status::BadRequest);
+    assert_eq!(obligation(org_a, 999999).await, Status::NotFound);
+    assert_eq!(obligation(org_a, org_b).await, Status::Ok);
+    assert_eq!(obligation(org_a, org_b).await, Status::Conflict);
+    assert_eq!(obligation(org_b, org_a).await, Status::Ok);
+
+    let (status, _) = send(
+        &client,
+        &admin,
+        Method::Post,
+        "/api/deployments/subunits".to_string(),
+        json!({ "organization_id": org_a, "name": "Signals", "deployment": "AWAY" }),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+    let (status, _) = send(
+        &client,
+        &admin,
+        Method::Post,
+        "/api/deployments/subunits".to_string(),
+        json!({ "organization_id": 999999, "name": "Signals" }),
+    )
+    .await;
+    assert_eq!(status, Status::NotFound);
+
+    let viewer = login(&client, "viewer").await;
+    let (status, _) = send(
+        &client,
+        &viewer,
+        Method::Post,
+        "/api/deployments/subunits".to_string(),
+        json!({ "organization_id": org_a, "name": "Signals" }),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+}
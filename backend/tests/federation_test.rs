@@ -0,0 +1,615 @@
+// Federation integration tests.
+//
+// Test map:
+//   GET    /api/federation/peers            — no token -> 401                   [no DB]
+//   POST   /api/federation/envelopes        — no key -> 401; sent in this       [no DB]
+//                                             instance's own name -> 403
+//   POST   /api/federation/publications     — published through a live hub      [DB: login]
+//   GET    /api/federation/discover           instance that knows this one's
+//   GET    /api/federation/transcript         key: pointer discovered; PUBLISH /
+//                                             DISCOVER / DISCOVER_RESULT in the
+//                                             transcript; republished -> 409
+//   PUT    /api/federation/peers/<entity>   — REQUEST_DETAIL from a peer: no    [DB: login]
+//   POST   /api/federation/envelopes          release agreement -> refused on
+//   POST   /api/sharing-agreements            Affiliation; release agreement
+//   PUT    /api/release-policies/...          but no sharing agreement with
+//                                             the peer's organization ->
+//                                             refused; sharing agreement ->
+//                                             record released; holder's floor
+//                                             above the requester -> refused
+//                                             (Clearance floor); record on
+//                                             hold -> refused (Record hold);
+//                                             unknown subject -> refused, no
+//                                             decision
+//   POST   /api/federation/envelopes        — unknown sender / requester of     [DB: login]
+//                                             another entity -> 403; another
+//                                             peer's key -> 401; misrouted
+//                                             request / reply kind -> 400
+//   PUT    /api/federation/peers/<entity>   — own entity id / short key -> 400; [DB: login]
+//                                             unknown organization -> 404
+//   POST   /api/federation/detail-requests  — viewer naming another person     [DB: login]
+//                                             -> 403
+//
+// Every client of this file runs as entity JANUS_TEST with its own key and a
+// hub URL on a free local port; only the publish test launches an instance
+// there, as entity JANUS_TEST_HUB. Peers and subject ids are unique per test.
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test federation_test -- --include-ignored
+
+mod common;
+
+use std::sync::OnceLock;
+
+use rocket::http::{ContentType, Header, Method, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use janus_backend::federation::client::FederationConfig;
+use janus_backend::shared::rocket_setup::{create_rocket, create_rocket_with_federation};
+
+const ENTITY_ID: &str = "JANUS_TEST";
+const FEDERATION_KEY: &str = "federation-test-key";
+const HUB_ID: &str = "JANUS_TEST_HUB";
+const HUB_KEY: &str = "federation-test-hub-key";
+
+/// The port the hub instance listens on, picked once for the whole file and
+/// exported with the rest of the federation config.
+fn hub_port() -> u16 {
+    static PORT: OnceLock<u16> = OnceLock::new();
+    *PORT.get_or_init(|| {
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|l| l.local_addr())
+            .expect("free port")
+            .port();
+        std::env::set_var("FEDERATION_ENTITY_ID", ENTITY_ID);
+        std::env::set_var("FEDERATION_KEY", FEDERATION_KEY);
+        std::env::set_var("FEDERATION_HUB_URL", format!("http://127.0.0.1:{}", port));
+        port
+    })
+}
+
+async fn create_test_client() -> Client {
+    hub_port();
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+/// Launch a second instance of the app, as its own hub, on the hub port for
+/// the rest of the test and wait until it accepts connections. The instances
+/// share one database, so registering JANUS_TEST's key there is what lets the
+/// hub accept its envelopes.
+async fn launch_hub(pool: &PgPool) {
+    let port = hub_port();
+    sqlx::query(
+        "INSERT INTO federation_peers (entity_id, base_url, created_by_person_id, key_hash) \
+         VALUES ($1, 'http://127.0.0.1:8000', 1, encode(sha256(convert_to($2, 'UTF8')), 'hex')) \
+         ON CONFLICT (entity_id) DO UPDATE SET key_hash = EXCLUDED.key_hash",
+    )
+    .bind(ENTITY_ID)
+    .bind(FEDERATION_KEY)
+    .execute(pool)
+    .await
+    .expect("member peer");
+    let config =
+        FederationConfig::from_values(Some(HUB_ID.to_string()), None, Some(HUB_KEY.to_string()));
+    let rocket = create_rocket_with_federation(config).await.configure(
+        rocket::Config::figment()
+            .merge(("port", port))
+            .merge(("address", "127.0.0.1"))
+            .merge(("log_level", "off"))
+            .merge(("shutdown.ctrlc", false)),
+    );
+    tokio::spawn(rocket.launch());
+    for _ in 0..50 {
+        if tokio::net::TcpStream::connect(("127.0.0.1", port))
+            .await
+            .is_ok()
+        {
+            return;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    panic!("hub instance did not start");
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn send(
+    client: &Client,
+    token: &str,
+    method: Method,
+    uri: String,
+    body: Value,
+) -> (Status, Value) {
+    let response = client
+        .req(method, uri)
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+/// The key a test peer is registered with.
+fn peer_key(peer: &str) -> String {
+    format!("key-of-{}", peer)
+}
+
+/// Post an envelope presenting `key`; the reply envelope on success.
+async fn send_envelope(client: &Client, key: &str, envelope: Value) -> (Status, Value) {
+    let response = client
+        .post("/api/federation/envelopes")
+        .header(ContentType::JSON)
+        .header(Header::new("X-Federation-Key", key.to_string()))
+        .body(envelope.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    let body = response.into_json().await.unwrap_or(Value::Null);
+    (status, body["data"].clone())
+}
+
+fn unique(prefix: &str) -> String {
+    format!(
+        "{}-{}",
+        prefix,
+        &uuid::Uuid::new_v4().simple().to_string()[..12]
+    )
+}
+
+async fn create_organization(client: &Client, admin: &str) -> i64 {
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    let (status, body) = send(
+        client,
+        admin,
+        Method::Post,
+        "/api/organizations".to_string(),
+        json!({
+            "company_name": format!("Federation Org {}", &suffix[..12]),
+            "contact_name": "Federation Contact",
+            "contact_email": "federation@example.com",
+            "clearance_level": "SECRET",
+            "contract_number": format!("FED-{}", &suffix[..8])
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    body["id"].as_i64().expect("organization id")
+}
+
+async fn set_peer(
+    client: &Client,
+    admin: &str,
+    peer: &str,
+    release_agreement: bool,
+    organization_id: Option<i64>,
+) {
+    let (status, body) = send(
+        client,
+        admin,
+        Method::Put,
+        format!("/api/federation/peers/{}", peer),
+        json!({
+            "base_url": "http://peer.example:8000/",
+            "key": peer_key(peer),
+            "release_agreement": release_agreement,
+            "organization_id": organization_id
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["base_url"], "http://peer.example:8000");
+}
+
+fn detail_request(peer: &str, subject_id: &str) -> Value {
+    json!({
+        "kind": "REQUEST_DETAIL",
+        "from": peer,
+        "to": ENTITY_ID,
+        "subjectId": subject_id,
+        "requester": {
+            "entity": peer,
+            "clearance": "TOP_SECRET",
+            "domainAuth": {},
+            "compartments": [],
+            "flags": {}
+        }
+    })
+}
+
+#[rocket::async_test]
+async fn test_list_peers_requires_auth() {
+    let client = create_test_client().await;
+    let response = client.get("/api/federation/peers").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+async fn test_envelopes_require_federation_key() {
+    let client = create_test_client().await;
+    let envelope = json!({ "kind": "DISCOVER", "from": ENTITY_ID, "subjectId": "S-1" });
+    let response = client
+        .post("/api/federation/envelopes")
+        .header(ContentType::JSON)
+        .body(envelope.to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // A peer knows this instance's key, so envelopes in its name are refused
+    // whatever key they carry: impersonating the holder would otherwise pass
+    // the Affiliation rule without a release agreement.
+    let (status, _) = send_envelope(&client, FEDERATION_KEY, envelope).await;
+    assert_eq!(status, Status::Forbidden);
+    let mut as_holder = detail_request(ENTITY_ID, "S-1");
+    as_holder["requester"]["entity"] = json!(ENTITY_ID);
+    let (status, _) = send_envelope(&client, FEDERATION_KEY, as_holder).await;
+    assert_eq!(status, Status::Forbidden);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_publish_and_discover_through_hub() {
+    let client = create_test_client().await;
+    launch_hub(client.rocket().state::<PgPool>().expect("pool")).await;
+    let admin = login(&client, "admin").await;
+    let person_id = common::create_person(
+        &client,
+        &admin,
+        json!({ "first_name": "Federated", "clearance_level": "SECRET" }),
+    )
+    .await;
+    let subject_id = unique("SUBJ");
+
+    let (status, body) = send(
+        &client,
+        &admin,
+        Method::Post,
+        "/api/federation/publications".to_string(),
+        json!({ "person_id": person_id, "subject_id": subject_id, "domain": "DATA" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok, "{body}");
+    assert_eq!(body["data"]["subject_id"], subject_id);
+
+    let (status, body) = send(
+        &client,
+        &admin,
+        Method::Get,
+        format!("/api/federation/discover?subject_id={}", subject_id),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(
+        body["data"],
+        json!([{ "holder": ENTITY_ID, "domain": "DATA" }])
+    );
+
+    let (status, body) = send(
+        &client,
+        &admin,
+        Method::Get,
+        "/api/federation/transcript?limit=500".to_string(),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let kinds: Vec<&str> = body["data"]
+        .as_array()
+        .expect("transcript")
+        .iter()
+        .filter(|e| e["envelope"]["subjectId"] == subject_id.as_str())
+        .map(|e| e["kind"].as_str().expect("kind"))
+        .collect();
+    for kind in ["PUBLISH", "DISCOVER", "DISCOVER_RESULT"] {
+        assert!(kinds.contains(&kind), "{kind} missing from {kinds:?}");
+    }
+
+    let (status, _) = send(
+        &client,
+        &admin,
+        Method::Post,
+        "/api/federation/publications".to_string(),
+        json!({ "person_id": person_id, "subject_id": subject_id, "domain": "DATA" }),
+    )
+    .await;
+    assert_eq!(status, Status::Conflict);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_detail_released_only_when_allowed() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let officer = login(&client, "security").await;
+    let person_id = common::create_person(
+        &client,
+        &admin,
+        json!({ "first_name": "Federated", "clearance_level": "SECRET" }),
+    )
+    .await;
+    let holder_org = create_organization(&client, &admin).await;
+    let peer_org = create_organization(&client, &admin).await;
+    let subject_id = unique("SUBJ");
+    let peer = unique("PEER");
+
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    sqlx::query(
+        "INSERT INTO relations (entity_type, entity_id, related_entity_type, related_entity_id, relation_type) \
+         VALUES ('vendor', $1, 'person', $2, 'employee')",
+    )
+    .bind(holder_org as i32)
+    .bind(person_id as i32)
+    .execute(pool)
+    .await
+    .expect("membership");
+    sqlx::query(
+        "INSERT INTO federation_publications (person_id, subject_id, domain, published_by_person_id) \
+         VALUES ($1, $2, 'DATA', $1)",
+    )
+    .bind(person_id as i32)
+    .bind(&subject_id)
+    .execute(pool)
+    .await
+    .expect("publication");
+
+    set_peer(&client, &admin, &peer, false, Some(peer_org)).await;
+    let (status, reply) = send_envelope(
+        &client,
+        &peer_key(&peer),
+        detail_request(&peer, &subject_id),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(reply["kind"], "DETAIL_RESPONSE");
+    assert_eq!(reply["to"], peer.as_str());
+    assert_eq!(reply["granted"], false);
+    assert_eq!(reply["record"], Value::Null);
+    assert_eq!(reply["decision"]["failed"], json!(["Affiliation"]));
+
+    // The release agreement flag alone no longer releases: the holder's
+    // organization needs a sharing agreement with the peer's.
+    set_peer(&client, &admin, &peer, true, Some(peer_org)).await;
+    let (_, reply) = send_envelope(
+        &client,
+        &peer_key(&peer),
+        detail_request(&peer, &subject_id),
+    )
+    .await;
+    assert_eq!(reply["granted"], false);
+    assert_eq!(reply["decision"]["failed"], json!(["Affiliation"]));
+
+    let (status, _) = send(
+        &client,
+        &officer,
+        Method::Post,
+        "/api/sharing-agreements".to_string(),
+        json!({
+            "from_organization_id": holder_org,
+            "to_organization_id": peer_org,
+            "direction": "UNILATERAL",
+            "max_classification": "SECRET",
+            "document_reference": "Federation MoU"
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let (_, reply) = send_envelope(
+        &client,
+        &peer_key(&peer),
+        detail_request(&peer, &subject_id),
+    )
+    .await;
+    assert_eq!(reply["granted"], true, "{reply}");
+    assert_eq!(reply["record"]["name"], "Federated Subject");
+    assert_eq!(reply["record"]["unit"], ENTITY_ID);
+    assert_eq!(reply["record"]["clearance"], "SECRET");
+
+    let (status, _) = send(
+        &client,
+        &officer,
+        Method::Put,
+        format!("/api/release-policies/organizations/{}", holder_org),
+        json!({
+            "label": "Senior only",
+            "clearance_rule": true,
+            "domain_tier_rule": true,
+            "need_to_know_rule": true,
+            "affiliation_rule": true,
+            "min_clearance_floor": "TOP_SECRET"
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let mut junior = detail_request(&peer, &subject_id);
+    junior["requester"]["clearance"] = json!("SECRET");
+    let (_, reply) = send_envelope(&client, &peer_key(&peer), junior).await;
+    assert_eq!(reply["granted"], false);
+    assert_eq!(reply["decision"]["failed"], json!(["Clearance floor"]));
+
+    let (status, _) = send(
+        &client,
+        &officer,
+        Method::Post,
+        format!("/api/security-flags/persons/{}", person_id),
+        json!({ "flag": "SECURITY_HOLD", "reason": "Federation test" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let (_, reply) = send_envelope(
+        &client,
+        &peer_key(&peer),
+        detail_request(&peer, &subject_id),
+    )
+    .await;
+    assert_eq!(reply["granted"], false);
+    assert_eq!(reply["record"], Value::Null);
+    assert_eq!(reply["decision"]["overrides"][0]["name"], "Record hold");
+
+    let (_, reply) = send_envelope(
+        &client,
+        &peer_key(&peer),
+        detail_request(&peer, "SUBJ-unknown"),
+    )
+    .await;
+    assert_eq!(reply["granted"], false);
+    assert_eq!(reply["decision"], Value::Null);
+
+    let released: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM audit_log \
+         WHERE action = 'FEDERATION_DETAIL_RELEASED' AND resource_id = $1",
+    )
+    .bind(person_id as i32)
+    .fetch_one(pool)
+    .await
+    .expect("audit count");
+    assert_eq!(released, 1);
+
+    let (status, _) = send(
+        &client,
+        &admin,
+        Method::Delete,
+        format!("/api/federation/peers/{}", peer),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_envelope_validation() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let peer = unique("PEER");
+
+    let (status, _) = send_envelope(&client, &peer_key(&peer), detail_request(&peer, "S-1")).await;
+    assert_eq!(status, Status::Forbidden);
+
+    set_peer(&client, &admin, &peer, true, None).await;
+    let (status, _) = send_envelope(
+        &client,
+        &peer_key("PEER-other"),
+        detail_request(&peer, "S-1"),
+    )
+    .await;
+    assert_eq!(status, Status::Unauthorized);
+    let mut other_requester = detail_request(&peer, "S-1");
+    other_requester["requester"]["entity"] = json!(ENTITY_ID);
+    let (status, _) = send_envelope(&client, &peer_key(&peer), other_requester).await;
+    assert_eq!(status, Status::Forbidden);
+    let mut misrouted = detail_request(&peer, "S-1");
+    misrouted["to"] = json!("JANUS_ELSEWHERE");
+    let (status, _) = send_envelope(&client, &peer_key(&peer), misrouted).await;
+    assert_eq!(status, Status::BadRequest);
+    let (status, _) = send_envelope(
+        &client,
+        &peer_key(&peer),
+        json!({ "kind": "DISCOVER_RESULT", "to": peer, "subjectId": "S-1", "pointers": [] }),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+    let (status, _) = send_envelope(
+        &client,
+        &peer_key(&peer),
+        json!({ "kind": "PUBLISH", "from": peer, "subjectId": "S-1", "domain": "FINANCE" }),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+
+    for (body, expected) in [
+        (
+            json!({ "base_url": "http://peer.example:8000", "key": "too-short" }),
+            Status::BadRequest,
+        ),
+        (
+            json!({ "base_url": "http://peer.example:8000" }),
+            Status::UnprocessableEntity,
+        ),
+        (
+            json!({
+                "base_url": "http://peer.example:8000",
+                "key": peer_key(&peer),
+                "organization_id": 999999
+            }),
+            Status::NotFound,
+        ),
+    ] {
+        let (status, _) = send(
+            &client,
+            &admin,
+            Method::Put,
+            format!("/api/federation/peers/{}", peer),
+            body,
+        )
+        .await;
+        assert_eq!(status, expected);
+    }
+    let (status, _) = send(
+        &client,
+        &admin,
+        Method::Put,
+        format!("/api/federation/peers/{}", ENTITY_ID),
+        json!({ "base_url": "http://peer.example:8000", "key": peer_key(ENTITY_ID) }),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+
+    let viewer = login(&client, "viewer").await;
+    let (status, _) = send(
+        &client,
+        &viewer,
+        Method::Get,
+        "/api/federation/peers".to_string(),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+
+    // A viewer asks only in their own name: naming another person would send
+    // that person's clearance and compartments to the holder.
+    let other = common::create_person(
+        &client,
+        &admin,
+        json!({ "first_name": "Federated", "clearance_level": "TOP_SECRET" }),
+    )
+    .await;
+    let (status, _) = send(
+        &client,
+        &viewer,
+        Method::Post,
+        "/api/federation/detail-requests".to_string(),
+        json!({ "holder": peer, "subject_id": "S-1", "requester_person_id": other }),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+
+    let (status, _) = send(
+        &client,
+        &admin,
+        Method::Delete,
+        format!("/api/federation/peers/{}", peer),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+}
@@ -0,0 +1,149 @@
+// Computer access ↔ info_systems registry integration tests.
+//
+// Test map:
+//   POST   /api/access/computer           — unregistered system -> 404,       [DB: login]
+//                                           INACTIVE system -> 409
+//   GET    /api/info-systems/<id>/access  — lists the active holder           [DB: login]
+//   DELETE /api/info-systems/<id>         — active grants revoked with the    [DB: login]
+//                                           deletion reason, history kept
+//   GET    /api/info-systems/<id>/access  — enduser -> 403                    [DB: login]
+//
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test info_system_access_test -- --include-ignored
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn post_json(client: &Client, token: &str, uri: &str, body: Value) -> (Status, Value) {
+    let response = client
+        .post(uri.to_string())
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+async fn get_json(client: &Client, token: &str, uri: String) -> (Status, Value) {
+    let response = client.get(uri).header(auth_header(token)).dispatch().await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+/// Register a uniquely named system; returns (id, name).
+async fn create_system(client: &Client, token: &str, status: &str) -> (i64, String) {
+    let name = format!("Registry System {}", uuid::Uuid::new_v4());
+    let (code, body) = post_json(
+        client,
+        token,
+        "/api/info-systems",
+        json!({ "system_name": name, "environment": "TEST", "status": status }),
+    )
+    .await;
+    assert_eq!(code, Status::Ok);
+    (body["id"].as_i64().expect("system id"), name)
+}
+
+async fn grant(client: &Client, token: &str, system: &str) -> (Status, Value) {
+    post_json(
+        client,
+        token,
+        "/api/access/computer",
+        json!({ "person_id": 7, "system_name": system, "access_level": "READ" }),
+    )
+    .await
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_grant_requires_registered_active_system() {
+    let client = create_test_client().await;
+    let token = login(&client, "admin").await;
+
+    let (status, _) = grant(&client, &token, "No Such System").await;
+    assert_eq!(status, Status::NotFound);
+
+    let (_, inactive) = create_system(&client, &token, "INACTIVE").await;
+    let (status, _) = grant(&client, &token, &inactive).await;
+    assert_eq!(status, Status::Conflict);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_holders_listed_and_revoked_on_delete() {
+    let client = create_test_client().await;
+    let token = login(&client, "admin").await;
+    let (id, name) = create_system(&client, &token, "ACTIVE").await;
+
+    let (status, body) = grant(&client, &token, &name).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["info_system_id"], id);
+    let grant_id = body["data"]["id"].clone();
+
+    let (status, body) =
+        get_json(&client, &token, format!("/api/info-systems/{}/access", id)).await;
+    assert_eq!(status, Status::Ok);
+    let holders = body.as_array().expect("holders");
+    assert_eq!(holders.len(), 1);
+    assert_eq!(holders[0]["person_id"], 7);
+
+    let response = client
+        .delete(format!("/api/info-systems/{}", id))
+        .header(auth_header(&token))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let (_, body) = get_json(
+        &client,
+        &token,
+        "/api/persons/7/access?include_inactive=true".to_string(),
+    )
+    .await;
+    let revoked = body["data"]["computer_access"]
+        .as_array()
+        .expect("array")
+        .iter()
+        .find(|g| g["id"] == grant_id)
+        .expect("revoked grant kept")
+        .clone();
+    assert_eq!(revoked["status"], "REVOKED");
+    assert_eq!(revoked["revocation_reason"], "Information system deleted");
+    assert!(revoked["info_system_id"].is_null());
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_holders_forbidden_for_enduser() {
+    let client = create_test_client().await;
+    let token = login(&client, "enduser").await;
+    let (status, _) = get_json(&client, &token, "/api/info-systems/1/access".to_string()).await;
+    assert_eq!(status, Status::Forbidden);
+}
@@ -0,0 +1,244 @@
+// Offboarding and clearance-downgrade revocation integration tests.
+//
+// Test map:
+//   POST /api/person/<id>/offboard  — data grant and pending NDA revoked;     [DB: login]
+//                                     person's token and login
+//                                     rejected afterwards
+//   POST /api/person/<id>/offboard  — unknown person -> 404                    [DB: login]
+//   PUT  /api/person/<id>           — lowering clearance revokes the SECRET    [DB: login]
+//                                     data grant, keeps CONFIDENTIAL
+//
+// Each test creates its own person so seed users are never offboarded.
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test offboarding_test -- --include-ignored
+
+mod common;
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn try_login(client: &Client, username: &str) -> (Status, Value) {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let (status, body) = try_login(client, username).await;
+    assert_eq!(status, Status::Ok);
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn send(
+    client: &Client,
+    token: &str,
+    method: &str,
+    uri: String,
+    body: Value,
+) -> (Status, Value) {
+    let request = match method {
+        "POST" => client.post(uri),
+        "PUT" => client.put(uri),
+        _ => client.get(uri),
+    };
+    let response = request
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+/// Create a user with the given clearance; returns (id, username).
+/// A person who can log in, and their username.
+async fn create_leaver(client: &Client, admin: &str, clearance: &str) -> (i64, String) {
+    let username = format!("leaver{}", &uuid::Uuid::new_v4().simple().to_string()[..12]);
+    let person_id = common::create_person(
+        client,
+        admin,
+        json!({
+            "first_name": "Leaving",
+            "last_name": "Person",
+            "username": username,
+            "password": "password123",
+            "role": "viewer",
+            "clearance_level": clearance
+        }),
+    )
+    .await;
+    (person_id, username)
+}
+
+async fn grant_data(client: &Client, admin: &str, person_id: i64, classification: &str) -> i64 {
+    let (status, body) = send(
+        client,
+        admin,
+        "POST",
+        "/api/access/data".to_string(),
+        json!({ "person_id": person_id, "data_classification": classification, "access_level": "READ" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    body["data"]["id"].as_i64().expect("grant id")
+}
+
+async fn data_grant_status(client: &Client, admin: &str, person_id: i64, grant_id: i64) -> Value {
+    let (_, body) = send(
+        client,
+        admin,
+        "GET",
+        format!("/api/persons/{}/access?include_inactive=true", person_id),
+        Value::Null,
+    )
+    .await;
+    body["data"]["data_access"]
+        .as_array()
+        .expect("array")
+        .iter()
+        .find(|g| g["id"] == grant_id)
+        .expect("grant listed")["status"]
+        .clone()
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_offboarding_revokes_everything_and_tokens() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let (person_id, username) = create_leaver(&client, &admin, "SECRET").await;
+    let leaver = login(&client, &username).await;
+
+    let grant_id = grant_data(&client, &admin, person_id, "SECRET").await;
+    let (status, _) = send(
+        &client,
+        &admin,
+        "POST",
+        "/api/nda".to_string(),
+        json!({ "person_id": person_id, "title": "Leaver NDA", "content": "Standard confidentiality terms", "version": "1" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+
+    let (status, _) = send(
+        &client,
+        &leaver,
+        "GET",
+        "/api/auth/profile".to_string(),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+
+    let (status, body) = send(
+        &client,
+        &admin,
+        "POST",
+        format!("/api/person/{}/offboard", person_id),
+        json!({ "reason": "Contract ended" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let summary = &body["data"];
+    assert!(summary["revoked_grants"]
+        .as_array()
+        .expect("grants")
+        .iter()
+        .any(|g| g["access_type"] == "data" && g["grant_id"] == grant_id.to_string().as_str()));
+    assert_eq!(
+        summary["revoked_nda_ids"].as_array().expect("ndas").len(),
+        1
+    );
+
+    assert_eq!(
+        data_grant_status(&client, &admin, person_id, grant_id).await,
+        "REVOKED"
+    );
+
+    let (status, _) = send(
+        &client,
+        &leaver,
+        "GET",
+        "/api/auth/profile".to_string(),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Unauthorized);
+    let (status, _) = try_login(&client, &username).await;
+    assert_eq!(status, Status::Unauthorized);
+
+    // Already offboarded.
+    let (status, _) = send(
+        &client,
+        &admin,
+        "POST",
+        format!("/api/person/{}/offboard", person_id),
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, Status::NotFound);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_offboard_unknown_person() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let (status, _) = send(
+        &client,
+        &admin,
+        "POST",
+        "/api/person/999999/offboard".to_string(),
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, Status::NotFound);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_clearance_downgrade_revokes_exceeding_grants() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let (person_id, _) = create_leaver(&client, &admin, "SECRET").await;
+    let secret = grant_data(&client, &admin, person_id, "SECRET").await;
+    let confidential = grant_data(&client, &admin, person_id, "CONFIDENTIAL").await;
+
+    let (status, _) = send(
+        &client,
+        &admin,
+        "PUT",
+        format!("/api/person/{}", person_id),
+        json!({ "clearance_level": "CONFIDENTIAL" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+
+    assert_eq!(
+        data_grant_status(&client, &admin, person_id, secret).await,
+        "REVOKED"
+    );
+    assert_eq!(
+        data_grant_status(&client, &admin, person_id, confidential).await,
+        "ACTIVE"
+    );
+}
@@ -0,0 +1,205 @@
+// Policy lint integration tests.
+//
+// Test map:
+//   GET /api/digital-resources/lint — no token -> 401                             [no DB]
+//   GET /api/digital-resources/lint — a network with a future gap, overlapping    [DB: login]
+//                                     windows and a policy with an unknown gate
+//                                     kind and an unheld REQUIRED_ROLE gets one
+//                                     finding of each kind
+//   GET /api/digital-resources/lint — malformed at -> 400                          [DB: login]
+//
+// Each test creates its own policies and network.
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test policy_lint_test -- --include-ignored
+
+mod common;
+
+use rocket::http::{ContentType, Header, Method, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn send(
+    client: &Client,
+    token: &str,
+    method: Method,
+    uri: String,
+    body: Value,
+) -> (Status, Value) {
+    let response = client
+        .req(method, uri)
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+/// A network covered by a baseline-like policy until 2100-01-01 and again
+/// from 2100-02-01, with a broken policy overlapping the first window.
+/// Returns the network and the two policy ids.
+async fn create_linted_network(client: &Client) -> (String, String, String) {
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    let good = format!("rsrc-pol-lint-ok-{}", suffix);
+    let broken = format!("rsrc-pol-lint-bad-{}", suffix);
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    for (policy_id, gates) in [
+        (&good, json!([{ "kind": "CLEARANCE" }])),
+        (
+            &broken,
+            json!([{ "kind": "NOT_A_GATE" }, { "kind": "REQUIRED_ROLE", "role": "LINT_ROLE" }]),
+        ),
+    ] {
+        sqlx::query(
+            "INSERT INTO resource_policies (id, label, gates) VALUES ($1, 'Lint policy', $2)",
+        )
+        .bind(policy_id)
+        .bind(gates)
+        .execute(pool)
+        .await
+        .expect("policy");
+    }
+    let id = common::create_network(client, "rsrc-lint", None).await;
+    for (policy_id, from, until) in [
+        (&good, None, Some("2100-01-01T00:00:00Z")),
+        (
+            &broken,
+            Some("2099-01-01T00:00:00Z"),
+            Some("2100-01-01T00:00:00Z"),
+        ),
+        (&good, Some("2100-02-01T00:00:00Z"), None),
+    ] {
+        sqlx::query(
+            "INSERT INTO resource_policy_assignments \
+             (resource_id, resource_tier, policy_id, valid_from, valid_until) \
+             VALUES ($1, 'NETWORK', $2, $3::timestamptz, $4::timestamptz)",
+        )
+        .bind(&id)
+        .bind(policy_id)
+        .bind(from)
+        .bind(until)
+        .execute(pool)
+        .await
+        .expect("policy assignment");
+    }
+    (id, good, broken)
+}
+
+/// Remove what create_linted_network made.
+async fn drop_linted_network(client: &Client, id: &str, policies: [&str; 2]) {
+    common::drop_network(client, id).await;
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    sqlx::query("DELETE FROM resource_policies WHERE id = ANY($1)")
+        .bind(&policies[..])
+        .execute(pool)
+        .await
+        .expect("cleanup");
+}
+
+#[rocket::async_test]
+async fn test_lint_requires_auth() {
+    let client = create_test_client().await;
+    let response = client.get("/api/digital-resources/lint").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_lint_reports_each_mistake() {
+    let client = create_test_client().await;
+    let viewer = login(&client, "viewer").await;
+    let (resource_id, good, broken) = create_linted_network(&client).await;
+
+    let (status, body) = send(
+        &client,
+        &viewer,
+        Method::Get,
+        "/api/digital-resources/lint".to_string(),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let findings = body["data"]["findings"].as_array().expect("findings");
+    let mine: Vec<&Value> = findings
+        .iter()
+        .filter(|f| f["resource_id"] == resource_id.as_str() || f["policy_id"] == broken.as_str())
+        .collect();
+    let kinds: Vec<&str> = mine
+        .iter()
+        .map(|f| f["kind"].as_str().expect("kind"))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            "UNKNOWN_GATE_KIND",
+            "COVERAGE_GAP",
+            "OVERLAPPING_WINDOWS",
+            "UNHELD_REQUIRED_ROLE"
+        ]
+    );
+    assert_eq!(mine[1]["gap"]["from"], "2100-01-01T00:00:00.000001Z");
+    assert_eq!(mine[1]["gap"]["until"], "2100-02-01T00:00:00Z");
+    assert_eq!(mine[3]["policy_id"], broken.as_str());
+    assert!(!findings
+        .iter()
+        .any(|f| f["policy_id"] == good.as_str() && f["kind"] == "UNKNOWN_GATE_KIND"));
+
+    // From inside the second window on, the gap is behind us.
+    let (status, body) = send(
+        &client,
+        &viewer,
+        Method::Get,
+        "/api/digital-resources/lint?at=2100-03-01T00:00:00Z".to_string(),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert!(!body["data"]["findings"]
+        .as_array()
+        .expect("findings")
+        .iter()
+        .any(|f| f["resource_id"] == resource_id.as_str() && f["kind"] == "COVERAGE_GAP"));
+    drop_linted_network(&client, &resource_id, [&good, &broken]).await;
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_lint_rejects_malformed_at() {
+    let client = create_test_client().await;
+    let viewer = login(&client, "viewer").await;
+    let (status, _) = send(
+        &client,
+        &viewer,
+        Method::Get,
+        "/api/digital-resources/lint?at=soon".to_string(),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+}
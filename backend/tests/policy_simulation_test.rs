@@ -0,0 +1,214 @@
+// What-if policy simulation integration tests.
+//
+// Test map:
+//   POST /api/digital-resources/simulations — no token -> 401                    [no DB]
+//   POST /api/digital-resources/simulations — dropping the grant gate lets an    [DB: login]
+//                                             ungranted person gain access;
+//                                             removing a grant or raising the
+//                                             classification loses the holder
+//                                             theirs; nothing is written
+//   POST /api/digital-resources/simulations — unknown grant -> 404; unknown      [DB: login]
+//                                             classification -> 400
+//
+// Each test creates its own policy, network and persons.
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test policy_simulation_test -- --include-ignored
+
+mod common;
+
+use rocket::http::{ContentType, Header, Method, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn send(
+    client: &Client,
+    token: &str,
+    method: Method,
+    uri: String,
+    body: Value,
+) -> (Status, Value) {
+    let response = client
+        .req(method, uri)
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+/// An UNCLASSIFIED network on its own CLEARANCE + OWN_TIER_GRANT policy;
+/// returns the network and policy ids.
+async fn create_network(client: &Client) -> (String, String) {
+    let policy_id = format!("rsrc-pol-sim-{}", uuid::Uuid::new_v4().simple());
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    sqlx::query(
+        "INSERT INTO resource_policies (id, label, gates) \
+         VALUES ($1, 'Simulation policy', '[{\"kind\":\"CLEARANCE\"},{\"kind\":\"OWN_TIER_GRANT\"}]')",
+    )
+    .bind(&policy_id)
+    .execute(pool)
+    .await
+    .expect("policy");
+    let id = common::create_network(client, "rsrc-sim", Some(&policy_id)).await;
+    (id, policy_id)
+}
+
+/// Remove what create_network made.
+async fn drop_network(client: &Client, id: &str, policy_id: &str) {
+    common::drop_network(client, id).await;
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    sqlx::query("DELETE FROM resource_policies WHERE id = $1")
+        .bind(policy_id)
+        .execute(pool)
+        .await
+        .expect("cleanup");
+}
+
+fn person_ids(rows: &Value, resource_id: &str) -> Vec<i64> {
+    rows.as_array()
+        .expect("rows")
+        .iter()
+        .filter(|r| r["resource_id"] == resource_id)
+        .map(|r| r["person_id"].as_i64().expect("person id"))
+        .collect()
+}
+
+#[rocket::async_test]
+async fn test_simulation_requires_auth() {
+    let client = create_test_client().await;
+    let response = client
+        .post("/api/digital-resources/simulations")
+        .header(ContentType::JSON)
+        .body("{}")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_simulation_reports_gains_and_losses() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let (resource_id, policy_id) = create_network(&client).await;
+    let holder = common::create_person(&client, &admin, json!({})).await;
+    let outsider = common::create_person(&client, &admin, json!({})).await;
+    let (status, body) = send(
+        &client,
+        &admin,
+        Method::Post,
+        "/api/digital-resources/grants".to_string(),
+        json!({ "resource_id": resource_id, "person_id": holder.to_string(), "actor_org_id": "" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let grant_id = body["data"]["id"].as_str().expect("grant id").to_string();
+    let simulate = |request: Value| {
+        let client = &client;
+        let admin = &admin;
+        async move {
+            let (status, body) = send(
+                client,
+                admin,
+                Method::Post,
+                "/api/digital-resources/simulations".to_string(),
+                request,
+            )
+            .await;
+            assert_eq!(status, Status::Ok);
+            body["data"].clone()
+        }
+    };
+
+    let result = simulate(json!({
+        "policy_gates": [{ "policy_id": policy_id, "gates": [{ "kind": "CLEARANCE" }] }]
+    }))
+    .await;
+    assert_eq!(result["resources"], json!([resource_id]));
+    let gained = person_ids(&result["gained"], &resource_id);
+    assert!(gained.contains(&outsider) && !gained.contains(&holder));
+    assert_eq!(result["lost"], json!([]));
+
+    let result = simulate(json!({ "removed_grants": [grant_id] })).await;
+    assert_eq!(result["decisions"], 1);
+    assert_eq!(person_ids(&result["lost"], &resource_id), vec![holder]);
+    let lost = &result["lost"][0];
+    assert_eq!(lost["before"]["allow"], true);
+    assert_eq!(lost["after"]["gates"][1]["reason"], "NO_OWN_TIER_GRANT");
+
+    let result = simulate(json!({
+        "classification_changes": [{ "resource_id": resource_id, "classification": "TOP_SECRET" }]
+    }))
+    .await;
+    assert_eq!(person_ids(&result["lost"], &resource_id), vec![holder]);
+
+    // The simulations wrote nothing: the holder still has access.
+    let (status, body) = send(
+        &client,
+        &admin,
+        Method::Get,
+        format!(
+            "/api/digital-resources/access?person_id={}&resource_id={}",
+            holder, resource_id
+        ),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["allow"], true);
+    drop_network(&client, &resource_id, &policy_id).await;
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_simulation_validation() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    for (request, expected) in [
+        (
+            json!({ "removed_grants": ["rsrc-grant-no-such"] }),
+            Status::NotFound,
+        ),
+        (
+            json!({ "classification_changes": [{ "resource_id": "rsrc-milnet", "classification": "COSMIC" }] }),
+            Status::BadRequest,
+        ),
+    ] {
+        let (status, _) = send(
+            &client,
+            &admin,
+            Method::Post,
+            "/api/digital-resources/simulations".to_string(),
+            request,
+        )
+        .await;
+        assert_eq!(status, expected);
+    }
+}
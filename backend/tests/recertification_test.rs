@@ -0,0 +1,194 @@
+// Access recertification integration tests.
+//
+// Test map:
+//   POST /api/recertification           — 401 without a token                [DB: conn]
+//   POST /api/recertification           — manager cannot open -> 403         [DB: login]
+//   campaign lifecycle                  — SYSTEM scope snapshots the grants,  [DB: login]
+//                                         REVOKE ends one, close auto-revokes
+//                                         the other, report counts both
+//
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test recertification_test -- --include-ignored
+
+use chrono::{Duration, Utc};
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn post_json(client: &Client, token: &str, uri: String, body: Value) -> (Status, Value) {
+    let response = client
+        .post(uri)
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    let body = response.into_json().await.unwrap_or(Value::Null);
+    (status, body)
+}
+
+fn campaign_body(system: &str) -> Value {
+    json!({
+        "name": format!("Quarterly review of {}", system),
+        "scope_type": "SYSTEM",
+        "scope_value": system,
+        "deadline": (Utc::now() + Duration::days(14)).naive_utc()
+    })
+}
+
+#[rocket::async_test]
+async fn test_create_campaign_unauthenticated() {
+    let client = create_test_client().await;
+    let response = client
+        .post("/api/recertification")
+        .header(ContentType::JSON)
+        .body("{}")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_manager_cannot_open_campaign() {
+    let client = create_test_client().await;
+    let token = login(&client, "manager").await;
+    let (status, _) = post_json(
+        &client,
+        &token,
+        "/api/recertification".to_string(),
+        campaign_body("Payroll"),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users and the recertification migration
+async fn test_campaign_lifecycle() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let manager = login(&client, "manager").await;
+    let system = format!(
+        "Recert System {}",
+        Utc::now().timestamp_nanos_opt().unwrap()
+    );
+
+    let (status, _) = post_json(
+        &client,
+        &admin,
+        "/api/info-systems".to_string(),
+        json!({ "system_name": system, "environment": "TEST", "status": "ACTIVE" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+
+    let mut grant_ids = Vec::new();
+    for person_id in [5, 6] {
+        let (status, body) = post_json(
+            &client,
+            &admin,
+            "/api/access/computer".to_string(),
+            json!({ "person_id": person_id, "system_name": system, "access_level": "READ" }),
+        )
+        .await;
+        assert_eq!(status, Status::Ok);
+        grant_ids.push(body["data"]["id"].as_i64().expect("grant id"));
+    }
+
+    let (status, body) = post_json(
+        &client,
+        &admin,
+        "/api/recertification".to_string(),
+        campaign_body(&system),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["item_count"], 2);
+    let campaign_id = body["data"]["campaign"]["id"]
+        .as_i64()
+        .expect("campaign id");
+
+    let response = client
+        .get(format!("/api/recertification/{}/items", campaign_id))
+        .header(auth_header(&manager))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let items: Value = response.into_json().await.expect("valid json");
+    let items = items["data"].as_array().expect("items").clone();
+    let first = items
+        .iter()
+        .find(|i| i["person_id"] == "5")
+        .expect("item for person 5");
+
+    let (status, body) = post_json(
+        &client,
+        &manager,
+        format!(
+            "/api/recertification/{}/items/{}/decision",
+            campaign_id, first["id"]
+        ),
+        json!({ "decision": "REVOKE", "note": "Left the project" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["decision"], "REVOKE");
+
+    let (status, body) = post_json(
+        &client,
+        &admin,
+        format!("/api/recertification/{}/close", campaign_id),
+        json!({}),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["status"], "CLOSED");
+
+    let pool = client.rocket().state::<PgPool>().expect("pool").clone();
+    for id in &grant_ids {
+        let status: String = sqlx::query_scalar("SELECT status FROM computer_access WHERE id = $1")
+            .bind(*id as i32)
+            .fetch_one(&pool)
+            .await
+            .expect("grant row");
+        assert_eq!(status, "REVOKED");
+    }
+
+    let response = client
+        .get(format!("/api/recertification/{}/report", campaign_id))
+        .header(auth_header(&admin))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let report: Value = response.into_json().await.expect("valid json");
+    assert_eq!(report["data"]["totals"]["revoked"], 1);
+    assert_eq!(report["data"]["totals"]["auto_revoked"], 1);
+    assert_eq!(report["data"]["totals"]["pending"], 0);
+}
@@ -0,0 +1,280 @@
+// Release policy integration tests.
+//
+// Test map:
+//   GET  /api/release-policies/organizations/<id>  — no token -> 401          [no DB]
+//   PUT  /api/release-policies/organizations/<id>  — standard policy without   [DB: login]
+//   POST /api/abac/release                           an agreement fails
+//   GET  /api/release-policies/organizations/<id>    Affiliation; v1 without
+//                                                    affiliation ALLOWs and
+//                                                    drops the rule; v2 with a
+//                                                    TOP_SECRET floor fails it;
+//                                                    history lists both
+//   PUT  /api/release-policies/organizations/<id>  — unknown floor -> 400;     [DB: login]
+//   POST /api/abac/release                           unknown organization
+//                                                    -> 404; viewer -> 403
+//
+// Each test creates its own organizations and persons.
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test release_policies_test -- --include-ignored
+
+use rocket::http::{ContentType, Header, Method, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn send(
+    client: &Client,
+    token: &str,
+    method: Method,
+    uri: String,
+    body: Value,
+) -> (Status, Value) {
+    let response = client
+        .req(method, uri)
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+async fn create_organization(client: &Client, admin: &str) -> i64 {
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    let (status, body) = send(
+        client,
+        admin,
+        Method::Post,
+        "/api/organizations".to_string(),
+        json!({
+            "company_name": format!("Release Org {}", &suffix[..12]),
+            "contact_name": "Release Contact",
+            "contact_email": "release@example.com",
+            "clearance_level": "SECRET",
+            "contract_number": format!("REL-{}", &suffix[..8])
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    body["id"].as_i64().expect("organization id")
+}
+
+/// A SECRET-cleared employee of `org_id`.
+async fn create_member(client: &Client, admin: &str, org_id: i64) -> i64 {
+    let (status, body) = send(
+        client,
+        admin,
+        Method::Post,
+        "/api/person".to_string(),
+        json!({ "first_name": "Release", "last_name": "Member", "clearance_level": "SECRET" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let person_id = body["id"].as_i64().expect("person id");
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    sqlx::query(
+        "INSERT INTO relations (entity_type, entity_id, related_entity_type, related_entity_id, relation_type) \
+         VALUES ('vendor', $1, 'person', $2, 'employee')",
+    )
+    .bind(org_id as i32)
+    .bind(person_id as i32)
+    .execute(pool)
+    .await
+    .expect("membership");
+    person_id
+}
+
+fn policy(label: &str, affiliation: bool, floor: Option<&str>) -> Value {
+    json!({
+        "label": label,
+        "clearance_rule": true,
+        "domain_tier_rule": true,
+        "need_to_know_rule": true,
+        "affiliation_rule": affiliation,
+        "min_clearance_floor": floor
+    })
+}
+
+fn rule_names(decision: &Value) -> Vec<&str> {
+    decision["rules"]
+        .as_array()
+        .expect("rules")
+        .iter()
+        .map(|r| r["name"].as_str().expect("rule name"))
+        .collect()
+}
+
+#[rocket::async_test]
+async fn test_get_release_policy_requires_auth() {
+    let client = create_test_client().await;
+    let response = client
+        .get("/api/release-policies/organizations/1")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_policy_versions_drive_release() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let officer = login(&client, "security").await;
+    let holder = create_organization(&client, &admin).await;
+    let other = create_organization(&client, &admin).await;
+    let subject = create_member(&client, &admin, holder).await;
+    let requester = create_member(&client, &admin, other).await;
+    let policy_uri = format!("/api/release-policies/organizations/{}", holder);
+    let request = json!({
+        "requester_person_id": requester,
+        "subject_person_id": subject,
+        "holder_organization_id": holder
+    });
+    let release = || {
+        let client = &client;
+        let admin = &admin;
+        let request = request.clone();
+        async move {
+            let (status, body) = send(
+                client,
+                admin,
+                Method::Post,
+                "/api/abac/release".to_string(),
+                request,
+            )
+            .await;
+            assert_eq!(status, Status::Ok);
+            body["data"].clone()
+        }
+    };
+
+    let decision = release().await;
+    assert_eq!(decision["decision"], "DENY");
+    assert_eq!(decision["policy_version"], Value::Null);
+    assert_eq!(decision["organization_id"], other);
+    assert_eq!(decision["failed"], json!(["Affiliation"]));
+
+    let (status, body) = send(
+        &client,
+        &officer,
+        Method::Put,
+        policy_uri.clone(),
+        policy("Relaxed", false, None),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["version"], 1);
+    let decision = release().await;
+    assert_eq!(decision["decision"], "ALLOW", "{decision}");
+    assert_eq!(decision["policy_version"], 1);
+    assert_eq!(rule_names(&decision), vec!["Clearance", "Need-to-know"]);
+
+    let (status, body) = send(
+        &client,
+        &officer,
+        Method::Put,
+        policy_uri.clone(),
+        policy("Strict", true, Some("TOP_SECRET")),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["version"], 2);
+    let decision = release().await;
+    assert_eq!(decision["decision"], "DENY");
+    assert_eq!(
+        decision["failed"],
+        json!(["Clearance floor", "Affiliation"])
+    );
+    let floor = decision["rules"]
+        .as_array()
+        .expect("rules")
+        .iter()
+        .find(|r| r["name"] == "Clearance floor")
+        .cloned()
+        .expect("floor rule");
+    assert_eq!(floor["detail"], "SECRET < entity floor TOP_SECRET");
+
+    let (status, body) = send(&client, &admin, Method::Get, policy_uri, Value::Null).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["current"]["label"], "Strict");
+    let versions: Vec<i64> = body["data"]["versions"]
+        .as_array()
+        .expect("versions")
+        .iter()
+        .map(|v| v["version"].as_i64().expect("version"))
+        .collect();
+    assert_eq!(versions, vec![2, 1]);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_set_release_policy_validation() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let holder = create_organization(&client, &admin).await;
+    let uri = format!("/api/release-policies/organizations/{}", holder);
+
+    let (status, _) = send(
+        &client,
+        &admin,
+        Method::Put,
+        uri.clone(),
+        policy("Bad floor", true, Some("COSMIC")),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+    let (status, _) = send(
+        &client,
+        &admin,
+        Method::Put,
+        "/api/release-policies/organizations/999999".to_string(),
+        policy("Standard", true, None),
+    )
+    .await;
+    assert_eq!(status, Status::NotFound);
+    let (status, _) = send(
+        &client,
+        &admin,
+        Method::Post,
+        "/api/abac/release".to_string(),
+        json!({ "requester_person_id": 1, "subject_person_id": 5, "holder_organization_id": 999999 }),
+    )
+    .await;
+    assert_eq!(status, Status::NotFound);
+
+    let viewer = login(&client, "viewer").await;
+    let (status, _) = send(
+        &client,
+        &viewer,
+        Method::Put,
+        uri,
+        policy("Standard", true, None),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+}
@@ -13,7 +13,8 @@ use serde_json::Value;
 use janus_backend::digital_resources::models::GateDescriptor;
 use janus_backend::digital_resources::models::ResourceAccessGrant;
 use janus_backend::digital_resources::resolver::{
-    resolve_resource_access, ResolverPolicy, ResolverPolicyAssignment, ResolverResource,
+    apply_authorization, apply_deny_overrides, resolve_resource_access, ResolverOrgLink,
+    ResolverPolicy, ResolverPolicyAssignment, ResolverResource,
 };
 
 // Parse a fixed UTC timestamp literal into DateTime<Utc> (the resolver's time
@@ -46,6 +47,8 @@ fn milnet_fixture() -> (ResolverResource, Vec<ResourceAccessGrant>) {
         parent_id: None,
         org_links: vec![],
         policy_assignments: vec![assignment],
+        shielded: false,
+        allowlist: vec![],
     };
     let grants = vec![ResourceAccessGrant {
         id: "g-milnet-subj1".to_string(),
@@ -103,3 +106,108 @@ fn resolver_parity_against_golden_fixtures() {
         "no-policy reason must be NO_ACTIVE_POLICY"
     );
 }
+
+// Server-side deny overrides: not part of the TS contract, so checked against
+// the mid-window ALLOW rather than a golden entry.
+#[test]
+fn deny_overrides_flip_allow_and_keep_gates() {
+    let (resource, grants) = milnet_fixture();
+    let resolve = || {
+        resolve_resource_access(
+            "subj-1",
+            "SECRET",
+            "MILITARY_1",
+            &resource,
+            &[],
+            &grants,
+            utc("2026-02-15T12:00:00"),
+        )
+    };
+    assert!(apply_deny_overrides(resolve(), false, false).allow);
+
+    let held = apply_deny_overrides(resolve(), false, true);
+    assert!(!held.allow);
+    assert_eq!(held.reason.as_deref(), Some("SECURITY_HOLD"));
+    assert!(held.gates.iter().all(|g| g.pass));
+
+    let revoked = apply_deny_overrides(resolve(), true, true);
+    assert_eq!(revoked.reason.as_deref(), Some("SUBJECT_REVOKED"));
+}
+
+#[test]
+fn authorization_rule_denies_unless_authorized() {
+    let (resource, grants) = milnet_fixture();
+    let resolve = || {
+        resolve_resource_access(
+            "subj-1",
+            "SECRET",
+            "MILITARY_1",
+            &resource,
+            &[],
+            &grants,
+            utc("2026-02-15T12:00:00"),
+        )
+    };
+    assert!(apply_authorization(resolve(), None).allow);
+    assert!(apply_authorization(resolve(), Some("AUTHORIZED")).allow);
+
+    let expired = apply_authorization(resolve(), Some("EXPIRED"));
+    assert!(!expired.allow);
+    assert_eq!(expired.reason.as_deref(), Some("AUTHORIZATION_NOT_VALID"));
+
+    // A deny override's reason wins.
+    let held = apply_authorization(
+        apply_deny_overrides(resolve(), false, true),
+        Some("WITHDRAWN"),
+    );
+    assert_eq!(held.reason.as_deref(), Some("SECURITY_HOLD"));
+}
+
+// Directional shielding (evaluateResourceAccess in obligations.ts): a shielded
+// node admits its ASSET_OWNER org and the allowlist only, as a leading
+// SHIELDING gate; the policy gates are still reported. Unshielded nodes get no
+// SHIELDING gate, so the golden traces above are unchanged.
+#[test]
+fn shielding_gate_admits_owner_and_allowlist_only() {
+    let (mut resource, grants) = milnet_fixture();
+    resource.shielded = true;
+    resource.allowlist = vec!["INTEL".to_string()];
+    resource.org_links = vec![ResolverOrgLink {
+        org_id: "MILITARY_1".to_string(),
+        role: "ASSET_OWNER".to_string(),
+        valid_from: None,
+        valid_until: None,
+    }];
+    let resolve = |resource: &ResolverResource, org: &str| {
+        resolve_resource_access(
+            "subj-1",
+            "SECRET",
+            org,
+            resource,
+            &[],
+            &grants,
+            utc("2026-02-15T12:00:00"),
+        )
+    };
+
+    for (org, allow, reason) in [
+        ("MILITARY_1", true, "SHIELDING_OWNER"),
+        ("INTEL", true, "SHIELDING_ALLOWLISTED"),
+        ("INFRA", false, "SHIELDED_NOT_ALLOWLISTED"),
+        ("", false, "SHIELDED_NOT_ALLOWLISTED"),
+    ] {
+        let result = resolve(&resource, org);
+        assert_eq!(result.allow, allow, "{org}");
+        assert_eq!(result.gates[0].kind, "SHIELDING");
+        assert_eq!(result.gates[0].reason.as_deref(), Some(reason), "{org}");
+        assert_eq!(result.gates.len(), 4);
+    }
+
+    // An owner link outside its window no longer admits.
+    resource.org_links[0].valid_until = Some(utc("2026-02-01T00:00:00"));
+    assert!(!resolve(&resource, "MILITARY_1").allow);
+    resource.shielded = false;
+    let unshielded = resolve(&resource, "INFRA");
+    assert!(unshielded.allow);
+    assert!(unshielded.gates.iter().all(|g| g.kind != "SHIELDING"));
+}
@@ -0,0 +1,225 @@
+// Security flag integration tests.
+//
+// Test map:
+//   GET    /api/security-flags                          — no token -> 401           [no DB]
+//   POST   /api/security-flags/persons/<id>             — officer holds a person:   [DB: login]
+//          GET /api/persons/<id>/access                   active listing empty, history
+//          GET /api/info-systems/<id>/access              keeps the grant; system holder
+//          POST /api/abac/decide                          list omits them; ABAC DENY with
+//          GET /api/digital-resources/access              the override; resolver DENY
+//          DELETE /api/security-flags/persons/<id>/<flag> SECURITY_HOLD; clearing restores
+//   POST   /api/security-flags/persons/<id>             — duplicate -> 409; unknown     [DB: login]
+//                                                         flag -> 400; on self -> 403
+//   POST   /api/security-flags/persons/<id>             — viewer -> 403                 [DB: login]
+//
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test security_flags_test -- --include-ignored
+
+mod common;
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn post(client: &Client, token: &str, uri: String, body: Value) -> (Status, Value) {
+    let response = client
+        .post(uri)
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+async fn get(client: &Client, token: &str, uri: String) -> (Status, Value) {
+    let response = client.get(uri).header(auth_header(token)).dispatch().await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+fn hold(reason: &str) -> Value {
+    json!({ "flag": "SECURITY_HOLD", "reason": reason })
+}
+
+#[rocket::async_test]
+async fn test_list_flags_requires_auth() {
+    let client = create_test_client().await;
+    let response = client.get("/api/security-flags").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_hold_denies_every_decision_path() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let officer = login(&client, "security").await;
+    let person_id = common::create_person(&client, &admin, json!({})).await;
+    let (status, _) = post(
+        &client,
+        &admin,
+        "/api/access/data".to_string(),
+        json!({ "person_id": person_id, "data_classification": "SECRET", "access_level": "READ" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let system_name = format!("Flagged System {}", uuid::Uuid::new_v4());
+    let (status, system) = post(
+        &client,
+        &admin,
+        "/api/info-systems".to_string(),
+        json!({ "system_name": system_name, "environment": "TEST", "status": "ACTIVE" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let (status, _) = post(
+        &client,
+        &admin,
+        "/api/access/computer".to_string(),
+        json!({ "person_id": person_id, "system_name": system_name, "access_level": "READ" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let holders_uri = format!("/api/info-systems/{}/access", system["id"]);
+
+    let (status, body) = post(
+        &client,
+        &officer,
+        format!("/api/security-flags/persons/{}", person_id),
+        hold("under investigation"),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["reason"], "under investigation");
+
+    let access_uri = format!("/api/persons/{}/access", person_id);
+    let (_, body) = get(&client, &admin, access_uri.clone()).await;
+    assert_eq!(body["data"]["data_access"], json!([]));
+    assert_eq!(body["data"]["security_flags"], json!(["SECURITY_HOLD"]));
+    let (_, body) = get(
+        &client,
+        &admin,
+        format!("{}?include_inactive=true", access_uri),
+    )
+    .await;
+    assert_eq!(body["data"]["data_access"].as_array().unwrap().len(), 1);
+    let (_, body) = get(&client, &admin, holders_uri.clone()).await;
+    assert_eq!(body, json!([]));
+
+    let (status, body) = post(
+        &client,
+        &admin,
+        "/api/abac/decide".to_string(),
+        json!({
+            "person_id": person_id,
+            "domain": "DATA",
+            "min_clearance": "UNCLASSIFIED",
+            "owner_organization_id": 1
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["decision"], "DENY");
+    assert_eq!(body["data"]["overrides"][0]["name"], "Security hold");
+
+    let resolve_uri = format!(
+        "/api/digital-resources/access?person_id={}&resource_id=rsrc-milnet",
+        person_id
+    );
+    let (status, body) = get(&client, &admin, resolve_uri.clone()).await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["allow"], false);
+    assert_eq!(body["data"]["reason"], "SECURITY_HOLD");
+
+    let response = client
+        .delete(format!(
+            "/api/security-flags/persons/{}/SECURITY_HOLD?reason=cleared",
+            person_id
+        ))
+        .header(auth_header(&officer))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+
+    let (_, body) = get(&client, &admin, access_uri).await;
+    assert_eq!(body["data"]["data_access"].as_array().unwrap().len(), 1);
+    assert_eq!(body["data"]["security_flags"], json!([]));
+    let (_, body) = get(&client, &admin, holders_uri).await;
+    assert_eq!(body[0]["person_id"], person_id);
+    let (_, body) = get(&client, &admin, resolve_uri).await;
+    assert_ne!(body["data"]["reason"], "SECURITY_HOLD");
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_set_flag_validation() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let person_id = common::create_person(&client, &admin, json!({})).await;
+    let uri = format!("/api/security-flags/persons/{}", person_id);
+
+    let (status, _) = post(
+        &client,
+        &admin,
+        uri.clone(),
+        json!({ "flag": "FROZEN", "reason": "x" }),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+
+    let (status, _) = post(&client, &admin, uri.clone(), hold("first")).await;
+    assert_eq!(status, Status::Ok);
+    let (status, _) = post(&client, &admin, uri, hold("second")).await;
+    assert_eq!(status, Status::Conflict);
+
+    // admin is person 1: flagging yourself breaks the SoD rule.
+    let (status, _) = post(
+        &client,
+        &admin,
+        "/api/security-flags/persons/1".to_string(),
+        hold("self"),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_set_flag_forbidden_for_viewer() {
+    let client = create_test_client().await;
+    let viewer = login(&client, "viewer").await;
+    let (status, _) = post(
+        &client,
+        &viewer,
+        "/api/security-flags/persons/5".to_string(),
+        hold("no"),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+}
@@ -0,0 +1,288 @@
+// Sharing agreement integration tests.
+//
+// Test map:
+//   GET    /api/sharing-agreements        — no token -> 401                     [no DB]
+//   POST   /api/sharing-agreements        — UNILATERAL A -> B: B's member       [DB: login]
+//   PUT    /api/sharing-agreements/<id>     passes Affiliation for A's records,
+//   DELETE /api/sharing-agreements/<id>     A's member fails for B's; above the
+//   POST   /api/abac/decide                 max classification fails; made
+//                                           BILATERAL the reverse passes;
+//                                           listed for B; deleted -> fails again
+//   POST   /api/sharing-agreements        — same organization / unknown terms / [DB: login]
+//                                           empty window -> 400; unknown
+//                                           organization -> 404; viewer -> 403
+//
+// Each test creates its own organizations and members.
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test sharing_agreements_test -- --include-ignored
+
+use rocket::http::{ContentType, Header, Method, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn send(
+    client: &Client,
+    token: &str,
+    method: Method,
+    uri: String,
+    body: Value,
+) -> (Status, Value) {
+    let response = client
+        .req(method, uri)
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+async fn create_organization(client: &Client, admin: &str) -> i64 {
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    let (status, body) = send(
+        client,
+        admin,
+        Method::Post,
+        "/api/organizations".to_string(),
+        json!({
+            "company_name": format!("Sharing Org {}", &suffix[..12]),
+            "contact_name": "Sharing Contact",
+            "contact_email": "sharing@example.com",
+            "clearance_level": "SECRET",
+            "contract_number": format!("SHR-{}", &suffix[..8])
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    body["id"].as_i64().expect("organization id")
+}
+
+/// A SECRET-cleared employee of `org_id`.
+async fn create_member(client: &Client, admin: &str, org_id: i64) -> i64 {
+    let (status, body) = send(
+        client,
+        admin,
+        Method::Post,
+        "/api/person".to_string(),
+        json!({ "first_name": "Sharing", "last_name": "Member", "clearance_level": "SECRET" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let person_id = body["id"].as_i64().expect("person id");
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    sqlx::query(
+        "INSERT INTO relations (entity_type, entity_id, related_entity_type, related_entity_id, relation_type) \
+         VALUES ('vendor', $1, 'person', $2, 'employee')",
+    )
+    .bind(org_id as i32)
+    .bind(person_id as i32)
+    .execute(pool)
+    .await
+    .expect("membership");
+    person_id
+}
+
+/// The Affiliation rule of a DATA decision about `owner`'s records, and the
+/// organization the person was evaluated as.
+async fn affiliation(
+    client: &Client,
+    token: &str,
+    person_id: i64,
+    owner: i64,
+    min_clearance: &str,
+) -> (Value, Value) {
+    let (status, body) = send(
+        client,
+        token,
+        Method::Post,
+        "/api/abac/decide".to_string(),
+        json!({
+            "person_id": person_id,
+            "domain": "DATA",
+            "min_clearance": min_clearance,
+            "owner_organization_id": owner
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let rule = body["data"]["rules"]
+        .as_array()
+        .expect("rules")
+        .iter()
+        .find(|r| r["name"] == "Affiliation")
+        .cloned()
+        .expect("rule present");
+    (rule, body["data"]["organization_id"].clone())
+}
+
+#[rocket::async_test]
+async fn test_list_agreements_requires_auth() {
+    let client = create_test_client().await;
+    let response = client.get("/api/sharing-agreements").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_agreement_drives_affiliation() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let officer = login(&client, "security").await;
+    let org_a = create_organization(&client, &admin).await;
+    let org_b = create_organization(&client, &admin).await;
+    let member_a = create_member(&client, &admin, org_a).await;
+    let member_b = create_member(&client, &admin, org_b).await;
+
+    let (rule, _) = affiliation(&client, &admin, member_b, org_a, "SECRET").await;
+    assert_eq!(rule["pass"], false);
+
+    let (status, body) = send(
+        &client,
+        &officer,
+        Method::Post,
+        "/api/sharing-agreements".to_string(),
+        json!({
+            "from_organization_id": org_a,
+            "to_organization_id": org_b,
+            "direction": "UNILATERAL",
+            "domain": "DATA",
+            "max_classification": "SECRET",
+            "document_reference": "MoU 2026/14"
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let agreement_id = body["data"]["id"].as_i64().expect("agreement id");
+    let uri = format!("/api/sharing-agreements/{}", agreement_id);
+
+    let (rule, organization_id) = affiliation(&client, &admin, member_b, org_a, "SECRET").await;
+    assert_eq!(rule["pass"], true, "{rule}");
+    assert_eq!(organization_id, org_b);
+    let (rule, _) = affiliation(&client, &admin, member_a, org_b, "SECRET").await;
+    assert_eq!(rule["pass"], false);
+    let (rule, _) = affiliation(&client, &admin, member_b, org_a, "TOP_SECRET").await;
+    assert_eq!(rule["pass"], false);
+
+    let (status, body) = send(
+        &client,
+        &officer,
+        Method::Put,
+        uri.clone(),
+        json!({
+            "direction": "BILATERAL",
+            "domain": "DATA",
+            "max_classification": "SECRET",
+            "valid_from": "2020-01-01T00:00:00"
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["document_reference"], Value::Null);
+    let (rule, _) = affiliation(&client, &admin, member_a, org_b, "SECRET").await;
+    assert_eq!(rule["pass"], true, "{rule}");
+
+    let (status, body) = send(
+        &client,
+        &admin,
+        Method::Get,
+        format!(
+            "/api/sharing-agreements?organization_id={}&active_only=true",
+            org_b
+        ),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"].as_array().expect("agreements").len(), 1);
+
+    let (status, _) = send(&client, &officer, Method::Delete, uri.clone(), Value::Null).await;
+    assert_eq!(status, Status::Ok);
+    let (status, _) = send(&client, &admin, Method::Get, uri, Value::Null).await;
+    assert_eq!(status, Status::NotFound);
+    let (rule, _) = affiliation(&client, &admin, member_b, org_a, "SECRET").await;
+    assert_eq!(rule["pass"], false);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_create_agreement_validation() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let org_a = create_organization(&client, &admin).await;
+    let org_b = create_organization(&client, &admin).await;
+    let base = json!({
+        "from_organization_id": org_a,
+        "to_organization_id": org_b,
+        "direction": "BILATERAL",
+        "max_classification": "CONFIDENTIAL"
+    });
+    let create = |body: Value| {
+        let client = &client;
+        let admin = &admin;
+        async move {
+            send(
+                client,
+                admin,
+                Method::Post,
+                "/api/sharing-agreements".to_string(),
+                body,
+            )
+            .await
+            .0
+        }
+    };
+
+    let mut same = base.clone();
+    same["to_organization_id"] = json!(org_a);
+    assert_eq!(create(same).await, Status::BadRequest);
+    let mut direction = base.clone();
+    direction["direction"] = json!("MUTUAL");
+    assert_eq!(create(direction).await, Status::BadRequest);
+    let mut level = base.clone();
+    level["max_classification"] = json!("RESTRICTED");
+    assert_eq!(create(level).await, Status::BadRequest);
+    let mut window = base.clone();
+    window["valid_from"] = json!("2026-06-01T00:00:00");
+    window["valid_until"] = json!("2026-05-01T00:00:00");
+    assert_eq!(create(window).await, Status::BadRequest);
+    let mut unknown = base.clone();
+    unknown["to_organization_id"] = json!(999999);
+    assert_eq!(create(unknown).await, Status::NotFound);
+
+    let viewer = login(&client, "viewer").await;
+    let (status, _) = send(
+        &client,
+        &viewer,
+        Method::Post,
+        "/api/sharing-agreements".to_string(),
+        base,
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+}
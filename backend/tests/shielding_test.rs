@@ -0,0 +1,393 @@
+// Directional shielding integration tests.
+//
+// Test map:
+//   GET  /api/digital-resources/resources/<id>/shielding — no token -> 401     [no DB]
+//   PUT  /api/digital-resources/resources/<id>/shielding — subjects stored     [DB: login]
+//   PUT  /api/digital-resources/persons/<id>/org           as the owner or an
+//   GET  /api/digital-resources/access                     allowlisted org pass
+//                                                          the SHIELDING gate,
+//                                                          others fail it; an
+//                                                          org in the query is
+//                                                          ignored; unshielding
+//                                                          drops it
+//   PUT  /api/digital-resources/resources/<id>/shielding — viewer -> 403;      [DB: login]
+//   PUT  /api/digital-resources/persons/<id>/org           blank org -> 400;
+//                                                          unknown -> 404
+//   PUT  /api/info-systems/<id>/shielding                — revokes grants it   [DB: login]
+//   POST /api/access/computer                              does not admit;
+//   POST /api/access/bulk/grant                            grants to members
+//                                                          of the owner or an
+//                                                          allowlisted org only;
+//                                                          bulk blocks the rest
+//
+// Each test creates its own resources, systems, organizations and persons.
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test shielding_test -- --include-ignored
+
+mod common;
+
+use rocket::http::{ContentType, Header, Method, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn send(
+    client: &Client,
+    token: &str,
+    method: Method,
+    uri: String,
+    body: Value,
+) -> (Status, Value) {
+    let response = client
+        .req(method, uri)
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+async fn create_organization(client: &Client, admin: &str) -> i64 {
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    let (status, body) = send(
+        client,
+        admin,
+        Method::Post,
+        "/api/organizations".to_string(),
+        json!({
+            "company_name": format!("Shield Org {}", &suffix[..12]),
+            "contact_name": "Shield Contact",
+            "contact_email": "shield@example.com",
+            "clearance_level": "SECRET",
+            "contract_number": format!("SHD-{}", &suffix[..8])
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    body["id"].as_i64().expect("organization id")
+}
+
+/// A person employed by `org_id`.
+async fn create_member(client: &Client, admin: &str, org_id: i64) -> i64 {
+    let person_id = common::create_person(client, admin, json!({})).await;
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    sqlx::query(
+        "INSERT INTO relations (entity_type, entity_id, related_entity_type, related_entity_id, relation_type) \
+         VALUES ('vendor', $1, 'person', $2, 'employee')",
+    )
+    .bind(org_id as i32)
+    .bind(person_id as i32)
+    .execute(pool)
+    .await
+    .expect("membership");
+    person_id
+}
+
+/// An UNCLASSIFIED network on the baseline policy, owned by SHIELD_OWNER.
+async fn create_network(client: &Client) -> String {
+    let id = common::create_network(client, "rsrc-shield", Some("rsrc-pol-baseline")).await;
+    let pool = client.rocket().state::<PgPool>().expect("pool");
+    sqlx::query(
+        "INSERT INTO resource_org_links (resource_id, resource_tier, org_id, role) \
+         VALUES ($1, 'NETWORK', 'SHIELD_OWNER', 'ASSET_OWNER')",
+    )
+    .bind(&id)
+    .execute(pool)
+    .await
+    .expect("owner link");
+    id
+}
+
+#[rocket::async_test]
+async fn test_get_shielding_requires_auth() {
+    let client = create_test_client().await;
+    let response = client
+        .get("/api/digital-resources/resources/rsrc-milnet/shielding")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+/// A person with a resource grant on `resource_id`, stored as acting for `org`.
+async fn create_grantee(client: &Client, admin: &str, resource_id: &str, org: &str) -> i64 {
+    let person_id = common::create_person(client, admin, json!({})).await;
+    let (status, _) = send(
+        client,
+        admin,
+        Method::Post,
+        "/api/digital-resources/grants".to_string(),
+        json!({ "resource_id": resource_id, "person_id": person_id.to_string(), "actor_org_id": "" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let (status, body) = send(
+        client,
+        admin,
+        Method::Put,
+        format!("/api/digital-resources/persons/{}/org", person_id),
+        json!({ "org_id": org }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["org_id"], org);
+    person_id
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_shielded_resource_admits_owner_and_allowlist() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let resource_id = create_network(&client).await;
+    let owner = create_grantee(&client, &admin, &resource_id, "SHIELD_OWNER").await;
+    let partner = create_grantee(&client, &admin, &resource_id, "SHIELD_PARTNER").await;
+    let other = create_grantee(&client, &admin, &resource_id, "SHIELD_OTHER").await;
+    let shielding_uri = format!("/api/digital-resources/resources/{}/shielding", resource_id);
+    let resolve = |person_id: i64| {
+        let client = &client;
+        let admin = &admin;
+        // An org in the query is not the caller's to assert; it is ignored.
+        let uri = format!(
+            "/api/digital-resources/access?person_id={}&resource_id={}&org_id=SHIELD_OWNER",
+            person_id, resource_id
+        );
+        async move {
+            let (status, body) = send(client, admin, Method::Get, uri, Value::Null).await;
+            assert_eq!(status, Status::Ok);
+            body["data"].clone()
+        }
+    };
+
+    let (status, body) = send(
+        &client,
+        &admin,
+        Method::Put,
+        shielding_uri.clone(),
+        json!({ "shielded": true, "allowlist": ["SHIELD_PARTNER", " SHIELD_PARTNER "] }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["allowlist"], json!(["SHIELD_PARTNER"]));
+
+    for (person_id, allow, reason) in [
+        (owner, true, "SHIELDING_OWNER"),
+        (partner, true, "SHIELDING_ALLOWLISTED"),
+        (other, false, "SHIELDED_NOT_ALLOWLISTED"),
+    ] {
+        let decision = resolve(person_id).await;
+        assert_eq!(decision["allow"], allow, "{person_id}: {decision}");
+        assert_eq!(decision["gates"][0]["kind"], "SHIELDING");
+        assert_eq!(decision["gates"][0]["pass"], allow);
+        assert_eq!(decision["gates"][0]["reason"], reason);
+    }
+
+    let (status, body) = send(
+        &client,
+        &admin,
+        Method::Get,
+        shielding_uri.clone(),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["shielded"], true);
+
+    let (status, _) = send(
+        &client,
+        &admin,
+        Method::Put,
+        shielding_uri,
+        json!({ "shielded": false }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let decision = resolve(other).await;
+    assert_eq!(decision["allow"], true, "{decision}");
+    assert_ne!(decision["gates"][0]["kind"], "SHIELDING");
+    common::drop_network(&client, &resource_id).await;
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_set_shielding_validation() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let resource_id = create_network(&client).await;
+    let uri = format!("/api/digital-resources/resources/{}/shielding", resource_id);
+
+    let (status, _) = send(
+        &client,
+        &admin,
+        Method::Put,
+        uri.clone(),
+        json!({ "shielded": true, "allowlist": ["  "] }),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+    let (status, _) = send(
+        &client,
+        &admin,
+        Method::Put,
+        "/api/digital-resources/resources/rsrc-no-such/shielding".to_string(),
+        json!({ "shielded": true }),
+    )
+    .await;
+    assert_eq!(status, Status::NotFound);
+
+    let person_uri = "/api/digital-resources/persons/5/org".to_string();
+    let (status, _) = send(
+        &client,
+        &admin,
+        Method::Put,
+        person_uri.clone(),
+        json!({ "org_id": " " }),
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+
+    let viewer = login(&client, "viewer").await;
+    let (status, _) = send(
+        &client,
+        &viewer,
+        Method::Put,
+        uri,
+        json!({ "shielded": true }),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+    let (status, _) = send(
+        &client,
+        &viewer,
+        Method::Put,
+        person_uri,
+        json!({ "org_id": "SHIELD_OWNER" }),
+    )
+    .await;
+    assert_eq!(status, Status::Forbidden);
+    common::drop_network(&client, &resource_id).await;
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_shielded_system_grants_owner_and_allowlist_only() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let owner = create_organization(&client, &admin).await;
+    let partner = create_organization(&client, &admin).await;
+    let other = create_organization(&client, &admin).await;
+    let owner_member = create_member(&client, &admin, owner).await;
+    let partner_member = create_member(&client, &admin, partner).await;
+    let outsider = create_member(&client, &admin, other).await;
+
+    let system_name = format!("Shielded System {}", uuid::Uuid::new_v4());
+    let (status, body) = send(
+        &client,
+        &admin,
+        Method::Post,
+        "/api/info-systems".to_string(),
+        json!({ "system_name": system_name, "environment": "TEST", "status": "ACTIVE" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let system_id = body["id"].as_i64().expect("system id");
+    let shielding_uri = format!("/api/info-systems/{}/shielding", system_id);
+    let holders_uri = format!("/api/info-systems/{}/access", system_id);
+
+    // Granted while the system was open; shielding it revokes the grant.
+    let (status, _) = send(
+        &client,
+        &admin,
+        Method::Post,
+        "/api/access/computer".to_string(),
+        json!({ "person_id": outsider, "system_name": system_name, "access_level": "READ" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+
+    let (status, _) = send(
+        &client,
+        &admin,
+        Method::Put,
+        shielding_uri.clone(),
+        json!({ "shielded": true, "owner_organization_id": 999999, "allowlist": [] }),
+    )
+    .await;
+    assert_eq!(status, Status::NotFound);
+    let (status, body) = send(
+        &client,
+        &admin,
+        Method::Put,
+        shielding_uri,
+        json!({ "shielded": true, "owner_organization_id": owner, "allowlist": [partner] }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["allowlist"], json!([partner]));
+    let (_, body) = send(&client, &admin, Method::Get, holders_uri, Value::Null).await;
+    assert_eq!(body, json!([]));
+
+    let bulk = json!({
+        "access_type": "computer",
+        "target": system_name,
+        "access_level": "READ",
+        "selector": { "person_ids": [owner_member, partner_member, outsider] }
+    });
+    let (status, body) = send(
+        &client,
+        &admin,
+        Method::Post,
+        "/api/access/bulk/grant".to_string(),
+        bulk,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    let outcomes: Vec<&str> = body["data"]["items"]
+        .as_array()
+        .expect("items")
+        .iter()
+        .map(|i| i["outcome"].as_str().expect("outcome"))
+        .collect();
+    assert_eq!(outcomes, vec!["GRANTED", "GRANTED", "BLOCKED"]);
+
+    for (person_id, expected) in [
+        (owner_member, Status::Ok),
+        (partner_member, Status::Ok),
+        (outsider, Status::Forbidden),
+    ] {
+        let (status, _) = send(
+            &client,
+            &admin,
+            Method::Post,
+            "/api/access/computer".to_string(),
+            json!({ "person_id": person_id, "system_name": system_name, "access_level": "READ" }),
+        )
+        .await;
+        assert_eq!(status, expected, "person {person_id}");
+    }
+}
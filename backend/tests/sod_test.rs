@@ -0,0 +1,136 @@
+// Separation-of-duties integration tests.
+//
+// Test map:
+//   GET  /api/sod/violations   — 401 without a token                     [DB: conn]
+//   POST /api/access/data      — admin granting themselves -> 403         [DB: login]
+//   GET  /api/sod/violations   — seeded roles carry no conflict           [DB: seed]
+//   PUT  /api/roles/<id>/permissions — conflicting set -> 400             [DB: seed]
+//
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test sod_test -- --include-ignored
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> (String, i64) {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+
+    assert_eq!(
+        response.status(),
+        Status::Ok,
+        "login must succeed for {username}"
+    );
+    let body: Value = response.into_json().await.expect("valid json");
+    let token = body["token"].as_str().expect("token field").to_string();
+    let person_id = body["person_id"]
+        .as_str()
+        .and_then(|s| s.parse().ok())
+        .expect("person_id field");
+    (token, person_id)
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+#[rocket::async_test]
+async fn test_violations_unauthenticated() {
+    let client = create_test_client().await;
+    let response = client.get("/api/sod/violations").dispatch().await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_self_grant_forbidden() {
+    let client = create_test_client().await;
+    let (token, person_id) = login(&client, "admin").await;
+
+    let response = client
+        .post("/api/access/data")
+        .header(ContentType::JSON)
+        .header(auth_header(&token))
+        .body(
+            json!({
+                "person_id": person_id,
+                "data_classification": "UNCLASSIFIED",
+                "access_level": "READ"
+            })
+            .to_string(),
+        )
+        .dispatch()
+        .await;
+
+    assert_eq!(
+        response.status(),
+        Status::Forbidden,
+        "no-self-access-grant must block an admin granting themselves"
+    );
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with the SoD seed migration
+async fn test_seeded_roles_have_no_violations() {
+    let client = create_test_client().await;
+    let (token, _) = login(&client, "admin").await;
+
+    let response = client
+        .get("/api/sod/violations")
+        .header(auth_header(&token))
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    assert!(
+        body.as_array().expect("array").is_empty(),
+        "seed splits access.request (manager) from access.approve (admin)"
+    );
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with the SoD seed migration
+async fn test_conflicting_permission_set_rejected() {
+    let client = create_test_client().await;
+    let (token, _) = login(&client, "admin").await;
+
+    let roles: Value = client
+        .get("/api/roles")
+        .header(auth_header(&token))
+        .dispatch()
+        .await
+        .into_json()
+        .await
+        .expect("valid json");
+    let manager_id = roles
+        .as_array()
+        .expect("array")
+        .iter()
+        .find(|r| r["name"] == "manager")
+        .and_then(|r| r["id"].as_i64())
+        .expect("manager role");
+
+    let response = client
+        .put(format!("/api/roles/{}/permissions", manager_id))
+        .header(ContentType::JSON)
+        .header(auth_header(&token))
+        .body(json!({ "permissions": ["access.request", "access.approve"] }).to_string())
+        .dispatch()
+        .await;
+
+    assert_eq!(response.status(), Status::BadRequest);
+}
@@ -0,0 +1,192 @@
+// Reverse resolver query integration tests.
+//
+// Test map:
+//   GET  /api/digital-resources/resources/<id>/who-can-access — no token -> 401  [no DB]
+//   GET  /api/digital-resources/resources/<id>/who-can-access — a granted        [DB: login]
+//                                                               person is
+//                                                               allowed, one
+//                                                               without a grant
+//                                                               is a near miss,
+//                                                               a held one is
+//                                                               neither; before
+//                                                               the grant window
+//                                                               the granted one
+//                                                               is a near miss
+//   GET  /api/digital-resources/resources/<id>/who-can-access — malformed at    [DB: login]
+//                                                               -> 400; unknown
+//                                                               -> 404
+//
+// Each test creates its own network and persons.
+// Login-dependent tests are #[ignore] per repo convention.
+// Run all with: cargo test --test who_can_access_test -- --include-ignored
+
+mod common;
+
+use rocket::http::{ContentType, Header, Method, Status};
+use rocket::local::asynchronous::Client;
+use serde_json::{json, Value};
+
+use janus_backend::shared::rocket_setup::create_rocket;
+
+async fn create_test_client() -> Client {
+    Client::tracked(create_rocket().await)
+        .await
+        .expect("valid rocket instance")
+}
+
+async fn login(client: &Client, username: &str) -> String {
+    let response = client
+        .post("/api/auth/login")
+        .header(ContentType::JSON)
+        .body(json!({ "username": username, "password": "password123" }).to_string())
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: Value = response.into_json().await.expect("valid json");
+    body["token"].as_str().expect("token field").to_string()
+}
+
+fn auth_header(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {}", token))
+}
+
+async fn send(
+    client: &Client,
+    token: &str,
+    method: Method,
+    uri: String,
+    body: Value,
+) -> (Status, Value) {
+    let response = client
+        .req(method, uri)
+        .header(ContentType::JSON)
+        .header(auth_header(token))
+        .body(body.to_string())
+        .dispatch()
+        .await;
+    let status = response.status();
+    (status, response.into_json().await.unwrap_or(Value::Null))
+}
+
+async fn grant(client: &Client, admin: &str, resource_id: &str, person_id: i64) {
+    let (status, _) = send(
+        client,
+        admin,
+        Method::Post,
+        "/api/digital-resources/grants".to_string(),
+        json!({
+            "resource_id": resource_id,
+            "person_id": person_id.to_string(),
+            "actor_org_id": "",
+            "valid_from": "2026-01-01T00:00:00Z"
+        }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+}
+
+fn person_ids(rows: &Value) -> Vec<i64> {
+    rows.as_array()
+        .expect("rows")
+        .iter()
+        .map(|r| r["person_id"].as_i64().expect("person id"))
+        .collect()
+}
+
+#[rocket::async_test]
+async fn test_who_can_access_requires_auth() {
+    let client = create_test_client().await;
+    let response = client
+        .get("/api/digital-resources/resources/rsrc-milnet/who-can-access")
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_who_can_access_lists_allowed_and_near_misses() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let officer = login(&client, "security").await;
+    let resource_id =
+        common::create_network(&client, "rsrc-reverse", Some("rsrc-pol-baseline")).await;
+    let granted = common::create_person(&client, &admin, json!({})).await;
+    let ungranted = common::create_person(&client, &admin, json!({})).await;
+    let held = common::create_person(&client, &admin, json!({})).await;
+    grant(&client, &admin, &resource_id, granted).await;
+    grant(&client, &admin, &resource_id, held).await;
+    let (status, _) = send(
+        &client,
+        &officer,
+        Method::Post,
+        format!("/api/security-flags/persons/{}", held),
+        json!({ "flag": "SECURITY_HOLD", "reason": "under investigation" }),
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+
+    let viewer = login(&client, "viewer").await;
+    let uri = format!(
+        "/api/digital-resources/resources/{}/who-can-access",
+        resource_id
+    );
+    let (status, body) = send(&client, &viewer, Method::Get, uri.clone(), Value::Null).await;
+    assert_eq!(status, Status::Ok);
+    let data = &body["data"];
+    assert_eq!(person_ids(&data["allowed"]), vec![granted]);
+    assert_eq!(data["allowed"][0]["decision"]["allow"], true);
+    // Every other person lacks a grant on the new network: one gate short.
+    let near_misses = person_ids(&data["near_misses"]);
+    assert!(near_misses.contains(&ungranted));
+    assert!(!near_misses.contains(&held) && !near_misses.contains(&granted));
+    let miss = data["near_misses"]
+        .as_array()
+        .expect("near misses")
+        .iter()
+        .find(|r| r["person_id"] == ungranted)
+        .expect("ungranted near miss");
+    assert_eq!(miss["failed_gate"], "OWN_TIER_GRANT");
+    assert_eq!(miss["decision"]["allow"], false);
+    assert!(data["candidates"].as_u64().expect("count") >= 3);
+
+    // Before the grants' window opens the granted person is a near miss too.
+    let (status, body) = send(
+        &client,
+        &viewer,
+        Method::Get,
+        format!("{}?at=2025-06-01T00:00:00Z", uri),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::Ok);
+    assert_eq!(body["data"]["at"], "2025-06-01T00:00:00Z");
+    assert_eq!(person_ids(&body["data"]["allowed"]), Vec::<i64>::new());
+    assert!(person_ids(&body["data"]["near_misses"]).contains(&granted));
+    common::drop_network(&client, &resource_id).await;
+}
+
+#[rocket::async_test]
+#[ignore] // requires live DB with seed users
+async fn test_who_can_access_validation() {
+    let client = create_test_client().await;
+    let admin = login(&client, "admin").await;
+    let (status, _) = send(
+        &client,
+        &admin,
+        Method::Get,
+        "/api/digital-resources/resources/rsrc-milnet/who-can-access?at=yesterday".to_string(),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::BadRequest);
+    let (status, _) = send(
+        &client,
+        &admin,
+        Method::Get,
+        "/api/digital-resources/resources/rsrc-no-such/who-can-access".to_string(),
+        Value::Null,
+    )
+    .await;
+    assert_eq!(status, Status::NotFound);
+}
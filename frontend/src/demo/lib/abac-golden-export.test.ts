@@ -0,0 +1,64 @@
+/// <reference types="node" />
+// @vitest-environment node
+//
+// Golden-fixture EXPORTER for the ABAC evaluator port.
+//
+// Not an assertion test: this run evaluates the seed subjects and resources
+// (the same fixtures abac.test.ts uses) and writes the Decisions to a
+// committed JSON file that the Rust parity test (backend/tests/abac_parity.rs)
+// loads and asserts equal against the ported evaluator
+// (backend/src/abac/evaluator.rs). Cases cover every base rule outcome, the
+// cross-entity release handshake, and both deny overrides.
+import { writeFileSync, mkdirSync } from "node:fs";
+import { dirname, resolve } from "node:path";
+import { fileURLToPath } from "node:url";
+import { it } from "vitest";
+import { SUBJECTS, RESOURCES, type Subject, type Resource } from "./seed";
+import {
+  evaluate,
+  principalFromSubject,
+  requirementFromResource,
+  releaseRequirementFor,
+} from "./abac";
+
+const subj = (id: string): Subject => SUBJECTS.find((s) => s.id === id)!;
+const res = (id: string): Resource => RESOURCES.find((r) => r.id === id)!;
+const decide = (sId: string, rId: string) =>
+  evaluate(principalFromSubject(subj(sId)), requirementFromResource(res(rId)));
+
+it("emits ABAC golden fixtures for the Rust parity test", () => {
+  const upgraded = principalFromSubject(subj("subj-1"));
+  upgraded.clearance = "TOP_SECRET";
+  upgraded.compartments = ["AURORA", "BLACKWING"];
+
+  const golden = {
+    allow_subj1_res1: decide("subj-1", "res-1"),
+    allow_subj4_res4: decide("subj-4", "res-4"),
+    tier_deny_subj3_res3: decide("subj-3", "res-3"),
+    ntk_deny_subj4_res1: decide("subj-4", "res-1"),
+    affiliation_deny_subj1_res3: decide("subj-1", "res-3"),
+    release_subj2_record_to_subj1: evaluate(
+      upgraded,
+      releaseRequirementFor(subj("subj-2"), "MILITARY_2"),
+    ),
+    security_hold_subj1_res1: evaluate(
+      { ...principalFromSubject(subj("subj-1")), flags: { securityHold: true } },
+      requirementFromResource(res("res-1")),
+    ),
+    revoked_subj2_res2: evaluate(
+      {
+        ...principalFromSubject(subj("subj-2")),
+        flags: { revoked: true, securityHold: true },
+      },
+      requirementFromResource(res("res-2")),
+    ),
+  };
+
+  const here = dirname(fileURLToPath(import.meta.url));
+  const outPath = resolve(
+    here,
+    "../../../../backend/tests/fixtures/abac-golden.json",
+  );
+  mkdirSync(dirname(outPath), { recursive: true });
+  writeFileSync(outPath, JSON.stringify(golden, null, 2) + "\n", "utf8");
+});